@@ -23,6 +23,7 @@ export type Operation =
 	| "DiscardCommit"
 	| "UnapplyBranch"
 	| "CherryPick"
+	| "RevertCommit"
 	| "SquashCommit"
 	| "UpdateCommitMessage"
 	| "MoveCommit"
@@ -45,6 +46,7 @@ export type Operation =
 	| "AutoHandleChangesBefore"
 	| "AutoHandleChangesAfter"
 	| "SplitBranch"
+	| "SplitCommit"
 	| "OnDemandSnapshot";
 
 export interface Trailer {
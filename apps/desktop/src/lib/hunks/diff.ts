@@ -8,10 +8,45 @@ import type { Patch } from "$lib/hunks/hunk";
 export type UnifiedDiff =
 	| { readonly type: "Binary" } // A binary file that can't be diffed.
 	| { readonly type: "TooLarge"; readonly subject: TooLarge }
-	| { readonly type: "Patch"; readonly subject: Patch };
+	| { readonly type: "Patch"; readonly subject: Patch }
+	| { readonly type: "Submodule"; readonly subject: Submodule }
+	| { readonly type: "Lfs"; readonly subject: Lfs };
 
 /** The file was too large and couldn't be diffed. */
 type TooLarge = {
 	/** The size of the file on disk that made it too large. */
 	readonly sizeInBytes: number;
 };
+
+/** A submodule that now points to a different commit. */
+type Submodule = {
+	/** The commit the submodule pointed to before, or `null` if it was added. */
+	readonly previousCommit: string | null;
+	/** The commit the submodule points to now, or `null` if it was removed. */
+	readonly commit: string | null;
+	/** The commits reachable from `commit` but not from `previousCommit`, newest first. */
+	readonly commitsAdded: SubmoduleCommit[];
+	/** The commits reachable from `previousCommit` but not from `commit`, newest first. */
+	readonly commitsRemoved: SubmoduleCommit[];
+};
+
+/** A file stored in Git LFS, shown by the pointers Git stores for it. */
+type Lfs = {
+	/** The pointer before the change, or `null` if the file was added. */
+	readonly previousPointer: LfsPointer | null;
+	/** The pointer after the change, or `null` if the file was removed. */
+	readonly pointer: LfsPointer | null;
+};
+
+type LfsPointer = {
+	/** The object id of the file content in the LFS store, like `sha256:4d7a…`. */
+	readonly oid: string;
+	/** The size of the file content in bytes. */
+	readonly size: number;
+};
+
+type SubmoduleCommit = {
+	readonly id: string;
+	/** The first line of the commit message. */
+	readonly title: string;
+};
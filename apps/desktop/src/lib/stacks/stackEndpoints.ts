@@ -98,6 +98,7 @@ export const REJECTTION_REASONS = [
 	"unsupportedDirectoryEntry",
 	"unsupportedTreeEntry",
 	"missingDiffSpecAssociation",
+	"lfsCleanFilterNotApplied",
 ] as const;
 
 type ReplacedCommit = [string, string];
@@ -129,6 +130,8 @@ export function readableRejectionReason(reason: RejectionReason): string {
 			return "Unsupported tree entry";
 		case "missingDiffSpecAssociation":
 			return "Missing diff spec association";
+		case "lfsCleanFilterNotApplied":
+			return "Git LFS clean filter not applied";
 	}
 }
 
@@ -28,7 +28,8 @@ export type RejectionReason =
 	| "pathNotFoundInBaseTree"
 	| "unsupportedDirectoryEntry"
 	| "unsupportedTreeEntry"
-	| "missingDiffSpecAssociation";
+	| "missingDiffSpecAssociation"
+	| "lfsCleanFilterNotApplied";
 
 export type StackSelection = {
 	branchName?: string;
@@ -39,25 +39,25 @@ pub struct ButlerAction {
     /// UUID identifier of the action
     pub id: Uuid,
     /// The time when the action was performed.
-    created_at: chrono::NaiveDateTime,
+    pub created_at: chrono::NaiveDateTime,
     /// The prompt that was used to generate the changes that were made, if applicable
-    external_prompt: Option<String>,
+    pub external_prompt: Option<String>,
     /// A description of the change that was made and why it was made - i.e. the information that can be obtained from the caller.
     pub external_summary: String,
     /// The handler / implementation that performed the action.
-    handler: ActionHandler,
+    pub handler: ActionHandler,
     /// A GitBulter Oplog snapshot ID before the action was performed.
     #[serde(serialize_with = "but_serde::object_id::serialize")]
-    snapshot_before: gix::ObjectId,
+    pub snapshot_before: gix::ObjectId,
     /// A GitBulter Oplog snapshot ID after the action was performed.
     #[serde(serialize_with = "but_serde::object_id::serialize")]
-    snapshot_after: gix::ObjectId,
+    pub snapshot_after: gix::ObjectId,
     /// The outcome of the action, if it was successful.
-    response: Option<Outcome>,
+    pub response: Option<Outcome>,
     /// An error message if the action failed.
-    error: Option<String>,
+    pub error: Option<String>,
     /// The source of the action (e.g. "ButCli", "GitButler", "Mcp", "Unknown")
-    source: Source,
+    pub source: Source,
 }
 
 impl TryFrom<but_db::ButlerAction> for ButlerAction {
@@ -202,6 +202,24 @@ pub fn list_actions(db: &DbHandle, offset: i64, limit: i64) -> anyhow::Result<Ac
     Ok(ActionListing { total, actions })
 }
 
+/// Find the persisted Butler action in `db` whose ID is `id` or starts with it.
+///
+/// Fails if no action matches, or if a prefix matches more than one action.
+pub fn find_action(db: &DbHandle, id: &str) -> anyhow::Result<ButlerAction> {
+    if id.is_empty() {
+        anyhow::bail!("An action ID is required");
+    }
+    let mut actions = db
+        .butler_actions()
+        .find_by_id_prefix(id)
+        .map_err(|e| anyhow::anyhow!("Failed to find action: {e}"))?;
+    match actions.len() {
+        0 => anyhow::bail!("No action found with ID '{id}'"),
+        1 => actions.pop().expect("one action").try_into(),
+        n => anyhow::bail!("The ID '{id}' is ambiguous as it matches {n} actions"),
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ActionListing {
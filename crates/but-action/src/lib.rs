@@ -17,9 +17,11 @@ pub mod cli;
 pub mod commit_format;
 mod generate;
 pub mod reword;
+mod rules;
 mod simple;
-pub use action::{ActionListing, Source, list_actions};
+pub use action::{ActionListing, ButlerAction, Source, find_action, list_actions};
 use but_core::ref_metadata::StackId;
+pub use rules::{Route, RoutedHunk};
 use strum::EnumString;
 
 /// React to detected worktree changes by creating commits on the appropriate workspace stacks.
@@ -33,17 +35,26 @@ use strum::EnumString;
 /// and operating-mode state. `change_summary` is the generated or user-provided summary used in the
 /// commit message. `external_prompt` is optional additional prompt text to prepend to the commit
 /// message. `handler` selects the concrete handle-changes implementation. `exclusive_stack` limits
-/// commits to one stack when set. `perm` is the caller-held exclusive worktree permission used for
-/// every repository and workspace access in this function.
+/// commits to one stack when set. If `dry_run` is `true`, the repository isn't prepared and only
+/// the routes of the changes are reported, which only the rules handler supports. `perm` is the
+/// caller-held exclusive worktree permission used for every repository and workspace access in
+/// this function.
 pub fn on_uncommitted_changes(
     ctx: &mut Context,
     change_summary: &str,
     external_prompt: Option<String>,
     handler: ActionHandler,
     exclusive_stack: Option<StackId>,
+    dry_run: bool,
     perm: &mut RepoExclusive,
 ) -> anyhow::Result<Outcome> {
-    prepare_handle_changes(ctx, perm)?;
+    if dry_run {
+        if handler != ActionHandler::HandleChangesRules {
+            anyhow::bail!("Dry runs are only supported by the rules handler");
+        }
+    } else {
+        prepare_handle_changes(ctx, perm)?;
+    }
     let context_lines = ctx.settings.context_lines;
     let mut meta = ctx.meta()?;
     let (repo, mut ws, mut db) = ctx.workspace_mut_and_db_mut_with_perm(perm)?;
@@ -59,6 +70,18 @@ pub fn on_uncommitted_changes(
             &mut meta,
             context_lines,
         ),
+        ActionHandler::HandleChangesRules => rules::handle_changes(
+            change_summary,
+            external_prompt,
+            exclusive_stack,
+            dry_run,
+            perm,
+            &repo,
+            &mut ws,
+            &mut db,
+            &mut meta,
+            context_lines,
+        ),
     }
 }
 
@@ -71,6 +94,10 @@ pub fn on_uncommitted_changes(
 /// handle-changes implementation. `source` records where the action originated. `exclusive_stack`
 /// limits commits to one stack when set. `perm` is the caller-held exclusive worktree permission
 /// used for snapshots, repository access, workspace access, and rule preparation.
+///
+/// If `dry_run` is `true`, the routes of the changes are returned without changing anything, so
+/// neither snapshots nor the action are recorded, and there is no action ID.
+#[expect(clippy::too_many_arguments)]
 pub fn record_uncommitted_changes_with_perm(
     ctx: &mut Context,
     change_summary: &str,
@@ -78,8 +105,21 @@ pub fn record_uncommitted_changes_with_perm(
     handler: ActionHandler,
     source: Source,
     exclusive_stack: Option<StackId>,
+    dry_run: bool,
     perm: &mut RepoExclusive,
-) -> anyhow::Result<(Uuid, Outcome)> {
+) -> anyhow::Result<(Option<Uuid>, Outcome)> {
+    if dry_run {
+        let outcome = on_uncommitted_changes(
+            ctx,
+            change_summary,
+            external_prompt,
+            handler,
+            exclusive_stack,
+            dry_run,
+            perm,
+        )?;
+        return Ok((None, outcome));
+    }
     let snapshot_before = ctx.create_snapshot(
         SnapshotDetails::new(OperationKind::AutoHandleChangesBefore),
         perm,
@@ -90,6 +130,7 @@ pub fn record_uncommitted_changes_with_perm(
         external_prompt.clone(),
         handler,
         exclusive_stack,
+        dry_run,
         perm,
     );
     let snapshot_after = ctx.create_snapshot(
@@ -109,7 +150,7 @@ pub fn record_uncommitted_changes_with_perm(
             &response,
         )?
     };
-    response.map(|outcome| (id, outcome))
+    response.map(|outcome| (Some(id), outcome))
 }
 
 /// Prepare repository state for handle-changes.
@@ -173,6 +214,8 @@ fn default_target_setting_if_none(ctx: &Context) -> anyhow::Result<()> {
 pub enum ActionHandler {
     #[default]
     HandleChangesSimple,
+    /// Route each hunk to a branch by the rules configured for the project before committing it.
+    HandleChangesRules,
 }
 
 impl Display for ActionHandler {
@@ -181,10 +224,13 @@ impl Display for ActionHandler {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Outcome {
     pub updated_branches: Vec<UpdatedBranch>,
+    /// Where each hunk was routed, for handlers that route by rules.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routed_hunks: Vec<RoutedHunk>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
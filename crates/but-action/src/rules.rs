@@ -0,0 +1,544 @@
+//! Route uncommitted hunks to workspace branches by project-configured rules before committing them.
+//!
+//! Rules are read from the Git configuration, one subsection per rule, and are tried in the order
+//! in which they appear:
+//!
+//! ```text
+//! [gitbutler "route.docs"]
+//!     branch = docs-update
+//!     path = docs/**
+//!     extension = md
+//! [gitbutler "route.frontend"]
+//!     branch = ui-work
+//!     owner = @frontend-team
+//! ```
+//!
+//! A rule matches a hunk if any of its criteria match the path of the hunk's file:
+//! * `path` is a gitignore-style glob, like `src/ui/` or `*.lock`.
+//! * `extension` is a file extension without its leading dot.
+//! * `owner` matches files that are owned by this owner according to the `CODEOWNERS` file,
+//!   using the same lookup locations and *last match wins* semantics as the forges.
+//!
+//! Rules pointing to branches that aren't applied to the workspace are ignored. Before any rule is
+//! tried, hunks that depend on commits of a single stack follow that dependency lock, which can be
+//! turned off with `gitbutler.routeFollowLocks = false`, and hunks that were assigned by hand keep
+//! their assignment. Everything else goes to the first stack, just like with the simple handler.
+
+use std::collections::BTreeMap;
+
+use anyhow::Context as _;
+use bstr::{BStr, BString, ByteSlice};
+use but_core::{DiffSpec, HunkHeader, RefMetadata, ref_metadata::StackId, sync::RepoExclusive};
+use but_db::DbHandle;
+use but_hunk_assignment::HunkAssignment;
+use but_hunk_dependency::ui::{HunkDependencies, HunkLockTarget};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Outcome, UpdatedBranch,
+    simple::{self, StackForAction},
+};
+
+/// The subsection prefix of rules in the `gitbutler` configuration section.
+const RULE_SUBSECTION_PREFIX: &[u8] = b"route.";
+/// Whether hunks that depend on commits in the workspace are routed to the stack owning these commits.
+const FOLLOW_LOCKS: &str = "gitbutler.routeFollowLocks";
+/// Where `CODEOWNERS` files are looked up, in order of precedence.
+const CODEOWNERS_PATHS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// How the target of a [`RoutedHunk`] was chosen.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type", content = "subject")]
+pub enum Route {
+    /// The hunk depends on commits of the target stack.
+    DependencyLock,
+    /// The hunk depends on commits of more than one stack, so it can't be committed anywhere and is left alone.
+    AmbiguousDependencyLock,
+    /// The hunk was already assigned to the target branch.
+    Assignment,
+    /// The rule with the given name matched.
+    #[serde(rename_all = "camelCase")]
+    Rule {
+        /// The name of the rule, as in `gitbutler.route.<name>`.
+        name: String,
+        /// The criterion that matched, like `path = docs/**`.
+        criterion: String,
+    },
+    /// Nothing else matched, so the hunk goes to the first stack.
+    Default,
+}
+
+/// A hunk along with the branch it was routed to, and why.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutedHunk {
+    /// The worktree-relative path of the file the hunk belongs to.
+    pub path: String,
+    /// The hunk within the file, or `None` if the whole file is affected.
+    pub hunk_header: Option<HunkHeader>,
+    /// Why the hunk was routed to [`branch_name`](Self::branch_name).
+    pub route: Route,
+    /// The stack of the target branch, if there is one.
+    pub stack_id: Option<StackId>,
+    /// The short name of the branch the hunk is committed to, or `None` if it isn't committed.
+    pub branch_name: Option<String>,
+    /// `true` if the hunk was left uncommitted even though it has a target, as another stack was exclusive.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub skipped: bool,
+}
+
+/// Route all uncommitted hunks by the rules configured in `repo` and commit them to their target branches.
+///
+/// Unlike the simple handler, this also reports the route of each hunk in [`Outcome::routed_hunks`].
+/// If `dry_run` is `true`, the routes are computed and returned, but nothing is committed and no
+/// stack is created.
+///
+/// `change_summary` forms the commit message, optionally prefixed by `external_prompt`.
+/// `exclusive_stack` limits commits to one stack when set, with all other hunks marked as skipped.
+/// Hunks that would go to the first stack by default are skipped as well.
+/// `perm` proves that the caller holds exclusive worktree access, and `repo`, `ws`, `db` and
+/// `meta` are supplied by the caller. `context_lines` is used for hunk assignments and commits.
+#[expect(clippy::too_many_arguments)]
+pub(crate) fn handle_changes(
+    change_summary: &str,
+    external_prompt: Option<String>,
+    exclusive_stack: Option<StackId>,
+    dry_run: bool,
+    perm: &mut RepoExclusive,
+    repo: &gix::Repository,
+    ws: &mut but_graph::Workspace,
+    db: &mut DbHandle,
+    meta: &mut impl RefMetadata,
+    context_lines: u32,
+) -> anyhow::Result<Outcome> {
+    let (assignments, _) = but_hunk_assignment::assignments_with_fallback(
+        db.hunk_assignments_mut()?,
+        repo,
+        ws,
+        None::<Vec<but_core::TreeChange>>,
+        context_lines,
+    )
+    .map_err(|err| serde_error::Error::new(&*err))?;
+    if assignments.is_empty() {
+        return Ok(Outcome::default());
+    }
+
+    let stacks = if dry_run {
+        simple::stack_info(ws)
+    } else {
+        simple::stacks_creating_if_none(repo, ws, meta, perm)?
+    };
+    let rules = Rules::from_repo(repo)?;
+    let dependencies = if rules.follow_locks {
+        but_hunk_dependency::ui::hunk_dependencies_for_workspace_changes_by_worktree_dir(
+            repo, ws, None,
+        )
+        .inspect_err(|err| tracing::warn!(?err, "Routing without hunk dependency locks"))
+        .ok()
+    } else {
+        None
+    };
+
+    let mut router = Router {
+        ws,
+        rules: &rules,
+        dependencies: dependencies.as_ref(),
+        default_stack: stacks.first(),
+        codeowners: None,
+        repo,
+    };
+    let mut routed_hunks = Vec::with_capacity(assignments.len());
+    let mut specs_by_branch = BTreeMap::<String, (StackId, Vec<DiffSpec>)>::new();
+    for assignment in assignments {
+        let (route, target) = router.route(&assignment)?;
+        // Like with the simple handler, an exclusive stack leaves all changes alone that weren't
+        // explicitly routed to it.
+        let skipped = target.as_ref().is_some_and(|target| {
+            exclusive_stack
+                .is_some_and(|exclusive| exclusive != target.id || route == Route::Default)
+        });
+        if let Some(target) = &target
+            && !skipped
+        {
+            specs_by_branch
+                .entry(target.branch_name.clone())
+                .or_insert_with(|| (target.id, vec![]))
+                .1
+                .push(assignment.clone().into());
+        }
+        routed_hunks.push(RoutedHunk {
+            path: assignment.path,
+            hunk_header: assignment.hunk_header,
+            route,
+            stack_id: target.as_ref().map(|t| t.id),
+            branch_name: target.map(|t| t.branch_name),
+            skipped,
+        });
+    }
+
+    let mut updated_branches = vec![];
+    if !dry_run {
+        let commit_message = simple::commit_message(change_summary, external_prompt);
+        for (branch_name, (stack_id, specs)) in specs_by_branch {
+            if let Some(new_commit) = simple::commit_to_branch(
+                repo,
+                ws,
+                meta,
+                &branch_name,
+                but_workspace::flatten_diff_specs(specs),
+                &commit_message,
+                context_lines,
+            )? {
+                updated_branches.push(UpdatedBranch {
+                    stack_id,
+                    branch_name,
+                    new_commits: vec![new_commit.to_string()],
+                });
+            }
+        }
+    }
+
+    Ok(Outcome {
+        updated_branches,
+        routed_hunks,
+    })
+}
+
+/// Decide where each hunk goes.
+struct Router<'a> {
+    repo: &'a gix::Repository,
+    ws: &'a but_graph::Workspace,
+    rules: &'a Rules,
+    dependencies: Option<&'a HunkDependencies>,
+    default_stack: Option<&'a StackForAction>,
+    /// Lazily loaded, as only `owner` criteria need it.
+    codeowners: Option<CodeOwners>,
+}
+
+impl Router<'_> {
+    /// Return how `assignment` is routed, and to which branch, if any.
+    fn route(
+        &mut self,
+        assignment: &HunkAssignment,
+    ) -> anyhow::Result<(Route, Option<StackForAction>)> {
+        if let Some(dependencies) = self.dependencies {
+            let mut stacks = locking_stacks(dependencies, assignment);
+            stacks.dedup();
+            match stacks.as_slice() {
+                [] => {}
+                [HunkLockTarget::Stack(stack_id)] => {
+                    if let Some(target) = self.top_branch_of(*stack_id) {
+                        return Ok((Route::DependencyLock, Some(target)));
+                    }
+                }
+                _ => return Ok((Route::AmbiguousDependencyLock, None)),
+            }
+        }
+
+        if let Some(branch_ref) = &assignment.branch_ref_bytes
+            && let Some(target) = self.branch(branch_ref.as_ref())
+        {
+            return Ok((Route::Assignment, Some(target)));
+        }
+
+        let rules = self.rules;
+        for rule in &rules.rules {
+            let Some(criterion) = rule.matches(
+                assignment.path_bytes.as_ref(),
+                self.repo,
+                &mut self.codeowners,
+            )?
+            else {
+                continue;
+            };
+            let Ok(branch_ref) =
+                gix::refs::FullName::try_from(format!("refs/heads/{}", rule.branch))
+            else {
+                continue;
+            };
+            match self.branch(branch_ref.as_ref()) {
+                Some(target) => {
+                    return Ok((
+                        Route::Rule {
+                            name: rule.name.clone(),
+                            criterion: criterion.to_string(),
+                        },
+                        Some(target),
+                    ));
+                }
+                None => {
+                    tracing::debug!(
+                        rule = rule.name,
+                        branch = rule.branch,
+                        "Ignoring routing rule as its branch isn't applied"
+                    );
+                }
+            }
+        }
+
+        Ok((
+            Route::Default,
+            self.default_stack.map(|s| StackForAction {
+                id: s.id,
+                branch_name: s.branch_name.clone(),
+            }),
+        ))
+    }
+
+    /// Return the stack containing the branch `name`, if it's applied to the workspace.
+    fn branch(&self, name: &gix::refs::FullNameRef) -> Option<StackForAction> {
+        let (stack, _segment) = self.ws.find_segment_and_stack_by_refname(name)?;
+        Some(StackForAction {
+            id: stack.id?,
+            branch_name: name.shorten().to_string(),
+        })
+    }
+
+    /// Return the topmost branch of the stack with `id`, so commits end up above all of its dependencies.
+    fn top_branch_of(&self, id: StackId) -> Option<StackForAction> {
+        let stack = self.ws.find_stack_by_id(id)?;
+        Some(StackForAction {
+            id,
+            branch_name: stack.ref_name()?.shorten().to_string(),
+        })
+    }
+}
+
+/// Return the targets of all locks on hunks of `dependencies` that overlap with `assignment`.
+///
+/// Hunks without header, like binary files, can't be matched by range and are never locked.
+fn locking_stacks(
+    dependencies: &HunkDependencies,
+    assignment: &HunkAssignment,
+) -> Vec<HunkLockTarget> {
+    let Some(header) = assignment.hunk_header else {
+        return vec![];
+    };
+    let mut out: Vec<_> = dependencies
+        .diffs
+        .iter()
+        .filter(|(path, hunk, _)| {
+            *path == assignment.path
+                && ranges_overlap(
+                    hunk.new_start,
+                    hunk.new_lines,
+                    header.new_start,
+                    header.new_lines,
+                )
+        })
+        .flat_map(|(_, _, locks)| locks.iter().map(|lock| lock.target))
+        .collect();
+    out.sort_by_key(|target| match target {
+        HunkLockTarget::Stack(id) => Some(*id),
+        HunkLockTarget::Unidentified => None,
+    });
+    out
+}
+
+/// Check whether two line ranges `[start, start + lines)` overlap, with empty ranges treated as a point at `start`.
+fn ranges_overlap(start_a: u32, lines_a: u32, start_b: u32, lines_b: u32) -> bool {
+    let end_a = start_a + lines_a.max(1);
+    let end_b = start_b + lines_b.max(1);
+    start_a < end_b && start_b < end_a
+}
+
+/// All routing configuration of a repository.
+struct Rules {
+    rules: Vec<Rule>,
+    follow_locks: bool,
+}
+
+/// A single rule, as configured in `gitbutler.route.<name>`.
+struct Rule {
+    name: String,
+    /// The short name of the branch to route matching hunks to.
+    branch: String,
+    criteria: Vec<Criterion>,
+}
+
+/// What a [`Rule`] matches hunks by.
+enum Criterion {
+    Path(Glob),
+    Extension(BString),
+    Owner(String),
+}
+
+impl std::fmt::Display for Criterion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Criterion::Path(glob) => write!(f, "path = {}", glob.text),
+            Criterion::Extension(ext) => write!(f, "extension = {ext}"),
+            Criterion::Owner(owner) => write!(f, "owner = {owner}"),
+        }
+    }
+}
+
+impl Rules {
+    /// Read all rules from the configuration of `repo`, failing if one of them is invalid.
+    fn from_repo(repo: &gix::Repository) -> anyhow::Result<Self> {
+        let config = repo.config_snapshot();
+        let mut rules = Vec::new();
+        for section in config.sections_by_name("gitbutler").into_iter().flatten() {
+            let Some(name) = section
+                .header()
+                .subsection_name()
+                .and_then(|name| name.strip_prefix(RULE_SUBSECTION_PREFIX))
+            else {
+                continue;
+            };
+            let name = name.to_str_lossy().into_owned();
+            let branch = section
+                .value("branch")
+                .with_context(|| format!("Routing rule '{name}' needs a 'branch' to route to"))?
+                .to_str_lossy()
+                .into_owned();
+            let mut criteria = Vec::new();
+            for path in section.values("path") {
+                criteria.push(Criterion::Path(Glob::new(&path).with_context(|| {
+                    format!("Routing rule '{name}' has an invalid path pattern: '{path}'")
+                })?));
+            }
+            criteria.extend(section.values("extension").into_iter().map(|ext| {
+                let ext = ext.as_bytes();
+                Criterion::Extension(ext.strip_prefix(b".").unwrap_or(ext).into())
+            }));
+            criteria.extend(
+                section
+                    .values("owner")
+                    .into_iter()
+                    .map(|owner| Criterion::Owner(owner.to_str_lossy().into_owned())),
+            );
+            if criteria.is_empty() {
+                tracing::warn!(rule = name, "Routing rule without criteria never matches");
+            }
+            rules.push(Rule {
+                name,
+                branch,
+                criteria,
+            });
+        }
+        Ok(Rules {
+            rules,
+            follow_locks: config.boolean(FOLLOW_LOCKS).unwrap_or(true),
+        })
+    }
+}
+
+impl Rule {
+    /// Return the first criterion matching `rela_path`, loading `codeowners` from `repo` if needed.
+    fn matches(
+        &self,
+        rela_path: &BStr,
+        repo: &gix::Repository,
+        codeowners: &mut Option<CodeOwners>,
+    ) -> anyhow::Result<Option<&Criterion>> {
+        for criterion in &self.criteria {
+            let is_match = match criterion {
+                Criterion::Path(glob) => glob.matches(rela_path),
+                Criterion::Extension(ext) => rela_path
+                    .rsplit_once_str(b"/")
+                    .map_or(rela_path.as_bytes(), |(_, name)| name)
+                    .rsplit_once_str(b".")
+                    .is_some_and(|(stem, file_ext)| !stem.is_empty() && file_ext == ext.as_bytes()),
+                Criterion::Owner(owner) => {
+                    if codeowners.is_none() {
+                        *codeowners = Some(CodeOwners::from_repo(repo)?);
+                    }
+                    codeowners
+                        .as_ref()
+                        .and_then(|codeowners| codeowners.owners(rela_path))
+                        .is_some_and(|owners| owners.iter().any(|o| o.eq_ignore_ascii_case(owner)))
+                }
+            };
+            if is_match {
+                return Ok(Some(criterion));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// A gitignore-style pattern that matches files by their own path or the path of any of their parent directories.
+struct Glob {
+    text: String,
+    pattern: gix::glob::Pattern,
+}
+
+impl Glob {
+    fn new(text: &BStr) -> Option<Self> {
+        Some(Glob {
+            text: text.to_str_lossy().into_owned(),
+            pattern: gix::glob::Pattern::from_bytes(text)?,
+        })
+    }
+
+    fn matches(&self, rela_path: &BStr) -> bool {
+        let is_match = |path: &BStr, is_dir: bool| {
+            self.pattern.matches_repo_relative_path(
+                path,
+                path.rfind_byte(b'/').map(|pos| pos + 1),
+                Some(is_dir),
+                gix::glob::pattern::Case::Sensitive,
+                gix::glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL,
+            )
+        };
+        is_match(rela_path, false)
+            || rela_path
+                .find_iter("/")
+                .any(|pos| is_match(rela_path[..pos].as_bstr(), true))
+    }
+}
+
+/// The parsed `CODEOWNERS` file of a repository.
+#[derive(Default)]
+struct CodeOwners {
+    entries: Vec<(Glob, Vec<String>)>,
+}
+
+impl CodeOwners {
+    /// Read the first `CODEOWNERS` file found in the worktree of `repo`, or return an empty instance if there is none.
+    fn from_repo(repo: &gix::Repository) -> anyhow::Result<Self> {
+        let Some(workdir) = repo.workdir() else {
+            return Ok(Self::default());
+        };
+        for path in CODEOWNERS_PATHS {
+            match std::fs::read(workdir.join(path)) {
+                Ok(data) => return Ok(Self::from_bytes(&data)),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    return Err(err).with_context(|| format!("Could not read '{path}'"));
+                }
+            }
+        }
+        Ok(Self::default())
+    }
+
+    fn from_bytes(data: &[u8]) -> Self {
+        let entries = data
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with(b"#") {
+                    return None;
+                }
+                let mut fields = line.fields();
+                let glob = Glob::new(fields.next()?.as_bstr())?;
+                let owners = fields
+                    .take_while(|field| !field.starts_with(b"#"))
+                    .map(|owner| owner.to_str_lossy().into_owned())
+                    .collect();
+                Some((glob, owners))
+            })
+            .collect();
+        CodeOwners { entries }
+    }
+
+    /// Return the owners of `rela_path`, as assigned by the last matching entry.
+    fn owners(&self, rela_path: &BStr) -> Option<&[String]> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(glob, _)| glob.matches(rela_path))
+            .map(|(_, owners)| owners.as_slice())
+    }
+}
@@ -10,9 +10,9 @@ use but_rebase::graph_rebase::{
 
 use crate::Outcome;
 
-struct StackForAction {
-    id: StackId,
-    branch_name: String,
+pub(crate) struct StackForAction {
+    pub(crate) id: StackId,
+    pub(crate) branch_name: String,
 }
 
 /// Create commits for currently uncommitted changes and report the updated stacks.
@@ -53,9 +53,7 @@ pub(crate) fn handle_changes(
     )
     .map_err(|err| serde_error::Error::new(&*err))?;
     if assignments.is_empty() {
-        return Ok(Outcome {
-            updated_branches: vec![],
-        });
+        return Ok(Outcome::default());
     }
 
     // Get the current stacks in the workspace, creating one if none exists.
@@ -89,12 +87,7 @@ pub(crate) fn handle_changes(
     }
 
     let mut updated_branches = vec![];
-
-    let commit_message = if let Some(prompt) = external_prompt {
-        format!("{prompt}\n\n{change_summary}")
-    } else {
-        change_summary.to_string()
-    };
+    let commit_message = commit_message(change_summary, external_prompt);
 
     for (stack_id, diff_specs) in stack_assignments {
         if diff_specs.is_empty() {
@@ -111,33 +104,15 @@ pub(crate) fn handle_changes(
             .find(|s| s.id == stack_id)
             .map(|s| s.branch_name.clone())
             .ok_or(anyhow!("Could not find associated reference name"))?;
-        let full_ref_name: gix::refs::FullName =
-            format!("refs/heads/{stack_branch_name}").try_into()?;
-
-        let editor = Editor::create(ws, meta, repo)?;
-        let outcome = but_workspace::commit::commit_create(
-            editor,
+        if let Some(new_commit) = commit_to_branch(
+            repo,
+            ws,
+            meta,
+            &stack_branch_name,
             diff_specs,
-            RelativeToRef::Reference(full_ref_name.as_ref()),
-            InsertSide::Below,
             &commit_message,
             context_lines,
-            but_workspace::commit::ChangeSource::Head,
-        )?;
-
-        if !outcome.rejected_specs.is_empty() {
-            tracing::warn!(
-                ?outcome.rejected_specs,
-                "Failed to commit at least one hunk"
-            );
-        }
-
-        if let Some(new_commit) = outcome
-            .commit_selector
-            .map(|selector| outcome.rebase.lookup_pick(selector))
-            .transpose()?
-        {
-            outcome.rebase.materialize(Default::default())?;
+        )? {
             updated_branches.push(crate::UpdatedBranch {
                 stack_id,
                 branch_name: stack_branch_name,
@@ -146,7 +121,63 @@ pub(crate) fn handle_changes(
         }
     }
 
-    Ok(Outcome { updated_branches })
+    Ok(Outcome {
+        updated_branches,
+        routed_hunks: vec![],
+    })
+}
+
+/// Build the commit message from `change_summary`, prefixed by `external_prompt` if present.
+pub(crate) fn commit_message(change_summary: &str, external_prompt: Option<String>) -> String {
+    if let Some(prompt) = external_prompt {
+        format!("{prompt}\n\n{change_summary}")
+    } else {
+        change_summary.to_string()
+    }
+}
+
+/// Commit `diff_specs` on top of the local branch `branch_name` and materialize the rebase.
+///
+/// Returns the id of the new commit, or `None` if nothing could be committed. Rejected specs are
+/// logged rather than treated as error, so one unfit hunk doesn't block the others.
+pub(crate) fn commit_to_branch(
+    repo: &gix::Repository,
+    ws: &mut but_graph::Workspace,
+    meta: &mut impl RefMetadata,
+    branch_name: &str,
+    diff_specs: Vec<DiffSpec>,
+    commit_message: &str,
+    context_lines: u32,
+) -> anyhow::Result<Option<gix::ObjectId>> {
+    let full_ref_name: gix::refs::FullName = format!("refs/heads/{branch_name}").try_into()?;
+
+    let editor = Editor::create(ws, meta, repo)?;
+    let outcome = but_workspace::commit::commit_create(
+        editor,
+        diff_specs,
+        RelativeToRef::Reference(full_ref_name.as_ref()),
+        InsertSide::Below,
+        commit_message,
+        context_lines,
+        but_workspace::commit::ChangeSource::Head,
+    )?;
+
+    if !outcome.rejected_specs.is_empty() {
+        tracing::warn!(
+            ?outcome.rejected_specs,
+            "Failed to commit at least one hunk"
+        );
+    }
+
+    let Some(new_commit) = outcome
+        .commit_selector
+        .map(|selector| outcome.rebase.lookup_pick(selector))
+        .transpose()?
+    else {
+        return Ok(None);
+    };
+    outcome.rebase.materialize(Default::default())?;
+    Ok(Some(new_commit))
 }
 
 /// Return the applied stacks that can receive action commits, creating one if none exists.
@@ -155,7 +186,7 @@ pub(crate) fn handle_changes(
 /// updated when the first stack has to be created. `meta` records the stack metadata written by the
 /// reference creation operation. `_perm` proves that the caller holds exclusive worktree access for
 /// the reference creation path.
-fn stacks_creating_if_none(
+pub(crate) fn stacks_creating_if_none(
     repo: &gix::Repository,
     ws: &mut but_graph::Workspace,
     meta: &mut impl RefMetadata,
@@ -195,7 +226,7 @@ fn stacks_creating_if_none(
 ///
 /// Stacks without an ID or reference name are skipped because the action needs both values to map
 /// assignments to a branch and report the resulting update.
-fn stack_info(ws: &but_graph::Workspace) -> Vec<StackForAction> {
+pub(crate) fn stack_info(ws: &but_graph::Workspace) -> Vec<StackForAction> {
     ws.stacks
         .iter()
         .filter_map(|stack| {
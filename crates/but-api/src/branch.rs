@@ -1,6 +1,6 @@
 use std::{borrow::Cow, collections::BTreeMap};
 
-use crate::WorkspaceState;
+use crate::{WorkspaceState, hooks};
 use anyhow::{Context as _, bail};
 use bstr::ByteSlice;
 use but_api_macros::but_api;
@@ -801,6 +801,121 @@ pub mod json {
             })
         }
     }
+
+    /// JSON sibling of [`crate::branch::BranchRangeDiff`].
+    #[derive(Debug, Serialize)]
+    #[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
+    #[serde(rename_all = "camelCase")]
+    pub struct BranchRangeDiff {
+        /// The remote-tracking branch that holds the old version of the branch.
+        pub upstream: crate::json::FullRefName,
+        /// The commits of both versions, in the order of the local version, oldest first.
+        pub entries: Vec<RangeDiffEntry>,
+    }
+    #[cfg(feature = "export-schema")]
+    but_schemars::register_sdk_type!(BranchRangeDiff);
+
+    /// JSON sibling of [`but_workspace::range_diff::RangeDiffEntry`].
+    #[derive(Debug, Serialize)]
+    #[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
+    #[serde(rename_all = "camelCase")]
+    pub struct RangeDiffEntry {
+        /// The commit in the old version, if there is one.
+        pub old: Option<crate::json::HexHashString>,
+        /// The commit in the new version, if there is one.
+        pub new: Option<crate::json::HexHashString>,
+        /// The title of the commit, taken from the new version if there is one.
+        pub title: String,
+        /// How `old` and `new` relate.
+        pub status: RangeDiffStatus,
+        /// How `old` and `new` were paired, or `null` if only one of them is set.
+        pub pairing: Option<RangeDiffPairing>,
+        /// The diff from the patch of `old` to the patch of `new`, including their messages,
+        /// or `null` if the patches are the same or there is nothing to compare to.
+        pub interdiff: Option<String>,
+    }
+    #[cfg(feature = "export-schema")]
+    but_schemars::register_sdk_type!(RangeDiffEntry);
+
+    /// JSON sibling of [`but_workspace::range_diff::RangeDiffStatus`].
+    #[derive(Debug, Clone, Copy, Serialize)]
+    #[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
+    #[serde(rename_all = "camelCase")]
+    pub enum RangeDiffStatus {
+        /// The commit only exists in the new version.
+        Added,
+        /// The commit only exists in the old version.
+        Removed,
+        /// Message and patch of both commits are the same.
+        Unchanged,
+        /// Message or patch changed.
+        Modified,
+    }
+    #[cfg(feature = "export-schema")]
+    but_schemars::register_sdk_type!(RangeDiffStatus);
+
+    /// JSON sibling of [`but_workspace::range_diff::RangeDiffPairing`].
+    #[derive(Debug, Clone, Copy, Serialize)]
+    #[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
+    #[serde(rename_all = "camelCase")]
+    pub enum RangeDiffPairing {
+        /// Both commits have the same change-id.
+        ChangeId,
+        /// The commits add and remove similar lines.
+        Similarity,
+    }
+    #[cfg(feature = "export-schema")]
+    but_schemars::register_sdk_type!(RangeDiffPairing);
+
+    impl From<super::BranchRangeDiff> for BranchRangeDiff {
+        fn from(value: super::BranchRangeDiff) -> Self {
+            let super::BranchRangeDiff {
+                upstream,
+                range_diff,
+            } = value;
+            Self {
+                upstream: upstream.into(),
+                entries: range_diff.entries.into_iter().map(Into::into).collect(),
+            }
+        }
+    }
+
+    impl From<but_workspace::range_diff::RangeDiffEntry> for RangeDiffEntry {
+        fn from(value: but_workspace::range_diff::RangeDiffEntry) -> Self {
+            let but_workspace::range_diff::RangeDiffEntry {
+                old,
+                new,
+                title,
+                status,
+                pairing,
+                interdiff,
+            } = value;
+            Self {
+                old: old.map(Into::into),
+                new: new.map(Into::into),
+                title: title.to_str_lossy().into_owned(),
+                status: match status {
+                    but_workspace::range_diff::RangeDiffStatus::Added => RangeDiffStatus::Added,
+                    but_workspace::range_diff::RangeDiffStatus::Removed => RangeDiffStatus::Removed,
+                    but_workspace::range_diff::RangeDiffStatus::Unchanged => {
+                        RangeDiffStatus::Unchanged
+                    }
+                    but_workspace::range_diff::RangeDiffStatus::Modified => {
+                        RangeDiffStatus::Modified
+                    }
+                },
+                pairing: pairing.map(|pairing| match pairing {
+                    but_workspace::range_diff::RangeDiffPairing::ChangeId => {
+                        RangeDiffPairing::ChangeId
+                    }
+                    but_workspace::range_diff::RangeDiffPairing::Similarity => {
+                        RangeDiffPairing::Similarity
+                    }
+                }),
+                interdiff: interdiff.map(|interdiff| interdiff.to_str_lossy().into_owned()),
+            }
+        }
+    }
 }
 
 /// Applies a branch using the behavior described by [`apply_only_with_perm()`].
@@ -896,6 +1011,29 @@ pub fn apply_with_perm(
     {
         snapshot.commit(ctx, perm).ok();
     }
+    if let Ok(out) = &res
+        && out.status.persisted_mutation()
+    {
+        let repo = ctx.repo.get()?;
+        hooks::run_post(&repo, hooks::Event::PostApply, || {
+            let refs = out
+                .applied_branches
+                .iter()
+                .map(|name| {
+                    let stack_id = out
+                        .workspace
+                        .find_segment_and_stack_by_refname(name.as_ref())
+                        .and_then(|(stack, _)| stack.id);
+                    Ok(hooks::RefUpdate::unchanged(
+                        stack_id,
+                        name.as_ref(),
+                        hooks::peel(&repo, name.as_ref())?,
+                    ))
+                })
+                .collect::<anyhow::Result<_>>()?;
+            Ok(hooks::Payload { refs, target: None })
+        });
+    }
     res
 }
 
@@ -1657,6 +1795,51 @@ pub fn branch_diff(ctx: &Context, branch: String) -> anyhow::Result<TreeChanges>
     but_workspace::ui::diff::changes_in_branch(&repo, &ws, branch.name())
 }
 
+/// Compares the version of `branch` on its remote-tracking branch with the local version, commit by commit.
+///
+/// This shows what changed relative to what was pushed, for instance after commits were amended or
+/// reordered, before force-pushing. Commits reachable from the project target are left out, so
+/// rebasing the branch onto a newer target doesn't make the target's commits show up.
+/// For lower-level implementation details, see [`but_workspace::range_diff()`].
+#[but_api(napi, json::BranchRangeDiff)]
+#[instrument(err(Debug))]
+pub fn branch_range_diff(ctx: &Context, branch: String) -> anyhow::Result<BranchRangeDiff> {
+    let project_meta = ctx.project_meta()?;
+    let _guard = ctx.shared_worktree_access();
+    let repo = ctx.repo.get()?;
+    let mut local = repo.find_reference(&branch)?;
+    let upstream =
+        but_workspace::resolve_tracking_branch_ref_name(local.name(), &repo)?.into_owned();
+    let new_tip = local.peel_to_id()?.detach();
+    let old_tip = repo
+        .find_reference(upstream.as_ref())?
+        .peel_to_id()?
+        .detach();
+
+    let mut hidden: Vec<_> = project_meta.target_commit_id.into_iter().collect();
+    if let Some(target_ref) = project_meta.target_ref.as_ref()
+        && let Some(mut target) = repo.try_find_reference(target_ref.as_ref())?
+    {
+        hidden.push(target.peel_to_id()?.detach());
+    }
+
+    let range_diff =
+        but_workspace::range_diff(&repo, old_tip, new_tip, hidden, ctx.settings.context_lines)?;
+    Ok(BranchRangeDiff {
+        upstream,
+        range_diff,
+    })
+}
+
+/// The outcome of comparing a branch with its remote-tracking branch.
+#[derive(Debug)]
+pub struct BranchRangeDiff {
+    /// The remote-tracking branch that holds the old version of the branch.
+    pub upstream: gix::refs::FullName,
+    /// The commits of both versions.
+    pub range_diff: but_workspace::range_diff::RangeDiff,
+}
+
 /// Lists all local and remote branches of the project, grouped into stacks where
 /// known, and enriched with cached review (PR/MR) information.
 ///
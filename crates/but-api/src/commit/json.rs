@@ -10,7 +10,8 @@ use super::types::{
     CommitCherryPickResult as EngineCommitCherryPickResult,
     CommitCreateResult as EngineCommitCreateResult,
     CommitInsertBlankResult as EngineCommitInsertBlankResult,
-    CommitMoveResult as EngineCommitMoveResult, CommitRewordResult as EngineCommitRewordResult,
+    CommitMoveResult as EngineCommitMoveResult, CommitRevertResult as EngineCommitRevertResult,
+    CommitRewordResult as EngineCommitRewordResult, CommitSplitResult as EngineCommitSplitResult,
     CommitSquashResult as EngineCommitSquashResult, MoveChangesResult as EngineMoveChangesResult,
     UncommitChangesFromCommitsResult as EngineUncommitChangesFromCommitsResult,
 };
@@ -46,6 +47,68 @@ impl TryFrom<EngineCommitCherryPickResult> for CommitCherryPickResult {
     }
 }
 
+/// JSON transport type for splitting a commit.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSplitResult {
+    /// The IDs of the commits created for each piece, in parent-to-child order.
+    #[cfg_attr(feature = "export-schema", schemars(with = "Vec<String>"))]
+    pub new_commits: Vec<HexHash>,
+    /// Workspace state after the split.
+    pub workspace: crate::json::WorkspaceState,
+}
+
+#[cfg(feature = "export-schema")]
+but_schemars::register_sdk_type!(CommitSplitResult);
+
+impl TryFrom<EngineCommitSplitResult> for CommitSplitResult {
+    type Error = anyhow::Error;
+
+    fn try_from(value: EngineCommitSplitResult) -> Result<Self, Self::Error> {
+        let EngineCommitSplitResult {
+            new_commits,
+            workspace,
+        } = value;
+
+        Ok(Self {
+            new_commits: new_commits.into_iter().map(Into::into).collect(),
+            workspace: workspace.try_into()?,
+        })
+    }
+}
+
+/// JSON transport type for reverting a commit.
+#[derive(Debug, Serialize)]
+#[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CommitRevertResult {
+    /// The ID of the commit that undoes the reverted commit's changes.
+    #[cfg_attr(feature = "export-schema", schemars(with = "String"))]
+    pub new_commit: HexHash,
+    /// Workspace state after the revert.
+    pub workspace: crate::json::WorkspaceState,
+}
+
+#[cfg(feature = "export-schema")]
+but_schemars::register_sdk_type!(CommitRevertResult);
+
+impl TryFrom<EngineCommitRevertResult> for CommitRevertResult {
+    type Error = anyhow::Error;
+
+    fn try_from(value: EngineCommitRevertResult) -> Result<Self, Self::Error> {
+        let EngineCommitRevertResult {
+            new_commit,
+            workspace,
+        } = value;
+
+        Ok(Self {
+            new_commit: new_commit.into(),
+            workspace: workspace.try_into()?,
+        })
+    }
+}
+
 /// JSON transport type for moving changes between commits.
 #[derive(Debug, Serialize)]
 #[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
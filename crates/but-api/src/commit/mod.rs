@@ -22,9 +22,15 @@ pub mod move_changes;
 /// Functions for moving commits within the graph.
 pub mod move_commit;
 
+/// Functions for reverting commits.
+pub mod revert;
+
 /// Functions for rewording commits.
 pub mod reword;
 
+/// Functions for splitting commits.
+pub mod split;
+
 /// Functions for squashing commits.
 pub mod squash;
 
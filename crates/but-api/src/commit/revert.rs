@@ -0,0 +1,117 @@
+use but_api_macros::but_api;
+use but_core::{DryRun, sync::RepoExclusive};
+use but_oplog::legacy::{OperationKind, SnapshotDetails, Trailer};
+use but_rebase::graph_rebase::{
+    Editor, LookupStep as _,
+    mutate::{InsertSide, RelativeTo},
+};
+use tracing::instrument;
+
+use crate::WorkspaceState;
+
+use super::types::CommitRevertResult;
+
+/// Creates a commit that undoes the changes of `subject_commit_id` and places it
+/// on `side` of `relative_to`.
+///
+/// The subject may live anywhere in the repository, including on the target
+/// branch or in another stack. If the inverted changes don't apply cleanly at
+/// the destination, the revert is recorded as a conflicted commit. When
+/// `dry_run` is enabled, the returned workspace previews the revert without
+/// materializing it.
+#[but_api(try_from = crate::commit::json::CommitRevertResult)]
+#[instrument(err(Debug))]
+pub fn commit_revert_only(
+    ctx: &mut but_ctx::Context,
+    subject_commit_id: gix::ObjectId,
+    #[but_api(crate::commit::json::RelativeTo)] relative_to: RelativeTo,
+    side: InsertSide,
+    dry_run: DryRun,
+) -> anyhow::Result<CommitRevertResult> {
+    let mut guard = ctx.exclusive_worktree_access();
+    commit_revert_only_with_perm(
+        ctx,
+        subject_commit_id,
+        relative_to,
+        side,
+        dry_run,
+        guard.write_permission(),
+    )
+}
+
+/// Reverts a commit under caller-held exclusive repository access without
+/// recording an oplog snapshot.
+pub fn commit_revert_only_with_perm(
+    ctx: &mut but_ctx::Context,
+    subject_commit_id: gix::ObjectId,
+    relative_to: RelativeTo,
+    side: InsertSide,
+    dry_run: DryRun,
+    perm: &mut RepoExclusive,
+) -> anyhow::Result<CommitRevertResult> {
+    let mut meta = ctx.meta()?;
+    let (repo, mut ws, db) = ctx.workspace_mut_and_db_with_perm(perm)?;
+    let editor = Editor::create(&mut ws, &mut meta, &repo)?;
+    let (rebase, revert_selector) =
+        but_workspace::commit::revert_commit(editor, subject_commit_id, relative_to, side)?;
+    let new_commit = rebase.lookup_pick(revert_selector)?;
+
+    Ok(CommitRevertResult {
+        new_commit,
+        workspace: WorkspaceState::from_successful_rebase_with_db(rebase, &repo, dry_run, &db)?,
+    })
+}
+
+/// Creates a commit that undoes the changes of `subject_commit_id` on `side`
+/// of `relative_to` and records an oplog snapshot on success.
+///
+/// When `dry_run` is enabled, the returned workspace previews the revert and
+/// no oplog entry is persisted. For details, see [`commit_revert_only()`].
+#[but_api(napi, try_from = crate::commit::json::CommitRevertResult)]
+#[instrument(err(Debug))]
+pub fn commit_revert(
+    ctx: &mut but_ctx::Context,
+    subject_commit_id: gix::ObjectId,
+    #[but_api(crate::commit::json::RelativeTo)] relative_to: RelativeTo,
+    side: InsertSide,
+    dry_run: DryRun,
+) -> anyhow::Result<CommitRevertResult> {
+    let mut guard = ctx.exclusive_worktree_access();
+    commit_revert_with_perm(
+        ctx,
+        subject_commit_id,
+        relative_to,
+        side,
+        dry_run,
+        guard.write_permission(),
+    )
+}
+
+/// Reverts a commit under caller-held exclusive repository access and records
+/// an oplog snapshot on success.
+pub fn commit_revert_with_perm(
+    ctx: &mut but_ctx::Context,
+    subject_commit_id: gix::ObjectId,
+    relative_to: RelativeTo,
+    side: InsertSide,
+    dry_run: DryRun,
+    perm: &mut RepoExclusive,
+) -> anyhow::Result<CommitRevertResult> {
+    let details = SnapshotDetails::new(OperationKind::RevertCommit)
+        .with_trailers([Trailer::Sha(subject_commit_id)]);
+    let maybe_oplog_entry = but_oplog::UnmaterializedOplogSnapshot::from_details_with_perm(
+        ctx,
+        details,
+        perm.read_permission(),
+        dry_run,
+    );
+
+    let res =
+        commit_revert_only_with_perm(ctx, subject_commit_id, relative_to, side, dry_run, perm);
+    if let Some(snapshot) = maybe_oplog_entry
+        && res.is_ok()
+    {
+        snapshot.commit(ctx, perm).ok();
+    }
+    res
+}
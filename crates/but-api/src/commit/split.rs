@@ -0,0 +1,111 @@
+use but_api_macros::but_api;
+use but_core::{DryRun, sync::RepoExclusive};
+use but_oplog::legacy::{OperationKind, SnapshotDetails, Trailer};
+use but_rebase::graph_rebase::{Editor, LookupStep as _};
+use tracing::instrument;
+
+use crate::WorkspaceState;
+
+use super::types::{CommitSplitPiece, CommitSplitResult};
+
+/// Splits `commit_id` into one commit per entry in `pieces`, stacked in the
+/// given order, without recording an oplog snapshot.
+///
+/// Changes no piece selects stay in the first piece, which also keeps the
+/// change-id of `commit_id`. Descendants are rebased onto the last piece. When
+/// `dry_run` is enabled, the returned workspace previews the split without
+/// materializing it. For lower-level implementation details, see
+/// [`but_workspace::commit::split_commit()`].
+#[but_api(try_from = crate::commit::json::CommitSplitResult)]
+#[instrument(err(Debug))]
+pub fn commit_split_only(
+    ctx: &mut but_ctx::Context,
+    commit_id: gix::ObjectId,
+    pieces: Vec<CommitSplitPiece>,
+    dry_run: DryRun,
+) -> anyhow::Result<CommitSplitResult> {
+    let mut guard = ctx.exclusive_worktree_access();
+    commit_split_only_with_perm(ctx, commit_id, pieces, dry_run, guard.write_permission())
+}
+
+/// Splits a commit under caller-held exclusive repository access without
+/// recording an oplog snapshot.
+pub fn commit_split_only_with_perm(
+    ctx: &mut but_ctx::Context,
+    commit_id: gix::ObjectId,
+    pieces: Vec<CommitSplitPiece>,
+    dry_run: DryRun,
+    perm: &mut RepoExclusive,
+) -> anyhow::Result<CommitSplitResult> {
+    let context_lines = ctx.settings.context_lines;
+    let mut meta = ctx.meta()?;
+    let (repo, mut ws, db) = ctx.workspace_mut_and_db_with_perm(perm)?;
+    let editor = Editor::create(&mut ws, &mut meta, &repo)?;
+    let pieces = pieces
+        .into_iter()
+        .map(|piece| but_workspace::commit::CommitSplitPiece {
+            changes: piece.changes,
+            message: piece.message.into(),
+        })
+        .collect();
+    let outcome = but_workspace::commit::split_commit(editor, commit_id, pieces, context_lines)?;
+    let new_commits = outcome
+        .piece_selectors
+        .into_iter()
+        .map(|selector| outcome.rebase.lookup_pick(selector))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    Ok(CommitSplitResult {
+        new_commits,
+        workspace: WorkspaceState::from_successful_rebase_with_db(
+            outcome.rebase,
+            &repo,
+            dry_run,
+            &db,
+        )?,
+    })
+}
+
+/// Splits `commit_id` into one commit per entry in `pieces` and records an
+/// oplog snapshot on success.
+///
+/// When `dry_run` is enabled, the returned workspace previews the split and
+/// no oplog entry is persisted. For details, see [`commit_split_only()`].
+#[but_api(napi, try_from = crate::commit::json::CommitSplitResult)]
+#[instrument(err(Debug))]
+pub fn commit_split(
+    ctx: &mut but_ctx::Context,
+    commit_id: gix::ObjectId,
+    pieces: Vec<CommitSplitPiece>,
+    dry_run: DryRun,
+) -> anyhow::Result<CommitSplitResult> {
+    let mut guard = ctx.exclusive_worktree_access();
+    commit_split_with_perm(ctx, commit_id, pieces, dry_run, guard.write_permission())
+}
+
+/// Splits a commit under caller-held exclusive repository access and records
+/// an oplog snapshot on success.
+pub fn commit_split_with_perm(
+    ctx: &mut but_ctx::Context,
+    commit_id: gix::ObjectId,
+    pieces: Vec<CommitSplitPiece>,
+    dry_run: DryRun,
+    perm: &mut RepoExclusive,
+) -> anyhow::Result<CommitSplitResult> {
+    let details =
+        SnapshotDetails::new(OperationKind::SplitCommit).with_trailers([Trailer::Sha(commit_id)]);
+    let maybe_oplog_entry = but_oplog::UnmaterializedOplogSnapshot::from_details_with_perm(
+        ctx,
+        details,
+        perm.read_permission(),
+        dry_run,
+    );
+
+    let res = commit_split_only_with_perm(ctx, commit_id, pieces, dry_run, perm);
+    if let Some(snapshot) = maybe_oplog_entry
+        && res.is_ok()
+    {
+        snapshot.commit(ctx, perm).ok();
+    }
+    res
+}
@@ -42,6 +42,8 @@ pub fn commit_squash_only(
 ///
 /// This variant does not create an oplog entry. When `dry_run` is enabled, it
 /// returns a preview of the resulting workspace state without materializing the rebases.
+///
+/// The squashed message must comply with the commit message policy of the repository.
 pub fn commit_squash_only_with_perm(
     ctx: &mut but_ctx::Context,
     subject_commit_ids: Vec<gix::ObjectId>,
@@ -66,6 +68,10 @@ pub fn commit_squash_only_with_perm(
         how_to_combine_messages,
     )?;
     let new_commit = rebase.lookup_pick(commit_selector)?;
+    but_core::commit::message_policy::enforce(
+        rebase.repo(),
+        rebase.repo().find_commit(new_commit)?.message_raw()?,
+    )?;
     let workspace = WorkspaceState::from_successful_rebase_with_db(rebase, &repo, dry_run, &db)?;
 
     Ok(CommitSquashResult {
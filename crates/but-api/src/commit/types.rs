@@ -37,6 +37,22 @@ pub struct UncommitChangesSource {
 #[cfg(feature = "export-schema")]
 but_schemars::register_sdk_type!(UncommitChangesSource);
 
+/// One of the commits a commit is split into.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct CommitSplitPiece {
+    /// The changes of the split commit that go into this piece.
+    ///
+    /// Changes that no piece selects stay in the first piece.
+    pub changes: Vec<DiffSpec>,
+    /// The message of the commit created for this piece.
+    pub message: String,
+}
+
+#[cfg(feature = "export-schema")]
+but_schemars::register_sdk_type!(CommitSplitPiece);
+
 /// A grouped source that could not be uncommitted.
 pub struct UncommitChangesFailure {
     /// The commit whose changes failed to uncommit.
@@ -85,6 +101,22 @@ pub struct CommitCherryPickResult {
     pub workspace: WorkspaceState,
 }
 
+/// Outcome of splitting a commit.
+pub struct CommitSplitResult {
+    /// The IDs of the commits created for each piece, in parent-to-child order.
+    pub new_commits: Vec<gix::ObjectId>,
+    /// Workspace state after the split.
+    pub workspace: WorkspaceState,
+}
+
+/// Outcome of reverting a commit.
+pub struct CommitRevertResult {
+    /// The ID of the commit that undoes the reverted commit's changes.
+    pub new_commit: gix::ObjectId,
+    /// Workspace state after the revert.
+    pub workspace: WorkspaceState,
+}
+
 /// Outcome after inserting a blank commit.
 pub struct CommitInsertBlankResult {
     /// The ID of the newly inserted blank commit.
@@ -161,9 +161,12 @@ pub fn commit_uncommit_only_with_perm(
         (&mut graph.into_workspace()?, replaced_commits, repo, meta)
     } else {
         let materialized = rebase.materialize_without_checkout()?;
+        let replaced_commits = materialized.history.commit_mappings();
+        #[cfg(feature = "legacy")]
+        crate::workspace_state::after_rewrite(&repo, &replaced_commits);
         (
             materialized.workspace,
-            materialized.history.commit_mappings(),
+            replaced_commits,
             &*repo,
             materialized.meta,
         )
@@ -298,9 +301,12 @@ pub fn commit_uncommit_changes_only_with_perm(
         (&mut graph.into_workspace()?, replaced_commits, repo, meta)
     } else {
         let materialized = outcome.rebase.materialize_without_checkout()?;
+        let replaced_commits = materialized.history.commit_mappings();
+        #[cfg(feature = "legacy")]
+        crate::workspace_state::after_rewrite(&repo, &replaced_commits);
         (
             materialized.workspace,
-            materialized.history.commit_mappings(),
+            replaced_commits,
             &*repo,
             materialized.meta,
         )
@@ -510,9 +516,12 @@ pub fn commit_uncommit_changes_from_commits_only_with_perm(
         }
     } else if let Some(rebase) = rebase {
         let materialized = rebase.materialize_without_checkout()?;
+        let replaced_commits = materialized.history.commit_mappings();
+        #[cfg(feature = "legacy")]
+        crate::workspace_state::after_rewrite(&repo, &replaced_commits);
         (
             materialized.workspace,
-            materialized.history.commit_mappings(),
+            replaced_commits,
             &*repo,
             materialized.meta,
         )
@@ -59,6 +59,87 @@ pub mod json {
             }
         }
     }
+
+    /// JSON sibling of [`but_hunk_dependency::blame::BlameLine`].
+    #[derive(Debug, Serialize)]
+    #[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
+    #[serde(rename_all = "camelCase")]
+    pub struct BlameLine {
+        /// The 1-based line number in the worktree file.
+        pub line_number: u32,
+        /// The content of the line, without its line terminator.
+        pub content: String,
+        /// Where the line came from.
+        pub origin: LineOrigin,
+    }
+    #[cfg(feature = "export-schema")]
+    but_schemars::register_sdk_type!(BlameLine);
+
+    /// JSON sibling of [`but_hunk_dependency::blame::LineOrigin`].
+    #[derive(Debug, Serialize)]
+    #[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
+    #[serde(
+        rename_all = "camelCase",
+        rename_all_fields = "camelCase",
+        tag = "type",
+        content = "subject"
+    )]
+    pub enum LineOrigin {
+        /// The line was added or changed in the worktree and isn't committed yet.
+        Uncommitted,
+        /// The line was introduced by a commit in a stack of the workspace.
+        Workspace {
+            /// The stack that owns the commit, if it can be identified.
+            #[cfg_attr(
+                feature = "export-schema",
+                schemars(schema_with = "but_schemars::stack_id_opt")
+            )]
+            stack_id: Option<but_core::ref_metadata::StackId>,
+            /// The commit that introduced the line.
+            commit_id: crate::json::HexHashString,
+            /// The branch of the stack that contains the commit, if it is named.
+            branch: Option<crate::json::FullRefName>,
+        },
+        /// The line was introduced by a commit that isn't part of any stack,
+        /// typically as part of the target branch.
+        Target {
+            /// The commit that introduced the line.
+            commit_id: crate::json::HexHashString,
+        },
+    }
+    #[cfg(feature = "export-schema")]
+    but_schemars::register_sdk_type!(LineOrigin);
+
+    impl From<but_hunk_dependency::blame::BlameLine> for BlameLine {
+        fn from(value: but_hunk_dependency::blame::BlameLine) -> Self {
+            let but_hunk_dependency::blame::BlameLine {
+                line_number,
+                content,
+                origin,
+            } = value;
+            BlameLine {
+                line_number,
+                content: content.to_string(),
+                origin: match origin {
+                    but_hunk_dependency::blame::LineOrigin::Uncommitted => LineOrigin::Uncommitted,
+                    but_hunk_dependency::blame::LineOrigin::Workspace {
+                        target,
+                        commit_id,
+                        branch,
+                    } => LineOrigin::Workspace {
+                        stack_id: target.into(),
+                        commit_id: commit_id.into(),
+                        branch: branch.map(Into::into),
+                    },
+                    but_hunk_dependency::blame::LineOrigin::Target { commit_id } => {
+                        LineOrigin::Target {
+                            commit_id: commit_id.into(),
+                        }
+                    }
+                },
+            }
+        }
+    }
 }
 
 /// Computes the tree diff for `commit_id` against its first parent and
@@ -90,6 +171,23 @@ pub fn commit_details_with_line_stats(
     commit_details(ctx, commit_id, ComputeLineStats::Yes)
 }
 
+/// Attributes each line of the file at `path` in the worktree to where it came from: the
+/// worktree itself if it is uncommitted, the workspace commit and branch that introduced it,
+/// or the commit outside of the workspace that did, typically one of the target branch.
+///
+/// For lower-level implementation details, see
+/// [`but_hunk_dependency::blame::blame_worktree_file()`].
+#[but_api(napi, json::BlameLine)]
+#[instrument(err(Debug))]
+pub fn blame_worktree_file(
+    ctx: &Context,
+    path: String,
+) -> anyhow::Result<Vec<but_hunk_dependency::blame::BlameLine>> {
+    let guard = ctx.shared_worktree_access();
+    let (repo, ws, _db) = ctx.workspace_and_db_with_perm(guard.read_permission())?;
+    but_hunk_dependency::blame::blame_worktree_file(&repo, &ws, path.as_str().into())
+}
+
 /// Produces a unified patch for `change`.
 ///
 /// `change` must not be a type change or a submodule change. For lower-level
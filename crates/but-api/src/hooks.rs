@@ -0,0 +1,248 @@
+//! GitButler lifecycle hooks, which run around workspace operations like Git hooks run around
+//! commits and pushes.
+//!
+//! A hook is an executable named after its [`Event`], like `post-pull`, in the directory
+//! configured with `gitbutler.hooksPath`. Relative paths are resolved against the worktree, so a
+//! team can commit its hooks and have each clone opt in with
+//! `git config gitbutler.hooksPath .gitbutler/hooks`. There is no default directory, so hooks
+//! that come with a repository never run without this opt-in.
+//!
+//! Each hook receives a [`Payload`] as JSON on standard input, tagged with its `event`.
+//! Its standard output is redirected to standard error, keeping the output of callers like the CLI
+//! machine-readable. A failing `pre-*` hook aborts its operation, while `post-*` hooks run once
+//! the operation can't be undone anymore, so their failure is only logged.
+//!
+//! Hooks run while the workspace is locked, so they must not run `but` commands that change it.
+
+use std::{io::Write as _, path::PathBuf, process::Stdio};
+
+use anyhow::{Context as _, Result, bail};
+use but_core::ref_metadata::StackId;
+use serde::Serialize;
+
+use crate::json::HexHash;
+
+const HOOKS_PATH: &str = "gitbutler.hooksPath";
+
+/// The workspace operations that hooks can run for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Event {
+    /// Upstream changes were integrated into the workspace, by `but pull` or after landing.
+    PostPull,
+    /// A branch was applied to the workspace.
+    PostApply,
+    /// A stack was unapplied from the workspace.
+    PostUnapply,
+    /// Branches are about to be landed onto the target. A failing hook prevents it.
+    PreLand,
+    /// Branches of a stack were pushed.
+    PostPush,
+}
+
+impl Event {
+    /// The name of the hook executable for this event.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Event::PostPull => "post-pull",
+            Event::PostApply => "post-apply",
+            Event::PostUnapply => "post-unapply",
+            Event::PreLand => "pre-land",
+            Event::PostPush => "post-push",
+        }
+    }
+}
+
+/// What the operation of an [`Event`] is about, passed to its hook.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Payload {
+    /// The references that the operation affects.
+    pub refs: Vec<RefUpdate>,
+    /// The target branch, if the operation integrates with it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<RefUpdate>,
+}
+
+/// A reference along with the commits it pointed to before and after an operation.
+///
+/// Both commits are the same for references that the operation didn't move, like a branch
+/// that was applied.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefUpdate {
+    /// The stack that the reference belongs to, if it is part of one.
+    pub stack_id: Option<StackId>,
+    /// The full name of the reference, like `refs/heads/feature`.
+    #[serde(rename = "ref")]
+    pub name: String,
+    /// The commit the reference pointed to before the operation, or `None` if it didn't exist.
+    pub old_commit: Option<HexHash>,
+    /// The commit the reference points to after the operation, or `None` if it doesn't exist
+    /// or, for `pre-*` events, isn't known yet.
+    pub new_commit: Option<HexHash>,
+}
+
+impl RefUpdate {
+    /// A reference that isn't changed by the operation and points to `commit`.
+    pub fn unchanged(
+        stack_id: Option<StackId>,
+        name: &gix::refs::FullNameRef,
+        commit: Option<gix::ObjectId>,
+    ) -> Self {
+        RefUpdate {
+            stack_id,
+            name: name.as_bstr().to_string(),
+            old_commit: commit.map(Into::into),
+            new_commit: commit.map(Into::into),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Input<'a> {
+    event: Event,
+    #[serde(flatten)]
+    payload: &'a Payload,
+}
+
+/// Return the path to the hook executable for `event`, or `None` if there is none.
+pub fn find(repo: &gix::Repository, event: Event) -> Result<Option<PathBuf>> {
+    let Some(hooks_dir) = repo
+        .config_snapshot()
+        .trusted_path(HOOKS_PATH)
+        .with_context(|| format!("Invalid value of {HOOKS_PATH}"))?
+    else {
+        return Ok(None);
+    };
+    let hooks_dir = match repo.workdir() {
+        Some(workdir) if hooks_dir.is_relative() => workdir.join(hooks_dir),
+        _ => hooks_dir.into_owned(),
+    };
+    let hook = hooks_dir.join(event.as_str());
+    Ok(hook.is_file().then_some(hook))
+}
+
+/// Run the hook for `event` in the worktree of `repo` with the payload returned by `payload`,
+/// which is only called if there is a hook.
+///
+/// Fail if the hook couldn't be run or exited with a non-zero code.
+pub fn run(
+    repo: &gix::Repository,
+    event: Event,
+    payload: impl FnOnce() -> Result<Payload>,
+) -> Result<()> {
+    let Some(hook) = find(repo, event)? else {
+        return Ok(());
+    };
+    let input = serde_json::to_vec(&Input {
+        event,
+        payload: &payload()?,
+    })?;
+
+    let mut child =
+        std::process::Command::from(but_core::cmd::prepare_with_shell_on_windows(&hook))
+            .current_dir(repo.workdir().unwrap_or(repo.git_dir()))
+            .stdin(Stdio::piped())
+            .stdout(std::io::stderr())
+            .spawn()
+            .with_context(|| format!("Failed to run the {} hook", event.as_str()))?;
+    {
+        let mut stdin = child.stdin.take().expect("configured");
+        // Hooks may exit before reading their input if they don't need it.
+        if let Err(err) = stdin.write_all(&input)
+            && err.kind() != std::io::ErrorKind::BrokenPipe
+        {
+            return Err(err.into());
+        }
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        match status.code() {
+            Some(code) => bail!("The {} hook exited with code {code}", event.as_str()),
+            None => bail!("The {} hook was terminated by a signal", event.as_str()),
+        }
+    }
+    Ok(())
+}
+
+/// Like [`run()`], but only log failures as the operation before the `post-*` `event` is done.
+pub(crate) fn run_post(
+    repo: &gix::Repository,
+    event: Event,
+    payload: impl FnOnce() -> Result<Payload>,
+) {
+    if let Err(err) = run(repo, event, payload) {
+        tracing::warn!(?err, "{} hook failed", event.as_str());
+    }
+}
+
+/// The named branches of a workspace along with their stacks and tips, to tell how an operation
+/// changed them.
+pub(crate) struct WorkspaceRefs(Vec<(Option<StackId>, gix::refs::FullName, Option<gix::ObjectId>)>);
+
+impl WorkspaceRefs {
+    pub(crate) fn new(repo: &gix::Repository, ws: &but_graph::Workspace) -> Result<Self> {
+        let mut refs = Vec::new();
+        for stack in &ws.stacks {
+            for name in stack
+                .segments
+                .iter()
+                .filter_map(|segment| segment.ref_name())
+            {
+                refs.push((stack.id, name.to_owned(), peel(repo, name)?));
+            }
+        }
+        Ok(WorkspaceRefs(refs))
+    }
+
+    /// Return all references that were added to, removed from or moved in `after`, using `repo`
+    /// to look up references that left the workspace. References that joined the workspace have
+    /// no old commit.
+    pub(crate) fn updates_to(
+        &self,
+        repo: &gix::Repository,
+        after: &WorkspaceRefs,
+    ) -> Result<Vec<RefUpdate>> {
+        let find = |refs: &WorkspaceRefs, name: &gix::refs::FullName| {
+            refs.0.iter().find(|(_, other, _)| other == name).cloned()
+        };
+        let mut updates = Vec::new();
+        for (stack_id, name, old) in &self.0 {
+            let (stack_id, new, left_workspace) = match find(after, name) {
+                Some((new_stack_id, _, new)) => (new_stack_id.or(*stack_id), new, false),
+                None => (*stack_id, peel(repo, name.as_ref())?, true),
+            };
+            if left_workspace || new != *old {
+                updates.push(RefUpdate {
+                    stack_id,
+                    name: name.as_bstr().to_string(),
+                    old_commit: old.map(Into::into),
+                    new_commit: new.map(Into::into),
+                });
+            }
+        }
+        for (stack_id, name, new) in &after.0 {
+            if find(self, name).is_none() {
+                updates.push(RefUpdate {
+                    stack_id: *stack_id,
+                    name: name.as_bstr().to_string(),
+                    old_commit: None,
+                    new_commit: new.map(Into::into),
+                });
+            }
+        }
+        Ok(updates)
+    }
+}
+
+/// Return the commit that `name` points to, or `None` if it doesn't exist.
+pub(crate) fn peel(
+    repo: &gix::Repository,
+    name: &gix::refs::FullNameRef,
+) -> Result<Option<gix::ObjectId>> {
+    Ok(repo
+        .try_find_reference(name)?
+        .map(|mut reference| reference.peel_to_id().map(|id| id.detach()))
+        .transpose()?)
+}
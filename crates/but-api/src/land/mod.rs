@@ -32,14 +32,14 @@ mod reconcile;
 
 use std::path::Path;
 
-use anyhow::bail;
+use anyhow::{Context as _, bail};
 use but_api_macros::but_api;
 use but_ctx::Context;
 use gitbutler_git::GitContextExt as _;
 use gix::prelude::ObjectIdExt;
 use tracing::instrument;
 
-use crate::WorkspaceState;
+use crate::{WorkspaceState, hooks};
 use merge::LandOutcome;
 
 /// How many times we re-fetch and re-merge when the target moved underneath us before giving up.
@@ -242,6 +242,39 @@ pub fn branch_land(
     // and an unreachable unrelated remote must not block the land.
     fetch_target_remote(ctx, &fetch_remote_name)?;
 
+    // Give the project a chance to refuse landing before anything is published.
+    {
+        let guard = ctx.exclusive_worktree_access();
+        let (repo, ws, _db) = ctx.workspace_and_db_with_perm(guard.read_permission())?;
+        hooks::run(&repo, hooks::Event::PreLand, || {
+            let refs = landed_branch_names
+                .iter()
+                .map(|name| {
+                    let name = gix::refs::FullName::try_from(format!("refs/heads/{name}"))?;
+                    let stack_id = ws
+                        .find_segment_and_stack_by_refname(name.as_ref())
+                        .and_then(|(stack, _)| stack.id);
+                    Ok(hooks::RefUpdate::unchanged(
+                        stack_id,
+                        name.as_ref(),
+                        hooks::peel(&repo, name.as_ref())?,
+                    ))
+                })
+                .collect::<anyhow::Result<_>>()?;
+            Ok(hooks::Payload {
+                refs,
+                target: Some(hooks::RefUpdate {
+                    stack_id: None,
+                    name: format!("refs/remotes/{fetch_remote_name}/{target_branch_name}"),
+                    old_commit: peel_target_tip(&repo, &fetch_remote_name, &target_branch_name)?
+                        .map(Into::into),
+                    new_commit: None,
+                }),
+            })
+        })
+        .with_context(|| format!("Refusing to land `{branch}`"))?;
+    }
+
     // Land the branch, retrying when the target moves underneath us (optimistic concurrency).
     let mut landed: Option<BranchLandKind> = None;
     for attempt in 1..=MAX_PUSH_ATTEMPTS {
@@ -457,8 +490,6 @@ fn validate_branch_landing(
 /// bookkeeping `fetch_from_remotes` performs so `last_fetched` and auto-fetch scheduling stay
 /// accurate for targeted fetches too.
 fn fetch_target_remote(ctx: &Context, remote: &str) -> anyhow::Result<()> {
-    use anyhow::Context as _;
-
     let result = ctx.fetch(remote, Some("land".to_string()));
     let timestamp = std::time::SystemTime::now();
     let project_data_last_fetched = match &result {
@@ -3,6 +3,7 @@ use anyhow::Result;
 use but_api_macros::but_api;
 use but_core::{ref_metadata::StackId, sync::RepoShared, ui::TreeChange};
 use but_ctx::Context;
+use but_worktrees::{Worktree, WorktreeId};
 use gitbutler_edit_mode::ConflictEntryPresence;
 use gitbutler_operating_modes::{EditModeMetadata, OperatingMode};
 use tracing::instrument;
@@ -117,6 +118,39 @@ pub fn save_edit_and_return_to_workspace_with_output(ctx: &mut but_ctx::Context)
     Ok(())
 }
 
+/// Check out `commit_id` into a new linked worktree to edit it there, while the workspace stays usable.
+#[but_api]
+#[instrument(err(Debug))]
+pub fn enter_edit_mode_in_worktree(
+    ctx: &mut but_ctx::Context,
+    commit_id: gix::ObjectId,
+    stack_id: StackId,
+) -> Result<Worktree> {
+    let mut guard = ctx.exclusive_worktree_access();
+    gitbutler_edit_mode::commands::enter_edit_mode_in_worktree(
+        ctx,
+        commit_id,
+        stack_id,
+        guard.write_permission(),
+    )
+}
+
+/// Apply the edit made in the edit worktree `id` to the workspace and remove the worktree.
+#[but_api]
+#[instrument(err(Debug))]
+pub fn save_worktree_edit_and_return(ctx: &mut but_ctx::Context, id: WorktreeId) -> Result<()> {
+    let mut guard = ctx.exclusive_worktree_access();
+    gitbutler_edit_mode::commands::save_worktree_edit_and_return(ctx, &id, guard.write_permission())
+}
+
+/// Remove the edit worktree `id` without applying its changes.
+#[but_api]
+#[instrument(err(Debug))]
+pub fn abort_worktree_edit(ctx: &mut but_ctx::Context, id: WorktreeId, force: bool) -> Result<()> {
+    let mut guard = ctx.exclusive_worktree_access();
+    gitbutler_edit_mode::commands::abort_worktree_edit(ctx, &id, force, guard.write_permission())
+}
+
 #[but_api]
 #[instrument(err(Debug))]
 pub fn edit_initial_index_state(
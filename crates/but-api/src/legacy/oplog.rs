@@ -367,6 +367,20 @@ pub fn snapshot_diff(
     Ok(diff)
 }
 
+/// Computes the file tree difference that restoring a specific snapshot would apply to the current
+/// state of the project, to preview a restore before doing it.
+///
+/// - `sha`: The SHA of the snapshot to restore.
+///
+/// Returns a vector of `TreeChange` entries representing the differences.
+pub fn restore_diff(
+    ctx: &but_ctx::Context,
+    sha: gix::ObjectId,
+) -> Result<Vec<but_core::ui::TreeChange>> {
+    let diff = ctx.restore_diff(sha)?;
+    Ok(diff.into_iter().map(Into::into).collect())
+}
+
 /// Find the final snapshot that a restore snapshot will restore from.
 ///
 /// For example if you do a reword and then a series of undos and redos the oplog would look like this:
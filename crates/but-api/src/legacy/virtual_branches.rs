@@ -29,7 +29,7 @@ use gitbutler_reference::{Refname, normalize_branch_name as normalize_name};
 use gix::reference::Category;
 use tracing::instrument;
 
-use crate::legacy::workspace::canned_branch_name;
+use crate::{hooks, legacy::workspace::canned_branch_name};
 // Parameter structs for all functions
 
 #[but_api]
@@ -556,6 +556,13 @@ fn unapply_stack_v3_with_perm(
     let Some(branch_to_unapply) = stack_branches.first().cloned() else {
         return Ok(());
     };
+    let tips_before = {
+        let repo = ctx.repo.get()?;
+        stack_branches
+            .iter()
+            .map(|name| hooks::peel(&repo, name.as_ref()))
+            .collect::<Result<Vec<_>>>()?
+    };
 
     let trailers = stack_branches
         .iter()
@@ -585,6 +592,22 @@ fn unapply_stack_v3_with_perm(
     // Keeping the workspace merge commit can make legacy reconciliation infer the
     // removed stack as applied again, so persist the explicit workspace metadata.
     meta.write_unreconciled()?;
+
+    hooks::run_post(&repo, hooks::Event::PostUnapply, || {
+        let refs = stack_branches
+            .iter()
+            .zip(tips_before)
+            .map(|(name, old)| {
+                Ok(hooks::RefUpdate {
+                    stack_id: Some(stack_id),
+                    name: name.as_bstr().to_string(),
+                    old_commit: old.map(Into::into),
+                    new_commit: hooks::peel(&repo, name.as_ref())?.map(Into::into),
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok(hooks::Payload { refs, target: None })
+    });
     Ok(())
 }
 
@@ -658,7 +681,10 @@ fn commit_assigned_diffspec(
 
     let mut meta = ctx.meta()?;
     let (repo, mut ws, _) = ctx.workspace_mut_and_db_with_perm(perm)?;
-    let editor = Editor::create(&mut ws, &mut meta, &repo)?;
+    // This commit is bookkeeping, the message policy only applies to messages written by users.
+    let mut editor_repo = repo.clone();
+    but_core::commit::message_policy::disable(&mut editor_repo)?;
+    let editor = Editor::create(&mut ws, &mut meta, &editor_repo)?;
     let outcome = but_workspace::commit::commit_create(
         editor,
         assigned_diffspec,
@@ -23,7 +23,7 @@ use gitbutler_oplog::{
 };
 use tracing::instrument;
 
-use crate::json::HexHash;
+use crate::{hooks, json::HexHash};
 
 #[but_api(napi, try_from = but_workspace::ui::RefInfo)]
 #[instrument(err(Debug))]
@@ -342,7 +342,10 @@ pub fn stash_into_branch(
     let outcome = {
         let mut meta = ctx.meta()?;
         let (repo, mut ws, _) = ctx.workspace_mut_and_db_with_perm(perm)?;
-        let editor = Editor::create(&mut ws, &mut meta, &repo)?;
+        // This commit is bookkeeping, the message policy only applies to messages written by users.
+        let mut editor_repo = repo.clone();
+        but_core::commit::message_policy::disable(&mut editor_repo)?;
+        let editor = Editor::create(&mut ws, &mut meta, &editor_repo)?;
         let but_workspace::commit::CommitCreateOutcome {
             rebase,
             commit_selector,
@@ -561,6 +564,35 @@ pub fn workspace_branch_and_ancestors_push_only(
         push_opts,
     )?;
 
+    // `run_hooks` is about Git's `pre-push` hook, lifecycle hooks only depend on their opt-in.
+    if !result.branch_sha_updates.is_empty() {
+        hooks::run_post(&repo, hooks::Event::PostPush, || {
+            let refs = result
+                .branch_to_remote
+                .iter()
+                .zip(&result.branch_sha_updates)
+                .map(|((branch, remote_ref), (_, before, after))| {
+                    let local_ref = gix::refs::FullName::try_from(format!("refs/heads/{branch}"))?;
+                    let commit = |hex: &str| -> Option<HexHash> {
+                        gix::ObjectId::from_hex(hex.as_bytes())
+                            .ok()
+                            .filter(|id| !id.is_null())
+                            .map(Into::into)
+                    };
+                    Ok(hooks::RefUpdate {
+                        stack_id: ws
+                            .find_segment_and_stack_by_refname(local_ref.as_ref())
+                            .and_then(|(stack, _)| stack.id),
+                        name: remote_ref.as_bstr().to_string(),
+                        old_commit: commit(before),
+                        new_commit: commit(after),
+                    })
+                })
+                .collect::<Result<_>>()?;
+            Ok(hooks::Payload { refs, target: None })
+        });
+    }
+
     Ok(result)
 }
 
@@ -612,6 +644,7 @@ mod tests {
             remote: "origin".into(),
             branch_to_remote: Vec::new(),
             branch_sha_updates: Vec::new(),
+            ref_updates: Vec::new(),
         };
         assert!(!push_needs_review_sync(&no_op));
 
@@ -624,6 +657,7 @@ mod tests {
                     .expect("valid remote reference"),
             )],
             branch_sha_updates: Vec::new(),
+            ref_updates: Vec::new(),
         };
         assert!(push_needs_review_sync(&pushed));
     }
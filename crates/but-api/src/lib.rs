@@ -57,6 +57,9 @@ pub mod resolve;
 /// Functions that show what changed in various Git entities, like trees, commits and the worktree.
 pub mod diff;
 
+/// Run project-configured hooks around workspace operations.
+pub mod hooks;
+
 /// Types meant to be serialised to JSON, without degenerating information despite the need to be UTF-8 encodable.
 /// EXPERIMENTAL
 pub mod json;
@@ -73,6 +76,8 @@ pub mod panic_capture;
 pub mod watcher;
 
 mod workspace_state;
+#[cfg(feature = "legacy")]
+pub use workspace_state::after_rewrite;
 
 /// Represents the workspace for the frontend
 ///
@@ -30,7 +30,8 @@ pub fn build_type() -> Result<String> {
     Ok(build_type.to_string())
 }
 
-/// Initialize the secret namespace used by build-kind scoped credentials.
+/// Initialize the secret namespace used by build-kind scoped credentials, and
+/// store secrets in the backend configured in the application settings.
 ///
 /// Applications embedding the SDK should call this once during startup before
 /// invoking APIs that read or write forge credentials. If `identifier` is
@@ -39,5 +40,8 @@ pub fn build_type() -> Result<String> {
 pub fn init_application_namespace(identifier: Option<String>) -> Result<()> {
     let identifier = identifier.unwrap_or_else(|| but_path::identifier().to_string());
     but_secret::secret::set_application_namespace(identifier);
+    let settings =
+        but_settings::AppSettings::load_from_default_path_creating_without_customization()?;
+    but_secret::secret::setup_from_settings(&settings.secrets);
     Ok(())
 }
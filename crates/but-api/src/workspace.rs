@@ -5,7 +5,7 @@ use std::{
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::WorkspaceState;
+use crate::{WorkspaceState, hooks};
 use bstr::ByteSlice;
 use but_api_macros::but_api;
 use but_core::{
@@ -515,6 +515,22 @@ pub fn workspace_integrate_upstream_with_perm(
         dry_run,
     );
 
+    // Only look at the workspace before the integration if a hook is going to report on it.
+    let refs_before = if dry_run.into() {
+        None
+    } else {
+        let (repo, ws, _db) = ctx.workspace_and_db_with_perm(perm.read_permission())?;
+        hooks::find(&repo, hooks::Event::PostPull)?
+            .map(|_| {
+                anyhow::Ok((
+                    hooks::WorkspaceRefs::new(&repo, &ws)?,
+                    ws.target_ref_name().map(ToOwned::to_owned),
+                    ws.stored_target_commit_id(),
+                ))
+            })
+            .transpose()?
+    };
+
     let result = workspace_integrate_upstream_only_with_perm(ctx, updates, dry_run, perm);
     if let Some(snapshot) = maybe_oplog_entry
         && result.is_ok()
@@ -522,6 +538,23 @@ pub fn workspace_integrate_upstream_with_perm(
         snapshot.commit(ctx, perm).ok();
     }
 
+    if let Some((refs_before, target_ref, old_target)) = refs_before
+        && result.is_ok()
+    {
+        let (repo, ws, _db) = ctx.workspace_and_db_with_perm(perm.read_permission())?;
+        hooks::run_post(&repo, hooks::Event::PostPull, || {
+            Ok(hooks::Payload {
+                refs: refs_before.updates_to(&repo, &hooks::WorkspaceRefs::new(&repo, &ws)?)?,
+                target: target_ref.map(|name| hooks::RefUpdate {
+                    stack_id: None,
+                    name: name.as_bstr().to_string(),
+                    old_commit: old_target.map(Into::into),
+                    new_commit: ws.stored_target_commit_id().map(Into::into),
+                }),
+            })
+        });
+    }
+
     result
 }
 
@@ -14,6 +14,31 @@ pub(crate) fn forge_prs_by_head(db: &but_db::DbHandle) -> anyhow::Result<HashMap
     but_forge::pr_numbers_by_head(db)
 }
 
+/// Copy git notes and run the `post-rewrite` hook for the `replaced_commits` of a materialized
+/// rebase, mapping each original commit to its replacement.
+///
+/// Call this for every rebase that rewrote commits in the repository, but not for previews.
+/// Like in Git, neither can undo the rewrite, so failures are only logged.
+/// Hooks in `.husky` aren't considered as the project settings aren't available here.
+#[cfg(feature = "legacy")]
+pub fn after_rewrite(
+    repo: &gix::Repository,
+    replaced_commits: &BTreeMap<gix::ObjectId, gix::ObjectId>,
+) {
+    use gitbutler_repo::hooks::{self, HookResult};
+
+    if let Err(err) = gitbutler_repo::notes::copy_for_rewrite(repo, replaced_commits) {
+        tracing::warn!(?err, "Failed to copy notes to rewritten commits");
+    }
+    match hooks::post_rewrite(repo, replaced_commits, false) {
+        Ok(HookResult::Failure(failure)) => {
+            tracing::warn!(error = failure.error, "post-rewrite hook failed");
+        }
+        Ok(HookResult::Success | HookResult::NotConfigured) => {}
+        Err(err) => tracing::warn!(?err, "Failed to run post-rewrite hook"),
+    }
+}
+
 impl WorkspaceState {
     /// Map each projected local reference to whether its commits contain conflicts.
     #[cfg(not(feature = "graph-workspace"))]
@@ -227,11 +252,14 @@ impl WorkspaceState {
         }
 
         let materialized = rebase.materialize(Default::default())?;
+        let replaced_commits = materialized.history.commit_mappings();
+        #[cfg(feature = "legacy")]
+        after_rewrite(repo, &replaced_commits);
         Self::from_workspace(
             materialized.workspace,
             materialized.meta,
             repo,
-            materialized.history.commit_mappings(),
+            replaced_commits,
             prs_by_head,
         )
     }
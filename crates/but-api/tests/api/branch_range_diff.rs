@@ -0,0 +1,104 @@
+//! Tests for [`but_api::branch::branch_range_diff()`].
+
+use but_core::ref_metadata::ProjectMeta;
+use but_testsupport::{CommandExt, git_at_dir, open_repo};
+use but_workspace::range_diff::{RangeDiffPairing, RangeDiffStatus};
+
+use crate::support::write_file;
+
+/// `feature` was pushed with one commit, then amended and extended locally, while the target
+/// advanced and `feature` was rebased onto it.
+fn context_with_rewritten_branch() -> anyhow::Result<(but_ctx::Context, tempfile::TempDir)> {
+    let tmp = tempfile::tempdir()?;
+    let git = |args: &[&str]| git_at_dir(tmp.path()).args(args).run();
+    git(&["init", "-b", "main"]);
+    git(&["config", "user.name", "GitButler"]);
+    git(&["config", "user.email", "gitbutler@example.com"]);
+    git(&["config", "remote.origin.url", "../origin"]);
+
+    write_file(tmp.path(), "base.txt", "base\n")?;
+    git(&["add", "base.txt"]);
+    git(&["commit", "-m", "base"]);
+
+    git(&["checkout", "-b", "feature"]);
+    write_file(tmp.path(), "feature.txt", "feature\n")?;
+    git(&["add", "feature.txt"]);
+    git(&["commit", "-m", "add feature"]);
+    git(&["update-ref", "refs/remotes/origin/feature", "HEAD"]);
+
+    git(&["checkout", "main"]);
+    write_file(tmp.path(), "main.txt", "main\n")?;
+    git(&["add", "main.txt"]);
+    git(&["commit", "-m", "advance main"]);
+    git(&["update-ref", "refs/remotes/origin/main", "HEAD"]);
+
+    git(&["checkout", "feature"]);
+    git(&["rebase", "main"]);
+    write_file(tmp.path(), "feature.txt", "feature\namended\n")?;
+    git(&["commit", "-a", "--amend", "-m", "add feature"]);
+    write_file(tmp.path(), "more.txt", "more\n")?;
+    git(&["add", "more.txt"]);
+    git(&["commit", "-m", "add more"]);
+
+    let repo = open_repo(tmp.path())?;
+    let target_commit_id = repo.rev_parse_single("refs/remotes/origin/main")?.detach();
+    ProjectMeta {
+        target_ref: Some("refs/remotes/origin/main".try_into()?),
+        target_commit_id: Some(target_commit_id),
+        push_remote: Some("origin".into()),
+    }
+    .persist(&repo)?;
+
+    let ctx = but_ctx::Context::from_repo_for_testing(repo)?.with_memory_app_cache();
+    Ok((ctx, tmp))
+}
+
+#[test]
+fn compares_the_pushed_branch_with_the_local_one() -> anyhow::Result<()> {
+    let (ctx, _tmp) = context_with_rewritten_branch()?;
+
+    let result = but_api::branch::branch_range_diff(&ctx, "feature".into())?;
+    assert_eq!(result.upstream.as_bstr(), "refs/remotes/origin/feature");
+
+    let entries: Vec<_> = result
+        .range_diff
+        .entries
+        .iter()
+        .map(|entry| (entry.title.to_string(), entry.status, entry.pairing))
+        .collect();
+    assert_eq!(
+        entries,
+        [
+            (
+                "add feature".to_owned(),
+                RangeDiffStatus::Modified,
+                Some(RangeDiffPairing::Similarity)
+            ),
+            ("add more".to_owned(), RangeDiffStatus::Added, None),
+        ],
+        "the commit of the advanced target isn't part of the comparison"
+    );
+    let interdiff = result.range_diff.entries[0]
+        .interdiff
+        .as_ref()
+        .expect("modified commits have an interdiff");
+    assert!(
+        interdiff.to_string().contains("++amended"),
+        "the amended line is added to the patch: {interdiff}"
+    );
+    Ok(())
+}
+
+#[test]
+fn fails_without_tracking_branch() -> anyhow::Result<()> {
+    let (ctx, tmp) = context_with_rewritten_branch()?;
+    git_at_dir(tmp.path()).args(["branch", "unpushed"]).run();
+
+    let err = but_api::branch::branch_range_diff(&ctx, "unpushed".into())
+        .expect_err("there is nothing to compare to");
+    assert!(
+        err.to_string().contains("has no tracking branch"),
+        "{err:#}"
+    );
+    Ok(())
+}
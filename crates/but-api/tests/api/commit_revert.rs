@@ -0,0 +1,147 @@
+use but_core::{DryRun, ref_metadata::ProjectMeta};
+use but_rebase::graph_rebase::mutate::{InsertSide, RelativeTo};
+use but_testsupport::{CommandExt, git_at_dir, open_repo};
+use gitbutler_oplog::OplogExt as _;
+
+use crate::support::write_file;
+
+fn context_with_revertable_history() -> anyhow::Result<(but_ctx::Context, tempfile::TempDir)> {
+    let tmp = tempfile::tempdir()?;
+    git_at_dir(tmp.path()).args(["init", "-b", "main"]).run();
+    git_at_dir(tmp.path())
+        .args(["config", "user.name", "GitButler"])
+        .run();
+    git_at_dir(tmp.path())
+        .args(["config", "user.email", "gitbutler@example.com"])
+        .run();
+
+    write_file(tmp.path(), "base.txt", "base\n")?;
+    git_at_dir(tmp.path()).args(["add", "base.txt"]).run();
+    git_at_dir(tmp.path()).args(["commit", "-m", "base"]).run();
+    git_at_dir(tmp.path())
+        .args(["config", "remote.origin.url", "../origin"])
+        .run();
+    git_at_dir(tmp.path())
+        .args(["update-ref", "refs/remotes/origin/main", "HEAD"])
+        .run();
+
+    write_file(tmp.path(), "feature.txt", "feature\n")?;
+    git_at_dir(tmp.path()).args(["add", "feature.txt"]).run();
+    git_at_dir(tmp.path())
+        .args(["commit", "-m", "add feature\n\nWith a body."])
+        .run();
+    write_file(tmp.path(), "main.txt", "main\n")?;
+    git_at_dir(tmp.path()).args(["add", "main.txt"]).run();
+    git_at_dir(tmp.path()).args(["commit", "-m", "main"]).run();
+
+    let repo = open_repo(tmp.path())?;
+    let target_commit_id = repo.rev_parse_single("refs/remotes/origin/main")?.detach();
+    ProjectMeta {
+        target_ref: Some("refs/remotes/origin/main".try_into()?),
+        target_commit_id: Some(target_commit_id),
+        push_remote: Some("origin".into()),
+    }
+    .persist(&repo)?;
+
+    let ctx = but_ctx::Context::from_repo_for_testing(repo)?.with_memory_app_cache();
+
+    Ok((ctx, tmp))
+}
+
+#[test]
+fn revert_creates_an_inverse_commit_on_top_of_the_branch() -> anyhow::Result<()> {
+    let (mut ctx, _tmp) = context_with_revertable_history()?;
+    let (feature, main_tip) = {
+        let repo = ctx.repo.get()?;
+        (
+            repo.rev_parse_single("main~1")?.detach(),
+            repo.rev_parse_single("main")?.detach(),
+        )
+    };
+    let main_ref: gix::refs::FullName = "refs/heads/main".try_into()?;
+
+    let result = but_api::commit::revert::commit_revert(
+        &mut ctx,
+        feature,
+        RelativeTo::Reference(main_ref.clone()),
+        InsertSide::Below,
+        DryRun::No,
+    )?;
+
+    let repo = ctx.repo.get()?;
+    assert_eq!(
+        repo.rev_parse_single(main_ref.as_ref())?.detach(),
+        result.new_commit,
+        "the revert becomes the new branch tip"
+    );
+    let revert = repo.find_commit(result.new_commit)?;
+    assert_eq!(
+        revert
+            .parent_ids()
+            .next()
+            .expect("the revert is stacked onto the previous tip")
+            .detach(),
+        main_tip
+    );
+    assert_eq!(
+        revert.message_raw()?.to_string(),
+        format!("Revert \"add feature\"\n\nThis reverts commit {feature}.\n")
+    );
+    let tree = revert.tree()?;
+    assert!(
+        tree.find_entry("feature.txt").is_none(),
+        "the reverted commit's changes are undone"
+    );
+    assert!(tree.find_entry("main.txt").is_some());
+
+    let snapshots = ctx
+        .snapshots_iter(None, Vec::new(), None)?
+        .collect::<Result<Vec<_>, _>>()?;
+    let revert_snapshot = snapshots
+        .iter()
+        .find_map(|snapshot| {
+            snapshot.details.as_ref().filter(|details| {
+                details.operation == but_oplog::legacy::OperationKind::RevertCommit
+            })
+        })
+        .expect("the revert should record an oplog snapshot");
+    assert_eq!(
+        revert_snapshot.trailers,
+        [but_oplog::legacy::Trailer::Sha(feature)],
+        "the oplog entry refers to the reverted commit"
+    );
+    Ok(())
+}
+
+#[test]
+fn revert_dry_run_does_not_persist_the_commit_or_move_the_reference() -> anyhow::Result<()> {
+    let (mut ctx, _tmp) = context_with_revertable_history()?;
+    let (feature, main_tip) = {
+        let repo = ctx.repo.get()?;
+        (
+            repo.rev_parse_single("main~1")?.detach(),
+            repo.rev_parse_single("main")?.detach(),
+        )
+    };
+    let main_ref: gix::refs::FullName = "refs/heads/main".try_into()?;
+
+    let result = but_api::commit::revert::commit_revert(
+        &mut ctx,
+        feature,
+        RelativeTo::Reference(main_ref.clone()),
+        InsertSide::Below,
+        DryRun::Yes,
+    )?;
+
+    let repo = ctx.repo.get()?;
+    assert_eq!(
+        repo.rev_parse_single(main_ref.as_ref())?.detach(),
+        main_tip,
+        "dry-run should not move the destination reference"
+    );
+    assert!(
+        repo.find_object(result.new_commit).is_err(),
+        "dry-run commits should remain in the preview object database"
+    );
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use but_api::commit::types::CommitSplitPiece;
+use but_core::{DiffSpec, DryRun, ref_metadata::ProjectMeta};
+use but_testsupport::{CommandExt, git_at_dir, open_repo};
+use gitbutler_oplog::OplogExt as _;
+
+use crate::support::write_file;
+
+fn context_with_commit_to_split() -> anyhow::Result<(but_ctx::Context, tempfile::TempDir)> {
+    let tmp = tempfile::tempdir()?;
+    git_at_dir(tmp.path()).args(["init", "-b", "main"]).run();
+    git_at_dir(tmp.path())
+        .args(["config", "user.name", "GitButler"])
+        .run();
+    git_at_dir(tmp.path())
+        .args(["config", "user.email", "gitbutler@example.com"])
+        .run();
+
+    write_file(tmp.path(), "base.txt", "base\n")?;
+    git_at_dir(tmp.path()).args(["add", "base.txt"]).run();
+    git_at_dir(tmp.path()).args(["commit", "-m", "base"]).run();
+    git_at_dir(tmp.path())
+        .args(["config", "remote.origin.url", "../origin"])
+        .run();
+    git_at_dir(tmp.path())
+        .args(["update-ref", "refs/remotes/origin/main", "HEAD"])
+        .run();
+
+    for name in ["first", "second", "third"] {
+        write_file(tmp.path(), &format!("{name}.txt"), &format!("{name}\n"))?;
+    }
+    git_at_dir(tmp.path()).args(["add", "."]).run();
+    git_at_dir(tmp.path())
+        .args(["commit", "-m", "everything at once"])
+        .run();
+    write_file(tmp.path(), "main.txt", "main\n")?;
+    git_at_dir(tmp.path()).args(["add", "main.txt"]).run();
+    git_at_dir(tmp.path()).args(["commit", "-m", "main"]).run();
+
+    let repo = open_repo(tmp.path())?;
+    let target_commit_id = repo.rev_parse_single("refs/remotes/origin/main")?.detach();
+    ProjectMeta {
+        target_ref: Some("refs/remotes/origin/main".try_into()?),
+        target_commit_id: Some(target_commit_id),
+        push_remote: Some("origin".into()),
+    }
+    .persist(&repo)?;
+
+    let ctx = but_ctx::Context::from_repo_for_testing(repo)?.with_memory_app_cache();
+
+    Ok((ctx, tmp))
+}
+
+fn piece(paths: &[&str], message: &str) -> CommitSplitPiece {
+    CommitSplitPiece {
+        changes: paths
+            .iter()
+            .map(|path| DiffSpec {
+                previous_path: None,
+                path: (*path).into(),
+                hunk_headers: vec![],
+            })
+            .collect(),
+        message: message.into(),
+    }
+}
+
+#[test]
+fn split_stacks_pieces_in_order_and_records_an_oplog_entry() -> anyhow::Result<()> {
+    let (mut ctx, _tmp) = context_with_commit_to_split()?;
+    let subject = ctx.repo.get()?.rev_parse_single("main~1")?.detach();
+
+    let result = but_api::commit::split::commit_split(
+        &mut ctx,
+        subject,
+        vec![
+            piece(&[], "first"),
+            piece(&["second.txt"], "second"),
+            piece(&["third.txt"], "third"),
+        ],
+        DryRun::No,
+    )?;
+
+    assert_eq!(result.new_commits.len(), 3);
+    let repo = ctx.repo.get()?;
+    assert_eq!(
+        repo.rev_parse_single("main~1")?.detach(),
+        result.new_commits[2],
+        "the commit on top of the split commit is rebased onto the last piece"
+    );
+    for (piece, expected_files) in result.new_commits.iter().zip([
+        &["first.txt"][..],
+        &["first.txt", "second.txt"][..],
+        &["first.txt", "second.txt", "third.txt"][..],
+    ]) {
+        let tree = repo.find_commit(*piece)?.tree()?;
+        for file in ["first.txt", "second.txt", "third.txt"] {
+            assert_eq!(
+                tree.find_entry(file).is_some(),
+                expected_files.contains(&file),
+                "each piece adds the changes it selects on top of the previous one"
+            );
+        }
+    }
+    let titles = result
+        .new_commits
+        .iter()
+        .map(|id| -> anyhow::Result<String> {
+            Ok(repo.find_commit(*id)?.message()?.title.to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(titles, ["first", "second", "third"]);
+
+    let snapshots = ctx
+        .snapshots_iter(None, Vec::new(), None)?
+        .collect::<Result<Vec<_>, _>>()?;
+    assert!(
+        snapshots.iter().any(|snapshot| {
+            snapshot.details.as_ref().is_some_and(|details| {
+                details.operation == but_oplog::legacy::OperationKind::SplitCommit
+            })
+        }),
+        "the split should record an oplog snapshot"
+    );
+    Ok(())
+}
+
+#[test]
+fn split_dry_run_does_not_move_the_reference() -> anyhow::Result<()> {
+    let (mut ctx, _tmp) = context_with_commit_to_split()?;
+    let (subject, main_tip) = {
+        let repo = ctx.repo.get()?;
+        (
+            repo.rev_parse_single("main~1")?.detach(),
+            repo.rev_parse_single("main")?.detach(),
+        )
+    };
+
+    but_api::commit::split::commit_split(
+        &mut ctx,
+        subject,
+        vec![piece(&[], "first"), piece(&["second.txt"], "second")],
+        DryRun::Yes,
+    )?;
+
+    assert_eq!(
+        ctx.repo.get()?.rev_parse_single("main")?.detach(),
+        main_tip,
+        "dry-run should not move the reference"
+    );
+    Ok(())
+}
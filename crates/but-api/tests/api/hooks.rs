@@ -0,0 +1,306 @@
+use std::path::{Path, PathBuf};
+
+use but_api::hooks::{self, Event, Payload, RefUpdate};
+use but_testsupport::{CommandExt, git_at_dir, open_repo};
+use tempfile::TempDir;
+
+#[test]
+fn post_apply_receives_the_applied_branch() -> anyhow::Result<()> {
+    let (repo, _tmp) = crate::support::writable_scenario("checkout-head-info");
+    crate::support::persist_default_target(&repo)?;
+    let workdir = repo.workdir().expect("non-bare").to_owned();
+    write_hook(&workdir, "post-apply", "cat > .git/hook-input\n")?;
+    git_at_dir(&workdir)
+        .args(["config", "gitbutler.hooksPath", ".gitbutler/hooks"])
+        .run();
+
+    let repo = open_repo(&workdir)?;
+
+    let feature_tip = repo.rev_parse_single("refs/heads/feature")?.detach();
+    let mut ctx = but_ctx::Context::from_repo_for_testing(repo)?.with_memory_app_cache();
+    let feature = gix::refs::FullName::try_from("refs/heads/feature")?;
+    but_api::branch::apply(&mut ctx, feature.as_ref())?;
+
+    let input: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(workdir.join(".git/hook-input"))?)?;
+    assert_eq!(input["event"], "post-apply");
+    let feature = input["refs"]
+        .as_array()
+        .expect("refs are listed")
+        .iter()
+        .find(|update| update["ref"] == "refs/heads/feature")
+        .unwrap_or_else(|| panic!("the applied branch is reported: {input}"));
+    assert_eq!(feature["oldCommit"], feature_tip.to_string());
+    assert_eq!(feature["newCommit"], feature_tip.to_string());
+    assert!(
+        feature["stackId"].is_string(),
+        "the branch is in a stack now"
+    );
+    assert!(input.get("target").is_none());
+    Ok(())
+}
+
+#[test]
+fn failing_hook_is_an_error() -> anyhow::Result<()> {
+    let (repo, _tmp) = crate::support::writable_scenario("checkout-head-info");
+    let workdir = repo.workdir().expect("non-bare").to_owned();
+    write_hook(&workdir, "pre-land", "echo 'not today' >&2\nexit 3\n")?;
+    git_at_dir(&workdir)
+        .args(["config", "gitbutler.hooksPath", ".gitbutler/hooks"])
+        .run();
+    let repo = open_repo(&workdir)?;
+
+    let err = hooks::run(&repo, Event::PreLand, || {
+        Ok(Payload {
+            refs: vec![RefUpdate::unchanged(
+                None,
+                "refs/heads/feature".try_into()?,
+                None,
+            )],
+            target: None,
+        })
+    })
+    .expect_err("the hook refuses");
+    assert_eq!(err.to_string(), "The pre-land hook exited with code 3");
+    Ok(())
+}
+
+#[test]
+fn hooks_only_run_once_configured() -> anyhow::Result<()> {
+    let (repo, _tmp) = crate::support::writable_scenario("checkout-head-info");
+    let workdir = repo.workdir().expect("non-bare").to_owned();
+    write_hook(&workdir, "pre-land", "exit 1\n")?;
+
+    assert_eq!(
+        hooks::find(&repo, Event::PreLand)?,
+        None,
+        "hooks that come with the repository need an opt-in"
+    );
+    hooks::run(&repo, Event::PreLand, || {
+        unreachable!("the payload isn't needed without a hook")
+    })?;
+    Ok(())
+}
+
+#[test]
+fn post_unapply_receives_the_branches_of_the_stack() -> anyhow::Result<()> {
+    let (mut ctx, workdir, _tmp) = workspace_with_feature_applied("post-unapply")?;
+    let feature_tip = tip(&workdir, "feature");
+    let stack_id = {
+        let (_guard, _repo, ws, _db) = ctx.workspace_and_db()?;
+        ws.stacks
+            .first()
+            .and_then(|stack| stack.id)
+            .expect("the feature stack is applied")
+    };
+
+    but_api::legacy::virtual_branches::unapply_stack(&mut ctx, stack_id)?;
+
+    let input = hook_input(&workdir)?;
+    assert_eq!(input["event"], "post-unapply");
+    let feature = find_ref(&input["refs"], "refs/heads/feature");
+    assert_eq!(feature["stackId"], serde_json::to_value(stack_id)?);
+    assert_eq!(feature["oldCommit"], feature_tip);
+    assert_eq!(
+        feature["newCommit"], feature_tip,
+        "the branch stays where it was"
+    );
+    assert!(input.get("target").is_none());
+    Ok(())
+}
+
+#[test]
+fn post_pull_receives_the_rebased_branches_and_the_target() -> anyhow::Result<()> {
+    let (mut ctx, workdir, tmp) = workspace_with_feature_applied("post-pull")?;
+    let feature_tip = tip(&workdir, "feature");
+    let old_target = tip(&workdir, "main");
+    let upstream = tmp.path().join("upstream");
+    git_at_dir(tmp.path())
+        .args(["clone", "origin.git", "upstream"])
+        .run();
+    commit_file(&upstream, "upstream.txt", "upstream")?;
+    git_at_dir(&upstream)
+        .args(["push", "origin", "HEAD:main"])
+        .run();
+    git_at_dir(&workdir).args(["fetch", "origin"]).run();
+    let new_target = tip(&workdir, "origin/main");
+
+    let head_info = but_api::legacy::workspace::head_info(&ctx)?;
+    let mut guard = ctx.exclusive_worktree_access();
+    but_api::workspace::workspace_integrate_upstream_with_perm(
+        &mut ctx,
+        but_api::workspace::rebase_stack_bottoms(&head_info),
+        but_core::DryRun::No,
+        guard.write_permission(),
+    )?;
+
+    let input = hook_input(&workdir)?;
+    assert_eq!(input["event"], "post-pull");
+    let feature = find_ref(&input["refs"], "refs/heads/feature");
+    assert_eq!(feature["oldCommit"], feature_tip);
+    assert_eq!(
+        feature["newCommit"],
+        tip(&workdir, "feature"),
+        "the branch was rebased onto the new target"
+    );
+    assert_ne!(feature["oldCommit"], feature["newCommit"]);
+    assert_eq!(input["target"]["ref"], "refs/remotes/origin/main");
+    assert_eq!(input["target"]["oldCommit"], old_target);
+    assert_eq!(input["target"]["newCommit"], new_target);
+    Ok(())
+}
+
+#[test]
+fn pre_land_receives_the_branches_to_land_and_can_refuse() -> anyhow::Result<()> {
+    let (mut ctx, workdir, _tmp) =
+        workspace_with_feature_applied_and_hook("pre-land", "cat > .git/hook-input\nexit 1\n")?;
+    let feature_tip = tip(&workdir, "feature");
+    let target_tip = tip(&workdir, "origin/main");
+
+    let err = but_api::land::branch_land(&mut ctx, "feature".into(), false, false)
+        .expect_err("the hook refuses");
+    assert_eq!(err.to_string(), "Refusing to land `feature`");
+    assert_eq!(
+        tip(&workdir, "origin/main"),
+        target_tip,
+        "nothing was landed"
+    );
+
+    let input = hook_input(&workdir)?;
+    assert_eq!(input["event"], "pre-land");
+    let feature = find_ref(&input["refs"], "refs/heads/feature");
+    assert!(feature["stackId"].is_string());
+    assert_eq!(feature["oldCommit"], feature_tip);
+    assert_eq!(feature["newCommit"], feature_tip);
+    assert_eq!(input["target"]["ref"], "refs/remotes/origin/main");
+    assert_eq!(input["target"]["oldCommit"], target_tip);
+    assert!(
+        input["target"]["newCommit"].is_null(),
+        "the landed target isn't known yet"
+    );
+    Ok(())
+}
+
+#[test]
+fn post_push_receives_the_pushed_branches_even_without_git_hooks() -> anyhow::Result<()> {
+    let (mut ctx, workdir, _tmp) = workspace_with_feature_applied("post-push")?;
+    let feature_tip = tip(&workdir, "feature");
+
+    let feature = gix::refs::FullName::try_from("refs/heads/feature")?;
+    let run_git_hooks = false;
+    but_api::legacy::workspace::workspace_branch_and_ancestors_push_only(
+        &mut ctx,
+        false,
+        false,
+        feature.as_ref(),
+        run_git_hooks,
+        Vec::new(),
+    )?;
+
+    let input = hook_input(&workdir)?;
+    assert_eq!(input["event"], "post-push");
+    let feature = find_ref(&input["refs"], "refs/remotes/origin/feature");
+    assert!(feature["stackId"].is_string());
+    assert!(
+        feature["oldCommit"].is_null(),
+        "the branch didn't exist on the remote"
+    );
+    assert_eq!(feature["newCommit"], feature_tip);
+    Ok(())
+}
+
+static ASKPASS: std::sync::Once = std::sync::Once::new();
+
+/// A clone of a bare `origin.git` next to it, with `main` as target and `feature` applied to the
+/// workspace, and a `hook` that stores its input in `.git/hook-input`.
+fn workspace_with_feature_applied(
+    hook: &str,
+) -> anyhow::Result<(but_ctx::Context, PathBuf, TempDir)> {
+    workspace_with_feature_applied_and_hook(hook, "cat > .git/hook-input\n")
+}
+
+/// Like [`workspace_with_feature_applied()`], but with `body` as script of the `hook`.
+fn workspace_with_feature_applied_and_hook(
+    hook: &str,
+    body: &str,
+) -> anyhow::Result<(but_ctx::Context, PathBuf, TempDir)> {
+    ASKPASS.call_once(but_askpass::disable);
+    let tmp = tempfile::tempdir()?;
+    git_at_dir(tmp.path())
+        .args(["init", "--bare", "origin.git"])
+        .run();
+    let workdir = tmp.path().join("repo");
+    git_at_dir(tmp.path())
+        .args(["clone", "origin.git", "repo"])
+        .run();
+    for (key, value) in [
+        ("user.name", "GitButler"),
+        ("user.email", "gitbutler@example.com"),
+    ] {
+        git_at_dir(&workdir).args(["config", key, value]).run();
+    }
+    commit_file(&workdir, "shared.txt", "main")?;
+    git_at_dir(&workdir).args(["branch", "-M", "main"]).run();
+    git_at_dir(&workdir).args(["push", "origin", "main"]).run();
+    git_at_dir(&workdir)
+        .args(["checkout", "-b", "feature"])
+        .run();
+    commit_file(&workdir, "feature.txt", "feature")?;
+    git_at_dir(&workdir).args(["checkout", "main"]).run();
+
+    write_hook(&workdir, hook, body)?;
+    git_at_dir(&workdir)
+        .args(["config", "gitbutler.hooksPath", ".gitbutler/hooks"])
+        .run();
+
+    let repo = open_repo(&workdir)?;
+    crate::support::persist_default_target(&repo)?;
+    let mut ctx = but_ctx::Context::from_repo_for_testing(repo)?.with_memory_app_cache();
+    let feature = gix::refs::FullName::try_from("refs/heads/feature")?;
+    but_api::branch::apply(&mut ctx, feature.as_ref())?;
+    Ok((ctx, workdir, tmp))
+}
+
+fn commit_file(workdir: &Path, name: &str, content: &str) -> anyhow::Result<()> {
+    crate::support::write_file(workdir, name, content)?;
+    git_at_dir(workdir).args(["add", name]).run();
+    git_at_dir(workdir).args(["commit", "-m", name]).run();
+    Ok(())
+}
+
+fn tip(workdir: &Path, rev: &str) -> String {
+    let out = git_at_dir(workdir)
+        .args(["rev-parse", rev])
+        .output()
+        .expect("git can run");
+    assert!(out.status.success(), "{rev} exists");
+    String::from_utf8(out.stdout)
+        .expect("hex")
+        .trim()
+        .to_owned()
+}
+
+fn hook_input(workdir: &Path) -> anyhow::Result<serde_json::Value> {
+    Ok(serde_json::from_slice(&std::fs::read(
+        workdir.join(".git/hook-input"),
+    )?)?)
+}
+
+fn find_ref<'a>(refs: &'a serde_json::Value, name: &str) -> &'a serde_json::Value {
+    refs.as_array()
+        .expect("refs are listed")
+        .iter()
+        .find(|update| update["ref"] == name)
+        .unwrap_or_else(|| panic!("{name} is reported: {refs}"))
+}
+
+fn write_hook(workdir: &Path, name: &str, body: &str) -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let hooks_dir = workdir.join(".gitbutler").join("hooks");
+    std::fs::create_dir_all(&hooks_dir)?;
+    let hook = hooks_dir.join(name);
+    std::fs::write(&hook, format!("#!/bin/sh\n{body}"))?;
+    std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+    Ok(())
+}
@@ -3,14 +3,19 @@ mod branch_checkout;
 mod branch_create;
 mod branch_list;
 mod branch_move;
+mod branch_range_diff;
 mod branch_remove;
 mod branch_rename;
 mod changes_in_worktree;
 mod commit_cherry_pick;
+mod commit_revert;
+mod commit_split;
 #[cfg(feature = "legacy")]
 mod forge_info;
 #[cfg(all(feature = "legacy", not(feature = "graph-workspace")))]
 mod forge_pr_association;
+#[cfg(unix)]
+mod hooks;
 #[cfg(feature = "legacy")]
 mod legacy_workspace;
 mod resolve_ai;
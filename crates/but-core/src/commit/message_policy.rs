@@ -0,0 +1,268 @@
+//! Project-wide policies for commit messages, configured in Git configuration and enforced
+//! whenever a new commit message is written.
+//!
+//! All keys live in the `gitbutler.commitPolicy` subsection:
+//!
+//! ```ini
+//! [gitbutler "commitPolicy"]
+//!     conventional = true
+//!     types = feat,fix,docs,chore
+//!     maxSubjectLength = 72
+//!     requiredTrailer = Ticket
+//!     forbiddenWord = WIP
+//! ```
+//!
+//! * `conventional` - subjects must follow [Conventional Commits](https://www.conventionalcommits.org),
+//!   i.e. `type(optional scope)!: description`.
+//! * `types` - a comma-separated list of allowed types for conventional subjects.
+//!   If unset, the types of the Angular convention are allowed.
+//! * `maxSubjectLength` - the maximum amount of characters in the first line of the message.
+//! * `requiredTrailer` - may be given multiple times, each naming a trailer token like `Ticket`
+//!   that must be present with a non-empty value in the trailer block of the message.
+//! * `forbiddenWord` - may be given multiple times, each naming a word that must not appear
+//!   anywhere in the message. Matching is case-insensitive and only considers whole words.
+//! * `enabled` - set to `false` to skip all checks. This is how callers override the policy
+//!   for a single operation, see [`disable()`].
+use std::fmt;
+
+use bstr::{BStr, ByteSlice};
+
+const ENABLED: &str = "gitbutler.commitPolicy.enabled";
+const CONVENTIONAL: &str = "gitbutler.commitPolicy.conventional";
+const TYPES: &str = "gitbutler.commitPolicy.types";
+const MAX_SUBJECT_LENGTH: &str = "gitbutler.commitPolicy.maxSubjectLength";
+const REQUIRED_TRAILER: &str = "gitbutler.commitPolicy.requiredTrailer";
+const FORBIDDEN_WORD: &str = "gitbutler.commitPolicy.forbiddenWord";
+
+/// The types allowed in conventional subjects if `gitbutler.commitPolicy.types` isn't set.
+pub const DEFAULT_CONVENTIONAL_TYPES: &[&str] = &[
+    "build", "chore", "ci", "docs", "feat", "fix", "perf", "refactor", "revert", "style", "test",
+];
+
+/// The rules a commit message has to follow, as configured for a repository.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MessagePolicy {
+    /// If `Some`, subjects must be conventional and use one of the contained types.
+    pub conventional_types: Option<Vec<String>>,
+    /// The maximum amount of characters in the subject line.
+    pub max_subject_length: Option<usize>,
+    /// Trailer tokens that must be present with a value.
+    pub required_trailers: Vec<String>,
+    /// Words that must not appear in the message.
+    pub forbidden_words: Vec<String>,
+}
+
+/// A single way in which a commit message doesn't follow a [`MessagePolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// The subject isn't of the form `type(scope)!: description`.
+    NotConventional,
+    /// The subject is conventional, but its `type` isn't allowed.
+    UnknownType {
+        /// The type used in the subject.
+        found: String,
+        /// All types that would have been allowed.
+        allowed: Vec<String>,
+    },
+    /// The subject line is longer than allowed.
+    SubjectTooLong {
+        /// The amount of characters in the subject.
+        length: usize,
+        /// The maximum amount of characters.
+        max: usize,
+    },
+    /// The trailer with the given token is missing or has no value.
+    MissingTrailer(String),
+    /// The given forbidden word was found in the message.
+    ForbiddenWord(String),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::NotConventional => f.write_str(
+                "the subject must follow Conventional Commits, like 'type(scope): description'",
+            ),
+            Violation::UnknownType { found, allowed } => write!(
+                f,
+                "the type '{found}' isn't allowed, use one of: {}",
+                allowed.join(", ")
+            ),
+            Violation::SubjectTooLong { length, max } => write!(
+                f,
+                "the subject is {length} characters long, but at most {max} are allowed"
+            ),
+            Violation::MissingTrailer(token) => {
+                write!(f, "the trailer '{token}: <value>' is required")
+            }
+            Violation::ForbiddenWord(word) => write!(f, "the word '{word}' isn't allowed"),
+        }
+    }
+}
+
+/// The error returned by [`enforce()`], listing all violations at once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyViolations(pub Vec<Violation>);
+
+impl fmt::Display for PolicyViolations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("The commit message violates the commit message policy of this project:")?;
+        for violation in &self.0 {
+            write!(f, "\n  - {violation}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for PolicyViolations {}
+
+impl MessagePolicy {
+    /// Read the policy from `config`, or return `None` if it is disabled or no rule is configured.
+    pub fn from_config(config: &gix::config::Snapshot<'_>) -> anyhow::Result<Option<Self>> {
+        if config.boolean(ENABLED) == Some(false) {
+            return Ok(None);
+        }
+        let conventional_types = config.boolean(CONVENTIONAL).unwrap_or(false).then(|| {
+            config
+                .string(TYPES)
+                .map(|types| {
+                    types
+                        .to_str_lossy()
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|ty| !ty.is_empty())
+                        .map(ToOwned::to_owned)
+                        .collect()
+                })
+                .unwrap_or_else(|| {
+                    DEFAULT_CONVENTIONAL_TYPES
+                        .iter()
+                        .copied()
+                        .map(ToOwned::to_owned)
+                        .collect()
+                })
+        });
+        let max_subject_length = config
+            .try_integer(MAX_SUBJECT_LENGTH)
+            .transpose()?
+            .map(|max| {
+                usize::try_from(max).map_err(|_| {
+                    anyhow::anyhow!("{MAX_SUBJECT_LENGTH} must not be negative, got {max}")
+                })
+            })
+            .transpose()?;
+        let values = |key: &str| -> Vec<String> {
+            config
+                .strings(key)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|value| value.to_str_lossy().trim().to_owned())
+                .filter(|value| !value.is_empty())
+                .collect()
+        };
+        let policy = MessagePolicy {
+            conventional_types,
+            max_subject_length,
+            required_trailers: values(REQUIRED_TRAILER),
+            forbidden_words: values(FORBIDDEN_WORD),
+        };
+        Ok((policy != MessagePolicy::default()).then_some(policy))
+    }
+
+    /// Return all the ways in which `message` doesn't follow this policy, or an empty list if it does.
+    pub fn violations(&self, message: &BStr) -> Vec<Violation> {
+        let message = message.to_str_lossy();
+        let subject = message.lines().next().unwrap_or_default().trim_end();
+        let mut out = Vec::new();
+
+        if let Some(allowed) = &self.conventional_types {
+            match conventional_type(subject) {
+                None => out.push(Violation::NotConventional),
+                Some(found) if !allowed.iter().any(|ty| ty == found) => {
+                    out.push(Violation::UnknownType {
+                        found: found.to_owned(),
+                        allowed: allowed.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        if let Some(max) = self.max_subject_length {
+            let length = subject.chars().count();
+            if length > max {
+                out.push(Violation::SubjectTooLong { length, max });
+            }
+        }
+
+        if !self.required_trailers.is_empty() {
+            let parsed = gix::objs::commit::MessageRef::from_bytes(message.as_bytes());
+            let trailers: Vec<_> = parsed
+                .body()
+                .map(|body| body.trailers().collect())
+                .unwrap_or_default();
+            for token in &self.required_trailers {
+                let present = trailers.iter().any(|trailer| {
+                    trailer.token.eq_ignore_ascii_case(token.as_bytes())
+                        && !trailer.value.trim().is_empty()
+                });
+                if !present {
+                    out.push(Violation::MissingTrailer(token.clone()));
+                }
+            }
+        }
+
+        for word in &self.forbidden_words {
+            let found = message
+                .split(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
+                .any(|candidate| candidate.eq_ignore_ascii_case(word));
+            if found {
+                out.push(Violation::ForbiddenWord(word.clone()));
+            }
+        }
+        out
+    }
+}
+
+/// Return the type of a conventional `subject` like `feat(scope)!: description`, or `None` if it
+/// isn't conventional.
+fn conventional_type(subject: &str) -> Option<&str> {
+    let (prefix, description) = subject.split_once(": ")?;
+    if description.trim().is_empty() {
+        return None;
+    }
+    let prefix = prefix.strip_suffix('!').unwrap_or(prefix);
+    let ty = match prefix.split_once('(') {
+        Some((ty, scope)) => {
+            let scope = scope.strip_suffix(')')?;
+            if scope.is_empty() || scope.contains(['(', ')']) {
+                return None;
+            }
+            ty
+        }
+        None => prefix,
+    };
+    (!ty.is_empty() && ty.chars().all(|c| c.is_ascii_alphanumeric())).then_some(ty)
+}
+
+/// Check `message` against the policy configured in `repo`, and fail with [`PolicyViolations`]
+/// if it doesn't comply.
+///
+/// This is a no-op if no policy is configured, or if it was [disabled](disable()).
+pub fn enforce(repo: &gix::Repository, message: &BStr) -> anyhow::Result<()> {
+    let Some(policy) = MessagePolicy::from_config(&repo.config_snapshot())? else {
+        return Ok(());
+    };
+    let violations = policy.violations(message);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    Err(PolicyViolations(violations).into())
+}
+
+/// Disable the commit message policy for all operations performed with `repo` or its clones,
+/// without persisting this to the Git configuration on disk.
+pub fn disable(repo: &mut gix::Repository) -> anyhow::Result<()> {
+    repo.config_snapshot_mut().set_raw_value(ENABLED, "false")?;
+    Ok(())
+}
@@ -0,0 +1,112 @@
+//! Support for [Git LFS](https://git-lfs.com), which stores large files outside of Git and commits
+//! small *pointer* files in their place.
+//!
+//! The `lfs` *clean* filter turns worktree files into pointers, which is what diffs are computed from
+//! with [`UnifiedPatch::CONVERSION_MODE`](crate::UnifiedPatch::CONVERSION_MODE), and what commits have to store.
+
+use bstr::{BStr, ByteSlice};
+use serde::Serialize;
+
+/// The name of the `filter` attribute value that marks paths as tracked by Git LFS.
+const FILTER_NAME: &str = "lfs";
+
+/// The parsed content of a Git LFS pointer file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Pointer {
+    /// The object id of the file content in the LFS store, like `sha256:4d7a…`.
+    pub oid: String,
+    /// The size of the file content in bytes.
+    pub size: u64,
+}
+#[cfg(feature = "export-schema")]
+but_schemars::register_sdk_type!(Pointer);
+
+impl Pointer {
+    /// Pointer files are never larger than this, so larger blobs don't need to be read to know they aren't pointers.
+    pub const MAX_SIZE: u64 = 1024;
+
+    /// Parse `data` as pointer file, or return `None` if it isn't one.
+    ///
+    /// Only the `version`, `oid` and `size` keys are interpreted, extensions are ignored.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        if data.len() as u64 > Self::MAX_SIZE {
+            return None;
+        }
+        let mut lines = data.lines();
+        let version = lines.next()?.strip_prefix(b"version ")?;
+        if !version.starts_with(b"https://git-lfs.github.com/spec/") {
+            return None;
+        }
+        let (mut oid, mut size) = (None, None);
+        for line in lines {
+            if let Some(value) = line.strip_prefix(b"oid ") {
+                let (algorithm, hash) = value.to_str().ok()?.split_once(':')?;
+                if algorithm.is_empty() || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return None;
+                }
+                oid = Some(value.to_str().ok()?.to_owned());
+            } else if let Some(value) = line.strip_prefix(b"size ") {
+                size = Some(value.to_str().ok()?.parse().ok()?);
+            }
+        }
+        Some(Pointer {
+            oid: oid?,
+            size: size?,
+        })
+    }
+
+    /// Return the `oid` without its algorithm prefix and shortened to `len` characters, for display.
+    pub fn short_oid(&self, len: usize) -> &str {
+        let hash = self
+            .oid
+            .split_once(':')
+            .map_or(self.oid.as_str(), |(_, hash)| hash);
+        &hash[..hash.len().min(len)]
+    }
+}
+
+/// Tell which paths are tracked by Git LFS, as configured by their `filter` attribute.
+pub struct Attributes<'repo> {
+    cache: gix::AttributeStack<'repo>,
+    matches: gix::attrs::search::Outcome,
+}
+
+impl<'repo> Attributes<'repo> {
+    /// Read attributes from the worktree of `repo`, or from `index` if a `.gitattributes` file isn't checked out.
+    pub fn new(repo: &'repo gix::Repository, index: &gix::index::State) -> anyhow::Result<Self> {
+        let cache = repo.attributes_only(
+            index,
+            gix::worktree::stack::state::attributes::Source::WorktreeThenIdMapping,
+        )?;
+        let matches = cache.selected_attribute_matches(Some("filter"));
+        Ok(Attributes { cache, matches })
+    }
+
+    /// Return `true` if the file at worktree-relative `rela_path` is stored in Git LFS.
+    pub fn is_lfs(&mut self, rela_path: &BStr) -> anyhow::Result<bool> {
+        let platform = self.cache.at_entry(rela_path, None)?;
+        Ok(platform.matching_attributes(&mut self.matches)
+            && self
+                .matches
+                .iter()
+                .any(|m| m.assignment.state.as_bstr() == Some(FILTER_NAME.into())))
+    }
+}
+
+/// Return `true` if `repo` configures the `lfs` filter driver, which turns files into pointers when they are added.
+pub fn has_clean_filter(repo: &gix::Repository) -> bool {
+    let config = repo.config_snapshot();
+    ["filter.lfs.clean", "filter.lfs.process"]
+        .into_iter()
+        .any(|key| config.string(key).is_some_and(|value| !value.is_empty()))
+}
+
+/// Return `true` if the blob `id` in `repo` is a Git LFS pointer, without reading blobs that are too large to be one.
+pub fn is_pointer_blob(repo: &gix::Repository, id: gix::ObjectId) -> anyhow::Result<bool> {
+    if repo.find_header(id)?.size() > Pointer::MAX_SIZE {
+        return Ok(false);
+    }
+    Ok(Pointer::from_bytes(&repo.find_blob(id)?.data).is_some())
+}
@@ -16,6 +16,8 @@ pub use commit_details::{CommitDetails, LineStats};
 /// conversion functions for use in the UI
 pub mod ui;
 
+pub mod lfs;
+
 impl TreeStatus {
     /// Learn what kind of status this is, useful if only this information is needed.
     pub fn kind(&self) -> TreeStatusKind {
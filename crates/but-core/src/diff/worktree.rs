@@ -886,7 +886,8 @@ impl TreeChange {
     ///  More content...
     /// ```
     ///
-    /// Return `None` if this change cannot produce a diff, typically because a submodule is involved.
+    /// Return `None` if this change cannot produce a textual diff, like binary files or files replaced by a submodule.
+    /// Submodules whose commit changed are shown like Git does, with a `Subproject commit <id>` line for each side.
     ///
     /// Warning: we return binary-to-text conversions as patches, so these diffs aren't usable for actual patching,
     /// as they also remove the information about such filter, and it's unclear to the caller if they ran at all.
@@ -932,6 +933,25 @@ impl TreeChange {
                     out.push_str(&hunk.diff);
                 }
             }
+            Some(UnifiedPatch::Submodule {
+                previous_commit,
+                commit,
+                ..
+            }) => {
+                // Like Git, show submodules by the commits they point to.
+                let range = |id: Option<gix::ObjectId>| if id.is_some() { "1" } else { "0,0" };
+                out.push_str(format!(
+                    "@@ -{} +{} @@\n",
+                    range(previous_commit),
+                    range(commit)
+                ));
+                if let Some(id) = previous_commit {
+                    out.push_str(format!("-Subproject commit {id}\n"));
+                }
+                if let Some(id) = commit {
+                    out.push_str(format!("+Subproject commit {id}\n"));
+                }
+            }
             None => {}
             _ => return Ok(None),
         }
@@ -942,7 +962,8 @@ impl TreeChange {
     /// for obtaining a working tree to read files from disk.
     /// Note that the mount of lines of context around each hunk are currently hardcoded to `3` as it *might* be relevant for creating
     /// commits later.
-    /// Return `None` if this change cannot produce a diff, typically because a submodule is involved.
+    /// Return `None` if this change cannot produce a diff, like when a file is replaced by a submodule.
+    /// Submodules whose commit changed yield a [`UnifiedPatch::Submodule`].
     /// Note that this format only contains hunk-headers and the patches themselves, not the file header.
     ///
     /// ### Example
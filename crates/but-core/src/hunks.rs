@@ -38,8 +38,8 @@ impl SingleHunk {
     /// Split `change` into the hunks of `patch`.
     ///
     /// Changes without sub-file identity — binaries, files too large to diff, patches
-    /// produced by a binary-to-text filter, and changes whose patch couldn't be computed
-    /// at all — yield a single whole-file hunk rather than disappearing.
+    /// produced by a binary-to-text filter, submodule pointer updates, and changes whose
+    /// patch couldn't be computed at all — yield a single whole-file hunk rather than disappearing.
     pub fn from_tree_change(change: &TreeChange, patch: Option<UnifiedPatch>) -> Vec<Self> {
         let hunks = match &patch {
             Some(UnifiedPatch::Patch {
@@ -48,7 +48,11 @@ impl SingleHunk {
                 ..
             }) if !is_result_of_binary_to_text_conversion => hunks.as_slice(),
             Some(
-                UnifiedPatch::Binary | UnifiedPatch::TooLarge { .. } | UnifiedPatch::Patch { .. },
+                UnifiedPatch::Binary
+                | UnifiedPatch::TooLarge { .. }
+                | UnifiedPatch::Patch { .. }
+                | UnifiedPatch::Submodule { .. }
+                | UnifiedPatch::Lfs { .. },
             )
             | None => &[],
         };
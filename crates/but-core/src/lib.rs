@@ -356,6 +356,40 @@ pub enum UnifiedPatch {
         /// The total amount of lines removed.
         lines_removed: u32,
     },
+    /// A submodule that now points to a different commit, which is shown by its commits instead of a patch.
+    #[serde(rename_all = "camelCase")]
+    Submodule {
+        /// The commit the submodule pointed to before, or `None` if it was added.
+        #[serde(with = "but_serde::object_id_opt")]
+        #[cfg_attr(
+            feature = "export-schema",
+            schemars(schema_with = "but_schemars::object_id_opt")
+        )]
+        previous_commit: Option<gix::ObjectId>,
+        /// The commit the submodule points to now, or `None` if it was removed.
+        #[serde(with = "but_serde::object_id_opt")]
+        #[cfg_attr(
+            feature = "export-schema",
+            schemars(schema_with = "but_schemars::object_id_opt")
+        )]
+        commit: Option<gix::ObjectId>,
+        /// The commits reachable from `commit` but not from `previous_commit`, newest first.
+        ///
+        /// This, like `commits_removed`, is empty if the submodule repository or its commits aren't available locally,
+        /// and holds at most [`unified_diff::SUBMODULE_LOG_LIMIT`] commits.
+        commits_added: Vec<unified_diff::SubmoduleCommit>,
+        /// The commits reachable from `previous_commit` but not from `commit`, newest first, which is the case
+        /// if the submodule was moved back or to another branch.
+        commits_removed: Vec<unified_diff::SubmoduleCommit>,
+    },
+    /// A file stored in Git LFS, which is shown by the objects its pointer files refer to instead of a patch.
+    #[serde(rename_all = "camelCase")]
+    Lfs {
+        /// The pointer before the change, or `None` if the file was added.
+        previous_pointer: Option<diff::lfs::Pointer>,
+        /// The pointer after the change, or `None` if the file was removed.
+        pointer: Option<diff::lfs::Pointer>,
+    },
 }
 #[cfg(feature = "export-schema")]
 but_schemars::register_sdk_type!(UnifiedPatch);
@@ -38,7 +38,8 @@ pub mod create_tree {
         /// Previously this was possible when untracked files were added with their single hunk specified, but now this shouldn't be happening anymore.
         PathNotFoundInBaseTree,
         /// There was a change, but the path pointed to something that wasn't a file or a link.
-        /// You would see this if also in case of submodules or repositories to be added with hunks, which shouldn't be easy to do accidentally even.
+        /// You would see this if also in case of submodules or repositories to be added with hunks, which shouldn't be easy to do accidentally even,
+        /// or for directories that aren't repositories with a checked-out commit.
         UnsupportedDirectoryEntry,
         /// The base version of a file to apply worktree changes to as present in a Git tree had an undiffable entry type.
         /// This can happen if the target tree has an entry that isn't of the same type as the source worktree changes.
@@ -46,6 +47,9 @@ pub mod create_tree {
         /// The DiffSpec points to an actual change, or a subset of that change using a file path and optionally hunks into that file.
         /// However, at least one hunk was not fully contained.
         MissingDiffSpecAssociation,
+        /// The file is tracked by Git LFS, but the `lfs` clean filter didn't turn it into a pointer, typically because `git-lfs`
+        /// isn't installed. Committing it would store the whole file in Git.
+        LfsCleanFilterNotApplied,
     }
 
     #[cfg(feature = "export-schema")]
@@ -191,6 +195,8 @@ pub fn apply_worktree_changes<'repo>(
     let base_tree = actual_base_tree.attach(repo).object()?.peel_to_tree()?;
     let mut base_tree_editor = base_tree.edit()?;
     let (mut pipeline, index) = repo.filter_pipeline(None)?;
+    let mut lfs_attributes = crate::diff::lfs::Attributes::new(repo, &index)?;
+    let has_lfs_clean_filter = crate::diff::lfs::has_clean_filter(repo);
     let has_changes_with_hunks = changes
         .iter()
         .filter_map(|c| c.as_ref().ok())
@@ -226,8 +232,36 @@ pub fn apply_worktree_changes<'repo>(
         };
         if change_request.hunk_headers.is_empty() {
             let rela_path = change_request.path.as_bstr();
+            if md.is_dir() {
+                // A submodule is committed by the commit that is checked out in it.
+                match submodule_head_id(&path) {
+                    Some(id) => {
+                        base_tree_editor.upsert(rela_path, EntryKind::Commit, id)?;
+                    }
+                    None => {
+                        into_err_spec(possible_change, RejectionReason::UnsupportedDirectoryEntry)
+                    }
+                }
+                continue;
+            }
+            // Without the filter, files too large to be pointers would be read and written to the
+            // object database only to be rejected.
+            if !has_lfs_clean_filter
+                && md.is_file()
+                && md.len() > crate::diff::lfs::Pointer::MAX_SIZE
+                && lfs_attributes.is_lfs(rela_path)?
+            {
+                into_err_spec(possible_change, RejectionReason::LfsCleanFilterNotApplied);
+                continue;
+            }
             match pipeline.worktree_file_to_object(rela_path, &index)? {
                 Some((id, kind, _fs_metadata)) => {
+                    if kind != EntryKind::Link
+                        && is_unfiltered_lfs_file(repo, &mut lfs_attributes, rela_path, id)?
+                    {
+                        into_err_spec(possible_change, RejectionReason::LfsCleanFilterNotApplied);
+                        continue;
+                    }
                     base_tree_editor.upsert(rela_path, kind, id)?;
                 }
                 None => into_err_spec(
@@ -340,6 +374,17 @@ pub fn apply_worktree_changes<'repo>(
                 &hunks_to_commit,
             )?;
             let blob_with_selected_patches = repo.write_blob(base_with_patches.as_slice())?;
+            if current_entry_kind != EntryKind::Link
+                && is_unfiltered_lfs_file(
+                    repo,
+                    &mut lfs_attributes,
+                    change_request.path.as_bstr(),
+                    blob_with_selected_patches.detach(),
+                )?
+            {
+                into_err_spec(possible_change, RejectionReason::LfsCleanFilterNotApplied);
+                continue;
+            }
             base_tree_editor.upsert(
                 change_request.path.as_bstr(),
                 current_entry_kind,
@@ -354,6 +399,26 @@ pub fn apply_worktree_changes<'repo>(
     Ok((altered_base_tree_id, actual_base_tree))
 }
 
+/// Return `true` if `rela_path` is tracked by Git LFS, but its blob `id` isn't a pointer as the clean filter wasn't applied.
+///
+/// Empty files are valid as they are, as Git LFS doesn't turn them into pointers either.
+fn is_unfiltered_lfs_file(
+    repo: &gix::Repository,
+    lfs_attributes: &mut crate::diff::lfs::Attributes<'_>,
+    rela_path: &bstr::BStr,
+    id: gix::ObjectId,
+) -> anyhow::Result<bool> {
+    Ok(lfs_attributes.is_lfs(rela_path)?
+        && !id.is_empty_blob()
+        && !crate::diff::lfs::is_pointer_blob(repo, id)?)
+}
+
+/// Return the commit checked out in the repository at `path`, if it is one.
+fn submodule_head_id(path: &std::path::Path) -> Option<gix::ObjectId> {
+    let repo = gix::open_opts(path, gix::open::Options::isolated()).ok()?;
+    Some(repo.head_id().ok()?.detach())
+}
+
 /// Given `hunks_to_keep` (ascending hunks by starting line) and the set of `worktree_hunks_no_context`
 /// (worktree hunks without context), return `(hunks_to_commit, rejected_hunks)`.
 /// `hunks_to_commit` is the headers to drive the additive operation to create the buffer to commit, and `rejected_hunks` is the list of
@@ -1,8 +1,11 @@
 use bstr::{BStr, BString, ByteSlice};
-use gix::diff::blob::{
-    ResourceKind,
-    platform::prepare_diff::{self, Operation},
-    unified_diff::{ConsumeBinaryHunk, ContextSize, HunkHeader},
+use gix::{
+    diff::blob::{
+        ResourceKind,
+        platform::prepare_diff::{self, Operation},
+        unified_diff::{ConsumeBinaryHunk, ContextSize, HunkHeader},
+    },
+    object::tree::EntryKind,
 };
 use serde::{Deserialize, Serialize};
 
@@ -57,6 +60,32 @@ impl std::fmt::Debug for DiffHunk {
     }
 }
 
+/// The most commits listed on either side of a [`UnifiedPatch::Submodule`].
+pub const SUBMODULE_LOG_LIMIT: usize = 10;
+
+/// A commit of a submodule as listed in a [`UnifiedPatch::Submodule`].
+#[derive(Debug, Clone, Serialize)]
+#[cfg_attr(feature = "export-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct SubmoduleCommit {
+    /// The id of the commit in the submodule repository.
+    #[serde(with = "but_serde::object_id")]
+    #[cfg_attr(
+        feature = "export-schema",
+        schemars(schema_with = "but_schemars::object_id")
+    )]
+    pub id: gix::ObjectId,
+    /// The first line of the commit message.
+    #[serde(serialize_with = "but_serde::bstring_lossy::serialize")]
+    #[cfg_attr(
+        feature = "export-schema",
+        schemars(schema_with = "but_schemars::bstring_lossy")
+    )]
+    pub title: BString,
+}
+#[cfg(feature = "export-schema")]
+but_schemars::register_sdk_type!(SubmoduleCommit);
+
 impl UnifiedPatch {
     /// Determine how resources are converted to their form used for diffing.
     ///
@@ -79,8 +108,9 @@ impl UnifiedPatch {
     ///
     /// ### Special Types
     ///
-    /// Note that *Submodules* won't render as patches, they have to be caught in the UI to render their previous hash
-    /// and current hash directly.
+    /// Note that *Submodules* won't render as patches, but as [`UnifiedPatch::Submodule`] with their previous and current
+    /// commit, along with the commits between them if the submodule repository is available.
+    /// Files stored in Git LFS render as [`UnifiedPatch::Lfs`] with the pointers they have in Git, as long as all versions of them are pointers.
     /// Type-changes, from file to submodule or vice-versa for instance, should be shown as typechange only, probably showing
     /// the old and the new type, without diff preview for now.
     pub fn compute(
@@ -120,6 +150,19 @@ impl UnifiedPatch {
         use gix::diff::blob;
         let current_state = current_state.into();
         let previous_state = previous_state.into();
+        let is_submodule_or_none =
+            |state: Option<ChangeState>| state.is_none_or(|state| state.kind == EntryKind::Commit);
+        if current_state.or(previous_state).is_some()
+            && is_submodule_or_none(current_state)
+            && is_submodule_or_none(previous_state)
+        {
+            return Ok(Some(submodule_patch(
+                repo,
+                path,
+                previous_state.map(|state| state.id),
+                current_state.map(|state| state.id),
+            )));
+        }
         match diff_filter.set_resource(
             current_state.map_or(repo.object_hash().null(), |state| state.id),
             current_state.map_or_else(
@@ -170,6 +213,10 @@ impl UnifiedPatch {
             Err(err) => return Err(err.into()),
         };
 
+        if let Some(patch) = lfs_patch(diff_filter) {
+            return Ok(Some(patch));
+        }
+
         let prep = match diff_filter.prepare_diff() {
             Ok(prep) => prep,
             Err(prepare_diff::Error::SourceAndDestinationRemoved) => {
@@ -180,32 +227,6 @@ impl UnifiedPatch {
         };
         Ok(Some(match prep.operation {
             Operation::InternalDiff { algorithm } => {
-                #[derive(Default)]
-                struct ProduceDiffHunk {
-                    hunks: Vec<DiffHunk>,
-                }
-                impl gix::diff::blob::unified_diff::ConsumeBinaryHunkDelegate for ProduceDiffHunk {
-                    fn consume_binary_hunk(
-                        &mut self,
-                        header: HunkHeader,
-                        header_str: &str,
-                        hunk: &[u8],
-                    ) -> std::io::Result<()> {
-                        self.hunks.push(DiffHunk {
-                            old_start: header.before_hunk_start,
-                            old_lines: header.before_hunk_len,
-                            new_start: header.after_hunk_start,
-                            new_lines: header.after_hunk_len,
-                            diff: {
-                                let mut buf = Vec::with_capacity(header_str.len() + hunk.len());
-                                buf.extend_from_slice(header_str.as_bytes());
-                                buf.extend_from_slice(hunk);
-                                detect_and_convert_to_utf8(buf.into())
-                            },
-                        });
-                        Ok(())
-                    }
-                }
                 let input = prep.interned_input();
                 let diff = gix::diff::blob::diff_with_slider_heuristics(algorithm, &input);
                 let (lines_added, lines_removed) = compute_line_changes(&diff);
@@ -254,6 +275,198 @@ impl UnifiedPatch {
             }
         }))
     }
+
+    /// If this is a [`UnifiedPatch::Submodule`], describe it in lines like Git does with `--submodule=log`,
+    /// i.e. `Submodule 1a2b3c4..5d6e7f8` followed by `> <title>` for each added commit and `< <title>`
+    /// for each removed one.
+    pub fn submodule_log_lines(&self) -> Option<Vec<String>> {
+        let UnifiedPatch::Submodule {
+            previous_commit,
+            commit,
+            commits_added,
+            commits_removed,
+        } = self
+        else {
+            return None;
+        };
+        let short = |id: &gix::ObjectId| id.to_hex_with_len(7).to_string();
+        let header = match (previous_commit, commit) {
+            (Some(previous_commit), Some(commit)) => {
+                format!("Submodule {}..{}", short(previous_commit), short(commit))
+            }
+            (None, Some(commit)) => format!("Submodule added at {}", short(commit)),
+            (Some(previous_commit), None) => {
+                format!("Submodule removed at {}", short(previous_commit))
+            }
+            (None, None) => "Submodule".into(),
+        };
+        Some(
+            std::iter::once(header)
+                .chain(commits_added.iter().map(|c| format!("> {}", c.title)))
+                .chain(commits_removed.iter().map(|c| format!("< {}", c.title)))
+                .collect(),
+        )
+    }
+
+    /// If this patch describes a change without a textual diff, return lines to show in its place.
+    ///
+    /// A [`UnifiedPatch::Submodule`] is described by its [log lines](Self::submodule_log_lines()).
+    /// A [`UnifiedPatch::Lfs`] is described with a `-` line for the previous object and a `+` line for the current one.
+    pub fn summary_lines(&self) -> Option<Vec<String>> {
+        match self {
+            UnifiedPatch::Submodule { .. } => self.submodule_log_lines(),
+            UnifiedPatch::Lfs {
+                previous_pointer,
+                pointer,
+            } => {
+                let line = |sign: char, pointer: &crate::diff::lfs::Pointer| {
+                    format!(
+                        "{sign}Git LFS object {oid} ({size} bytes)",
+                        oid = pointer.short_oid(7),
+                        size = pointer.size
+                    )
+                };
+                Some(
+                    previous_pointer
+                        .iter()
+                        .map(|p| line('-', p))
+                        .chain(pointer.iter().map(|p| line('+', p)))
+                        .collect(),
+                )
+            }
+            UnifiedPatch::Binary | UnifiedPatch::TooLarge { .. } | UnifiedPatch::Patch { .. } => {
+                None
+            }
+        }
+    }
+}
+
+/// Return a [`UnifiedPatch::Lfs`] if the resources of `diff_filter` are Git LFS pointers, or were added or removed.
+fn lfs_patch(diff_filter: &gix::diff::blob::Platform) -> Option<UnifiedPatch> {
+    use gix::diff::blob::platform::resource::Data;
+    let (old, new) = diff_filter.resources()?;
+    let pointer = |data: Data<'_>| match data {
+        Data::Buffer { buf, is_derived } if !is_derived => {
+            crate::diff::lfs::Pointer::from_bytes(buf).map(Some)
+        }
+        Data::Missing => Some(None),
+        Data::Buffer { .. } | Data::Binary { .. } => None,
+    };
+    let (previous_pointer, pointer) = (pointer(old.data)?, pointer(new.data)?);
+    (previous_pointer.is_some() || pointer.is_some()).then_some(UnifiedPatch::Lfs {
+        previous_pointer,
+        pointer,
+    })
+}
+
+/// Describe the submodule at `path` moving from `previous_commit` to `commit`, listing the commits in between
+/// if the submodule repository is available.
+fn submodule_patch(
+    repo: &gix::Repository,
+    path: &BStr,
+    previous_commit: Option<gix::ObjectId>,
+    commit: Option<gix::ObjectId>,
+) -> UnifiedPatch {
+    let (commits_added, commits_removed) = match (previous_commit, commit) {
+        (Some(previous_commit), Some(commit)) => submodule_log(repo, path, previous_commit, commit)
+            .unwrap_or_else(|err| {
+                tracing::debug!(?err, %path, "couldn't list submodule commits");
+                Default::default()
+            }),
+        _ => Default::default(),
+    };
+    UnifiedPatch::Submodule {
+        previous_commit,
+        commit,
+        commits_added,
+        commits_removed,
+    }
+}
+
+/// Return `(added, removed)` commits when moving the submodule at `path` from `previous_commit` to `commit`.
+fn submodule_log(
+    repo: &gix::Repository,
+    path: &BStr,
+    previous_commit: gix::ObjectId,
+    commit: gix::ObjectId,
+) -> anyhow::Result<(Vec<SubmoduleCommit>, Vec<SubmoduleCommit>)> {
+    let mut submodule_repo = None;
+    for submodule in repo.submodules()?.into_iter().flatten() {
+        if submodule.path()?.as_ref() == path {
+            submodule_repo = submodule.open()?;
+            break;
+        }
+    }
+    let Some(submodule_repo) = submodule_repo else {
+        return Ok(Default::default());
+    };
+    let log = |tip: gix::ObjectId, hidden: gix::ObjectId| -> anyhow::Result<Vec<SubmoduleCommit>> {
+        let mut out = Vec::new();
+        for info in submodule_repo
+            .rev_walk([tip])
+            .with_hidden([hidden])
+            .all()?
+            .take(SUBMODULE_LOG_LIMIT)
+        {
+            let commit = submodule_repo.find_commit(info?.id)?;
+            out.push(SubmoduleCommit {
+                id: commit.id,
+                title: commit.message()?.title.trim().into(),
+            });
+        }
+        Ok(out)
+    };
+    Ok((log(commit, previous_commit)?, log(previous_commit, commit)?))
+}
+
+/// Diff the lines of `old` against the lines of `new`, both of which are in-memory text buffers,
+/// and return the resulting hunks with the given amount of `context_lines`.
+///
+/// This is useful to compare text that isn't stored as blob, like the patches of two commits.
+pub fn hunks_from_buffers(
+    old: &[u8],
+    new: &[u8],
+    context_lines: u32,
+) -> anyhow::Result<Vec<DiffHunk>> {
+    let input = gix::diff::blob::InternedInput::new(old, new);
+    let diff =
+        gix::diff::blob::diff_with_slider_heuristics(gix::diff::blob::Algorithm::Histogram, &input);
+    Ok(gix::diff::blob::UnifiedDiff::new(
+        &diff,
+        &input,
+        ConsumeBinaryHunk::new(ProduceDiffHunk::default(), "\n"),
+        ContextSize::symmetrical(context_lines),
+    )
+    .consume()?
+    .hunks)
+}
+
+#[derive(Default)]
+struct ProduceDiffHunk {
+    hunks: Vec<DiffHunk>,
+}
+
+impl gix::diff::blob::unified_diff::ConsumeBinaryHunkDelegate for ProduceDiffHunk {
+    fn consume_binary_hunk(
+        &mut self,
+        header: HunkHeader,
+        header_str: &str,
+        hunk: &[u8],
+    ) -> std::io::Result<()> {
+        self.hunks.push(DiffHunk {
+            old_start: header.before_hunk_start,
+            old_lines: header.before_hunk_len,
+            new_start: header.after_hunk_start,
+            new_lines: header.after_hunk_len,
+            diff: {
+                let mut buf = Vec::with_capacity(header_str.len() + hunk.len());
+                buf.extend_from_slice(header_str.as_bytes());
+                buf.extend_from_slice(hunk);
+                detect_and_convert_to_utf8(buf.into())
+            },
+        });
+        Ok(())
+    }
 }
 
 /// Detect the encoding of the given byte content and convert it to UTF-8, after attempting to guess its encoding.
@@ -456,3 +456,135 @@ mod create {
         Ok(commit)
     }
 }
+
+mod message_policy {
+    use bstr::ByteSlice;
+    use but_core::commit::message_policy::{self, MessagePolicy, PolicyViolations, Violation};
+    use but_testsupport::read_only_in_memory_scenario;
+
+    fn policy() -> MessagePolicy {
+        MessagePolicy {
+            conventional_types: Some(vec!["feat".into(), "fix".into()]),
+            max_subject_length: Some(30),
+            required_trailers: vec!["Ticket".into()],
+            forbidden_words: vec!["wip".into()],
+        }
+    }
+
+    #[test]
+    fn compliant_messages_have_no_violations() {
+        let policy = policy();
+        for message in [
+            "feat: add it\n\nTicket: GB-1\n",
+            "fix(cli)!: break it\n\nbody\n\nTicket: GB-2",
+            "feat(core): wiping is fine\n\nticket: GB-3\n",
+        ] {
+            let violations = policy.violations(message.as_bytes().as_bstr());
+            assert!(violations.is_empty(), "{message}: {violations:?}");
+        }
+    }
+
+    #[test]
+    fn all_violations_are_reported() {
+        let violations = policy().violations(b"WIP Add a subject that is way too long".as_bstr());
+        assert_eq!(
+            violations,
+            [
+                Violation::NotConventional,
+                Violation::SubjectTooLong {
+                    length: 38,
+                    max: 30
+                },
+                Violation::MissingTrailer("Ticket".into()),
+                Violation::ForbiddenWord("wip".into()),
+            ]
+        );
+        assert_eq!(
+            PolicyViolations(violations).to_string(),
+            "The commit message violates the commit message policy of this project:
+  - the subject must follow Conventional Commits, like 'type(scope): description'
+  - the subject is 38 characters long, but at most 30 are allowed
+  - the trailer 'Ticket: <value>' is required
+  - the word 'wip' isn't allowed"
+        );
+    }
+
+    #[test]
+    fn conventional_subjects_must_use_an_allowed_type() {
+        let policy = MessagePolicy {
+            conventional_types: Some(vec!["feat".into()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            policy.violations(b"docs: explain".as_bstr()),
+            [Violation::UnknownType {
+                found: "docs".into(),
+                allowed: vec!["feat".into()]
+            }]
+        );
+        for not_conventional in ["feat:", "feat() : x", "feat(): x", "two words: x", ": x"] {
+            assert_eq!(
+                policy.violations(not_conventional.as_bytes().as_bstr()),
+                [Violation::NotConventional],
+                "{not_conventional}"
+            );
+        }
+    }
+
+    #[test]
+    fn trailers_need_a_value_in_the_trailer_block() {
+        let policy = MessagePolicy {
+            required_trailers: vec!["Ticket".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            policy
+                .violations(b"subject\n\nTicket: GB-1 is mentioned in the body\n\nbody".as_bstr()),
+            [Violation::MissingTrailer("Ticket".into())],
+            "only the last paragraph holds trailers"
+        );
+        assert_eq!(
+            policy.violations(b"Ticket: GB-1".as_bstr()),
+            [Violation::MissingTrailer("Ticket".into())],
+            "the subject is never a trailer"
+        );
+    }
+
+    #[test]
+    fn from_config() -> anyhow::Result<()> {
+        let mut repo = read_only_in_memory_scenario("unborn-empty")?;
+        assert_eq!(
+            MessagePolicy::from_config(&repo.config_snapshot())?,
+            None,
+            "no policy is configured by default"
+        );
+        message_policy::enforce(&repo, b"anything goes".as_bstr())?;
+
+        {
+            let mut config = repo.config_snapshot_mut();
+            config.set_raw_value("gitbutler.commitPolicy.conventional", "true")?;
+            config.set_raw_value("gitbutler.commitPolicy.maxSubjectLength", "50")?;
+            config.set_raw_value("gitbutler.commitPolicy.forbiddenWord", "fixup")?;
+        }
+        let policy =
+            MessagePolicy::from_config(&repo.config_snapshot())?.expect("policy is configured");
+        assert_eq!(policy.max_subject_length, Some(50));
+        assert_eq!(policy.forbidden_words, ["fixup"]);
+        assert_eq!(
+            policy.conventional_types.as_deref().map(<[String]>::len),
+            Some(message_policy::DEFAULT_CONVENTIONAL_TYPES.len()),
+            "all common types are allowed by default"
+        );
+
+        let err = message_policy::enforce(&repo, b"fixup typo".as_bstr())
+            .expect_err("the message violates the policy");
+        let violations: &PolicyViolations = err.downcast_ref().expect("typed error");
+        assert_eq!(violations.0.len(), 2);
+        message_policy::enforce(&repo, b"docs: fix typo".as_bstr())?;
+
+        message_policy::disable(&mut repo)?;
+        message_policy::enforce(&repo, b"fixup typo".as_bstr())?;
+        assert_eq!(MessagePolicy::from_config(&repo.config_snapshot())?, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use but_core::{UnifiedPatch, diff::lfs::Pointer};
+use but_testsupport::read_only_in_memory_scenario;
+
+#[test]
+fn pointer_from_bytes() {
+    let pointer = Pointer::from_bytes(
+        b"version https://git-lfs.github.com/spec/v1\n\
+          oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n\
+          size 12345\n",
+    )
+    .expect("valid pointer");
+    assert_eq!(
+        pointer,
+        Pointer {
+            oid: "sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393".into(),
+            size: 12345,
+        }
+    );
+    assert_eq!(pointer.short_oid(7), "4d7a214");
+
+    assert_eq!(
+        Pointer::from_bytes(
+            b"version https://git-lfs.github.com/spec/v1\n\
+              ext-0-foo sha256:0000000000000000000000000000000000000000000000000000000000000000\n\
+              oid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\n\
+              size 1\n"
+        )
+        .map(|p| p.size),
+        Some(1),
+        "extensions are ignored"
+    );
+}
+
+#[test]
+fn non_pointers_are_rejected() {
+    for input in [
+        &b""[..],
+        b"just some text\n",
+        b"version https://git-lfs.github.com/spec/v1\nsize 12\n",
+        b"version https://git-lfs.github.com/spec/v1\noid sha256:4d7a\n",
+        b"version https://git-lfs.github.com/spec/v1\noid sha256:not-hex\nsize 12\n",
+        b"version https://example.com/spec/v1\noid sha256:4d7a\nsize 12\n",
+    ] {
+        assert_eq!(Pointer::from_bytes(input), None, "{input:?}");
+    }
+    let mut too_large =
+        b"version https://git-lfs.github.com/spec/v1\noid sha256:4d7a\nsize 1\n".to_vec();
+    too_large.resize(Pointer::MAX_SIZE as usize + 1, b'\n');
+    assert_eq!(Pointer::from_bytes(&too_large), None);
+}
+
+#[test]
+fn modified_pointer_is_shown_by_its_objects() -> anyhow::Result<()> {
+    let repo = read_only_in_memory_scenario("lfs-pointer-modified")?;
+    let changes = but_core::diff::worktree_changes(&repo)?.changes;
+    let change = changes
+        .iter()
+        .find(|change| change.path == "large.bin")
+        .expect("the pointer changed");
+
+    let Some(UnifiedPatch::Lfs {
+        previous_pointer,
+        pointer,
+    }) = change.unified_patch(&repo, 3)?
+    else {
+        unreachable!("pointers aren't diffed as text");
+    };
+    assert_eq!(previous_pointer.map(|p| p.size), Some(12));
+    assert_eq!(pointer.map(|p| p.size), Some(42));
+
+    let unfiltered = changes
+        .iter()
+        .find(|change| change.path == "unfiltered.bin")
+        .expect("the untracked file is listed");
+    assert!(
+        matches!(
+            unfiltered.unified_patch(&repo, 3)?,
+            Some(UnifiedPatch::Binary)
+        ),
+        "without a clean filter, the file content is diffed as is"
+    );
+    Ok(())
+}
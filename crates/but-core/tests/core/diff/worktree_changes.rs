@@ -347,9 +347,27 @@ WorktreeChanges {
 
 "#]]
     );
+    let patches = unified_patches(actual, &repo)?;
+    let UnifiedPatch::Submodule {
+        previous_commit,
+        commit,
+        commits_added,
+        commits_removed,
+    } = &patches[1]
+    else {
+        unreachable!(
+            "submodules are shown by their commits, got {:?}",
+            patches[1]
+        );
+    };
+    assert_eq!(*previous_commit, None);
     assert_eq!(
-        unified_patches(actual, &repo).unwrap_err().to_string(),
-        "Can only diff blobs and links, not Commit"
+        commit.map(|id| id.to_string()).as_deref(),
+        Some("e95516bd2f49a83a6cdb98cfec40b2717fbc2c1b")
+    );
+    assert!(
+        commits_added.is_empty() && commits_removed.is_empty(),
+        "new submodules don't list their whole history"
     );
     Ok(())
 }
@@ -432,10 +450,36 @@ WorktreeChanges {
 
 "#]]
     );
+    let patches = unified_patches(actual, &repo)?;
+    let UnifiedPatch::Submodule {
+        previous_commit,
+        commit,
+        commits_added,
+        commits_removed,
+    } = &patches[0]
+    else {
+        unreachable!(
+            "submodules are shown by their commits, got {:?}",
+            patches[0]
+        );
+    };
+    assert_eq!(
+        previous_commit.map(|id| id.to_string()).as_deref(),
+        Some("e95516bd2f49a83a6cdb98cfec40b2717fbc2c1b")
+    );
     assert_eq!(
-        unified_patches(actual, &repo).unwrap_err().to_string(),
-        "Can only diff blobs and links, not Commit"
+        commit.map(|id| id.to_string()).as_deref(),
+        Some("800a5398d76f28db44bc976b561d8885687fd1b6")
     );
+    assert_eq!(
+        commits_added
+            .iter()
+            .map(|commit| commit.title.to_string())
+            .collect::<Vec<_>>(),
+        ["change in submodule to adjust its HEAD ref"],
+        "the submodule repository is available, so the new commits are listed"
+    );
+    assert!(commits_removed.is_empty(), "it's a fast-forward");
     Ok(())
 }
 
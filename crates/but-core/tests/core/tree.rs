@@ -1,4 +1,4 @@
-use but_core::DiffSpec;
+use but_core::{DiffSpec, tree::create_tree::RejectionReason};
 use but_testsupport::writable_scenario;
 use gix::object::tree::EntryKind;
 
@@ -64,6 +64,92 @@ fn rename_with_new_directory_at_old_path_keeps_directory() -> anyhow::Result<()>
     Ok(())
 }
 
+#[test]
+fn submodule_with_new_head_is_committed_by_its_commit() -> anyhow::Result<()> {
+    let (repo, _tmp) = writable_scenario("submodule-changed-head");
+    let base_tree = repo.head_tree_id()?.detach();
+    let recorded_commit = repo.rev_parse_single("@:submodule")?.detach();
+    let checked_out_commit = gix::open(repo.workdir().expect("non-bare").join("submodule"))?
+        .head_id()?
+        .detach();
+    assert_ne!(
+        recorded_commit, checked_out_commit,
+        "the submodule moved on"
+    );
+
+    let mut changes = vec![Ok(spec(None, "submodule"))];
+    let (new_tree, _base) =
+        but_core::tree::apply_worktree_changes(base_tree, &repo, &mut changes, 0)?;
+
+    assert!(
+        changes.iter().all(|c| c.is_ok()),
+        "submodules are committed as a whole: {changes:?}"
+    );
+    let entry = new_tree
+        .object()?
+        .into_tree()
+        .lookup_entry_by_path("submodule")?
+        .expect("the submodule is still present");
+    assert_eq!(entry.mode().kind(), EntryKind::Commit);
+    assert_eq!(
+        entry.object_id(),
+        checked_out_commit,
+        "the commit checked out in the submodule is recorded"
+    );
+    Ok(())
+}
+
+#[test]
+fn lfs_files_are_only_committed_as_pointers() -> anyhow::Result<()> {
+    let (repo, _tmp) = writable_scenario("lfs-pointer-modified");
+    let base_tree = repo.head_tree_id()?.detach();
+
+    let mut changes = vec![
+        Ok(spec(None, "large.bin")),
+        Ok(spec(None, "unfiltered.bin")),
+        Ok(spec(None, "unfiltered-large.bin")),
+        Ok(spec(None, "empty.bin")),
+    ];
+    let (new_tree, _base) =
+        but_core::tree::apply_worktree_changes(base_tree, &repo, &mut changes, 0)?;
+
+    assert!(changes[0].is_ok(), "pointers are committed as they are");
+    assert!(
+        matches!(
+            changes[1],
+            Err((RejectionReason::LfsCleanFilterNotApplied, _))
+        ),
+        "without `git-lfs`, the file would be stored in Git: {changes:?}"
+    );
+    assert!(
+        matches!(
+            changes[2],
+            Err((RejectionReason::LfsCleanFilterNotApplied, _))
+        ),
+        "files too large to be pointers are rejected as well: {changes:?}"
+    );
+    let unfiltered_large_blob =
+        gix::ObjectId::from_hex(b"08e7df176454f3ee5eeda13efa0adaa54828dfd8")?;
+    assert!(
+        !repo.has_object(unfiltered_large_blob),
+        "they are rejected before they are written to the object database"
+    );
+    assert!(
+        changes[3].is_ok(),
+        "empty files aren't pointers, but Git LFS stores them as they are"
+    );
+    let tree = new_tree.object()?.into_tree();
+    assert!(tree.lookup_entry_by_path("large.bin")?.is_some());
+    assert!(tree.lookup_entry_by_path("empty.bin")?.is_some());
+    for rejected in ["unfiltered.bin", "unfiltered-large.bin"] {
+        assert!(
+            tree.lookup_entry_by_path(rejected)?.is_none(),
+            "the rejected file {rejected} isn't committed"
+        );
+    }
+    Ok(())
+}
+
 fn spec(previous_path: Option<&str>, path: &str) -> DiffSpec {
     DiffSpec {
         previous_path: previous_path.map(Into::into),
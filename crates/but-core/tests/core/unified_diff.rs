@@ -166,7 +166,10 @@ fn big_file_20_in_worktree() -> anyhow::Result<()> {
     )?
     .expect("present");
     match actual {
-        UnifiedPatch::Binary | UnifiedPatch::Patch { .. } => {
+        UnifiedPatch::Binary
+        | UnifiedPatch::Patch { .. }
+        | UnifiedPatch::Submodule { .. }
+        | UnifiedPatch::Lfs { .. } => {
             unreachable!("Should be considered too large")
         }
         UnifiedPatch::TooLarge { size_in_bytes } => {
@@ -195,7 +198,10 @@ fn binary_file_in_worktree() -> anyhow::Result<()> {
     )?
     .expect("present");
     match actual {
-        UnifiedPatch::TooLarge { .. } | UnifiedPatch::Patch { .. } => {
+        UnifiedPatch::TooLarge { .. }
+        | UnifiedPatch::Patch { .. }
+        | UnifiedPatch::Submodule { .. }
+        | UnifiedPatch::Lfs { .. } => {
             unreachable!("Should be considered binary, but was {actual:?}");
         }
         UnifiedPatch::Binary => {
@@ -272,15 +278,28 @@ fn submodule_added() -> anyhow::Result<()> {
 "#]]
     );
     assert!(
-        changes[1].unified_patch(&repo, 3)?.is_none(),
-        "submodules produce no diffs"
+        matches!(
+            changes[1].unified_patch(&repo, 3)?,
+            Some(UnifiedPatch::Submodule {
+                previous_commit: None,
+                commit: Some(_),
+                ..
+            })
+        ),
+        "submodules are shown by their commits"
     );
     Ok(())
 }
 
 fn extract_patch(diff: Option<UnifiedPatch>) -> Vec<unified_diff::DiffHunk> {
     match diff {
-        None | Some(UnifiedPatch::Binary | UnifiedPatch::TooLarge { .. }) => {
+        None
+        | Some(
+            UnifiedPatch::Binary
+            | UnifiedPatch::TooLarge { .. }
+            | UnifiedPatch::Submodule { .. }
+            | UnifiedPatch::Lfs { .. },
+        ) => {
             unreachable!("should have patches")
         }
         Some(UnifiedPatch::Patch { hunks, .. }) => hunks,
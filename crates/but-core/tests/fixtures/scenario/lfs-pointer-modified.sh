@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+
+### Description
+# A file tracked by Git LFS whose pointer changed in the worktree, as `git-lfs` would leave it after `git lfs pull` of
+# a newer version, and untracked files that should be stored in LFS, but which no clean filter converted: a small one,
+# a large one, and an empty one, which Git LFS stores as it is.
+set -eu -o pipefail
+
+git init
+echo '*.bin filter=lfs diff=lfs merge=lfs -text' >.gitattributes
+printf 'version https://git-lfs.github.com/spec/v1\noid sha256:%064d\nsize 12\n' 1 >large.bin
+git add . && git commit -m "init"
+
+printf 'version https://git-lfs.github.com/spec/v1\noid sha256:%064d\nsize 42\n' 2 >large.bin
+printf 'raw\0content\n' >unfiltered.bin
+head -c 4096 /dev/zero >unfiltered-large.bin
+touch empty.bin
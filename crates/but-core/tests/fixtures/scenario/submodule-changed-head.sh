@@ -0,0 +1,18 @@
+#!/usr/bin/env bash
+
+### Description
+# A submodule with a new commit checked out, which isn't recorded in `HEAD` yet.
+set -eu -o pipefail
+
+git init embedded-repository
+(cd embedded-repository
+  echo content >file && git add . && git commit -m "init"
+)
+
+git init
+git submodule add ./embedded-repository submodule
+git commit -m "add submodule"
+
+(cd submodule
+  echo change >>file && git commit -am "change in submodule"
+)
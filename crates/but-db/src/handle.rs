@@ -76,6 +76,14 @@ impl DbHandle {
     }
 }
 
+/// Access
+impl DbHandle {
+    /// Return the schema versions of this database, compared to what this binary supports.
+    pub fn schema_versions(&self) -> anyhow::Result<migration::SchemaVersions> {
+        migration::versions(&self.conn, migration::ours())
+    }
+}
+
 fn cache_for_db_path(path: &Path) -> OnDemand<CacheHandle> {
     if path == Path::new(":memory:") {
         return OnDemand::new(|| Ok(CacheHandle::new_at_path(":memory:")));
@@ -106,6 +106,37 @@ pub fn run<'m>(
     Ok(count)
 }
 
+/// The schema versions of a database, which is all that's needed to tell how it was migrated.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaVersions {
+    /// The forward-compatibility version of the database.
+    pub forward_compatibility: u32,
+    /// The highest forward-compatibility version the migrations passed to [`versions()`] lead to.
+    pub supported_forward_compatibility: u32,
+    /// The creation times of all migrations that were applied to the database, in order.
+    pub applied_migrations: Vec<String>,
+}
+
+/// Read the schema versions of the database behind `conn` without changing it, and compare them to
+/// what `migrations` would produce.
+pub fn versions<'m>(
+    conn: &rusqlite::Connection,
+    migrations: impl IntoIterator<Item = M<'m>>,
+) -> anyhow::Result<SchemaVersions> {
+    let migrations: Vec<_> = migrations.into_iter().collect();
+    let mut stmt =
+        conn.prepare("SELECT version FROM __diesel_schema_migrations ORDER BY version")?;
+    let applied_migrations = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(SchemaVersions {
+        forward_compatibility: conn.query_row("PRAGMA user_version", [], |row| row.get(0))?,
+        supported_forward_compatibility: highest_application_schema_version(&migrations),
+        applied_migrations,
+    })
+}
+
 fn db_forward_compatibility_version(conn: &rusqlite::Connection) -> Result<u32, Error> {
     conn.query_row("PRAGMA user_version", [], |row| row.get(0))
         .map_err(crate::map_err)
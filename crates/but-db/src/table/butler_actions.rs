@@ -107,20 +107,7 @@ impl ButlerActionsHandle<'_> {
         )?;
 
         let actions = stmt
-            .query_map([limit, offset], |row| {
-                Ok(ButlerAction {
-                    id: row.get(0)?,
-                    created_at: row.get(1)?,
-                    external_prompt: row.get(2)?,
-                    external_summary: row.get(3)?,
-                    handler: row.get(4)?,
-                    snapshot_before: row.get(5)?,
-                    snapshot_after: row.get(6)?,
-                    response: row.get(7)?,
-                    error: row.get(8)?,
-                    source: row.get(9)?,
-                })
-            })?
+            .query_map([limit, offset], butler_action_from_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         let total: i64 = self
@@ -129,6 +116,32 @@ impl ButlerActionsHandle<'_> {
 
         Ok((total, actions))
     }
+
+    /// Return all butler actions whose ID starts with `prefix`, ordered by created_at descending.
+    pub fn find_by_id_prefix(&self, prefix: &str) -> rusqlite::Result<Vec<ButlerAction>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT id, created_at, external_prompt, external_summary, handler, \
+             snapshot_before, snapshot_after, response, error, source \
+             FROM butler_actions WHERE substr(id, 1, length(?1)) = ?1 ORDER BY created_at DESC",
+        )?;
+        stmt.query_map([prefix], butler_action_from_row)?
+            .collect::<Result<Vec<_>, _>>()
+    }
+}
+
+fn butler_action_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ButlerAction> {
+    Ok(ButlerAction {
+        id: row.get(0)?,
+        created_at: row.get(1)?,
+        external_prompt: row.get(2)?,
+        external_summary: row.get(3)?,
+        handler: row.get(4)?,
+        snapshot_before: row.get(5)?,
+        snapshot_after: row.get(6)?,
+        response: row.get(7)?,
+        error: row.get(8)?,
+        source: row.get(9)?,
+    })
 }
 
 impl ButlerActionsHandleMut<'_> {
@@ -285,6 +285,36 @@ first | two
         Ok(())
     }
 
+    #[test]
+    fn versions_are_read_without_migrating() -> anyhow::Result<()> {
+        let mut db = rusqlite::Connection::open_in_memory()?;
+        let first = M::up(1, ZERO, "CREATE TABLE T1 ( first TEXT PRIMARY KEY );");
+        migration::run(&mut db, [first])?;
+
+        let second = M::up(
+            2,
+            SchemaVersion::One,
+            "CREATE TABLE T2 ( first TEXT PRIMARY KEY );",
+        );
+        let versions = migration::versions(&db, [first, second])?;
+        assert_eq!(
+            versions,
+            migration::SchemaVersions {
+                forward_compatibility: ZERO as u32,
+                supported_forward_compatibility: SchemaVersion::One as u32,
+                applied_migrations: vec!["1".into()],
+            },
+            "only what was applied is listed, while the supported version is what the migrations lead to"
+        );
+        let num_t2_tables: u32 = db.query_row(
+            "SELECT count(*) FROM sqlite_master WHERE name = 'T2'",
+            [],
+            |row| row.get(0),
+        )?;
+        assert_eq!(num_t2_tables, 0, "reading versions doesn't migrate");
+        Ok(())
+    }
+
     #[test]
     fn schema_version_bumps_even_if_all_migrations_are_already_applied() -> anyhow::Result<()> {
         let mut db = rusqlite::Connection::open_in_memory()?;
@@ -86,6 +86,37 @@ fn list_empty() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn find_by_id_prefix() -> anyhow::Result<()> {
+    let mut db = in_memory_db();
+
+    for id in ["abc1", "abc2", "abd3"] {
+        db.butler_actions_mut()
+            .insert(butler_action(id, "handler", "summary", "before", "after"))?;
+    }
+
+    let ids = |prefix: &str| -> anyhow::Result<Vec<String>> {
+        let mut ids: Vec<_> = db
+            .butler_actions()
+            .find_by_id_prefix(prefix)?
+            .into_iter()
+            .map(|action| action.id)
+            .collect();
+        ids.sort();
+        Ok(ids)
+    };
+    assert_eq!(ids("abc")?, ["abc1", "abc2"]);
+    assert_eq!(ids("abd3")?, ["abd3"]);
+    assert_eq!(ids("ab")?.len(), 3);
+    assert!(ids("abc1x")?.is_empty());
+    assert!(
+        ids("%")?.is_empty(),
+        "prefixes are compared literally, not as patterns"
+    );
+
+    Ok(())
+}
+
 #[test]
 fn with_transaction() -> anyhow::Result<()> {
     let mut db = in_memory_db();
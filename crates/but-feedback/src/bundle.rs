@@ -0,0 +1,295 @@
+use std::{
+    fs,
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use anyhow::{Context as _, Result};
+use but_ctx::Context;
+use gitbutler_oplog::OplogExt;
+use serde::Serialize;
+use zip::ZipWriter;
+
+use crate::zip::file_options;
+
+/// The amount of most recently modified log files to include.
+const MAX_LOG_FILES: usize = 3;
+/// Only the tail of each log file is included, as the most recent entries are the interesting ones.
+const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+/// The amount of most recent oplog entries to summarize.
+const MAX_OPLOG_ENTRIES: usize = 100;
+/// Setting values that may identify the user or their machine, as JSON pointers into the serialized settings.
+const REDACTED_SETTINGS: &[&str] = &[
+    "/telemetry/appDistinctId",
+    "/githubOauthApp/oauthClientId",
+    "/extraCsp/hosts",
+    "/extraCsp/imgSrc",
+    "/claude/executable",
+    "/secrets/keyFile",
+    "/secrets/encryptedFilePath",
+];
+/// Parts of git configuration keys that indicate the value is a secret.
+const SECRET_KEY_PARTS: &[&str] = &["token", "secret", "password", "key"];
+const REDACTED: &str = "<redacted>";
+
+/// A single file within a [`Bundle`].
+#[derive(Debug, Clone)]
+pub struct BundleFile {
+    /// The path of the file within the archive.
+    pub name: String,
+    /// A human-readable explanation of what the file contains.
+    pub description: &'static str,
+    /// The content of the file, exactly as it will be shared.
+    pub content: Vec<u8>,
+}
+
+/// An entry in the manifest of a [`Bundle`], describing one of its files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ManifestEntry {
+    /// The path of the file within the archive.
+    pub name: String,
+    /// A human-readable explanation of what the file contains.
+    pub description: String,
+    /// The size of the file in bytes.
+    pub size: usize,
+}
+
+/// A collection of diagnostics that can be shared with developers when reporting a bug,
+/// with everything that could identify the user or their code removed or anonymized.
+///
+/// All content is collected in memory so it can be previewed before it is written.
+#[derive(Debug, Clone, Default)]
+pub struct Bundle {
+    /// The files to include, in order.
+    pub files: Vec<BundleFile>,
+}
+
+impl Bundle {
+    /// Collect diagnostics for the project in `ctx`, along with the most recent logs in `logs_dir`.
+    ///
+    /// Sections that fail to be collected don't fail the bundle, but are listed in `errors.txt` instead.
+    pub fn collect(ctx: &Context, logs_dir: &Path) -> Self {
+        let mut bundle = Bundle::default();
+        let mut errors = Vec::new();
+
+        let mut add =
+            |name: &str, description: &'static str, content: Result<Vec<u8>>| match content {
+                Ok(content) => bundle.push(name, description, content),
+                Err(err) => errors.push(format!("{name}: {err:#}")),
+            };
+        add(
+            "settings.json",
+            "Application settings, with identifying values redacted",
+            redacted_settings(ctx),
+        );
+        add(
+            "git-config.txt",
+            "GitButler-specific git configuration of the repository, with secrets redacted",
+            gitbutler_config(ctx),
+        );
+        add(
+            "graph.dot",
+            "The commit graph with all names, messages and hashes anonymized",
+            anonymous_graph_dot(ctx).map(String::into_bytes),
+        );
+        add(
+            "oplog.txt",
+            "Time and kind of the most recent operations, without titles or descriptions",
+            oplog_summary(ctx),
+        );
+        add(
+            "db.json",
+            "Schema versions of the project database",
+            db_schema_versions(ctx),
+        );
+        match recent_log_files(logs_dir) {
+            Ok(paths) => {
+                for path in paths {
+                    let name = format!(
+                        "logs/{}",
+                        path.file_name().unwrap_or_default().to_string_lossy()
+                    );
+                    add(
+                        &name,
+                        "The most recent application log entries",
+                        read_tail(&path, MAX_LOG_BYTES),
+                    );
+                }
+            }
+            Err(err) => errors.push(format!("logs: {err:#}")),
+        }
+
+        if !errors.is_empty() {
+            errors.push(String::new());
+            bundle.push(
+                "errors.txt",
+                "Diagnostics that could not be collected",
+                errors.join("\n").into_bytes(),
+            );
+        }
+        bundle
+    }
+
+    /// Add a file named `name` with `content`, explained by `description`.
+    pub fn push(&mut self, name: &str, description: &'static str, content: Vec<u8>) {
+        self.files.push(BundleFile {
+            name: name.to_owned(),
+            description,
+            content,
+        });
+    }
+
+    /// Describe every file in the bundle, which is exactly what [`Self::write_zip()`] will share.
+    pub fn manifest(&self) -> Vec<ManifestEntry> {
+        self.files
+            .iter()
+            .map(|file| ManifestEntry {
+                name: file.name.clone(),
+                description: file.description.to_owned(),
+                size: file.content.len(),
+            })
+            .collect()
+    }
+
+    /// Write all files along with a `manifest.json` into a zip archive at `dst_file`,
+    /// possibly overwriting it if it exists.
+    pub fn write_zip(&self, dst_file: impl AsRef<Path>) -> Result<PathBuf> {
+        let dst_file = dst_file.as_ref();
+        let mut zip = ZipWriter::new(fs::File::create(dst_file)?);
+
+        zip.start_file("manifest.json", file_options())?;
+        zip.write_all(&serde_json::to_vec_pretty(&self.manifest())?)?;
+        for file in &self.files {
+            zip.start_file(file.name.as_str(), file_options())?;
+            zip.write_all(&file.content)?;
+        }
+        zip.finish()?;
+
+        Ok(dst_file.to_owned())
+    }
+}
+
+/// Build the commit graph of `ctx` and render it as anonymized dot graph.
+pub(crate) fn anonymous_graph_dot(ctx: &Context) -> Result<String> {
+    let mut options = ctx.graph_options(Default::default())?;
+    let repo = ctx.repo.get()?;
+    let meta = ctx.meta()?;
+    let project_meta = ctx.project_meta()?;
+    let mut graph =
+        but_graph::Graph::from_head(&repo, &meta, project_meta.clone(), options.clone()).or_else(
+            |_| {
+                // Assume it fails because of post-processing, try again without.
+                options.dangerously_skip_postprocessing_for_debugging = true;
+                but_graph::Graph::from_head(&repo, &meta, project_meta, options)
+            },
+        )?;
+    Ok(graph.anonymize(&repo.remote_names())?.dot_graph_pruned())
+}
+
+fn redacted_settings(ctx: &Context) -> Result<Vec<u8>> {
+    let mut settings = serde_json::to_value(&ctx.settings)?;
+    for pointer in REDACTED_SETTINGS {
+        if let Some(value) = settings.pointer_mut(pointer) {
+            *value = REDACTED.into();
+        }
+    }
+    Ok(serde_json::to_vec_pretty(&settings)?)
+}
+
+fn gitbutler_config(ctx: &Context) -> Result<Vec<u8>> {
+    let repo = ctx.repo.get()?;
+    let config = repo.config_snapshot();
+    let mut out = String::new();
+    for section in config.sections() {
+        let header = section.header();
+        let section_name = header.name().to_str_lossy();
+        if section_name != "gitbutler" && section_name != "but" {
+            continue;
+        }
+        let prefix = match header.subsection_name() {
+            Some(subsection) => format!("{section_name}.{}", subsection.to_str_lossy()),
+            None => section_name.into_owned(),
+        };
+        for value_name in section.value_names() {
+            let key = format!("{prefix}.{value_name}");
+            let is_secret = SECRET_KEY_PARTS
+                .iter()
+                .any(|part| key.to_lowercase().contains(part));
+            let value = if is_secret {
+                REDACTED.into()
+            } else {
+                section
+                    .value(value_name)
+                    .map(|value| value.to_str_lossy().into_owned())
+                    .unwrap_or_default()
+            };
+            out.push_str(&format!("{key}={value}\n"));
+        }
+    }
+    Ok(out.into_bytes())
+}
+
+fn oplog_summary(ctx: &Context) -> Result<Vec<u8>> {
+    let mut out = String::new();
+    for snapshot in ctx
+        .snapshots_iter(None, Vec::new(), None)?
+        .take(MAX_OPLOG_ENTRIES)
+    {
+        let snapshot = snapshot?;
+        let kind = snapshot
+            .details
+            .map_or("Unknown", |details| details.operation.as_persisted_str());
+        let time = snapshot
+            .created_at
+            .format_or_unix(gix::date::time::format::ISO8601);
+        out.push_str(&format!("{time} {kind}\n"));
+    }
+    Ok(out.into_bytes())
+}
+
+fn db_schema_versions(ctx: &Context) -> Result<Vec<u8>> {
+    let db = but_db::DbHandle::open_existing_read_only_in_directory(ctx.project_data_dir())?
+        .context("The project database does not exist")?;
+    Ok(serde_json::to_vec_pretty(&db.schema_versions()?)?)
+}
+
+/// Return the paths of the most recently modified files directly in `logs_dir`, most recent first.
+///
+/// Only the GUI writes log files, so it's fine for `logs_dir` not to exist.
+fn recent_log_files(logs_dir: &Path) -> Result<Vec<PathBuf>> {
+    if !logs_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(logs_dir)
+        .with_context(|| format!("Could not read '{}'", logs_dir.display()))?
+    {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            files.push((
+                metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                entry.path(),
+            ));
+        }
+    }
+    files.sort_by(|a, b| b.0.cmp(&a.0));
+    Ok(files
+        .into_iter()
+        .take(MAX_LOG_FILES)
+        .map(|(_, path)| path)
+        .collect())
+}
+
+/// Read at most the last `max_bytes` of the file at `path`.
+fn read_tail(path: &Path, max_bytes: u64) -> Result<Vec<u8>> {
+    let mut file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    if len > max_bytes {
+        file.seek(SeekFrom::Start(len - max_bytes))?;
+    }
+    let mut buf = Vec::new();
+    file.take(max_bytes).read_to_end(&mut buf)?;
+    Ok(buf)
+}
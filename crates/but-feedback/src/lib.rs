@@ -14,7 +14,7 @@ pub struct Archival {
 }
 
 /// Create timestamps like `2025-08-20T14-31-22`, which are safe even for Windows.
-fn filesafe_date_time() -> String {
+pub fn filesafe_date_time() -> String {
     chrono::Local::now().format("%Y-%m-%dT%H-%M-%S").to_string()
 }
 
@@ -33,18 +33,7 @@ impl Archival {
 
     /// Create an anonymous archive commit graph for `ctx`, such that it doesn't reveal PII.
     pub fn zip_anonymous_graph(&self, ctx: &Context) -> Result<PathBuf> {
-        let mut options = ctx.graph_options(Default::default())?;
-        let repo = ctx.repo.get()?;
-        let meta = ctx.meta()?;
-        let project_meta = ctx.project_meta()?;
-        let mut graph =
-            but_graph::Graph::from_head(&repo, &meta, project_meta.clone(), options.clone())
-                .or_else(|_| {
-                    // Assume it fails because of post-processing, try again without.
-                    options.dangerously_skip_postprocessing_for_debugging = true;
-                    but_graph::Graph::from_head(&repo, &meta, project_meta, options)
-                })?;
-        let dot_file_contents = graph.anonymize(&repo.remote_names())?.dot_graph_pruned();
+        let dot_file_contents = bundle::anonymous_graph_dot(ctx)?;
         let output_file = self.cache_dir.join(format!(
             "commit-graph-anon-{date}.zip",
             date = filesafe_date_time()
@@ -52,6 +41,15 @@ impl Archival {
         create_zip_file_from_content(&dot_file_contents, "anon-graph.dot", output_file)
     }
 
+    /// Collect a [`Bundle`] of anonymized diagnostics for `ctx` and write it into the cache directory.
+    pub fn zip_diagnostics_bundle(&self, ctx: &Context) -> Result<PathBuf> {
+        let output_file = self.cache_dir.join(format!(
+            "gitbutler-bundle-{date}.zip",
+            date = filesafe_date_time()
+        ));
+        Bundle::collect(ctx, &self.logs_dir).write_zip(output_file)
+    }
+
     /// Create an archive of all logs in the application log directory.
     pub fn zip_logs(&self) -> Result<PathBuf> {
         let output_file = self
@@ -61,5 +59,8 @@ impl Archival {
     }
 }
 
+mod bundle;
+pub use bundle::{Bundle, BundleFile, ManifestEntry};
+
 mod zip;
 pub use zip::{create_zip_file_from_content, create_zip_file_from_dir};
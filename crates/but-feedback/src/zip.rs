@@ -94,7 +94,7 @@ where
     Result::Ok(())
 }
 
-fn file_options() -> SimpleFileOptions {
+pub(crate) fn file_options() -> SimpleFileOptions {
     SimpleFileOptions::default()
         .compression_method(CompressionMethod::Bzip2)
         .unix_permissions(0o755)
@@ -75,3 +75,46 @@ mod create_zip_file_from_stream {
         Ok(())
     }
 }
+
+mod bundle {
+    use std::{fs::File, io::Read};
+
+    use but_feedback::{Bundle, ManifestEntry};
+    use tempfile::tempdir;
+
+    #[test]
+    fn write_zip_includes_manifest() -> anyhow::Result<()> {
+        let mut bundle = Bundle::default();
+        bundle.push("settings.json", "settings", b"{}".to_vec());
+        bundle.push("logs/app.log", "logs", b"a log line".to_vec());
+        assert_eq!(
+            bundle.manifest(),
+            [
+                ManifestEntry {
+                    name: "settings.json".into(),
+                    description: "settings".into(),
+                    size: 2,
+                },
+                ManifestEntry {
+                    name: "logs/app.log".into(),
+                    description: "logs".into(),
+                    size: 10,
+                },
+            ]
+        );
+
+        let out_dir = tempdir()?;
+        let zip_file_path = bundle.write_zip(out_dir.path().join("out.zip"))?;
+
+        let mut archive = zip::ZipArchive::new(File::open(&zip_file_path)?)?;
+        let names: Vec<_> = archive.file_names().collect();
+        assert_eq!(names, ["manifest.json", "settings.json", "logs/app.log"]);
+
+        let mut contents = String::new();
+        archive
+            .by_name("logs/app.log")?
+            .read_to_string(&mut contents)?;
+        assert_eq!(contents, "a log line");
+        Ok(())
+    }
+}
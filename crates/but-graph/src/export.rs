@@ -0,0 +1,372 @@
+//! Stable, user-facing exports of the segmented graph.
+//!
+//! Unlike [`Graph::dot_graph_pruned()`], which is a debugging aid whose output changes whenever
+//! it's convenient for developers, the exports here are meant to be pasted into design documents
+//! or bug reports, and parsed by tools. They are rendered from an intermediate [`Document`]
+//! which is also what the JSON format serializes.
+//!
+//! To avoid leaking branch names, call [`Graph::anonymize()`] on a clone of the graph before exporting it.
+use std::collections::BTreeSet;
+
+use bstr::ByteSlice;
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+use crate::{
+    CommitFlags, Graph, Segment, SegmentIndex, SegmentMetadata, workspace::workspace::Downgrade,
+};
+
+/// The format to export a graph in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// The Graphviz `dot` language.
+    Dot,
+    /// A Mermaid `flowchart`, renderable by many Markdown viewers.
+    Mermaid,
+    /// A JSON serialization of the [`Document`].
+    Json,
+}
+
+/// The level of detail to export.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Detail {
+    /// Each segment is a node, connected to other segments.
+    #[default]
+    Segments,
+    /// Each commit is a node, connected to its parents and grouped by the segment that owns it.
+    Commits,
+}
+
+/// Options for use in [`Graph::export()`] and [`Graph::export_document()`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Options {
+    /// The level of detail of the export.
+    pub detail: Detail,
+    /// If `true`, only export segments that are part of the workspace projection, i.e. the workspace tip,
+    /// the segments of all stacks and the workspace lower bound.
+    ///
+    /// This fails if no workspace can be projected from the graph.
+    pub workspace_only: bool,
+}
+
+/// A format-independent representation of the exported graph.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Document {
+    /// All exported segments, ordered by their id.
+    pub segments: Vec<SegmentNode>,
+    /// All connections between exported segments.
+    ///
+    /// With [`Detail::Commits`], commits are connected through [`CommitNode::parents`] instead.
+    pub connections: Vec<Connection>,
+}
+
+/// The kind of metadata associated with a segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SegmentKind {
+    /// The segment carries workspace metadata and is the tip of a managed workspace.
+    Workspace,
+    /// The segment carries branch metadata.
+    Branch,
+}
+
+/// An exported segment.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SegmentNode {
+    /// The graph-local id of the segment, only stable within this export.
+    pub id: usize,
+    /// The shortened name of the reference at the tip of the segment, if it is named.
+    pub name: Option<String>,
+    /// The shortened name of the remote tracking branch of the segment, if there is one.
+    pub remote: Option<String>,
+    /// The kind of metadata stored with the segment, if any.
+    pub kind: Option<SegmentKind>,
+    /// `true` if this is the segment the traversal started at.
+    pub is_entrypoint: bool,
+    /// The amount of commits owned by the segment.
+    pub num_commits: usize,
+    /// The top-most commit of the segment, if it owns any.
+    pub tip: Option<String>,
+    /// The commits owned by this segment, top-most first, only filled in with [`Detail::Commits`].
+    pub commits: Vec<CommitNode>,
+}
+
+/// An exported commit.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct CommitNode {
+    /// The hex-encoded commit hash.
+    pub id: String,
+    /// The hex-encoded parent hashes of all parents that are part of the export.
+    pub parents: Vec<String>,
+    /// The shortened names of all references pointing to this commit.
+    pub refs: Vec<String>,
+    /// The names of all flags set on the commit.
+    pub flags: Vec<String>,
+}
+
+/// A connection from a segment to its parent segment.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Connection {
+    /// The id of the segment whose commit has the parent.
+    pub from: usize,
+    /// The id of the segment that owns the parent commit.
+    pub to: usize,
+    /// The hex-encoded commit in `from` that has the parent, if `from` owns commits.
+    pub from_commit: Option<String>,
+    /// The hex-encoded parent commit in `to`, if `to` owns commits.
+    pub to_commit: Option<String>,
+}
+
+/// Export
+impl Graph {
+    /// Render this graph in the given `format` with `options`.
+    pub fn export(&self, format: Format, options: Options) -> anyhow::Result<String> {
+        let doc = self.export_document(options)?;
+        Ok(match format {
+            Format::Dot => doc.to_dot(options.detail),
+            Format::Mermaid => doc.to_mermaid(options.detail),
+            Format::Json => serde_json::to_string_pretty(&doc)?,
+        })
+    }
+
+    /// Create the format-independent [`Document`] for this graph, configured by `options`.
+    pub fn export_document(&self, options: Options) -> anyhow::Result<Document> {
+        let included = if options.workspace_only {
+            let ws = self.to_workspace_state(Downgrade::Allow)?;
+            let mut included = BTreeSet::from([ws.id]);
+            included.extend(ws.lower_bound_segment_id);
+            included.extend(
+                ws.stacks
+                    .iter()
+                    .flat_map(|stack| stack.segments.iter())
+                    .flat_map(|segment| {
+                        std::iter::once(segment.id)
+                            .chain(segment.commits_by_segment.iter().map(|(sidx, _)| *sidx))
+                    }),
+            );
+            Some(included)
+        } else {
+            None
+        };
+        let is_included = |sidx: SegmentIndex| included.as_ref().is_none_or(|s| s.contains(&sidx));
+
+        let mut segment_ids: Vec<_> = self.segments().filter(|sidx| is_included(*sidx)).collect();
+        segment_ids.sort();
+        let exported_commits: BTreeSet<gix::ObjectId> = segment_ids
+            .iter()
+            .flat_map(|sidx| self[*sidx].commits.iter().map(|c| c.id))
+            .collect();
+
+        let entrypoint = self.entrypoint_location().map(|(sidx, _)| sidx);
+        let segments = segment_ids
+            .iter()
+            .map(|sidx| {
+                let s = &self[*sidx];
+                SegmentNode {
+                    id: sidx.index(),
+                    name: s
+                        .ref_name()
+                        .map(|rn| rn.shorten().to_str_lossy().into_owned()),
+                    remote: s
+                        .remote_tracking_ref_name
+                        .as_ref()
+                        .map(|rn| rn.shorten().to_str_lossy().into_owned()),
+                    kind: s.metadata.as_ref().map(|md| match md {
+                        SegmentMetadata::Workspace(_) => SegmentKind::Workspace,
+                        SegmentMetadata::Branch(_) => SegmentKind::Branch,
+                    }),
+                    is_entrypoint: entrypoint == Some(*sidx),
+                    num_commits: s.commits.len(),
+                    tip: s.tip().map(|id| id.to_string()),
+                    commits: match options.detail {
+                        Detail::Segments => Vec::new(),
+                        Detail::Commits => commit_nodes(s, &exported_commits),
+                    },
+                }
+            })
+            .collect();
+
+        let mut connections: Vec<_> = self
+            .inner
+            .edge_references()
+            .filter(|e| is_included(e.source()) && is_included(e.target()))
+            .map(|e| Connection {
+                from: e.source().index(),
+                to: e.target().index(),
+                from_commit: e.weight().src_id.map(|id| id.to_string()),
+                to_commit: e.weight().dst_id.map(|id| id.to_string()),
+            })
+            .collect();
+        connections.sort_by(|a, b| (a.from, a.to).cmp(&(b.from, b.to)));
+
+        Ok(Document {
+            segments,
+            connections,
+        })
+    }
+}
+
+fn commit_nodes(segment: &Segment, exported_commits: &BTreeSet<gix::ObjectId>) -> Vec<CommitNode> {
+    segment
+        .commits
+        .iter()
+        .map(|c| CommitNode {
+            id: c.id.to_string(),
+            parents: c
+                .parent_ids
+                .iter()
+                .filter(|id| exported_commits.contains(id))
+                .map(|id| id.to_string())
+                .collect(),
+            refs: c
+                .ref_name_iter()
+                .map(|rn| rn.shorten().to_str_lossy().into_owned())
+                .collect(),
+            flags: flag_names(c.flags),
+        })
+        .collect()
+}
+
+fn flag_names(flags: CommitFlags) -> Vec<String> {
+    flags
+        .iter_names()
+        .map(|(name, _)| name.to_owned())
+        .collect()
+}
+
+/// Rendering
+impl Document {
+    /// Render this document in the Graphviz `dot` language, using `detail` to know what to show.
+    pub fn to_dot(&self, detail: Detail) -> String {
+        let mut out = String::from("digraph {\n    node [shape = box, fontname = Courier];\n");
+        match detail {
+            Detail::Segments => {
+                for s in &self.segments {
+                    out.push_str(&format!(
+                        "    s{id} [label = \"{label}\"];\n",
+                        id = s.id,
+                        label = dot_escape(&s.label())
+                    ));
+                }
+                for c in &self.connections {
+                    out.push_str(&format!("    s{} -> s{};\n", c.from, c.to));
+                }
+            }
+            Detail::Commits => {
+                for s in &self.segments {
+                    out.push_str(&format!(
+                        "    subgraph cluster_{id} {{\n        label = \"{label}\";\n",
+                        id = s.id,
+                        label = dot_escape(&s.label())
+                    ));
+                    for c in &s.commits {
+                        out.push_str(&format!(
+                            "        \"{id}\" [label = \"{label}\"];\n",
+                            id = c.id,
+                            label = dot_escape(&c.label())
+                        ));
+                    }
+                    out.push_str("    }\n");
+                }
+                for s in &self.segments {
+                    for c in &s.commits {
+                        for parent in &c.parents {
+                            out.push_str(&format!("    \"{}\" -> \"{parent}\";\n", c.id));
+                        }
+                    }
+                }
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Render this document as Mermaid flowchart, using `detail` to know what to show.
+    pub fn to_mermaid(&self, detail: Detail) -> String {
+        let mut out = String::from("flowchart TD\n");
+        match detail {
+            Detail::Segments => {
+                for s in &self.segments {
+                    out.push_str(&format!(
+                        "    s{id}[\"{label}\"]\n",
+                        id = s.id,
+                        label = mermaid_escape(&s.label())
+                    ));
+                }
+                for c in &self.connections {
+                    out.push_str(&format!("    s{} --> s{}\n", c.from, c.to));
+                }
+            }
+            Detail::Commits => {
+                for s in &self.segments {
+                    out.push_str(&format!(
+                        "    subgraph s{id}[\"{label}\"]\n",
+                        id = s.id,
+                        label = mermaid_escape(&s.label())
+                    ));
+                    for c in &s.commits {
+                        out.push_str(&format!(
+                            "        c{id}[\"{label}\"]\n",
+                            id = c.id,
+                            label = mermaid_escape(&c.label())
+                        ));
+                    }
+                    out.push_str("    end\n");
+                }
+                for s in &self.segments {
+                    for c in &s.commits {
+                        for parent in &c.parents {
+                            out.push_str(&format!("    c{} --> c{parent}\n", c.id));
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+}
+
+impl SegmentNode {
+    fn label(&self) -> String {
+        let mut label = match &self.name {
+            Some(name) => name.clone(),
+            None => format!("anon:{}", self.id),
+        };
+        if let Some(remote) = &self.remote {
+            label.push_str(&format!(" <> {remote}"));
+        }
+        match self.kind {
+            Some(SegmentKind::Workspace) => label.push_str(" [workspace]"),
+            Some(SegmentKind::Branch) | None => {}
+        }
+        if self.is_entrypoint {
+            label.push_str(" [entrypoint]");
+        }
+        if self.num_commits > 0 {
+            label.push_str(&format!(
+                " ({} commit{})",
+                self.num_commits,
+                if self.num_commits == 1 { "" } else { "s" }
+            ));
+        }
+        label
+    }
+}
+
+impl CommitNode {
+    fn label(&self) -> String {
+        let short_id = &self.id[..self.id.len().min(7)];
+        if self.refs.is_empty() {
+            short_id.to_owned()
+        } else {
+            format!("{short_id} {}", self.refs.join(", "))
+        }
+    }
+}
+
+fn dot_escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn mermaid_escape(label: &str) -> String {
+    label.replace('"', "#quot;")
+}
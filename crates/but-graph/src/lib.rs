@@ -222,6 +222,9 @@ pub use statistics::Statistics;
 
 mod debug;
 
+/// Stable exports of the graph for consumption by users and tools.
+pub mod export;
+
 /// Edges to other segments are the index into the list of local commits of the parent segment.
 /// That way we can tell where a segment branches off, despite the graph only connecting segments, and not commits.
 pub type CommitIndex = usize;
@@ -0,0 +1,156 @@
+//! Tests for the stable graph exports.
+
+use std::str::FromStr;
+
+use but_graph::{
+    Commit, CommitFlags, Graph, RefInfo, Segment,
+    export::{Detail, Format, Options},
+};
+use gix::ObjectId;
+
+#[test]
+fn segments_as_mermaid() -> anyhow::Result<()> {
+    let graph = two_segments()?;
+    snapbox::assert_data_eq!(
+        graph.export(Format::Mermaid, Options::default())?,
+        snapbox::str![[r#"
+flowchart TD
+    s0["main <> origin/main [entrypoint] (1 commit)"]
+    s1["anon:1 (1 commit)"]
+    s0 --> s1
+
+"#]]
+    );
+    Ok(())
+}
+
+#[test]
+fn commits_as_mermaid() -> anyhow::Result<()> {
+    let graph = two_segments()?;
+    snapbox::assert_data_eq!(
+        graph.export(
+            Format::Mermaid,
+            Options {
+                detail: Detail::Commits,
+                ..Default::default()
+            }
+        )?,
+        snapbox::str![[r#"
+flowchart TD
+    subgraph s0["main <> origin/main [entrypoint] (1 commit)"]
+        caaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa["aaaaaaa"]
+    end
+    subgraph s1["anon:1 (1 commit)"]
+        cbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb["bbbbbbb"]
+    end
+    caaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa --> cbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb
+
+"#]]
+    );
+    Ok(())
+}
+
+#[test]
+fn segments_as_dot() -> anyhow::Result<()> {
+    let graph = two_segments()?;
+    snapbox::assert_data_eq!(
+        graph.export(Format::Dot, Options::default())?,
+        snapbox::str![[r#"
+digraph {
+    node [shape = box, fontname = Courier];
+    s0 [label = "main <> origin/main [entrypoint] (1 commit)"];
+    s1 [label = "anon:1 (1 commit)"];
+    s0 -> s1;
+}
+
+"#]]
+    );
+    Ok(())
+}
+
+#[test]
+fn segments_as_json() -> anyhow::Result<()> {
+    let graph = two_segments()?;
+    snapbox::assert_data_eq!(
+        graph.export(Format::Json, Options::default())?,
+        snapbox::str![[r#"
+{
+  "segments": [
+    {
+      "id": 0,
+      "name": "main",
+      "remote": "origin/main",
+      "kind": null,
+      "is_entrypoint": true,
+      "num_commits": 1,
+      "tip": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+      "commits": []
+    },
+    {
+      "id": 1,
+      "name": null,
+      "remote": null,
+      "kind": null,
+      "is_entrypoint": false,
+      "num_commits": 1,
+      "tip": "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+      "commits": []
+    }
+  ],
+  "connections": [
+    {
+      "from": 0,
+      "to": 1,
+      "from_commit": "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+      "to_commit": "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb"
+    }
+  ]
+}
+"#]]
+    );
+    Ok(())
+}
+
+/// A named segment with a single commit on top of an anonymous one.
+fn two_segments() -> anyhow::Result<Graph> {
+    let mut graph = Graph::default();
+    let main = graph.insert_segment_set_entrypoint(Segment {
+        ref_info: Some(RefInfo {
+            ref_name: "refs/heads/main".try_into()?,
+            commit_id: None,
+            worktree: None,
+        }),
+        remote_tracking_ref_name: Some("refs/remotes/origin/main".try_into()?),
+        commits: vec![commit(id("a"), Some(id("b")), CommitFlags::InWorkspace)],
+        ..Default::default()
+    });
+    graph.connect_new_segment(
+        main,
+        0,
+        Segment {
+            commits: vec![commit(id("b"), None, CommitFlags::empty())],
+            ..Default::default()
+        },
+        0,
+        None,
+        0,
+    );
+    Ok(graph)
+}
+
+fn id(hex: &str) -> ObjectId {
+    ObjectId::from_str(&hex.repeat(gix::hash::Kind::Sha1.len_in_hex() / hex.len())).unwrap()
+}
+
+fn commit(
+    id: ObjectId,
+    parent_ids: impl IntoIterator<Item = ObjectId>,
+    flags: CommitFlags,
+) -> Commit {
+    Commit {
+        id,
+        parent_ids: parent_ids.into_iter().collect(),
+        refs: Vec::new(),
+        flags,
+    }
+}
@@ -1,3 +1,4 @@
+mod export;
 mod init;
 mod merge_base;
 mod vis;
@@ -0,0 +1,218 @@
+//! Attribute each line of a file in the worktree to where it came from.
+//!
+//! Lines that were changed in the worktree are uncommitted. All other lines are looked up in the
+//! [`WorkspaceRanges`](crate::WorkspaceRanges) to find the workspace commit that owns them, and
+//! only lines that no stack touched are blamed through the history of `HEAD`, which typically
+//! attributes them to commits of the target branch.
+
+use anyhow::{Context as _, bail};
+use but_core::{TreeStatusKind, UnifiedPatch, unified_diff::DiffHunk};
+use gix::bstr::{BStr, BString, ByteSlice as _};
+
+use crate::{HunkRange, ui::HunkLockTarget};
+
+/// A single line of a worktree file, along with its origin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlameLine {
+    /// The 1-based line number in the worktree file.
+    pub line_number: u32,
+    /// The content of the line, without its line terminator.
+    pub content: BString,
+    /// Where the line came from.
+    pub origin: LineOrigin,
+}
+
+/// Where a line of a worktree file came from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineOrigin {
+    /// The line was added or changed in the worktree and isn't committed yet.
+    Uncommitted,
+    /// The line was introduced by `commit_id` in a stack of the workspace.
+    Workspace {
+        /// The stack that owns `commit_id`.
+        target: HunkLockTarget,
+        /// The commit that introduced the line.
+        commit_id: gix::ObjectId,
+        /// The branch of the stack that contains `commit_id`, if it is named.
+        branch: Option<gix::refs::FullName>,
+    },
+    /// The line was introduced by `commit_id`, which isn't part of any stack,
+    /// typically as part of the target branch.
+    Target {
+        /// The commit that introduced the line.
+        commit_id: gix::ObjectId,
+    },
+}
+
+/// Return one [`BlameLine`] for each line of the file at `path` in the worktree of `repo`,
+/// using `workspace` to attribute lines to the commits of its stacks.
+///
+/// Binary files and files that are too large to diff can't be blamed.
+pub fn blame_worktree_file(
+    repo: &gix::Repository,
+    workspace: &but_graph::Workspace,
+    path: &BStr,
+) -> anyhow::Result<Vec<BlameLine>> {
+    let workdir = repo
+        .workdir()
+        .context("Cannot blame files in a bare repository")?;
+    let content = std::fs::read(workdir.join(gix::path::from_bstr(path)))
+        .with_context(|| format!("Cannot read '{path}' in the worktree"))?;
+
+    let worktree_hunks = match but_core::diff::worktree_changes(repo)?
+        .changes
+        .into_iter()
+        .find(|change| change.path == path)
+    {
+        Some(change) => match change.unified_patch(repo, 0 /* zero context lines */)? {
+            Some(UnifiedPatch::Patch { hunks, .. }) => hunks,
+            Some(UnifiedPatch::Binary) => bail!("Cannot blame binary file '{path}'"),
+            Some(UnifiedPatch::Lfs { .. }) => {
+                bail!("Cannot blame '{path}' as it is stored in Git LFS")
+            }
+            Some(UnifiedPatch::TooLarge { .. }) => {
+                bail!("Cannot blame '{path}' as it is too large")
+            }
+            Some(UnifiedPatch::Submodule { .. }) | None => {
+                bail!("Cannot blame '{path}' as it isn't a file")
+            }
+        },
+        None => Vec::new(),
+    };
+
+    let lines: Vec<_> = content.lines().collect();
+    let head_lines = head_line_numbers(lines.len(), &worktree_hunks)?;
+
+    let repo = repo.clone().for_tree_diffing()?.with_object_memory();
+    let input_stacks = crate::new_stacks_to_input_stacks(&repo, workspace)?;
+    let ranges = crate::WorkspaceRanges::try_from_stacks(input_stacks)?;
+    let path_ranges = ranges
+        .ranges_by_path_map()
+        .get(path)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut origins: Vec<Option<LineOrigin>> = head_lines
+        .iter()
+        .map(|head_line| match head_line {
+            None => Some(LineOrigin::Uncommitted),
+            Some(head_line) => owning_range(path_ranges, *head_line)
+                .map(|range| workspace_origin(workspace, range.target, range.commit_id)),
+        })
+        .collect();
+
+    if origins.iter().any(Option::is_none) {
+        let head_id = repo.head_id()?.detach();
+        let mut entries = repo.blame_file(path, head_id, Default::default())?.entries;
+        entries.sort_by_key(|entry| entry.start_in_blamed_file);
+        for (head_line, origin) in head_lines.iter().zip(origins.iter_mut()) {
+            let Some(head_line) = head_line.filter(|_| origin.is_none()) else {
+                continue;
+            };
+            // Blame entries use 0-based line numbers.
+            let line = head_line - 1;
+            let Some(entry) = entries
+                .partition_point(|entry| entry.start_in_blamed_file <= line)
+                .checked_sub(1)
+                .map(|idx| &entries[idx])
+                .filter(|entry| line < entry.start_in_blamed_file + entry.len.get())
+            else {
+                continue;
+            };
+            *origin = Some(
+                match workspace.find_commit_and_containers(entry.commit_id) {
+                    Some((stack, _, _)) => workspace_origin(
+                        workspace,
+                        stack
+                            .id
+                            .map_or(HunkLockTarget::Unidentified, HunkLockTarget::Stack),
+                        entry.commit_id,
+                    ),
+                    None => LineOrigin::Target {
+                        commit_id: entry.commit_id,
+                    },
+                },
+            );
+        }
+    }
+
+    lines
+        .into_iter()
+        .zip(origins)
+        .enumerate()
+        .map(|(idx, (content, origin))| {
+            let line_number = u32::try_from(idx + 1)?;
+            Ok(BlameLine {
+                line_number,
+                content: content.into(),
+                origin: origin
+                    .with_context(|| format!("Could not find the origin of line {line_number}"))?,
+            })
+        })
+        .collect()
+}
+
+/// For each of the `line_count` lines of the worktree file, return the 1-based line number it
+/// has in `HEAD`, or `None` if the line is part of one of `worktree_hunks`, i.e. uncommitted.
+fn head_line_numbers(
+    line_count: usize,
+    worktree_hunks: &[DiffHunk],
+) -> anyhow::Result<Vec<Option<u32>>> {
+    let mut hunks = worktree_hunks.iter().peekable();
+    let mut shift = 0i64;
+    let mut head_lines = Vec::with_capacity(line_count);
+    for line in 1..=u32::try_from(line_count)? {
+        while let Some(hunk) = hunks.peek()
+            && line > last_new_line(hunk)
+        {
+            shift += i64::from(hunk.new_lines) - i64::from(hunk.old_lines);
+            hunks.next();
+        }
+        if let Some(hunk) = hunks.peek()
+            && hunk.new_lines > 0
+            && line >= hunk.new_start
+        {
+            head_lines.push(None);
+        } else {
+            head_lines.push(Some(u32::try_from(i64::from(line) - shift)?));
+        }
+    }
+    Ok(head_lines)
+}
+
+/// The last line in the new version of the file that `hunk` covers. For hunks that only remove
+/// lines, it is the line after which they were removed.
+fn last_new_line(hunk: &DiffHunk) -> u32 {
+    if hunk.new_lines == 0 {
+        hunk.new_start
+    } else {
+        hunk.new_start + hunk.new_lines - 1
+    }
+}
+
+/// Return the range that owns the 1-based `line` in the workspace commit, if any.
+///
+/// Ranges of later commits come after the ones they overwrote, so the last match wins.
+fn owning_range(ranges: &[HunkRange], line: u32) -> Option<&HunkRange> {
+    ranges.iter().rev().find(|range| {
+        range.change_type != TreeStatusKind::Deletion
+            && range.lines > 0
+            && range.start <= line
+            && line < range.start + range.lines
+    })
+}
+
+fn workspace_origin(
+    workspace: &but_graph::Workspace,
+    target: HunkLockTarget,
+    commit_id: gix::ObjectId,
+) -> LineOrigin {
+    let branch = workspace
+        .find_commit_and_containers(commit_id)
+        .and_then(|(_, segment, _)| segment.ref_name().map(ToOwned::to_owned));
+    LineOrigin::Workspace {
+        target,
+        commit_id,
+        branch,
+    }
+}
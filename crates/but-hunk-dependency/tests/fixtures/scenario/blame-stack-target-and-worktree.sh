@@ -0,0 +1,17 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+source "${BASH_SOURCE[0]%/*}/shared.sh"
+
+init-repo-with-files-and-remote
+
+printf "first\nfrom target\n" > file
+commit "extend file on target"
+remote-tracking-caught-up main
+
+git checkout -b my_stack
+printf "first\nfrom target\nfrom stack\n" > file
+commit "add line from stack"
+
+create_workspace_commit_once my_stack
+printf "changed in worktree\nfrom target\nfrom stack\nadded in worktree\n" > file
@@ -0,0 +1,46 @@
+use but_hunk_dependency::blame::{LineOrigin, blame_worktree_file};
+use gix::bstr::BStr;
+
+#[test]
+fn attributes_lines_to_worktree_stack_and_target() -> anyhow::Result<()> {
+    let (repo, _tmp) = but_testsupport::writable_scenario("blame-stack-target-and-worktree");
+    let ctx = but_ctx::Context::from_repo_for_testing(repo)?;
+    let (_guard, repo, ws, _) = ctx.workspace_and_db()?;
+
+    let lines = blame_worktree_file(&repo, &ws, BStr::new("file"))?;
+    let target_commit = repo.rev_parse_single("main")?.detach();
+    let stack_commit = repo.rev_parse_single("my_stack")?.detach();
+
+    let actual: Vec<_> = lines
+        .iter()
+        .map(|line| {
+            let origin = match &line.origin {
+                LineOrigin::Uncommitted => "uncommitted".to_owned(),
+                LineOrigin::Workspace {
+                    commit_id, branch, ..
+                } => {
+                    assert_eq!(*commit_id, stack_commit);
+                    format!(
+                        "workspace {}",
+                        branch.as_ref().map(|b| b.to_string()).unwrap_or_default()
+                    )
+                }
+                LineOrigin::Target { commit_id } => {
+                    assert_eq!(*commit_id, target_commit);
+                    "target".to_owned()
+                }
+            };
+            format!("{} {} {origin}", line.line_number, line.content)
+        })
+        .collect();
+    assert_eq!(
+        actual,
+        [
+            "1 changed in worktree uncommitted",
+            "2 from target target",
+            "3 from stack workspace refs/heads/my_stack",
+            "4 added in worktree uncommitted",
+        ]
+    );
+    Ok(())
+}
@@ -5,6 +5,8 @@ mod workspace_dependencies;
 
 mod ui;
 
+mod blame;
+
 fn intersect_workspace_ranges(
     repo: &gix::Repository,
     ranges: but_hunk_dependency::WorkspaceRanges,
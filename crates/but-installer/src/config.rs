@@ -115,9 +115,77 @@ impl VersionRequest {
     }
 }
 
+/// The environment variable to set the [`ReleaseSource`] from, as URL of a mirror or path to a release directory.
+pub const MIRROR_ENV_VAR: &str = "GITBUTLER_INSTALL_MIRROR";
+
+/// The environment variable to set to `1` to allow mirrors with a plain `http://` URL.
+pub const ALLOW_HTTP_ENV_VAR: &str = "GITBUTLER_INSTALL_ALLOW_HTTP";
+
+/// Where release information and artifacts are obtained from.
+///
+/// Independently of the source, all artifacts must carry a valid signature by GitButler,
+/// so mirrors can only serve official releases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReleaseSource {
+    /// The public GitButler release API
+    Official,
+    /// A server below this base URL which serves the same paths as the release API, i.e. `releases`,
+    /// `releases/nightly` and `releases/version/<version>`.
+    ///
+    /// Artifacts may be downloaded from the mirror itself, or from GitButler's servers.
+    Mirror(url::Url),
+    /// A local directory for machines without network access. It contains release information in
+    /// `releases.json`, `releases/nightly.json` and `releases/version/<version>.json`, next to the
+    /// artifacts they refer to, which are looked up by file name.
+    ///
+    /// This way, the responses of the release API can be stored unaltered along with the downloads.
+    Directory(PathBuf),
+}
+
+impl ReleaseSource {
+    /// Parse `value` as `https://` URL of a mirror, or as path to a release directory.
+    ///
+    /// `http://` URLs are only accepted if [`ALLOW_HTTP_ENV_VAR`] is set to `1`, as anyone on the
+    /// network could then serve older releases, which are validly signed as well.
+    pub fn parse(value: &str) -> Result<Self> {
+        Self::parse_with_http(value, env::var(ALLOW_HTTP_ENV_VAR).is_ok_and(|v| v == "1"))
+    }
+
+    /// Like [`Self::parse()`], but accept `http://` URLs if `allow_http` is `true`.
+    pub(crate) fn parse_with_http(value: &str, allow_http: bool) -> Result<Self> {
+        if value.starts_with("http://") && !allow_http {
+            bail!("Mirror '{value}' must use HTTPS, set {ALLOW_HTTP_ENV_VAR}=1 to use it anyway");
+        }
+        if value.starts_with("https://") || value.starts_with("http://") {
+            let mut url = url::Url::parse(value)
+                .map_err(|err| anyhow!("Invalid mirror URL '{value}': {err}"))?;
+            // Make sure joining paths keeps everything below the base.
+            if !url.path().ends_with('/') {
+                url.set_path(&format!("{}/", url.path()));
+            }
+            Ok(ReleaseSource::Mirror(url))
+        } else {
+            let dir = PathBuf::from(value);
+            if !dir.is_dir() {
+                bail!("Release directory '{value}' does not exist or is not a directory");
+            }
+            Ok(ReleaseSource::Directory(dir))
+        }
+    }
+
+    /// Read the source from the [`MIRROR_ENV_VAR`] environment variable, or use the official source if it isn't set.
+    pub fn from_env() -> Result<Self> {
+        match env::var(MIRROR_ENV_VAR) {
+            Ok(value) if !value.trim().is_empty() => Self::parse(value.trim()),
+            _ => Ok(ReleaseSource::Official),
+        }
+    }
+}
+
 /// Configuration for the installer
 pub struct InstallerConfig {
     pub version_request: VersionRequest,
+    pub source: ReleaseSource,
     pub home_dir: PathBuf,
     pub platform: String,
 }
@@ -140,11 +208,14 @@ impl InstallerConfig {
             .or_else(|| env::var("GITBUTLER_VERSION").ok());
 
         let version_request = VersionRequest::from_string(version_string)?;
-        Self::new_with_version(version_request)
+        Self::new_with_version(version_request, ReleaseSource::from_env()?)
     }
 
-    /// Create a new installer config with an explicit version request
-    pub(crate) fn new_with_version(version_request: VersionRequest) -> Result<Self> {
+    /// Create a new installer config with an explicit version request and release source
+    pub(crate) fn new_with_version(
+        version_request: VersionRequest,
+        source: ReleaseSource,
+    ) -> Result<Self> {
         let home_dir =
             dirs::home_dir().ok_or_else(|| anyhow!("Failed to determine home directory"))?;
 
@@ -162,21 +233,44 @@ impl InstallerConfig {
 
         Ok(Self {
             version_request,
+            source,
             home_dir,
             platform: platform.to_string(),
         })
     }
 
-    pub fn releases_url(&self) -> String {
+    /// The path of the release information relative to the API root, like `releases/nightly`.
+    fn release_path(&self) -> String {
         match &self.version_request {
-            VersionRequest::Nightly => "https://app.gitbutler.com/releases/nightly".to_string(),
+            VersionRequest::Nightly => "releases/nightly".to_string(),
             VersionRequest::Specific(version) => {
-                format!(
-                    "https://app.gitbutler.com/releases/version/{}",
-                    version.as_str()
-                )
+                format!("releases/version/{}", version.as_str())
             }
-            VersionRequest::Release => "https://app.gitbutler.com/releases".to_string(),
+            VersionRequest::Release => "releases".to_string(),
+        }
+    }
+
+    /// The URL to fetch release information from, or `None` if the source is a local directory.
+    pub fn releases_url(&self) -> Option<String> {
+        match &self.source {
+            ReleaseSource::Official => {
+                Some(format!("https://app.gitbutler.com/{}", self.release_path()))
+            }
+            ReleaseSource::Mirror(base) => base
+                .join(&self.release_path())
+                .ok()
+                .map(|url| url.to_string()),
+            ReleaseSource::Directory(_) => None,
+        }
+    }
+
+    /// The file to read release information from, or `None` if the source isn't a local directory.
+    pub fn release_file(&self) -> Option<PathBuf> {
+        match &self.source {
+            ReleaseSource::Directory(dir) => {
+                Some(dir.join(format!("{}.json", self.release_path())))
+            }
+            ReleaseSource::Official | ReleaseSource::Mirror(_) => None,
         }
     }
 }
@@ -272,6 +366,77 @@ mod tests {
         assert_eq!(s, "1.2.3");
     }
 
+    fn config(version_request: VersionRequest, source: ReleaseSource) -> InstallerConfig {
+        InstallerConfig {
+            version_request,
+            source,
+            home_dir: PathBuf::from("/home/user"),
+            platform: "linux-x86_64".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_release_source_parse() {
+        assert_eq!(
+            ReleaseSource::parse("https://mirror.example.com/gitbutler").unwrap(),
+            ReleaseSource::Mirror(
+                url::Url::parse("https://mirror.example.com/gitbutler/").unwrap()
+            )
+        );
+        assert!(
+            ReleaseSource::parse_with_http("http://127.0.0.1:8080/", false).is_err(),
+            "plain HTTP must be allowed explicitly"
+        );
+        assert_eq!(
+            ReleaseSource::parse_with_http("http://127.0.0.1:8080/", true).unwrap(),
+            ReleaseSource::Mirror(url::Url::parse("http://127.0.0.1:8080/").unwrap())
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(
+            ReleaseSource::parse(dir.path().to_str().unwrap()).unwrap(),
+            ReleaseSource::Directory(dir.path().to_owned())
+        );
+        assert!(ReleaseSource::parse(dir.path().join("missing").to_str().unwrap()).is_err());
+        assert!(ReleaseSource::parse("https://").is_err());
+    }
+
+    #[test]
+    fn test_releases_url() {
+        let version = Version::new("0.18.7".to_string()).unwrap();
+        let official = |request| config(request, ReleaseSource::Official).releases_url();
+        assert_eq!(
+            official(VersionRequest::Release).as_deref(),
+            Some("https://app.gitbutler.com/releases")
+        );
+        assert_eq!(
+            official(VersionRequest::Nightly).as_deref(),
+            Some("https://app.gitbutler.com/releases/nightly")
+        );
+        assert_eq!(
+            official(VersionRequest::Specific(version.clone())).as_deref(),
+            Some("https://app.gitbutler.com/releases/version/0.18.7")
+        );
+
+        let mirror = ReleaseSource::parse("https://mirror.example.com/gitbutler").unwrap();
+        assert_eq!(
+            config(VersionRequest::Specific(version.clone()), mirror)
+                .releases_url()
+                .as_deref(),
+            Some("https://mirror.example.com/gitbutler/releases/version/0.18.7")
+        );
+
+        let directory = config(
+            VersionRequest::Specific(version),
+            ReleaseSource::Directory(PathBuf::from("/srv/releases")),
+        );
+        assert_eq!(directory.releases_url(), None);
+        assert_eq!(
+            directory.release_file(),
+            Some(PathBuf::from("/srv/releases/releases/version/0.18.7.json"))
+        );
+    }
+
     #[test]
     fn test_channel_display_name() {
         assert_eq!(Channel::Release.display_name(), "Release");
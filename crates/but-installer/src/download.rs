@@ -1,14 +1,36 @@
 //! Download and verification logic
 
-use std::{fs::File, io::Write, path::Path};
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 use anyhow::{Context, Result, anyhow, bail};
 
-use crate::http::create_client;
+use crate::{config::ReleaseSource, http::create_client, release::local_artifact_name};
 
-/// Download a URL and return its contents as a string.
+/// Return the path of the artifact at `url` in the release directory `dir`.
+fn local_artifact_path(dir: &Path, url: &str) -> Result<PathBuf> {
+    let path = dir.join(local_artifact_name(url)?);
+    if !path.is_file() {
+        bail!(
+            "The release directory does not contain {}, which is needed for {url}",
+            path.display()
+        );
+    }
+    Ok(path)
+}
+
+/// Download a URL from `source` and return its contents as a string.
 #[cfg(target_os = "linux")]
-pub(crate) fn download_to_string(url: &str) -> Result<String> {
+pub(crate) fn download_to_string(source: &ReleaseSource, url: &str) -> Result<String> {
+    if let ReleaseSource::Directory(dir) = source {
+        let path = local_artifact_path(dir, url)?;
+        return fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()));
+    }
+
     let mut easy = create_client()?;
 
     easy.url(url)
@@ -42,13 +64,26 @@ pub(crate) fn download_to_string(url: &str) -> Result<String> {
         .context("Failed to get effective URL")?
         .ok_or_else(|| anyhow!("Effective URL is missing"))?;
 
-    crate::release::validate_download_url(effective_url)
+    source
+        .validate_download_url(effective_url)
         .with_context(|| format!("Download was redirected to an untrusted URL: {effective_url}"))?;
 
     String::from_utf8(buf.into_inner()).context("Signature file is not valid UTF-8")
 }
 
-pub(crate) fn download_file(url: &str, dest: &Path) -> Result<()> {
+/// Download a URL from `source` into the file at `dest`.
+pub(crate) fn download_file(source: &ReleaseSource, url: &str, dest: &Path) -> Result<()> {
+    if let ReleaseSource::Directory(dir) = source {
+        let path = local_artifact_path(dir, url)?;
+        fs::copy(&path, dest).with_context(|| {
+            format!(
+                "Failed to copy {} from the release directory",
+                path.display()
+            )
+        })?;
+        return Ok(());
+    }
+
     let mut easy = create_client()?;
 
     easy.url(url)
@@ -136,8 +171,72 @@ pub(crate) fn download_file(url: &str, dest: &Path) -> Result<()> {
         .context("Failed to get effective URL")?
         .ok_or_else(|| anyhow!("Effective URL is missing"))?;
 
-    crate::release::validate_download_url(effective_url)
+    source
+        .validate_download_url(effective_url)
         .with_context(|| format!("Download was redirected to an untrusted URL: {effective_url}"))?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::stand_in::{self, Response};
+
+    #[test]
+    fn test_download_file_from_mirror() {
+        let base = stand_in::serve(vec![("/downloads/but", Response::Ok(b"binary".to_vec()))]);
+        let mirror = ReleaseSource::parse_with_http(&base, true).unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        let dest = dest.path().join("but");
+
+        download_file(&mirror, &format!("{base}downloads/but"), &dest).unwrap();
+        assert_eq!(fs::read(&dest).unwrap(), b"binary");
+
+        let err = download_file(&mirror, &format!("{base}downloads/missing"), &dest).unwrap_err();
+        assert!(err.to_string().contains("HTTP status: 404"));
+    }
+
+    #[test]
+    fn test_download_file_from_mirror_rejects_redirect_to_other_host() {
+        let elsewhere = stand_in::serve(vec![("/but", Response::Ok(b"binary".to_vec()))]);
+        let base = stand_in::serve(vec![(
+            "/but",
+            Response::Redirect(format!("{elsewhere}but")),
+        )]);
+        let dest = tempfile::tempdir().unwrap();
+
+        let err = download_file(
+            &ReleaseSource::parse_with_http(&base, true).unwrap(),
+            &format!("{base}but"),
+            &dest.path().join("but"),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("redirected to an untrusted URL"));
+    }
+
+    #[test]
+    fn test_download_file_from_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("but"), b"binary").unwrap();
+        let source = ReleaseSource::Directory(dir.path().to_owned());
+        let dest = tempfile::tempdir().unwrap();
+        let dest = dest.path().join("but");
+
+        download_file(
+            &source,
+            "https://releases.gitbutler.com/releases/0.18.7/linux/x86_64/but",
+            &dest,
+        )
+        .unwrap();
+        assert_eq!(fs::read(&dest).unwrap(), b"binary");
+
+        let err = download_file(
+            &source,
+            "https://releases.gitbutler.com/releases/0.18.7/linux/x86_64/but.sig",
+            &dest,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("does not contain"));
+    }
+}
@@ -29,3 +29,63 @@ pub(crate) fn create_client() -> Result<Easy> {
         .context("Failed to set max redirects")?;
     Ok(easy)
 }
+
+#[cfg(test)]
+pub(crate) mod stand_in {
+    //! A minimal local HTTP server which stands in for a release mirror in tests.
+
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+    };
+
+    /// How to answer a request for a path.
+    pub(crate) enum Response {
+        /// Answer with `200 OK` and the given body.
+        Ok(Vec<u8>),
+        /// Redirect to the given URL.
+        Redirect(String),
+    }
+
+    /// Serve `routes` from a local port in the background and return the base URL,
+    /// like `http://127.0.0.1:1234/`. Requests for unknown paths are answered with `404`.
+    pub(crate) fn serve(routes: Vec<(&'static str, Response)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let mut reader = BufReader::new(&stream);
+                let mut request_line = String::new();
+                if reader.read_line(&mut request_line).is_err() {
+                    continue;
+                }
+                // Skip the headers, the request has no body.
+                let mut line = String::new();
+                while reader.read_line(&mut line).is_ok_and(|n| n > 2) {
+                    line.clear();
+                }
+
+                let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+                let (status, location, body): (_, _, &[u8]) =
+                    match routes.iter().find(|(route, _)| *route == path) {
+                        Some((_, Response::Ok(body))) => ("200 OK", None, body),
+                        Some((_, Response::Redirect(url))) => ("302 Found", Some(url), b""),
+                        None => ("404 Not Found", None, b""),
+                    };
+                let mut response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n",
+                    body.len()
+                );
+                if let Some(url) = location {
+                    response.push_str(&format!("Location: {url}\r\n"));
+                }
+                response.push_str("\r\n");
+                let mut stream = &stream;
+                stream.write_all(response.as_bytes()).ok();
+                stream.write_all(body).ok();
+            }
+        });
+        base
+    }
+}
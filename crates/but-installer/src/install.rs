@@ -16,6 +16,13 @@ pub(crate) fn but_binary_path(home_dir: &Path) -> PathBuf {
     home_dir.join(".local/bin/but")
 }
 
+/// Returns the path at which the `but` CLI binary replaced by the latest installation is kept
+/// for `but update rollback`.
+#[cfg(target_os = "linux")]
+pub(crate) fn previous_but_binary_path(home_dir: &Path) -> PathBuf {
+    home_dir.join(".local/bin/but.previous")
+}
+
 /// Validate that but can be executed
 pub(crate) fn validate_installed_binary(path: &Path) -> bool {
     Command::new(path)
@@ -7,9 +7,11 @@ use anyhow::{Context, Result, anyhow, bail};
 use crate::{
     config::{Channel, InstallerConfig},
     download::{download_file, download_to_string},
-    install::{but_binary_path, validate_installed_binary, verify_signature},
-    release::{PlatformInfo, Release, validate_download_url},
-    ui::{info, warn},
+    install::{
+        but_binary_path, previous_but_binary_path, validate_installed_binary, verify_signature,
+    },
+    release::{PlatformInfo, Release},
+    ui::{info, success, warn},
 };
 
 pub(crate) fn download_and_install_app(
@@ -36,8 +38,8 @@ pub(crate) fn download_and_install_app(
     let download_url = format!("{base_download_url}/{filename}");
     let signature_url = format!("{download_url}.sig");
 
-    validate_download_url(&signature_url)?;
-    validate_download_url(&download_url)?;
+    config.source.validate_download_url(&signature_url)?;
+    config.source.validate_download_url(&download_url)?;
     info(&format!("Download URL: {download_url}"));
 
     let temp_dir = tempfile::Builder::new()
@@ -46,10 +48,10 @@ pub(crate) fn download_and_install_app(
     let tmp_filepath = temp_dir.path().join(filename);
 
     info(&format!("Downloading GitButler {}...", release.version));
-    download_file(&download_url, &tmp_filepath)?;
+    download_file(&config.source, &download_url, &tmp_filepath)?;
     info("Download completed successfully");
 
-    let signature_b64 = download_to_string(&signature_url).with_context(|| {
+    let signature_b64 = download_to_string(&config.source, &signature_url).with_context(|| {
         anyhow!("Failed to get signature for but, requested version may be too old")
     })?;
     verify_signature(&tmp_filepath, &signature_b64, temp_dir.path())?;
@@ -124,12 +126,132 @@ fn install_app(but_path: &Path, home_dir: &Path, channel: Option<Channel>) -> Re
             bail!("Installation failed and no backup available to restore");
         }
     } else if let Some(but_backup) = but_backup {
+        let previous = previous_but_binary_path(home_dir);
         info(&format!(
-            "Removing backup at {}",
-            but_backup.to_string_lossy()
+            "Keeping previous installation at {} for 'but update rollback'",
+            previous.to_string_lossy()
         ));
-        fs::remove_file(&but_backup)?;
+        fs::rename(&but_backup, &previous)?;
     }
 
     Ok(())
 }
+
+/// Swap the installed `but` binary with the one kept by the previous installation.
+///
+/// The replaced binary is kept in turn, so rolling back twice restores the original state.
+pub(crate) fn rollback(home_dir: &Path) -> Result<()> {
+    let install_bin_path = but_binary_path(home_dir);
+    let previous = previous_but_binary_path(home_dir);
+    if !previous.is_file() {
+        bail!(
+            "No previous installation to roll back to, expected it at {}",
+            previous.to_string_lossy()
+        );
+    }
+    if !validate_installed_binary(&previous) {
+        bail!(
+            "The previous installation at {} cannot run, refusing to roll back to it",
+            previous.to_string_lossy()
+        );
+    }
+
+    info(&format!(
+        "Restoring previous installation from {}...",
+        previous.to_string_lossy()
+    ));
+    let mut swap_name = install_bin_path.as_os_str().to_owned();
+    swap_name.push(".rollback");
+    let swap = std::path::PathBuf::from(swap_name);
+    let had_current = install_bin_path.is_file();
+    if had_current {
+        fs::rename(&install_bin_path, &swap)?;
+    }
+    if let Err(err) = fs::rename(&previous, &install_bin_path) {
+        if had_current {
+            fs::rename(&swap, &install_bin_path)?;
+        }
+        return Err(err).context("Failed to restore the previous installation");
+    }
+    if had_current {
+        fs::rename(&swap, &previous)?;
+    }
+
+    success(&format!(
+        "Rolled back to the previous installation at {}",
+        install_bin_path.to_string_lossy()
+    ));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Write an executable to `path` which prints `version` when asked for it.
+    fn fake_but(path: &Path, version: &str) {
+        fs::write(path, format!("#!/bin/sh\necho {version}\n")).unwrap();
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    fn installed(path: &Path) -> String {
+        fs::read_to_string(path).unwrap()
+    }
+
+    #[test]
+    fn install_keeps_previous_binary_for_rollback() {
+        let home = tempfile::tempdir().unwrap();
+        let downloads = tempfile::tempdir().unwrap();
+        let (old, new) = (downloads.path().join("old"), downloads.path().join("new"));
+        fake_but(&old, "0.18.6");
+        fake_but(&new, "0.18.7");
+
+        install_app(&old, home.path(), None).unwrap();
+        assert!(
+            !previous_but_binary_path(home.path()).exists(),
+            "there was nothing to keep"
+        );
+
+        install_app(&new, home.path(), None).unwrap();
+        let but = but_binary_path(home.path());
+        let previous = previous_but_binary_path(home.path());
+        assert!(installed(&but).contains("0.18.7"));
+        assert!(installed(&previous).contains("0.18.6"));
+
+        rollback(home.path()).unwrap();
+        assert!(installed(&but).contains("0.18.6"));
+        assert!(
+            installed(&previous).contains("0.18.7"),
+            "the replaced binary is kept so the rollback can be undone"
+        );
+
+        rollback(home.path()).unwrap();
+        assert!(installed(&but).contains("0.18.7"));
+        assert!(installed(&previous).contains("0.18.6"));
+    }
+
+    #[test]
+    fn rollback_without_previous_installation_fails() {
+        let home = tempfile::tempdir().unwrap();
+        let err = rollback(home.path()).unwrap_err();
+        assert!(err.to_string().contains("No previous installation"));
+    }
+
+    #[test]
+    fn rollback_refuses_broken_previous_binary() {
+        let home = tempfile::tempdir().unwrap();
+        let but = but_binary_path(home.path());
+        fs::create_dir_all(but.parent().unwrap()).unwrap();
+        fake_but(&but, "0.18.7");
+        let previous = previous_but_binary_path(home.path());
+        fs::write(&previous, "#!/bin/sh\nexit 1\n").unwrap();
+        fs::set_permissions(&previous, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let err = rollback(home.path()).unwrap_err();
+        assert!(err.to_string().contains("cannot run"));
+        assert!(
+            installed(&but).contains("0.18.7"),
+            "the current installation is untouched"
+        );
+    }
+}
@@ -17,7 +17,7 @@ use crate::{
     config::{Channel, InstallerConfig},
     download::download_file,
     install::{validate_installed_binary, verify_signature},
-    release::{PlatformInfo, Release, validate_download_url},
+    release::{PlatformInfo, Release},
     ui::{info, success, warn},
 };
 
@@ -35,7 +35,7 @@ pub fn download_and_install_app(
         )
     })?;
 
-    validate_download_url(download_url)?;
+    config.source.validate_download_url(download_url)?;
     info(&format!("Download URL: {download_url}"));
 
     let temp_dir = tempfile::Builder::new()
@@ -49,7 +49,7 @@ pub fn download_and_install_app(
     let tarball_path = temp_dir.path().join(filename);
 
     info(&format!("Downloading GitButler {}...", release.version));
-    download_file(download_url, &tarball_path)?;
+    download_file(&config.source, download_url, &tarball_path)?;
 
     validate_tarball(&tarball_path)?;
     success("Download completed successfully");
@@ -283,14 +283,99 @@ pub(crate) fn install_app(app_dir: &Path, home_dir: &Path, channel: Option<Chann
         "{} installed successfully",
         app_basename.to_string_lossy()
     ));
-    // Remove backup on success
-    let _ = fs::remove_dir_all(&install_app_backup);
+    // Keep the replaced installation for `but update rollback`
+    if install_app_backup.exists() {
+        let install_app_previous = previous_app_path(&install_app);
+        let _ = fs::remove_dir_all(&install_app_previous);
+        if let Err(e) = fs::rename(&install_app_backup, &install_app_previous) {
+            warn(&format!(
+                "Could not keep previous installation for rollback: {e}"
+            ));
+            let _ = fs::remove_dir_all(&install_app_backup);
+        }
+    }
 
     success("GitButler CLI (but) installed successfully");
 
     Ok(())
 }
 
+/// Returns the path at which the app bundle replaced by the latest installation of `install_app`
+/// is kept for `but update rollback`.
+fn previous_app_path(install_app: &Path) -> PathBuf {
+    let mut name = install_app.as_os_str().to_owned();
+    name.push(".previous");
+    PathBuf::from(name)
+}
+
+/// Swap the app bundle that `~/.local/bin/but` points to with the one kept by the previous
+/// installation.
+///
+/// The replaced app bundle is kept in turn, so rolling back twice restores the original state.
+pub(crate) fn rollback(home_dir: &Path) -> Result<()> {
+    let but_symlink = home_dir.join(".local/bin/but");
+    if !but_symlink.is_symlink() {
+        bail!(
+            "{} is not a symlink into a GitButler installation, there is nothing to roll back",
+            but_symlink.display()
+        );
+    }
+    let target = fs::read_link(&but_symlink)?;
+    let install_app = target
+        .ancestors()
+        .find(|path| path.extension().and_then(|ext| ext.to_str()) == Some("app"))
+        .ok_or_else(|| {
+            anyhow!(
+                "{} does not point into an app bundle: {}",
+                but_symlink.display(),
+                target.display()
+            )
+        })?
+        .to_owned();
+    let install_app_previous = previous_app_path(&install_app);
+    if !install_app_previous.is_dir() {
+        bail!(
+            "No previous installation to roll back to, expected it at {}",
+            install_app_previous.display()
+        );
+    }
+    verify_app_structure(&install_app_previous)?;
+    if !validate_installed_binary(&install_app_previous.join("Contents/MacOS/gitbutler-tauri")) {
+        bail!(
+            "The previous installation at {} cannot run, refusing to roll back to it",
+            install_app_previous.display()
+        );
+    }
+
+    info(&format!(
+        "Restoring previous installation from {}...",
+        install_app_previous.display()
+    ));
+    let mut swap_name = install_app.as_os_str().to_owned();
+    swap_name.push(".rollback");
+    let swap = PathBuf::from(swap_name);
+    let _ = fs::remove_dir_all(&swap);
+    let had_current = install_app.exists();
+    if had_current {
+        fs::rename(&install_app, &swap)?;
+    }
+    if let Err(e) = fs::rename(&install_app_previous, &install_app) {
+        if had_current {
+            fs::rename(&swap, &install_app)?;
+        }
+        return Err(e).context("Failed to restore the previous installation");
+    }
+    if had_current {
+        fs::rename(&swap, &install_app_previous)?;
+    }
+
+    success(&format!(
+        "Rolled back to the previous installation at {}",
+        install_app.display()
+    ));
+    Ok(())
+}
+
 fn copy_dir_all(src: &Path, dst: &Path) -> io::Result<()> {
     fs::create_dir_all(dst)?;
     for entry in fs::read_dir(src)? {
@@ -22,16 +22,16 @@ mod install_macos;
 use anyhow::Result;
 use config::{Channel, InstallerConfig};
 // Re-export types for public API consumers
-pub use config::{Version, VersionRequest};
+pub use config::{ALLOW_HTTP_ENV_VAR, MIRROR_ENV_VAR, ReleaseSource, Version, VersionRequest};
 use install::but_binary_path;
 use release::fetch_release;
 use shell::configure_shell;
 use ui::{info, success};
 
 #[cfg(target_os = "linux")]
-use crate::install_linux::download_and_install_app;
+use crate::install_linux::{download_and_install_app, rollback};
 #[cfg(target_os = "macos")]
-use crate::install_macos::download_and_install_app;
+use crate::install_macos::{download_and_install_app, rollback};
 
 /// Runs the complete GitButler installation process with a specific version.
 ///
@@ -43,6 +43,7 @@ use crate::install_macos::download_and_install_app;
 ///
 /// # Arguments
 /// * `version_request` - Version to install (Release, Nightly, or Specific version).
+/// * `source` - Where to obtain the release from, like a mirror or a local release directory.
 /// * `interactive` - Whether this is an interactive installation (e.g., from the install script).
 ///   When `true`, runs `but onboarding` and prints usage instructions after installation.
 ///   Set to `false` when called from CLI tools (like `but update`) where the user
@@ -52,16 +53,29 @@ use crate::install_macos::download_and_install_app;
 /// changes on installation failure.
 pub fn run_installation_with_version(
     version_request: VersionRequest,
+    source: ReleaseSource,
     interactive: bool,
 ) -> Result<()> {
-    let config = InstallerConfig::new_with_version(version_request)?;
+    let config = InstallerConfig::new_with_version(version_request, source)?;
     run_installation_impl(config, interactive)
 }
 
+/// Restores the installation that was replaced by the most recent installation.
+///
+/// The installation that is rolled back is kept instead, so calling this again undoes the rollback.
+/// Returns an error if there is no previous installation, or if it cannot run.
+pub fn run_rollback() -> Result<()> {
+    let home_dir =
+        dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Failed to determine home directory"))?;
+    rollback(&home_dir)
+}
+
 /// Runs the complete GitButler installation process.
 ///
 /// Reads version from command-line arguments or GITBUTLER_VERSION environment variable.
 /// If neither is provided, installs the latest release.
+/// Releases are obtained from the mirror or release directory in the GITBUTLER_INSTALL_MIRROR
+/// environment variable if it is set.
 ///
 /// This is the entry point for the standalone installer binary, and runs in interactive mode with
 /// shell configuration and other onboarding if there is a terminal connected. Otherwise it runs
@@ -111,6 +125,13 @@ fn run_installation_impl(config: InstallerConfig, interactive: bool) -> Result<(
     }
 
     info(&format!("Detected platform: {}", config.platform));
+    match &config.source {
+        ReleaseSource::Official => {}
+        ReleaseSource::Mirror(url) => info(&format!("Using release mirror: {url}")),
+        ReleaseSource::Directory(dir) => {
+            info(&format!("Using release directory: {}", dir.display()))
+        }
+    }
 
     // Fetch release information
     let message = match &config.version_request {
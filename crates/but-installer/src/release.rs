@@ -1,11 +1,14 @@
 //! Release fetching and validation
 
-use std::collections::HashMap;
+use std::{collections::HashMap, fs, path::Path};
 
 use anyhow::{Context, Result, anyhow, bail};
 use serde::Deserialize;
 
-use crate::{config::InstallerConfig, http::create_client};
+use crate::{
+    config::{InstallerConfig, ReleaseSource, VersionRequest},
+    http::create_client,
+};
 
 /// Release information from the GitButler API
 #[derive(Debug, Deserialize)]
@@ -23,7 +26,56 @@ pub struct PlatformInfo {
 }
 
 pub(crate) fn fetch_release(config: &InstallerConfig) -> Result<Release> {
-    let url = config.releases_url();
+    let release = match config.release_file() {
+        Some(path) => read_release_file(config, &path)?,
+        None => fetch_release_from_api(config)?,
+    };
+
+    // Verify we got the version we requested (skip check for nightly)
+    if let VersionRequest::Specific(ref requested) = config.version_request
+        && release.version != requested.as_str()
+    {
+        bail!(
+            "API returned version {} but requested version {}",
+            release.version,
+            requested
+        );
+    }
+
+    Ok(release)
+}
+
+fn read_release_file(config: &InstallerConfig, path: &Path) -> Result<Release> {
+    let data = match fs::read(path) {
+        Ok(data) => data,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => match &config.version_request {
+            VersionRequest::Specific(version) => {
+                bail!(
+                    "Version {version} is not available in the release directory, expected {}",
+                    path.display()
+                );
+            }
+            VersionRequest::Nightly | VersionRequest::Release => {
+                bail!(
+                    "No release information in the release directory, expected {}",
+                    path.display()
+                );
+            }
+        },
+        Err(err) => {
+            return Err(err).with_context(|| {
+                format!("Failed to read release information from {}", path.display())
+            });
+        }
+    };
+    serde_json::from_slice(&data)
+        .with_context(|| format!("Failed to parse release information in {}", path.display()))
+}
+
+fn fetch_release_from_api(config: &InstallerConfig) -> Result<Release> {
+    let url = config
+        .releases_url()
+        .ok_or_else(|| anyhow!("Release source has no release API"))?;
     let mut easy = create_client()?;
 
     easy.url(&url)
@@ -49,15 +101,15 @@ pub(crate) fn fetch_release(config: &InstallerConfig) -> Result<Release> {
 
     if response_code != 200 {
         match &config.version_request {
-            crate::config::VersionRequest::Specific(version) => {
+            VersionRequest::Specific(version) => {
                 bail!(
                     "Failed to fetch release information for version {version}. Version may not exist. HTTP {response_code}"
                 );
             }
-            crate::config::VersionRequest::Nightly => {
+            VersionRequest::Nightly => {
                 bail!("Failed to fetch nightly release information. HTTP {response_code}");
             }
-            crate::config::VersionRequest::Release => {
+            VersionRequest::Release => {
                 bail!("Failed to fetch release information from {url}. HTTP {response_code}");
             }
         }
@@ -70,25 +122,66 @@ pub(crate) fn fetch_release(config: &InstallerConfig) -> Result<Release> {
         .context("Failed to get effective URL")?
         .ok_or_else(|| anyhow!("Effective URL is missing"))?;
 
-    validate_api_url(effective_url).with_context(|| {
-        format!("Release API was redirected to an untrusted URL: {effective_url}")
-    })?;
+    config
+        .source
+        .validate_api_url(effective_url)
+        .with_context(|| {
+            format!("Release API was redirected to an untrusted URL: {effective_url}")
+        })?;
 
-    let release: Release =
-        serde_json::from_slice(&response_data).context("Failed to parse release information")?;
+    serde_json::from_slice(&response_data).context("Failed to parse release information")
+}
 
-    // Verify we got the version we requested (skip check for nightly)
-    if let crate::config::VersionRequest::Specific(ref requested) = config.version_request
-        && release.version != requested.as_str()
-    {
-        bail!(
-            "API returned version {} but requested version {}",
-            release.version,
-            requested
-        );
+impl ReleaseSource {
+    /// Validates that release information at `url` may be used.
+    ///
+    /// Mirrors must answer from the scheme, host and port they were configured with, so plain HTTP
+    /// is only used if it was allowed explicitly.
+    pub(crate) fn validate_api_url(&self, url: &str) -> Result<()> {
+        match self {
+            ReleaseSource::Official => validate_api_url(url),
+            ReleaseSource::Mirror(base) => validate_mirror_url(base, url, "API URL"),
+            ReleaseSource::Directory(_) => {
+                bail!("A release directory has no API URL: {url}")
+            }
+        }
     }
 
-    Ok(release)
+    /// Validates that artifacts may be downloaded from `url`.
+    ///
+    /// Mirrors may also refer to artifacts on trusted GitButler domains, and release directories
+    /// only use the file name of `url` to find artifacts locally.
+    pub(crate) fn validate_download_url(&self, url: &str) -> Result<()> {
+        match self {
+            ReleaseSource::Official => validate_download_url(url),
+            ReleaseSource::Mirror(base) => validate_mirror_url(base, url, "Download URL")
+                .or_else(|err| validate_download_url(url).map_err(|_| err)),
+            ReleaseSource::Directory(_) => local_artifact_name(url).map(|_| ()),
+        }
+    }
+}
+
+/// Validates that `url` has the same scheme, host and port as the mirror at `base`.
+fn validate_mirror_url(base: &url::Url, url: &str, url_type: &str) -> Result<()> {
+    let url_parsed =
+        url::Url::parse(url).with_context(|| format!("Invalid {} URL", url_type.to_lowercase()))?;
+    if url_parsed.origin() != base.origin() {
+        bail!("{url_type} is not from the configured mirror at {base}: {url}");
+    }
+    Ok(())
+}
+
+/// Return the file name of the artifact at `url`, which is how it's found in a release directory.
+pub(crate) fn local_artifact_name(url: &str) -> Result<&str> {
+    let name = url
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty() && *name != "." && *name != "..")
+        .ok_or_else(|| anyhow!("Failed to extract filename from download URL: {url}"))?;
+    if name.contains(['\\', '?', '#']) {
+        bail!("Download URL has no plain file name: {url}");
+    }
+    Ok(name)
 }
 
 /// Common URL validation logic for GitButler domains.
@@ -189,6 +282,143 @@ mod tests {
         assert!(validate_download_url("https://evil.com/file.tar.gz").is_err());
     }
 
+    #[test]
+    fn test_validate_mirror_urls() {
+        let mirror =
+            ReleaseSource::parse_with_http("http://127.0.0.1:8080/gitbutler", true).unwrap();
+
+        // Valid - same scheme, host and port as the mirror
+        assert!(
+            mirror
+                .validate_api_url("http://127.0.0.1:8080/gitbutler/releases")
+                .is_ok()
+        );
+        assert!(
+            mirror
+                .validate_download_url("http://127.0.0.1:8080/downloads/but")
+                .is_ok()
+        );
+
+        // Valid - downloads may still come from GitButler, but the API may not
+        assert!(
+            mirror
+                .validate_download_url("https://releases.gitbutler.com/but")
+                .is_ok()
+        );
+        assert!(
+            mirror
+                .validate_api_url("https://app.gitbutler.com/releases")
+                .is_err()
+        );
+
+        // Invalid - different port, scheme or host
+        assert!(
+            mirror
+                .validate_api_url("http://127.0.0.1:8081/gitbutler/releases")
+                .is_err()
+        );
+        assert!(
+            mirror
+                .validate_download_url("https://127.0.0.1:8080/downloads/but")
+                .is_err()
+        );
+        assert!(
+            mirror
+                .validate_download_url("http://evil.com/downloads/but")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_local_artifact_name() {
+        assert_eq!(
+            local_artifact_name(
+                "https://releases.gitbutler.com/releases/release/0.18.7-2373/linux/x86_64/but"
+            )
+            .unwrap(),
+            "but"
+        );
+        assert!(local_artifact_name("https://releases.gitbutler.com/").is_err());
+        assert!(local_artifact_name("https://releases.gitbutler.com/..").is_err());
+        assert!(local_artifact_name("https://releases.gitbutler.com/but?x=1").is_err());
+    }
+
+    /// Create a configuration for `version`, or for the latest release if `None`.
+    fn config(version: Option<&str>, source: ReleaseSource) -> InstallerConfig {
+        InstallerConfig {
+            version_request: VersionRequest::from_string(version.map(str::to_string)).unwrap(),
+            source,
+            home_dir: "/home/user".into(),
+            platform: "linux-x86_64".to_string(),
+        }
+    }
+
+    const RELEASE_JSON: &str = r#"{
+        "version": "0.18.7",
+        "platforms": {
+            "linux-x86_64": {
+                "url": "https://releases.gitbutler.com/releases/0.18.7/GitButler.AppImage",
+                "signature": "sig-linux"
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_fetch_release_from_mirror() {
+        let base = crate::http::stand_in::serve(vec![(
+            "/releases/version/0.18.7",
+            crate::http::stand_in::Response::Ok(RELEASE_JSON.into()),
+        )]);
+        let mirror = ReleaseSource::parse_with_http(&base, true).unwrap();
+
+        let release = fetch_release(&config(Some("0.18.7"), mirror.clone())).unwrap();
+        assert_eq!(release.version, "0.18.7");
+        assert!(release.platforms.contains_key("linux-x86_64"));
+
+        let err = fetch_release(&config(Some("0.18.8"), mirror)).unwrap_err();
+        assert!(err.to_string().contains("Version may not exist"));
+    }
+
+    #[test]
+    fn test_fetch_release_from_mirror_rejects_redirect_to_other_host() {
+        let elsewhere = crate::http::stand_in::serve(vec![(
+            "/releases",
+            crate::http::stand_in::Response::Ok(RELEASE_JSON.into()),
+        )]);
+        let base = crate::http::stand_in::serve(vec![(
+            "/releases",
+            crate::http::stand_in::Response::Redirect(format!("{elsewhere}releases")),
+        )]);
+
+        let err = fetch_release(&config(
+            None,
+            ReleaseSource::parse_with_http(&base, true).unwrap(),
+        ))
+        .unwrap_err();
+        assert!(err.to_string().contains("redirected to an untrusted URL"));
+    }
+
+    #[test]
+    fn test_fetch_release_from_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_dir = dir.path().join("releases/version");
+        fs::create_dir_all(&version_dir).unwrap();
+        fs::write(version_dir.join("0.18.7.json"), RELEASE_JSON).unwrap();
+        let source = ReleaseSource::Directory(dir.path().to_owned());
+
+        let release = fetch_release(&config(Some("0.18.7"), source.clone())).unwrap();
+        assert_eq!(release.version, "0.18.7");
+
+        let err = fetch_release(&config(Some("0.18.8"), source.clone())).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("not available in the release directory")
+        );
+
+        let err = fetch_release(&config(Some("nightly"), source)).unwrap_err();
+        assert!(err.to_string().contains("No release information"));
+    }
+
     #[test]
     fn test_release_parsing_allows_null_platform_url() {
         let json = r#"{
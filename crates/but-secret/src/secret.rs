@@ -3,12 +3,19 @@
 //! These are stateless and global, while discouraging storing secrets
 //! in memory beyond their use.
 
-use std::sync::Mutex;
+use std::{path::PathBuf, sync::Mutex};
 
-use anyhow::Result;
+use anyhow::{Context as _, Result, bail};
+use but_settings::app_settings::{Secrets, SecretsBackend};
 
 use crate::Sensitive;
 
+pub mod encrypted_file;
+
+/// The environment variable holding the passphrase for the [encrypted file](Backend::EncryptedFile)
+/// backend if no key file is configured.
+pub const PASSPHRASE_ENV_VAR: &str = "GITBUTLER_SECRETS_PASSPHRASE";
+
 /// Determines how a secret's name should be modified to produce a namespace.
 ///
 /// Namespaces can be used to partition secrets, depending on some criteria.
@@ -23,19 +30,132 @@ pub enum Namespace {
     Global,
 }
 
+/// Where secrets are stored.
+pub enum Backend {
+    /// Keep using the credential store that is set up by default, which is the platform keychain,
+    /// or `git credential` in some debug builds (see [`set_application_namespace()`]).
+    Keychain,
+    /// Store all secrets in the file at `path`, encrypted with a key derived from `key`.
+    EncryptedFile {
+        /// The file to store secrets in. It's created on first write.
+        path: PathBuf,
+        /// The material to derive the encryption key from.
+        key: encrypted_file::KeySource,
+    },
+    /// Read secrets from environment variables named like [`environment_variable_name()`] first,
+    /// and otherwise retrieve and store them with `git credential` helpers.
+    GitCredentials,
+}
+
+impl Backend {
+    /// Create the backend configured in `settings`.
+    ///
+    /// The encrypted file defaults to `secrets.enc` in the application data directory if its path is empty.
+    /// The key is read from the key file if it's set, or from the passphrase in [`PASSPHRASE_ENV_VAR`] otherwise.
+    pub fn from_settings(settings: &Secrets) -> Result<Self> {
+        let Secrets {
+            backend,
+            encrypted_file_path,
+            key_file,
+        } = settings;
+        Ok(match backend {
+            SecretsBackend::Keychain => Backend::Keychain,
+            SecretsBackend::EncryptedFile => Backend::EncryptedFile {
+                path: if encrypted_file_path.is_empty() {
+                    but_path::app_data_dir()?.join("secrets.enc")
+                } else {
+                    encrypted_file_path.into()
+                },
+                key: if key_file.is_empty() {
+                    encrypted_file::KeySource::Passphrase(Sensitive(
+                        std::env::var(PASSPHRASE_ENV_VAR).with_context(|| {
+                            format!(
+                                "The encrypted secrets file needs a key file or a passphrase in {PASSPHRASE_ENV_VAR}"
+                            )
+                        })?,
+                    ))
+                } else {
+                    encrypted_file::KeySource::KeyFile(key_file.into())
+                },
+            },
+            SecretsBackend::GitCredentials => Backend::GitCredentials,
+        })
+    }
+}
+
+/// Store secrets in the backend configured in `settings` from now on.
+///
+/// If it can't be set up, all secret access fails with the reason, instead of silently using
+/// another store.
+pub fn setup_from_settings(settings: &Secrets) {
+    if let Err(err) = Backend::from_settings(settings).and_then(set_backend) {
+        tracing::warn!(?err, "Could not set up the configured secrets backend");
+        *BACKEND.lock().unwrap() = BackendKind::Unavailable(format!("{err:#}"));
+    }
+}
+
+/// Store secrets in `backend` from now on, for all namespaces.
+///
+/// Previously stored secrets are not migrated.
+pub fn set_backend(backend: Backend) -> Result<()> {
+    let kind = match backend {
+        Backend::Keychain => {
+            setup_default_store()?;
+            BackendKind::Keychain
+        }
+        Backend::EncryptedFile { path, key } => {
+            encrypted_file::setup(&path, key)?;
+            BackendKind::EncryptedFile
+        }
+        Backend::GitCredentials => {
+            git_credentials::setup()?;
+            BackendKind::GitCredentials
+        }
+    };
+    *BACKEND.lock().unwrap() = kind;
+    Ok(())
+}
+
+/// The name of the environment variable that overrides the secret known as `handle`
+/// when using the [`Backend::GitCredentials`] backend, like `GITBUTLER_SECRET_MY_TOKEN` for `my-token`.
+pub fn environment_variable_name(handle: &str) -> String {
+    let handle: String = handle
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("GITBUTLER_SECRET_{handle}")
+}
+
 /// Persist `secret` in `namespace` so that it can be retrieved by the given `handle`.
+///
+/// An empty `secret` deletes it, just like [`delete()`].
+/// This fails if the secret is overridden by its [environment variable](environment_variable_name()),
+/// as the stored secret wouldn't be used.
 pub fn persist(handle: &str, secret: &Sensitive<String>, namespace: Namespace) -> Result<()> {
-    let entry = entry_for(handle, namespace)?;
+    ensure_backend_is_available()?;
+    ensure_not_overridden_by_environment(handle)?;
     if secret.0.is_empty() {
-        entry.delete_credential()?;
-    } else {
-        entry.set_password(&secret.0)?;
+        return delete(handle, namespace);
     }
+    entry_for(handle, namespace)?.set_password(&secret.0)?;
     Ok(())
 }
 
 /// Obtain the previously [stored](persist()) secret known as `handle` from `namespace`.
+///
+/// With the [`Backend::GitCredentials`] backend, a non-empty [environment variable](environment_variable_name())
+/// takes precedence over the stored secret.
 pub fn retrieve(handle: &str, namespace: Namespace) -> Result<Option<Sensitive<String>>> {
+    ensure_backend_is_available()?;
+    if let Some(secret) = environment_override(handle) {
+        return Ok(Some(secret));
+    }
     match entry_for(handle, namespace)
         .map_err(annotate_keychain_error)?
         .get_password()
@@ -46,7 +166,39 @@ pub fn retrieve(handle: &str, namespace: Namespace) -> Result<Option<Sensitive<S
     }
 }
 
+fn ensure_backend_is_available() -> Result<()> {
+    if let BackendKind::Unavailable(reason) = &*BACKEND.lock().unwrap() {
+        bail!("The configured secrets backend could not be set up: {reason}");
+    }
+    Ok(())
+}
+
+/// Return the secret known as `handle` from its environment variable, if the backend reads them.
+fn environment_override(handle: &str) -> Option<Sensitive<String>> {
+    if *BACKEND.lock().unwrap() != BackendKind::GitCredentials {
+        return None;
+    }
+    std::env::var(environment_variable_name(handle))
+        .ok()
+        .filter(|secret| !secret.is_empty())
+        .map(Sensitive)
+}
+
+fn ensure_not_overridden_by_environment(handle: &str) -> Result<()> {
+    if environment_override(handle).is_some() {
+        bail!(
+            "The secret '{handle}' is set by the environment variable {}, unset it to change the stored secret",
+            environment_variable_name(handle)
+        );
+    }
+    Ok(())
+}
+
 fn annotate_keychain_error(err: anyhow::Error) -> anyhow::Error {
+    if *BACKEND.lock().unwrap() != BackendKind::Keychain {
+        // Errors of other backends are descriptive already, and not about the keychain.
+        return err;
+    }
     let err_string = err.to_string();
 
     if cfg!(target_os = "linux") {
@@ -87,7 +239,11 @@ fn annotate_keychain_error(err: anyhow::Error) -> anyhow::Error {
 }
 
 /// Delete the secret at `handle` permanently from `namespace`.
+///
+/// Like [`persist()`], this fails if the secret is overridden by its environment variable.
 pub fn delete(handle: &str, namespace: Namespace) -> Result<()> {
+    ensure_backend_is_available()?;
+    ensure_not_overridden_by_environment(handle)?;
     match entry_for(handle, namespace)
         .map_err(annotate_keychain_error)?
         .delete_credential()
@@ -108,18 +264,31 @@ pub fn delete(handle: &str, namespace: Namespace) -> Result<()> {
 pub fn set_application_namespace(identifier: impl Into<String>) {
     *NAMESPACE.lock().unwrap() = identifier.into();
 
-    // In debug builds, use a git-credential based implementation when platform keychains are
-    // either noisy (macOS rebuild prompts) or unavailable (headless e2e Linux containers).
-    // Release builds keep using the platform keychain.
     // HACK: we do this here because it's always called by client binaries, and we want it to work
     //       equally there and automatically.
-    if cfg!(debug_assertions)
-        && (cfg!(target_os = "macos") || std::env::var_os("E2E_TEST_APP_DATA_DIR").is_some())
-    {
+    if uses_git_credentials_instead_of_keychain() {
         git_credentials::setup().ok();
     }
 }
 
+/// In debug builds, use a git-credential based implementation when platform keychains are
+/// either noisy (macOS rebuild prompts) or unavailable (headless e2e Linux containers).
+/// Release builds keep using the platform keychain.
+fn uses_git_credentials_instead_of_keychain() -> bool {
+    cfg!(debug_assertions)
+        && (cfg!(target_os = "macos") || std::env::var_os("E2E_TEST_APP_DATA_DIR").is_some())
+}
+
+/// Restore the credential store that is used by default, after another backend replaced it.
+fn setup_default_store() -> Result<()> {
+    if uses_git_credentials_instead_of_keychain() {
+        git_credentials::setup()
+    } else {
+        keyring::set_default_credential_builder(keyring::default::default_credential_builder());
+        Ok(())
+    }
+}
+
 fn entry_for(handle: &str, namespace: Namespace) -> Result<keyring::Entry> {
     let ns = match namespace {
         Namespace::BuildKind => NAMESPACE.lock().unwrap().clone(),
@@ -136,6 +305,17 @@ fn entry_for(handle: &str, namespace: Namespace) -> Result<keyring::Entry> {
 
 /// How to further specialize secrets to avoid name clashes in the globally shared keystore.
 static NAMESPACE: Mutex<String> = Mutex::new(String::new());
+/// The kind of [`Backend`] that was set last.
+static BACKEND: Mutex<BackendKind> = Mutex::new(BackendKind::Keychain);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BackendKind {
+    Keychain,
+    EncryptedFile,
+    GitCredentials,
+    /// The configured backend couldn't be set up, for the contained reason.
+    Unavailable(String),
+}
 
 /// A keystore that uses git-credentials under to hood. It's useful on Systems that nag the user
 /// with popups if the underlying binary changes, and is available if `git` can be found and executed.
@@ -0,0 +1,274 @@
+//! A keystore that keeps all secrets in a single file, encrypted with a key derived from a passphrase
+//! or a key file. It's useful on headless systems which don't run a Secret Service, like CI machines
+//! and containers.
+//!
+//! The file starts with a magic header, followed by the salt used to derive the key, the nonce and the
+//! encrypted JSON object which maps secret names to their values.
+
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context as _, Result, bail};
+use chacha20poly1305::{
+    ChaCha20Poly1305, Key, KeyInit, Nonce,
+    aead::{Aead, Payload},
+};
+use keyring::{
+    Credential,
+    credential::{CredentialApi, CredentialBuilderApi, CredentialPersistence},
+};
+use tracing::instrument;
+
+use crate::Sensitive;
+
+/// Identifies the file format, and is authenticated along with the secrets.
+const MAGIC: &[u8] = b"gitbutler-secrets-v1\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+/// The material to derive the encryption key from.
+pub enum KeySource {
+    /// A passphrase, typically provided through an environment variable.
+    Passphrase(Sensitive<String>),
+    /// A file whose entire content is used as key material. It's read each time the store is accessed.
+    KeyFile(PathBuf),
+}
+
+impl KeySource {
+    fn material(&self) -> Result<Sensitive<Vec<u8>>> {
+        Ok(Sensitive(match self {
+            KeySource::Passphrase(passphrase) => passphrase.0.as_bytes().to_vec(),
+            KeySource::KeyFile(path) => std::fs::read(path).with_context(|| {
+                format!("Could not read secrets key file at '{}'", path.display())
+            })?,
+        }))
+    }
+
+    fn derive_key(&self, salt: &[u8]) -> Result<Key> {
+        let material = self.material()?;
+        if material.0.is_empty() {
+            bail!("Refusing to encrypt secrets with an empty passphrase or key file");
+        }
+        let mut key = Key::default();
+        argon2::Argon2::default()
+            .hash_password_into(&material.0, salt, &mut key)
+            .map_err(|err| anyhow::anyhow!("Could not derive secrets key: {err}"))?;
+        Ok(key)
+    }
+}
+
+struct Store {
+    path: PathBuf,
+    key: KeySource,
+    /// Serializes read-modify-write cycles within this process.
+    lock: Mutex<()>,
+}
+
+/// Secrets by name, along with the salt used to derive the key they are encrypted with.
+struct Secrets {
+    salt: [u8; SALT_LEN],
+    by_name: BTreeMap<String, String>,
+}
+
+impl Store {
+    fn read(&self) -> Result<Secrets> {
+        let data = match std::fs::read(&self.path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Secrets {
+                    salt: rand::random(),
+                    by_name: BTreeMap::new(),
+                });
+            }
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!("Could not read secrets file at '{}'", self.path.display())
+                });
+            }
+        };
+        let Some(data) = data.strip_prefix(MAGIC) else {
+            bail!("'{}' is not a GitButler secrets file", self.path.display());
+        };
+        if data.len() < SALT_LEN + NONCE_LEN {
+            bail!("Secrets file at '{}' is truncated", self.path.display());
+        }
+        let (salt, data) = data.split_at(SALT_LEN);
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let cipher = ChaCha20Poly1305::new(&self.key.derive_key(salt)?);
+        let plaintext = Sensitive(
+            cipher
+                .decrypt(
+                    Nonce::from_slice(nonce),
+                    Payload {
+                        msg: ciphertext,
+                        aad: MAGIC,
+                    },
+                )
+                .map_err(|_| {
+                    anyhow::anyhow!(
+                        "Could not decrypt secrets file at '{}' - is the passphrase or key file correct?",
+                        self.path.display()
+                    )
+                })?,
+        );
+        Ok(Secrets {
+            salt: salt.try_into().expect("split at SALT_LEN"),
+            by_name: serde_json::from_slice(&plaintext.0)?,
+        })
+    }
+
+    fn write(&self, secrets: &Secrets) -> Result<()> {
+        let plaintext = Sensitive(serde_json::to_vec(&secrets.by_name)?);
+        let nonce: [u8; NONCE_LEN] = rand::random();
+        let cipher = ChaCha20Poly1305::new(&self.key.derive_key(&secrets.salt)?);
+        let ciphertext = cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext.0,
+                    aad: MAGIC,
+                },
+            )
+            .map_err(|_| anyhow::anyhow!("Could not encrypt secrets"))?;
+
+        let mut data = MAGIC.to_vec();
+        data.extend_from_slice(&secrets.salt);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+        but_utils::create_dirs_then_write(&self.path, data).with_context(|| {
+            format!("Could not write secrets file at '{}'", self.path.display())
+        })?;
+        // The file is written through a temporary file which is only accessible by us, but don't
+        // rely on that to keep it private.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            std::fs::set_permissions(&self.path, std::fs::Permissions::from_mode(0o600))
+                .with_context(|| {
+                    format!(
+                        "Could not restrict access to secrets file at '{}'",
+                        self.path.display()
+                    )
+                })?;
+        }
+        Ok(())
+    }
+
+    /// Apply `f` to all secrets and write them back if it returns `true`.
+    fn modify(&self, f: impl FnOnce(&mut BTreeMap<String, String>) -> bool) -> Result<bool> {
+        let _guard = self.lock.lock().unwrap();
+        let mut secrets = self.read()?;
+        let changed = f(&mut secrets.by_name);
+        if changed {
+            self.write(&secrets)?;
+        }
+        Ok(changed)
+    }
+}
+
+type SharedStore = Arc<Store>;
+
+fn platform_failure(err: anyhow::Error) -> keyring::Error {
+    keyring::Error::PlatformFailure(err.into())
+}
+
+struct Entry {
+    handle: String,
+    store: SharedStore,
+}
+
+impl CredentialApi for Entry {
+    #[instrument(skip(self, password), err(Debug))]
+    fn set_password(&self, password: &str) -> keyring::Result<()> {
+        self.store
+            .modify(|secrets| {
+                secrets.insert(self.handle.clone(), password.to_owned());
+                true
+            })
+            .map_err(platform_failure)?;
+        Ok(())
+    }
+
+    #[instrument(skip(self), level = "trace")]
+    fn get_password(&self) -> keyring::Result<String> {
+        let _guard = self.store.lock.lock().unwrap();
+        self.store
+            .read()
+            .map_err(platform_failure)?
+            .by_name
+            .remove(&self.handle)
+            .ok_or(keyring::Error::NoEntry)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn set_secret(&self, _password: &[u8]) -> keyring::Result<()> {
+        unreachable!("unused")
+    }
+
+    fn get_secret(&self) -> keyring::Result<Vec<u8>> {
+        unreachable!("unused")
+    }
+
+    #[instrument(skip(self), err(Debug))]
+    fn delete_credential(&self) -> keyring::Result<()> {
+        if self
+            .store
+            .modify(|secrets| secrets.remove(&self.handle).is_some())
+            .map_err(platform_failure)?
+        {
+            Ok(())
+        } else {
+            Err(keyring::Error::NoEntry)
+        }
+    }
+}
+
+struct Builder {
+    store: SharedStore,
+}
+
+impl CredentialBuilderApi for Builder {
+    fn build(
+        &self,
+        _target: Option<&str>,
+        service: &str,
+        _user: &str,
+    ) -> keyring::Result<Box<Credential>> {
+        let credential = Entry {
+            handle: service.to_string(),
+            store: self.store.clone(),
+        };
+        Ok(Box::new(credential))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn persistence(&self) -> CredentialPersistence {
+        CredentialPersistence::UntilDelete
+    }
+}
+
+/// Initialize the credentials store so that secrets are kept in the file at `path`, encrypted with
+/// a key derived from `key`.
+///
+/// Neither the file nor the key need to exist yet, they are only accessed when secrets are.
+#[instrument(skip(key), err(Debug))]
+pub fn setup(path: &Path, key: KeySource) -> Result<()> {
+    let store = Arc::new(Store {
+        path: path.to_owned(),
+        key,
+        lock: Mutex::new(()),
+    });
+    keyring::set_default_credential_builder(Box::new(Builder { store }));
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! The credential store is global, so the `git credential` backend is tested in its own process.
+
+use but_secret::{
+    Sensitive,
+    secret::{self, Backend, Namespace},
+};
+
+#[test]
+fn environment_variables_override_secrets_stored_with_git_credential() -> anyhow::Result<()> {
+    let tmp = tempfile::tempdir()?;
+    let credentials = tmp.path().join("credentials");
+    let global_config = tmp.path().join("global.gitconfig");
+    std::fs::write(
+        &global_config,
+        format!(
+            "[credential]\n\thelper = store --file={}\n",
+            credentials.display().to_string().replace('\\', "/")
+        ),
+    )?;
+    let env_var = secret::environment_variable_name("token");
+
+    temp_env::with_vars(
+        [
+            ("GIT_CONFIG_GLOBAL", Some(global_config.as_os_str())),
+            ("GIT_CONFIG_NOSYSTEM", Some("1".as_ref())),
+            (env_var.as_str(), None),
+        ],
+        || -> anyhow::Result<()> {
+            secret::set_backend(Backend::GitCredentials)?;
+
+            let ns = Namespace::Global;
+            assert!(
+                secret::retrieve("token", ns)?.is_none(),
+                "nothing stored yet"
+            );
+            secret::persist("token", &Sensitive("stored".into()), ns)?;
+            assert_eq!(
+                secret::retrieve("token", ns)?.map(|s| s.0).as_deref(),
+                Some("stored"),
+                "without the environment variable, `git credential` is used"
+            );
+            assert!(
+                std::fs::read_to_string(&credentials)?.contains("stored"),
+                "the configured credential helper keeps the secret"
+            );
+
+            temp_env::with_var(
+                &env_var,
+                Some("from environment"),
+                || -> anyhow::Result<()> {
+                    assert_eq!(
+                        secret::retrieve("token", ns)?.map(|s| s.0).as_deref(),
+                        Some("from environment"),
+                        "the environment variable takes precedence"
+                    );
+                    for err in [
+                        secret::persist("token", &Sensitive("changed".into()), ns).unwrap_err(),
+                        secret::delete("token", ns).unwrap_err(),
+                    ] {
+                        assert!(
+                            err.to_string().contains(&env_var),
+                            "changes that wouldn't be visible are rejected: {err:#}"
+                        );
+                    }
+                    Ok(())
+                },
+            )?;
+
+            temp_env::with_var(&env_var, Some(""), || -> anyhow::Result<()> {
+                assert_eq!(
+                    secret::retrieve("token", ns)?.map(|s| s.0).as_deref(),
+                    Some("stored"),
+                    "empty variables are ignored"
+                );
+                Ok(())
+            })?;
+
+            secret::delete("token", ns)?;
+            assert!(secret::retrieve("token", ns)?.is_none(), "it was erased");
+            Ok(())
+        },
+    )
+}
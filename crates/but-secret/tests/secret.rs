@@ -5,3 +5,103 @@ fn sensitive_does_not_debug_print_itself() {
     let s = Sensitive("password");
     assert_eq!(format!("{s:?}"), "\"<redacted>\"");
 }
+
+/// Note that this is the only test which sets up a credential store, which is global.
+#[test]
+fn encrypted_file_backend() -> anyhow::Result<()> {
+    use but_secret::secret::{self, Backend, Namespace, encrypted_file::KeySource};
+    use but_settings::app_settings::{Secrets, SecretsBackend};
+
+    let tmp = tempfile::tempdir()?;
+    let path = tmp.path().join("nested").join("secrets.enc");
+    let backend = |passphrase: &str| Backend::EncryptedFile {
+        path: path.clone(),
+        key: KeySource::Passphrase(Sensitive(passphrase.into())),
+    };
+    secret::set_backend(backend("passphrase"))?;
+
+    for ns in [Namespace::Global, Namespace::BuildKind] {
+        assert!(
+            secret::retrieve("token", ns)?.is_none(),
+            "nothing stored yet"
+        );
+        secret::delete("token", ns)?;
+
+        secret::persist("token", &Sensitive("secret value".into()), ns)?;
+        assert_eq!(
+            secret::retrieve("token", ns)?.map(|s| s.0).as_deref(),
+            Some("secret value")
+        );
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_eq!(
+            std::fs::metadata(&path)?.permissions().mode() & 0o777,
+            0o600,
+            "only the owner can access the file"
+        );
+    }
+    let data = std::fs::read(&path)?;
+    assert!(
+        !data
+            .windows(b"secret value".len())
+            .any(|w| w == b"secret value"),
+        "the file is encrypted"
+    );
+
+    secret::set_backend(backend("wrong"))?;
+    let err = secret::retrieve("token", Namespace::Global).unwrap_err();
+    assert!(format!("{err:#}").contains("is the passphrase or key file correct?"));
+
+    let key_file = tmp.path().join("key");
+    std::fs::write(&key_file, "key material")?;
+    let key_file_path = tmp.path().join("with-key-file.enc");
+    secret::set_backend(Backend::EncryptedFile {
+        path: key_file_path.clone(),
+        key: KeySource::KeyFile(key_file),
+    })?;
+    secret::persist(
+        "token",
+        &Sensitive("from key file".into()),
+        Namespace::Global,
+    )?;
+    assert_eq!(
+        secret::retrieve("token", Namespace::Global)?
+            .map(|s| s.0)
+            .as_deref(),
+        Some("from key file")
+    );
+
+    secret::persist("token", &Sensitive("".into()), Namespace::Global)?;
+    assert!(
+        secret::retrieve("token", Namespace::Global)?.is_none(),
+        "empty secrets are deleted, just like with the keychain"
+    );
+
+    if std::env::var_os(secret::PASSPHRASE_ENV_VAR).is_none() {
+        secret::setup_from_settings(&Secrets {
+            backend: SecretsBackend::EncryptedFile,
+            encrypted_file_path: key_file_path.display().to_string(),
+            key_file: "".into(),
+        });
+        let err = secret::retrieve("token", Namespace::Global).unwrap_err();
+        assert!(
+            format!("{err:#}").contains(secret::PASSPHRASE_ENV_VAR),
+            "the setup error is reported instead of using another store: {err:#}"
+        );
+        assert!(
+            secret::persist("token", &Sensitive("value".into()), Namespace::Global).is_err(),
+            "nothing is stored elsewhere"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn environment_variable_name() {
+    assert_eq!(
+        but_secret::secret::environment_variable_name("github-oauth_user.name"),
+        "GITBUTLER_SECRET_GITHUB_OAUTH_USER_NAME"
+    );
+}
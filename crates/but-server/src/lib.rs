@@ -379,6 +379,9 @@ pub async fn run(config: Config) -> anyhow::Result<()> {
     };
     let app_settings = AppSettingsWithDiskSync::new_with_customization(config_dir.clone(), None)
         .expect("failed to create app settings");
+    if let Ok(settings) = app_settings.get() {
+        but_secret::secret::setup_from_settings(&settings.secrets);
+    }
 
     // If a project path was provided, auto-activate that project.
     if let Some(ref project_path) = config.project_path {
@@ -446,12 +449,20 @@ pub async fn run(config: Config) -> anyhow::Result<()> {
             but_post(legacy::git::git_get_global_config_cmd),
         )
         .route("/tree_change_diffs", but_post(diff::tree_change_diffs_cmd))
+        .route(
+            "/blame_worktree_file",
+            but_post(diff::blame_worktree_file_cmd),
+        )
         .route("/get_workspace", but_post(workspace::get_workspace_cmd))
         .route(
             "/commit_details_with_line_stats",
             but_post(diff::commit_details_with_line_stats_cmd),
         )
         .route("/branch_diff", but_post(but_api::branch::branch_diff_cmd))
+        .route(
+            "/branch_range_diff",
+            but_post(but_api::branch::branch_range_diff_cmd),
+        )
         .route("/move_branch", but_post(but_api::branch::move_branch_cmd))
         .route(
             "/set_target_ref_and_init_project",
@@ -639,6 +650,18 @@ pub async fn run(config: Config) -> anyhow::Result<()> {
             "/save_edit_and_return_to_workspace",
             but_post(legacy::modes::save_edit_and_return_to_workspace_cmd),
         )
+        .route(
+            "/enter_edit_mode_in_worktree",
+            but_post(legacy::modes::enter_edit_mode_in_worktree_cmd),
+        )
+        .route(
+            "/save_worktree_edit_and_return",
+            but_post(legacy::modes::save_worktree_edit_and_return_cmd),
+        )
+        .route(
+            "/abort_worktree_edit",
+            but_post(legacy::modes::abort_worktree_edit_cmd),
+        )
         .route(
             "/edit_initial_index_state",
             but_post(legacy::modes::edit_initial_index_state_cmd),
@@ -774,6 +797,11 @@ pub async fn run(config: Config) -> anyhow::Result<()> {
             "/commit_cherry_pick",
             but_post(commit::cherry_pick::commit_cherry_pick_cmd),
         )
+        .route(
+            "/commit_revert",
+            but_post(commit::revert::commit_revert_cmd),
+        )
+        .route("/commit_split", but_post(commit::split::commit_split_cmd))
         .route(
             "/commit_move",
             but_post(commit::move_commit::commit_move_cmd),
@@ -122,3 +122,33 @@ pub struct UiSettings {
     pub check_for_updates_interval_in_seconds: u64,
 }
 but_schemars::register_sdk_type!(UiSettings);
+
+/// Where secrets like forge tokens are stored.
+#[derive(
+    Clone, Copy, Debug, Default, Serialize, Deserialize, PartialEq, Eq, schemars::JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub enum SecretsBackend {
+    /// The system keychain.
+    #[default]
+    Keychain,
+    /// `encryptedFilePath`, encrypted with `keyFile` or the passphrase in `GITBUTLER_SECRETS_PASSPHRASE`.
+    EncryptedFile,
+    /// `GITBUTLER_SECRET_<HANDLE>` environment variables, and `git credential` helpers otherwise.
+    GitCredentials,
+}
+but_schemars::register_sdk_type!(SecretsBackend);
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Secrets {
+    /// Where secrets like forge tokens are stored.
+    pub backend: SecretsBackend,
+    /// The file to store secrets in with the "encryptedFile" backend.
+    /// If empty, `secrets.enc` in the application data directory is used.
+    pub encrypted_file_path: String,
+    /// A file whose content is the key for the "encryptedFile" backend.
+    /// If empty, the passphrase in `GITBUTLER_SECRETS_PASSPHRASE` is used.
+    pub key_file: String,
+}
+but_schemars::register_sdk_type!(Secrets);
@@ -23,10 +23,16 @@ pub struct AppSettings {
     pub reviews: app_settings::Reviews,
     /// UI settings.
     pub ui: app_settings::UiSettings,
+    /// Settings related to storing secrets.
+    pub secrets: app_settings::Secrets,
     /// The duration between application update checks in seconds. If `0`, no update checks will be performed.
     /// This setting controls background update checks for both the CLI and GUI.
     /// In the future, this will replace the legacy `ui.checkForUpdatesIntervalInSeconds` setting.
     pub app_updates_check_interval_sec: u64,
+    /// Where `but update install` obtains releases from instead of GitButler's servers.
+    /// Either the URL of a server which mirrors the release API, or the path to a local release directory.
+    /// If empty, the `GITBUTLER_INSTALL_MIRROR` environment variable is used, or GitButler's servers otherwise.
+    pub app_updates_mirror: String,
 }
 
 impl Default for AppSettings {
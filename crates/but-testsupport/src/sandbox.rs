@@ -474,8 +474,8 @@ impl Sandbox {
         use but_settings::{
             AppSettings,
             app_settings::{
-                Claude, ExtraCsp, FeatureFlags, Fetch, GitHubOAuthAppSettings, Reviews,
-                TelemetrySettings, UiSettings,
+                Claude, ExtraCsp, FeatureFlags, Fetch, GitHubOAuthAppSettings, Reviews, Secrets,
+                SecretsBackend, TelemetrySettings, UiSettings,
             },
         };
         let settings = AppSettings {
@@ -523,7 +523,13 @@ impl Sandbox {
                 #[expect(deprecated)]
                 check_for_updates_interval_in_seconds: 0,
             },
+            secrets: Secrets {
+                backend: SecretsBackend::Keychain,
+                encrypted_file_path: "".into(),
+                key_file: "".into(),
+            },
             app_updates_check_interval_sec: 0,
+            app_updates_mirror: "".into(),
         };
         settings
             .save(&self.app_data_dir().join("gitbutler/settings.json"), None)
@@ -128,6 +128,7 @@ where
             pending_ref_changes: PendingRefChanges::default(),
             context_lines,
             materialize_without_checkout: None,
+            squash_targets: Vec::new(),
         };
 
         let callback_outcome = {
@@ -146,17 +147,25 @@ where
         let Inner {
             mut rebase,
             db_tx,
-            commit_mappings: _,
+            commit_mappings,
             pending_metadata_removals,
             pending_metadata_updates,
             pending_created_independent_refs,
             mut pending_ref_changes,
             context_lines: _,
             materialize_without_checkout,
+            squash_targets,
         } = inner;
         let rebase = rebase.take().expect("rebase is always Some(_)");
 
         let should_rollback = callback_outcome.should_rollback();
+        if !should_rollback
+            && let Err(err) =
+                enforce_message_policy(&rebase, &commit_mappings, squash_targets.into_iter())
+        {
+            pending_ref_changes.rollback(&repo)?;
+            return Err(err);
+        }
         let outcome = callback_outcome.maybe_commit(
             &repo,
             rebase,
@@ -234,6 +243,10 @@ where
     //
     // Mixing different kinds of materialize requests results in an error.
     materialize_without_checkout: Option<bool>,
+    // The targets of `squash_commits`, as given by the caller. Their combined messages are only
+    // checked against the commit message policy once the transaction is about to be committed,
+    // as they are usually reworded right after squashing.
+    squash_targets: Vec<ObjectId>,
 }
 
 impl<'rebase, M> Transaction<'_, 'rebase, M>
@@ -254,7 +267,7 @@ where
         target: ObjectId,
         how_to_combine_messages: MessageCombinationStrategy,
     ) -> anyhow::Result<CommitIdentifiers> {
-        self.rebase(|editor, commit_mappings, _| {
+        let new_commit = self.rebase(|editor, commit_mappings, _| {
             let SquashCommitsOutcome {
                 rebase,
                 commit_selector,
@@ -269,7 +282,9 @@ where
             )?;
             let new_commit = rebase.lookup_commit(commit_selector)?;
             Ok((new_commit, MaterializeWithoutCheckout::No, rebase))
-        })
+        })?;
+        self.inner.squash_targets.push(target);
+        Ok(new_commit)
     }
 
     pub fn reword_commit(
@@ -337,6 +352,34 @@ where
         })
     }
 
+    /// Split `source` into one commit per entry in `pieces`, stacked in the given order.
+    ///
+    /// The returned identifiers refer to the commits created for each piece, parent first.
+    pub fn split_commit(
+        &mut self,
+        source: gix::ObjectId,
+        pieces: Vec<but_workspace::commit::CommitSplitPiece>,
+    ) -> anyhow::Result<Vec<CommitIdentifiers>> {
+        let context_lines = self.inner.context_lines;
+        self.rebase(|editor, commit_mappings, _| {
+            let but_workspace::commit::SplitCommitOutcome {
+                rebase,
+                piece_selectors,
+            } = but_workspace::commit::split_commit(
+                editor,
+                commit_mappings.map(source),
+                pieces,
+                context_lines,
+            )?;
+
+            let new_commits = piece_selectors
+                .into_iter()
+                .map(|selector| rebase.lookup_commit(selector))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            Ok((new_commits, MaterializeWithoutCheckout::No, rebase))
+        })
+    }
+
     pub fn remove_reference(&mut self, ref_name: &FullNameRef) -> anyhow::Result<()> {
         self.rebase(|mut editor, _, _| {
             let selector = editor.select_reference(ref_name)?;
@@ -750,6 +793,31 @@ where
         })
     }
 
+    /// Insert a commit that undoes the changes of `subject_commit_id` at `side` of `relative_to`.
+    ///
+    /// Subject and target commit IDs are automatically mapped through changes made earlier in the
+    /// transaction. The returned identifiers refer to the newly created revert commit.
+    pub fn revert_commit(
+        &mut self,
+        subject_commit_id: ObjectId,
+        relative_to: RelativeTo,
+        side: InsertSide,
+    ) -> anyhow::Result<CommitIdentifiers> {
+        self.rebase(|editor, commit_mappings, _| {
+            let subject_commit_id = commit_mappings.map(subject_commit_id);
+            let relative_to = match relative_to {
+                RelativeTo::Commit(object_id) => RelativeTo::Commit(commit_mappings.map(object_id)),
+                RelativeTo::Reference(full_name) => RelativeTo::Reference(full_name),
+            };
+
+            let (rebase, revert_selector) =
+                but_workspace::commit::revert_commit(editor, subject_commit_id, relative_to, side)?;
+            let new_commit = rebase.lookup_commit(revert_selector)?;
+
+            Ok((new_commit, MaterializeWithoutCheckout::No, rebase))
+        })
+    }
+
     pub fn move_commits(
         &mut self,
         subject_commit_ids: impl IntoIterator<Item = ObjectId>,
@@ -1135,6 +1203,21 @@ impl<M: RefMetadata> RefMetadata for RecordingMetadata<'_, M> {
     }
 }
 
+/// Check the final messages of `commits`, which may be commits of the live repository, against
+/// the commit message policy.
+fn enforce_message_policy<M: RefMetadata>(
+    rebase: &SuccessfulRebase<'_, '_, M>,
+    commit_mappings: &CommitMappings,
+    commits: impl Iterator<Item = ObjectId>,
+) -> anyhow::Result<()> {
+    let repo = rebase.repo();
+    for commit in commits {
+        let commit = repo.find_commit(commit_mappings.map(commit))?;
+        but_core::commit::message_policy::enforce(repo, commit.message_raw()?)?;
+    }
+    Ok(())
+}
+
 #[derive(Debug, Default)]
 struct CommitMappings(BTreeMap<gix::ObjectId, gix::ObjectId>);
 
@@ -1384,11 +1467,15 @@ fn workspace_state_from_rebase<M: RefMetadata>(
         materialized.meta.remove(ref_name.as_ref())?;
     }
 
+    let replaced_commits = materialized.history.commit_mappings();
+    #[cfg(feature = "legacy")]
+    but_api::after_rewrite(repo, &replaced_commits);
+
     WorkspaceState::from_workspace_without_pr_associations(
         materialized.workspace,
         materialized.meta,
         repo,
-        materialized.history.commit_mappings(),
+        replaced_commits,
     )
 }
 
@@ -10,6 +10,7 @@
 //! - **Caching**: Persist update check results to avoid redundant network requests
 //! - **Suppression**: Temporarily hide update notifications for a configurable duration
 //! - **Locking**: Prevent concurrent update checks across multiple processes
+//! - **Version Policies**: Check the running version against the versions a project accepts
 //!
 //! # Usage Example
 //!
@@ -67,6 +68,10 @@ pub use check::{AppName, CheckUpdateStatus, check_status};
 pub mod cache;
 pub use cache::{AvailableUpdate, available_update, last_checked, suppress_update};
 
+/// Module with project-level requirements on the version of the CLI.
+pub mod version_policy;
+pub use version_policy::{PolicyMismatch, VersionPolicy};
+
 /// Return `true` if we are still holding a lock on our data as we are fetching it.
 pub fn is_probably_still_running(cache: &mut but_db::AppCacheHandle) -> bool {
     cache
@@ -0,0 +1,146 @@
+//! Requirements of a project on the version of the CLI that operates on it.
+//!
+//! This allows teams to make sure everyone uses a version with the fixes or features they rely on,
+//! or the exact version they validated, which matters most when updates come from a mirror.
+
+use std::{cmp::Ordering, fmt};
+
+/// The versions of the CLI a project accepts.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct VersionPolicy {
+    /// The oldest version that may be used.
+    pub minimum: Option<String>,
+    /// The exact version that should be used.
+    pub pinned: Option<String>,
+}
+
+/// A way in which a version doesn't satisfy a [`VersionPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyMismatch {
+    /// The version is older than the minimum version.
+    BelowMinimum {
+        /// The version that was checked.
+        current: String,
+        /// The oldest version that may be used.
+        minimum: String,
+    },
+    /// The version isn't the pinned version.
+    NotPinned {
+        /// The version that was checked.
+        current: String,
+        /// The exact version that should be used.
+        pinned: String,
+    },
+}
+
+impl fmt::Display for PolicyMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyMismatch::BelowMinimum { current, minimum } => write!(
+                f,
+                "This project requires `but` {minimum} or newer, but {current} is installed"
+            ),
+            PolicyMismatch::NotPinned { current, pinned } => write!(
+                f,
+                "This project pins `but` to version {pinned}, but {current} is installed"
+            ),
+        }
+    }
+}
+
+impl VersionPolicy {
+    /// Return `true` if no version requirements are configured.
+    pub fn is_empty(&self) -> bool {
+        self.minimum.is_none() && self.pinned.is_none()
+    }
+
+    /// Check if `current` satisfies this policy, and return how it doesn't if that's the case.
+    ///
+    /// A pinned version takes precedence over the minimum version. Versions that can't be parsed
+    /// are ignored, so a typo in the configuration can't lead to misleading warnings.
+    pub fn check(&self, current: &str) -> Option<PolicyMismatch> {
+        if let Some(pinned) = &self.pinned {
+            return (compare_versions(current, pinned)? != Ordering::Equal).then(|| {
+                PolicyMismatch::NotPinned {
+                    current: current.to_owned(),
+                    pinned: pinned.clone(),
+                }
+            });
+        }
+        let minimum = self.minimum.as_ref()?;
+        (compare_versions(current, minimum)? == Ordering::Less).then(|| {
+            PolicyMismatch::BelowMinimum {
+                current: current.to_owned(),
+                minimum: minimum.clone(),
+            }
+        })
+    }
+}
+
+/// Compare the versions `a` and `b` by semantic versioning precedence, or return `None` if
+/// one of them can't be parsed.
+///
+/// A leading `v` is allowed, missing minor and patch components count as `0`, and build metadata
+/// after `+` is ignored. Pre-releases like `0.19.0-rc.1` precede their release.
+pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let a = ParsedVersion::parse(a)?;
+    let b = ParsedVersion::parse(b)?;
+    Some(a.core.cmp(&b.core).then_with(|| match (a.pre, b.pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => compare_pre_releases(a, b),
+    }))
+}
+
+struct ParsedVersion<'a> {
+    core: [u64; 3],
+    pre: Option<&'a str>,
+}
+
+impl<'a> ParsedVersion<'a> {
+    fn parse(version: &'a str) -> Option<Self> {
+        let version = version.trim();
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let version = version
+            .split_once('+')
+            .map_or(version, |(version, _build)| version);
+        let (core_str, pre) = match version.split_once('-') {
+            Some((core, pre)) if !pre.is_empty() => (core, Some(pre)),
+            Some(_) => return None,
+            None => (version, None),
+        };
+
+        let mut core = [0; 3];
+        let mut components = core_str.split('.');
+        for (idx, component) in components.by_ref().take(3).enumerate() {
+            core[idx] = component.parse().ok()?;
+        }
+        if components.next().is_some() {
+            return None;
+        }
+        Some(ParsedVersion { core, pre })
+    }
+}
+
+/// Compare dot-separated pre-release identifiers, numerically if both are numbers.
+fn compare_pre_releases(a: &str, b: &str) -> Ordering {
+    let mut a = a.split('.');
+    let mut b = b.split('.');
+    loop {
+        let ordering = match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a), Some(b)) => match (a.parse::<u64>(), b.parse::<u64>()) {
+                (Ok(a), Ok(b)) => a.cmp(&b),
+                (Ok(_), Err(_)) => Ordering::Less,
+                (Err(_), Ok(_)) => Ordering::Greater,
+                (Err(_), Err(_)) => a.cmp(b),
+            },
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
@@ -2,3 +2,4 @@ mod app_name;
 mod available_update;
 mod response_parsing;
 mod suppress_update;
+mod version_policy;
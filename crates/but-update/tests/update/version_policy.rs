@@ -0,0 +1,115 @@
+use std::cmp::Ordering;
+
+use but_update::{PolicyMismatch, VersionPolicy, version_policy::compare_versions};
+
+#[test]
+fn compare_versions_by_precedence() {
+    assert_eq!(compare_versions("0.18.7", "0.18.7"), Some(Ordering::Equal));
+    assert_eq!(compare_versions("0.18.7", "0.18.10"), Some(Ordering::Less));
+    assert_eq!(
+        compare_versions("0.19.0", "0.18.10"),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(
+        compare_versions("1.0.0", "0.99.99"),
+        Some(Ordering::Greater)
+    );
+
+    // A leading `v`, missing components and build metadata don't matter.
+    assert_eq!(compare_versions("v0.18.7", "0.18.7"), Some(Ordering::Equal));
+    assert_eq!(compare_versions("0.19", "0.19.0"), Some(Ordering::Equal));
+    assert_eq!(
+        compare_versions("0.18.7+2373", "0.18.7"),
+        Some(Ordering::Equal)
+    );
+
+    // Pre-releases precede their release and are ordered by their identifiers.
+    assert_eq!(
+        compare_versions("0.19.0-rc.1", "0.19.0"),
+        Some(Ordering::Less)
+    );
+    assert_eq!(
+        compare_versions("0.19.0-rc.2", "0.19.0-rc.10"),
+        Some(Ordering::Less)
+    );
+    assert_eq!(
+        compare_versions("0.19.0-beta", "0.19.0-alpha"),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(
+        compare_versions("0.19.0-rc", "0.19.0-rc.1"),
+        Some(Ordering::Less)
+    );
+}
+
+#[test]
+fn compare_versions_rejects_invalid_versions() {
+    for invalid in ["", "nightly", "0.18.x", "0.18.7.1", "0.18.7-", "-1"] {
+        assert_eq!(compare_versions(invalid, "0.18.7"), None, "{invalid:?}");
+        assert_eq!(compare_versions("0.18.7", invalid), None, "{invalid:?}");
+    }
+}
+
+#[test]
+fn empty_policy_accepts_everything() {
+    let policy = VersionPolicy::default();
+    assert!(policy.is_empty());
+    assert_eq!(policy.check("0.0.1"), None);
+}
+
+#[test]
+fn minimum_version() {
+    let policy = VersionPolicy {
+        minimum: Some("0.18.7".into()),
+        pinned: None,
+    };
+    assert_eq!(policy.check("0.18.7"), None);
+    assert_eq!(policy.check("0.19.0"), None);
+    assert_eq!(
+        policy.check("0.18.6"),
+        Some(PolicyMismatch::BelowMinimum {
+            current: "0.18.6".into(),
+            minimum: "0.18.7".into(),
+        })
+    );
+    assert_eq!(
+        policy.check("0.18.6").unwrap().to_string(),
+        "This project requires `but` 0.18.7 or newer, but 0.18.6 is installed"
+    );
+}
+
+#[test]
+fn pinned_version_takes_precedence() {
+    let policy = VersionPolicy {
+        minimum: Some("0.18.0".into()),
+        pinned: Some("0.18.7".into()),
+    };
+    assert_eq!(policy.check("0.18.7"), None);
+    assert_eq!(
+        policy.check("0.19.0"),
+        Some(PolicyMismatch::NotPinned {
+            current: "0.19.0".into(),
+            pinned: "0.18.7".into(),
+        }),
+        "newer versions are also a mismatch"
+    );
+    assert_eq!(
+        policy.check("0.19.0").unwrap().to_string(),
+        "This project pins `but` to version 0.18.7, but 0.19.0 is installed"
+    );
+}
+
+#[test]
+fn unparseable_versions_are_ignored() {
+    let policy = VersionPolicy {
+        minimum: Some("latest".into()),
+        pinned: None,
+    };
+    assert_eq!(policy.check("0.18.7"), None);
+
+    let policy = VersionPolicy {
+        minimum: Some("0.18.7".into()),
+        pinned: None,
+    };
+    assert_eq!(policy.check("dev"), None);
+}
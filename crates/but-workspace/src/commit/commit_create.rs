@@ -41,7 +41,9 @@ pub struct CommitCreateOutcome<'ws, 'meta, M: RefMetadata> {
 /// `relative_to` and `side` determine the position to insert the commit.
 /// See [`InsertSide`] to learn more about insertion semantics.
 ///
-/// `message` will be the message used for the newly created commit.
+/// `message` will be the message used for the newly created commit. It must comply with the
+/// [commit message policy](but_core::commit::message_policy) configured in the editor's repository,
+/// which is checked before anything is written.
 ///
 /// `context_lines` define how many diff context lines are being used for
 /// this particular function call. The provided `context_lines` MUST align
@@ -56,6 +58,7 @@ pub fn commit_create<'ws, 'meta, M: RefMetadata>(
     context_lines: u32,
     source: ChangeSource<'_>,
 ) -> Result<CommitCreateOutcome<'ws, 'meta, M>> {
+    but_core::commit::message_policy::enforce(editor.repo(), message.into())?;
     let relative_to_selector = relative_to.to_selector(&editor)?;
     let parent_commit_id =
         parent_commit_id_for_new_commit(&editor, editor.lookup_step(relative_to_selector)?, side)?;
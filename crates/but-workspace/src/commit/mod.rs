@@ -118,10 +118,14 @@ pub mod move_commit;
 pub use move_commit::move_commits;
 pub mod cherry_pick;
 pub use cherry_pick::cherry_pick_commits;
+pub mod revert_commit;
+pub use revert_commit::revert_commit;
 pub mod discard_commit;
 pub use discard_commit::discard_commits;
 pub mod squash_commits;
 pub use squash_commits::{SquashCommitsOutcome, squash_commits};
+pub mod split_commit;
+pub use split_commit::{CommitSplitPiece, SplitCommitOutcome, split_commit};
 
 /// A minimal stack for use by [WorkspaceCommit::new_from_stacks()].
 #[derive(Clone)]
@@ -0,0 +1,92 @@
+//! Reverting a commit
+
+use anyhow::{Result, bail};
+use bstr::{BString, ByteSlice as _};
+use but_core::{RefMetadata, commit::message_policy::MessagePolicy};
+use but_rebase::{
+    commit::DateMode,
+    graph_rebase::{
+        Editor, Selector, Step, SuccessfulRebase, ToSelector as _,
+        mutate::{InsertSide, RelativeTo},
+    },
+};
+use gix::prelude::ObjectIdExt as _;
+
+/// Insert a commit that undoes the changes of `subject` at `side` of `relative_to`.
+///
+/// The subject is read from the object database, so it may live anywhere in the repository,
+/// including on the target branch. The revert is a cherry-pick of a commit that has the subject
+/// as its only parent and the subject's parent tree as its tree, so picking it applies the
+/// subject's changes in reverse. If they don't apply cleanly where the revert lands, it becomes
+/// a conflicted commit like any other pick.
+///
+/// The message of the revert is the one Git would use, or `revert: <title>` if the
+/// [commit message policy](but_core::commit::message_policy) configured in the editor's repository
+/// requires conventional subjects. Either way it must comply with that policy.
+///
+/// Merge commits and conflicted commits can't be reverted.
+pub fn revert_commit<'ws, 'meta, M: RefMetadata>(
+    mut editor: Editor<'ws, 'meta, M>,
+    subject: gix::ObjectId,
+    relative_to: RelativeTo,
+    side: InsertSide,
+) -> Result<(SuccessfulRebase<'ws, 'meta, M>, Selector)> {
+    if matches!(
+        (&relative_to, side),
+        (RelativeTo::Reference(_), InsertSide::Above)
+    ) {
+        bail!("Cannot revert above a reference")
+    }
+
+    let subject_commit = editor.find_commit(subject)?;
+    if subject_commit.is_conflicted() {
+        bail!("Cannot revert conflicted commit {subject}, resolve it first")
+    }
+    let parent_tree = match subject_commit.parents.as_slice() {
+        [] => gix::ObjectId::empty_tree(editor.repo().object_hash()),
+        [parent] => but_core::Commit::from_id(parent.attach(editor.repo()))?
+            .tree_id_or_auto_resolution()?
+            .detach(),
+        _ => bail!("Cannot revert merge commit {subject}"),
+    };
+
+    let mut template = editor.empty_commit()?;
+    template.inner.tree = parent_tree;
+    template.inner.parents = vec![subject].into();
+    template.inner.message =
+        revert_message(editor.repo(), subject, subject_commit.message.as_bstr())?;
+    but_core::commit::message_policy::enforce(editor.repo(), template.inner.message.as_bstr())?;
+    let template_id = editor.new_commit(template, DateMode::CommitterUpdateAuthorUpdate)?;
+
+    let target = relative_to.to_selector(&editor)?;
+    let selector = editor.insert(target, Step::new_untracked_pick(template_id), side)?;
+
+    Ok((editor.rebase()?, selector))
+}
+
+/// The message git uses for reverts, referring to the title and the full id of `subject`.
+///
+/// If the message policy of `repo` requires conventional subjects and allows the `revert` type,
+/// the subject is `revert: <title>` instead.
+fn revert_message(
+    repo: &gix::Repository,
+    subject: gix::ObjectId,
+    subject_message: &bstr::BStr,
+) -> Result<BString> {
+    let title = subject_message.lines().next().unwrap_or_default();
+    let conventional = MessagePolicy::from_config(&repo.config_snapshot())?
+        .and_then(|policy| policy.conventional_types)
+        .is_some_and(|types| types.iter().any(|ty| ty == "revert"));
+    let mut message = if conventional {
+        let mut message = BString::from("revert: ");
+        message.extend_from_slice(title);
+        message
+    } else {
+        let mut message = BString::from("Revert \"");
+        message.extend_from_slice(title);
+        message.push(b'"');
+        message
+    };
+    message.extend_from_slice(format!("\n\nThis reverts commit {subject}.\n").as_bytes());
+    Ok(message)
+}
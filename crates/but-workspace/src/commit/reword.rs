@@ -11,12 +11,16 @@ use but_rebase::{
 /// This action will rewrite a commit and any relevant history so it uses
 /// the new name.
 ///
+/// `new_message` must comply with the [commit message policy](but_core::commit::message_policy)
+/// configured in the editor's repository.
+///
 /// Returns a selector to the rewritten commit
 pub fn reword<'ws, 'meta, M: RefMetadata>(
     mut editor: Editor<'ws, 'meta, M>,
     commit: impl ToCommitSelector,
     new_message: &BStr,
 ) -> Result<(SuccessfulRebase<'ws, 'meta, M>, Selector)> {
+    but_core::commit::message_policy::enforce(editor.repo(), new_message)?;
     let (target_selector, mut commit) = editor.find_selectable_commit(commit)?;
 
     commit.message = new_message.to_owned();
@@ -0,0 +1,137 @@
+//! Splitting a commit into several commits
+
+use anyhow::{Result, bail};
+use bstr::BString;
+use but_core::{DiffSpec, RefMetadata};
+use but_rebase::{
+    commit::DateMode,
+    graph_rebase::{
+        Editor, Selector, Step, SuccessfulRebase, ToCommitSelector, mutate::InsertSide,
+    },
+};
+use gix::prelude::ObjectIdExt as _;
+
+use super::uncommit_changes::merge_specs_by_path;
+use crate::tree_manipulation::{ChangesSource, create_tree_without_diff};
+
+/// One of the commits a commit is split into.
+#[derive(Debug, Clone)]
+pub struct CommitSplitPiece {
+    /// The changes of the split commit that go into this piece.
+    pub changes: Vec<DiffSpec>,
+    /// The message of the commit created for this piece.
+    pub message: BString,
+}
+
+/// The result of a split_commit operation.
+#[derive(Debug)]
+pub struct SplitCommitOutcome<'ws, 'meta, M: RefMetadata> {
+    /// The successful rebase result
+    pub rebase: SuccessfulRebase<'ws, 'meta, M>,
+    /// Selectors pointing to the commits created for each piece, in parent-to-child order.
+    pub piece_selectors: Vec<Selector>,
+}
+
+/// Split `commit` into one commit per entry in `pieces`, stacked in the given order.
+///
+/// Each piece receives the changes it selects. Changes not selected by any piece stay in the
+/// first piece, so its selection may be empty to mean "everything else". The first piece
+/// replaces `commit` and keeps its change-id and author, the other pieces are new commits with
+/// the same author. Descendants of `commit` are rebased onto the last piece, whose tree is the
+/// one of `commit`.
+///
+/// Messages other than the one of `commit` must comply with the
+/// [commit message policy](but_core::commit::message_policy) configured in the editor's repository.
+///
+/// It's an error if there are less than two pieces, if any piece would end up without changes,
+/// or if the selected changes can't be found in `commit`.
+pub fn split_commit<'ws, 'meta, M: RefMetadata>(
+    mut editor: Editor<'ws, 'meta, M>,
+    commit: impl ToCommitSelector,
+    pieces: Vec<CommitSplitPiece>,
+    context_lines: u32,
+) -> Result<SplitCommitOutcome<'ws, 'meta, M>> {
+    if pieces.len() < 2 {
+        bail!("A commit must be split into at least two pieces")
+    }
+
+    let (commit_selector, commit) = editor.find_selectable_commit(commit)?;
+    if commit.clone().attach(editor.repo()).is_conflicted() {
+        bail!("Cannot split a conflicted commit")
+    }
+    for piece in pieces
+        .iter()
+        .filter(|piece| piece.message != commit.message)
+    {
+        but_core::commit::message_policy::enforce(editor.repo(), piece.message.as_ref())?;
+    }
+    let parent_tree = match commit.parents.as_slice() {
+        [] => gix::ObjectId::empty_tree(editor.repo().object_hash()),
+        [parent] => but_core::Commit::from_id(parent.attach(editor.repo()))?
+            .tree_id_or_auto_resolution()?
+            .detach(),
+        _ => bail!("Cannot split a merge commit"),
+    };
+
+    // The tree of each piece is the tree of the commit without the changes of all later pieces.
+    let mut trees = Vec::with_capacity(pieces.len());
+    for later_pieces in (1..pieces.len()).map(|idx| &pieces[idx..]) {
+        let later_changes = merge_specs_by_path(
+            later_pieces
+                .iter()
+                .flat_map(|piece| piece.changes.iter().cloned())
+                .collect(),
+        );
+        let (tree, dropped_diffs) = create_tree_without_diff(
+            editor.repo(),
+            ChangesSource::Commit { id: commit.id },
+            later_changes,
+            context_lines,
+        )?;
+        if !dropped_diffs.is_empty() {
+            bail!("Failed to extract the selected changes from the commit")
+        }
+        trees.push(tree);
+    }
+    trees.push(commit.tree);
+
+    let mut previous_tree = parent_tree;
+    for (piece_number, tree) in trees.iter().enumerate().map(|(idx, tree)| (idx + 1, *tree)) {
+        if tree == previous_tree {
+            bail!("Piece {piece_number} of the split commit wouldn't contain any changes")
+        }
+        previous_tree = tree;
+    }
+
+    let mut pieces = pieces.into_iter().zip(trees);
+    let (first_piece, first_tree) = pieces.next().expect("at least two pieces");
+    let mut previous_id = {
+        let mut first_commit = commit.clone();
+        first_commit.tree = first_tree;
+        first_commit.message = first_piece.message;
+        editor.new_commit(first_commit, DateMode::CommitterUpdateAuthorKeep)?
+    };
+    editor.replace(commit_selector, Step::new_pick(previous_id))?;
+
+    let mut piece_selectors = vec![commit_selector];
+    for (piece, tree) in pieces {
+        let mut template = editor.empty_commit()?;
+        template.inner.tree = tree;
+        template.inner.parents = vec![previous_id].into();
+        template.inner.author = commit.author.clone();
+        template.inner.message = piece.message;
+        previous_id = editor.new_commit(template, DateMode::CommitterUpdateAuthorKeep)?;
+
+        let previous_selector = *piece_selectors.last().expect("never empty");
+        piece_selectors.push(editor.insert(
+            previous_selector,
+            Step::new_untracked_pick(previous_id),
+            InsertSide::Above,
+        )?);
+    }
+
+    Ok(SplitCommitOutcome {
+        rebase: editor.rebase()?,
+        piece_selectors,
+    })
+}
@@ -134,6 +134,10 @@ but_schemars::register_sdk_type!(MessageCombinationStrategy);
 /// Subject messages are appended in the order they are provided, with at least
 /// one blank line between non-empty message blocks.
 ///
+/// The combined message is *not* checked against the
+/// [commit message policy](but_core::commit::message_policy) as it is often
+/// reworded right after. Callers must check the final message instead.
+///
 pub fn squash_commits<'ws, 'meta, M: RefMetadata, S: ToCommitSelector, T: ToCommitSelector>(
     editor: Editor<'ws, 'meta, M>,
     subjects: Vec<S>,
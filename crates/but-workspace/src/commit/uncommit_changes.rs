@@ -266,7 +266,7 @@ fn group_sources_by_commit(
 /// An empty `hunk_headers` list means "the whole file"; when it appears for a
 /// path it supersedes any hunk subset, since removing the whole file already
 /// covers every hunk.
-fn merge_specs_by_path(specs: Vec<DiffSpec>) -> Vec<DiffSpec> {
+pub(crate) fn merge_specs_by_path(specs: Vec<DiffSpec>) -> Vec<DiffSpec> {
     let mut order = Vec::<(Option<BString>, BString)>::new();
     let mut by_path = HashMap::<(Option<BString>, BString), DiffSpec>::new();
     for spec in specs {
@@ -10,7 +10,7 @@ use anyhow::{Context as _, Result};
 use bstr::{BStr, BString, ByteSlice, ByteVec};
 use but_core::{extract_remote_name_and_short_name, ref_metadata::ProjectMeta};
 use but_db::DbHandle;
-use gitbutler_git::{PushResult, push_with_askpass};
+use gitbutler_git::{PushResult, push_atomic_with_askpass, push_with_askpass};
 use gitbutler_reference::RemoteRefname;
 use gitbutler_repo::hooks;
 use gix::refs::Category;
@@ -19,6 +19,10 @@ use indexmap::IndexMap;
 use crate::{RefInfo, ref_info::Segment, ui::PushStatus};
 
 /// Push a given branch and its ancestors
+///
+/// Outside of Gerrit mode, all branches that go to the same remote are pushed at once and atomically,
+/// so either all of them are updated on the remote or none of them are, as long as the remote
+/// supports atomic pushes.
 #[allow(clippy::too_many_arguments)]
 pub fn workspace_branch_and_ancestors_push(
     repo: &gix::Repository,
@@ -55,7 +59,10 @@ pub fn workspace_branch_and_ancestors_push(
         remote: push_remote.clone(),
         branch_to_remote: vec![],
         branch_sha_updates: vec![],
+        ref_updates: vec![],
     };
+    // The branches to push atomically, by the name of their remote.
+    let mut atomic_pushes = IndexMap::<String, Vec<PendingPush>>::new();
 
     for (sidx, segment) in to_push.iter().rev() {
         // this will always be set
@@ -107,6 +114,17 @@ pub fn workspace_branch_and_ancestors_push(
             }?;
         }
 
+        let pending = PendingPush {
+            branch_name: ref_name.shorten().to_str_lossy().to_string(),
+            remote_refname,
+            before_sha,
+            local_sha: local_sha.id,
+        };
+        if !gerrit_mode {
+            atomic_pushes.entry(remote_name).or_default().push(pending);
+            continue;
+        }
+
         let gerrit_push_args = gerrit_push_args(
             gerrit_mode,
             local_sha.id,
@@ -116,7 +134,7 @@ pub fn workspace_branch_and_ancestors_push(
         let push_output = push_with_askpass(
             repo,
             local_sha.id,
-            remote_refname.as_ref(),
+            pending.remote_refname.as_ref(),
             with_force,
             force_push_protection && !skip_force_push_protection,
             gerrit_push_args.refspec,
@@ -128,18 +146,50 @@ pub fn workspace_branch_and_ancestors_push(
         )?;
 
         maybe_record_gerrit_push_metadata(repo, db, gerrit_mode, segment, &push_output)?;
-        let branch_name = ref_name.shorten().to_str_lossy().to_string();
+        pending.record(&mut result);
+    }
+
+    for pending in atomic_pushes.into_values() {
+        let ref_updates = push_atomic_with_askpass(
+            repo,
+            pending
+                .iter()
+                .map(|push| (push.local_sha, push.remote_refname.clone()))
+                .collect(),
+            with_force,
+            force_push_protection && !skip_force_push_protection,
+            Some(None),
+            vec![],
+        )?;
+        result.ref_updates.extend(ref_updates);
+        for push in pending {
+            push.record(&mut result);
+        }
+    }
+
+    Ok(result)
+}
+
+/// A branch that is about to be pushed.
+struct PendingPush {
+    branch_name: String,
+    remote_refname: gix::refs::FullName,
+    before_sha: gix::ObjectId,
+    local_sha: gix::ObjectId,
+}
+
+impl PendingPush {
+    /// Add this push to `result` once it succeeded.
+    fn record(self, result: &mut PushResult) {
         result
             .branch_to_remote
-            .push((branch_name.clone(), remote_refname));
+            .push((self.branch_name.clone(), self.remote_refname));
         result.branch_sha_updates.push((
-            branch_name,
-            before_sha.to_string(),
-            local_sha.id.to_string(),
+            self.branch_name,
+            self.before_sha.to_string(),
+            self.local_sha.to_string(),
         ));
     }
-
-    Ok(result)
 }
 
 /// Return the selected local branch and its ancestors in top-to-base order.
@@ -59,6 +59,9 @@ pub mod commit;
 pub mod ref_info;
 pub use ref_info::{graph_to_ref_info, head_info, head_info_and_workspace, ref_info};
 
+pub mod range_diff;
+pub use range_diff::range_diff;
+
 mod branch_details;
 pub use branch_details::{branch_details, local_commits_for_branch};
 use but_graph::{SegmentIndex, workspace::TargetCommit};
@@ -0,0 +1,368 @@
+//! Compare two versions of a branch commit by commit, like `git range-diff`.
+//!
+//! Commits of the old and the new version are paired by their change-id first. Commits left over
+//! are paired by the similarity of the lines they add and remove, so rewritten commits that lost
+//! their change-id, or were created by other tools, still find their counterpart.
+//! Each pair is then compared by diffing the patches of both commits, which is the *interdiff*.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use bstr::{BStr, BString, ByteSlice as _};
+use but_core::{ChangeId, UnifiedPatch};
+use gix::prelude::ObjectIdExt as _;
+
+/// The minimal similarity, between `0.0` and `1.0`, for two commits without matching change-id
+/// to be considered versions of each other.
+const MIN_SIMILARITY: f32 = 0.5;
+
+/// The result of [`range_diff()`].
+#[derive(Debug, Clone)]
+pub struct RangeDiff {
+    /// The commits of both versions, in the order of the new version, oldest first.
+    ///
+    /// Commits that only exist in the old version are placed after the commit they followed there.
+    pub entries: Vec<RangeDiffEntry>,
+}
+
+/// A commit of the old version, the new version, or both.
+#[derive(Debug, Clone)]
+pub struct RangeDiffEntry {
+    /// The commit in the old version, if there is one.
+    pub old: Option<gix::ObjectId>,
+    /// The commit in the new version, if there is one.
+    pub new: Option<gix::ObjectId>,
+    /// The title of the commit, taken from the new version if there is one.
+    pub title: BString,
+    /// How `old` and `new` relate.
+    pub status: RangeDiffStatus,
+    /// How `old` and `new` were paired, or `None` if only one of them is set.
+    pub pairing: Option<RangeDiffPairing>,
+    /// The diff from the patch of `old` to the patch of `new`, including their messages,
+    /// or `None` if the patches are the same or there is nothing to compare to.
+    pub interdiff: Option<BString>,
+}
+
+/// How a commit in a [`RangeDiff`] changed between the old and the new version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeDiffStatus {
+    /// The commit only exists in the new version.
+    Added,
+    /// The commit only exists in the old version.
+    Removed,
+    /// Message and patch of both commits are the same.
+    Unchanged,
+    /// Message or patch changed.
+    Modified,
+}
+
+/// Why two commits in a [`RangeDiff`] are considered versions of each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeDiffPairing {
+    /// Both commits have the same change-id.
+    ChangeId,
+    /// The commits add and remove similar lines.
+    Similarity,
+}
+
+/// Compare the commits reachable from `old_tip` with the commits reachable from `new_tip`.
+///
+/// Only the commits that aren't reachable from the other tip or any of `hidden` are compared,
+/// so passing the target branch as `hidden` keeps commits out that came in through a rebase.
+/// Both histories are traversed along their first parents, and each commit is compared to its
+/// first parent.
+///
+/// Patches and the interdiff use `context_lines` lines of context.
+pub fn range_diff(
+    repo: &gix::Repository,
+    old_tip: gix::ObjectId,
+    new_tip: gix::ObjectId,
+    hidden: impl IntoIterator<Item = gix::ObjectId>,
+    context_lines: u32,
+) -> Result<RangeDiff> {
+    let hidden: Vec<_> = hidden.into_iter().collect();
+    let old = commits_in_range(repo, old_tip, new_tip, &hidden, context_lines)?;
+    let new = commits_in_range(repo, new_tip, old_tip, &hidden, context_lines)?;
+
+    let pairs = pair_commits(&old, &new);
+    let mut old_is_paired = vec![false; old.len()];
+    for (old_idx, _) in pairs.iter().flatten() {
+        old_is_paired[*old_idx] = true;
+    }
+
+    let mut entries = Vec::with_capacity(old.len().max(new.len()));
+    let mut next_old = 0;
+    let mut push_removed_until = |entries: &mut Vec<RangeDiffEntry>, end: usize| {
+        while next_old < end {
+            if !old_is_paired[next_old] {
+                entries.push(RangeDiffEntry::removed(&old[next_old]));
+            }
+            next_old += 1;
+        }
+    };
+    for (new_commit, pair) in new.iter().zip(&pairs) {
+        match pair {
+            Some((old_idx, pairing)) => {
+                push_removed_until(&mut entries, *old_idx);
+                entries.push(RangeDiffEntry::paired(
+                    &old[*old_idx],
+                    new_commit,
+                    *pairing,
+                    context_lines,
+                )?);
+            }
+            None => entries.push(RangeDiffEntry::added(new_commit)),
+        }
+    }
+    push_removed_until(&mut entries, old.len());
+
+    Ok(RangeDiff { entries })
+}
+
+impl RangeDiffEntry {
+    fn added(new: &RangeCommit) -> Self {
+        RangeDiffEntry {
+            old: None,
+            new: Some(new.id),
+            title: new.title(),
+            status: RangeDiffStatus::Added,
+            pairing: None,
+            interdiff: None,
+        }
+    }
+
+    fn removed(old: &RangeCommit) -> Self {
+        RangeDiffEntry {
+            old: Some(old.id),
+            new: None,
+            title: old.title(),
+            status: RangeDiffStatus::Removed,
+            pairing: None,
+            interdiff: None,
+        }
+    }
+
+    fn paired(
+        old: &RangeCommit,
+        new: &RangeCommit,
+        pairing: RangeDiffPairing,
+        context_lines: u32,
+    ) -> Result<Self> {
+        let (status, interdiff) = if old.patch == new.patch {
+            (RangeDiffStatus::Unchanged, None)
+        } else {
+            let hunks =
+                but_core::unified_diff::hunks_from_buffers(&old.patch, &new.patch, context_lines)?;
+            let mut interdiff = BString::default();
+            for hunk in hunks {
+                interdiff.extend_from_slice(&hunk.diff);
+            }
+            (RangeDiffStatus::Modified, Some(interdiff))
+        };
+        Ok(RangeDiffEntry {
+            old: Some(old.id),
+            new: Some(new.id),
+            title: new.title(),
+            status,
+            pairing: Some(pairing),
+            interdiff,
+        })
+    }
+}
+
+/// A commit in one of the compared ranges, along with everything needed to pair and compare it.
+struct RangeCommit {
+    id: gix::ObjectId,
+    /// The change-id stored in the commit, without falling back to one derived from the commit id.
+    change_id: Option<ChangeId>,
+    message: BString,
+    /// The message followed by the patch of all changes, which is what interdiffs are made of.
+    ///
+    /// Hunk headers have no line numbers so moved hunks compare equal.
+    patch: BString,
+    /// The lines added and removed by the patch, with their `+` or `-` prefix.
+    changed_lines: Vec<BString>,
+}
+
+impl RangeCommit {
+    fn title(&self) -> BString {
+        self.message.lines().next().unwrap_or_default().into()
+    }
+}
+
+/// Return the first-parent commits reachable from `tip` but not from `other_tip` or `hidden`,
+/// oldest first.
+fn commits_in_range(
+    repo: &gix::Repository,
+    tip: gix::ObjectId,
+    other_tip: gix::ObjectId,
+    hidden: &[gix::ObjectId],
+    context_lines: u32,
+) -> Result<Vec<RangeCommit>> {
+    let mut commits = Vec::new();
+    for info in tip
+        .attach(repo)
+        .ancestors()
+        .first_parent_only()
+        .with_hidden(hidden.iter().copied().chain(Some(other_tip)))
+        .all()?
+    {
+        let info = info?;
+        commits.push(range_commit(repo, info.id, context_lines)?);
+    }
+    commits.reverse();
+    Ok(commits)
+}
+
+fn range_commit(
+    repo: &gix::Repository,
+    id: gix::ObjectId,
+    context_lines: u32,
+) -> Result<RangeCommit> {
+    let commit = but_core::Commit::from_id(id.attach(repo))?;
+    let change_id = commit.headers().and_then(|headers| headers.change_id);
+    let message = commit.message.clone();
+
+    let mut patch = message.clone();
+    if !patch.ends_with(b"\n") {
+        patch.push(b'\n');
+    }
+    let mut changed_lines = Vec::new();
+    for change in but_core::diff::commit_changes(id.attach(repo))?.into_tree_changes() {
+        patch.extend_from_slice(b"\n## ");
+        if let Some(previous_path) = change.previous_path() {
+            patch.extend_from_slice(previous_path);
+            patch.extend_from_slice(b" -> ");
+        }
+        patch.extend_from_slice(&change.path);
+        patch.push(b'\n');
+        match change.unified_patch(repo, context_lines)? {
+            Some(UnifiedPatch::Patch { hunks, .. }) => {
+                for hunk in hunks {
+                    changed_lines.extend(
+                        hunk.diff
+                            .lines()
+                            .filter(|line| line.starts_with(b"+") || line.starts_with(b"-"))
+                            .map(BString::from),
+                    );
+                    patch.extend_from_slice(&without_line_numbers(hunk.diff.as_ref()));
+                }
+            }
+            Some(
+                UnifiedPatch::Binary
+                | UnifiedPatch::TooLarge { .. }
+                | UnifiedPatch::Submodule { .. }
+                | UnifiedPatch::Lfs { .. },
+            )
+            | None => {
+                // Without a textual patch, the object ids are the best way to tell versions apart.
+                let previous = change.status.previous_state_and_path();
+                let line = format!(
+                    "{} -> {}\n",
+                    previous.map_or_else(|| "none".into(), |(state, _)| state.id.to_string()),
+                    change
+                        .status
+                        .state()
+                        .map_or_else(|| "none".into(), |state| state.id.to_string()),
+                );
+                changed_lines.push(line.trim_end().into());
+                patch.extend_from_slice(line.as_bytes());
+            }
+        }
+    }
+
+    Ok(RangeCommit {
+        id,
+        change_id,
+        message,
+        patch,
+        changed_lines,
+    })
+}
+
+/// Return `hunk` with the line numbers removed from its `@@ -a,b +c,d @@` header, like
+/// `git range-diff` does. They change whenever lines are added or removed above the hunk,
+/// which happens with every rebase and isn't a change of the commit itself.
+fn without_line_numbers(hunk: &BStr) -> BString {
+    match hunk.find_byte(b'\n') {
+        Some(header_end) if hunk.starts_with(b"@@") => {
+            let mut out = BString::from("@@");
+            out.extend_from_slice(&hunk[header_end..]);
+            out
+        }
+        _ => hunk.to_owned(),
+    }
+}
+
+/// For each commit in `new`, return the index of its counterpart in `old` and how they were paired.
+fn pair_commits(
+    old: &[RangeCommit],
+    new: &[RangeCommit],
+) -> Vec<Option<(usize, RangeDiffPairing)>> {
+    let mut pairs = vec![None; new.len()];
+    let mut old_is_paired = vec![false; old.len()];
+
+    let old_by_change_id: HashMap<_, _> = old
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, commit)| commit.change_id.as_ref().map(|change_id| (change_id, idx)))
+        .collect();
+    for (new_idx, commit) in new.iter().enumerate() {
+        if let Some(&old_idx) = commit
+            .change_id
+            .as_ref()
+            .and_then(|change_id| old_by_change_id.get(change_id))
+            && !old_is_paired[old_idx]
+        {
+            pairs[new_idx] = Some((old_idx, RangeDiffPairing::ChangeId));
+            old_is_paired[old_idx] = true;
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for (new_idx, new_commit) in new.iter().enumerate() {
+        if pairs[new_idx].is_some() {
+            continue;
+        }
+        for (old_idx, old_commit) in old.iter().enumerate() {
+            if old_is_paired[old_idx] {
+                continue;
+            }
+            let similarity = similarity(&old_commit.changed_lines, &new_commit.changed_lines);
+            if similarity >= MIN_SIMILARITY {
+                candidates.push((similarity, new_idx, old_idx));
+            }
+        }
+    }
+    // Most similar first, and in commit order among equals to remain deterministic.
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)).then(a.2.cmp(&b.2)));
+    for (_, new_idx, old_idx) in candidates {
+        if pairs[new_idx].is_none() && !old_is_paired[old_idx] {
+            pairs[new_idx] = Some((old_idx, RangeDiffPairing::Similarity));
+            old_is_paired[old_idx] = true;
+        }
+    }
+    pairs
+}
+
+/// Return how many of the changed lines `a` and `b` have in common, relative to their total,
+/// as value between `0.0` and `1.0`.
+fn similarity(a: &[BString], b: &[BString]) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    let mut counts: HashMap<&BString, usize> = HashMap::new();
+    for line in a {
+        *counts.entry(line).or_default() += 1;
+    }
+    let mut common = 0;
+    for line in b {
+        if let Some(count) = counts.get_mut(line)
+            && *count > 0
+        {
+            *count -= 1;
+            common += 1;
+        }
+    }
+    (2 * common) as f32 / (a.len() + b.len()) as f32
+}
@@ -0,0 +1,24 @@
+#!/usr/bin/env bash
+
+set -eu -o pipefail
+
+source "${BASH_SOURCE[0]%/*}/shared.sh"
+
+git init
+seq 1 10 >file && git add file && git commit -m "base"
+setup_target_to_match_main
+
+# The version of A that was pushed.
+git checkout -b A main
+  seq 1 10 | sed 's/^8$/eight/' >file && git commit -am "change eight"
+  add_change_id_to_given_commit 1 @ | write_ref_safely .git/refs/heads/A
+  setup_remote_tracking A
+
+# Upstream inserts lines above the change, which moves its hunk.
+git checkout main
+  { printf "new 1\nnew 2\nnew 3\n"; seq 1 10; } >file && git commit -am "insert at the top"
+
+# The local version of A, rebased onto main without changing the commit.
+git checkout -B A main
+  git cherry-pick refs/remotes/origin/A
+  add_change_id_to_given_commit 1 @ | write_ref_safely .git/refs/heads/A
@@ -0,0 +1,26 @@
+#!/usr/bin/env bash
+
+set -eu -o pipefail
+
+source "${BASH_SOURCE[0]%/*}/shared.sh"
+
+git init
+commit-file base
+setup_target_to_match_main
+
+# The version of A that was pushed.
+git checkout -b A main
+  commit-file one
+  printf "two\n" >two && git add two && git commit -m "add two"
+  add_change_id_to_given_commit 2 @ | write_ref_safely .git/refs/heads/A
+  commit-file dropped
+  setup_remote_tracking A
+
+# The local version of A, rewritten after pushing.
+git reset --hard main
+  # Same patch, new message, no change-id: paired by similarity.
+  echo one >one && git add one && git commit -m "add one, reworded"
+  # Same change-id, amended patch.
+  printf "two\namended\n" >two && git add two && git commit -m "add two, amended"
+  add_change_id_to_given_commit 2 @ | write_ref_safely .git/refs/heads/A
+  commit-file new
@@ -5,7 +5,9 @@ mod discard_commit;
 mod insert_blank_commit;
 mod move_changes;
 mod move_commit;
+mod revert_commit;
 mod reword;
+mod split_commit;
 mod squash_commits;
 mod uncommit_changes;
 
@@ -0,0 +1,91 @@
+use bstr::ByteSlice as _;
+use but_rebase::graph_rebase::{
+    Editor,
+    mutate::{InsertSide, RelativeTo},
+};
+use but_testsupport::visualize_commit_graph_all;
+
+use crate::ref_info::with_workspace_commit::utils::named_writable_scenario_with_description_and_graph as writable_scenario;
+
+#[test]
+fn revert_commit_on_top_of_branch() -> anyhow::Result<()> {
+    let (_tmp, graph, repo, mut meta, _description) =
+        writable_scenario("reword-three-commits", |_| {})?;
+    let mut workspace = graph.into_workspace()?;
+    let two = repo.rev_parse_single("two")?.detach();
+    let three_ref: gix::refs::FullName = "refs/heads/three".try_into()?;
+
+    snapbox::assert_data_eq!(
+        visualize_commit_graph_all(&repo)?,
+        snapbox::str![[r#"
+* c9f444c (HEAD -> three) commit three
+* 16fd221 (origin/two, two) commit two
+* 8b426d0 (one) commit one
+
+"#]]
+    );
+
+    let editor = Editor::create(&mut workspace, &mut meta, &repo)?;
+    but_workspace::commit::revert_commit(
+        editor,
+        two,
+        RelativeTo::Reference(three_ref),
+        InsertSide::Below,
+    )?
+    .0
+    .materialize(Default::default())?;
+
+    let revert = repo.rev_parse_single("three")?.object()?.into_commit();
+    assert_eq!(
+        revert
+            .parent_ids()
+            .map(|id| id.detach())
+            .collect::<Vec<_>>(),
+        [repo.rev_parse_single("three~1")?.detach()],
+        "the revert lands on top of the branch, which is otherwise unchanged"
+    );
+    assert_eq!(
+        revert.message_raw()?.to_str_lossy(),
+        format!("Revert \"commit two\"\n\nThis reverts commit {two}.\n")
+    );
+
+    let tree = revert.tree()?;
+    assert!(tree.find_entry("one.txt").is_some());
+    assert!(
+        tree.find_entry("two.txt").is_none(),
+        "the changes of the reverted commit are undone"
+    );
+    assert!(tree.find_entry("three.txt").is_some());
+
+    Ok(())
+}
+
+#[test]
+fn revert_root_commit() -> anyhow::Result<()> {
+    let (_tmp, graph, repo, mut meta, _description) =
+        writable_scenario("reword-three-commits", |_| {})?;
+    let mut workspace = graph.into_workspace()?;
+    let one = repo.rev_parse_single("one")?.detach();
+    let three = repo.rev_parse_single("three")?.detach();
+
+    let editor = Editor::create(&mut workspace, &mut meta, &repo)?;
+    but_workspace::commit::revert_commit(
+        editor,
+        one,
+        RelativeTo::Commit(three),
+        InsertSide::Above,
+    )?
+    .0
+    .materialize(Default::default())?;
+
+    let revert = repo.rev_parse_single("three")?.object()?.into_commit();
+    let tree = revert.tree()?;
+    assert!(
+        tree.find_entry("one.txt").is_none(),
+        "reverting the root commit removes everything it added"
+    );
+    assert!(tree.find_entry("two.txt").is_some());
+    assert!(tree.find_entry("three.txt").is_some());
+
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use bstr::ByteSlice as _;
+use but_core::DiffSpec;
+use but_rebase::graph_rebase::{Editor, LookupStep as _};
+use but_testsupport::visualize_commit_graph_all;
+use but_workspace::commit::{CommitSplitPiece, split_commit};
+use gix::prelude::ObjectIdExt as _;
+
+use crate::ref_info::with_workspace_commit::utils::named_writable_scenario_with_description_and_graph as writable_scenario;
+
+fn piece(paths: &[&str], message: &str) -> CommitSplitPiece {
+    CommitSplitPiece {
+        changes: paths
+            .iter()
+            .map(|path| DiffSpec {
+                previous_path: None,
+                path: (*path).into(),
+                hunk_headers: vec![],
+            })
+            .collect(),
+        message: message.into(),
+    }
+}
+
+fn message(repo: &gix::Repository, id: gix::ObjectId) -> anyhow::Result<String> {
+    Ok(repo
+        .find_commit(id)?
+        .message_raw()?
+        .to_str_lossy()
+        .into_owned())
+}
+
+#[test]
+fn split_commit_below_other_commits() -> anyhow::Result<()> {
+    let (_tmp, graph, repo, mut meta, _description) =
+        writable_scenario("reword-three-commits", |_| {})?;
+    let mut workspace = graph.into_workspace()?;
+    let one = repo.rev_parse_single("one")?.detach();
+    let original_change_id = but_core::Commit::from_id(one.attach(&repo))?.change_id();
+
+    snapbox::assert_data_eq!(
+        visualize_commit_graph_all(&repo)?,
+        snapbox::str![[r#"
+* c9f444c (HEAD -> three) commit three
+* 16fd221 (origin/two, two) commit two
+* 8b426d0 (one) commit one
+
+"#]]
+    );
+
+    let editor = Editor::create(&mut workspace, &mut meta, &repo)?;
+    let outcome = split_commit(
+        editor,
+        one,
+        vec![
+            piece(&[], "add gitignore"),
+            piece(&["one.txt"], "add one.txt"),
+        ],
+        0,
+    )?;
+    let materialized = outcome.rebase.materialize(Default::default())?;
+    let pieces = outcome
+        .piece_selectors
+        .into_iter()
+        .map(|selector| materialized.lookup_pick(selector))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    assert_eq!(pieces.len(), 2);
+    assert_eq!(
+        repo.rev_parse_single("one")?.detach(),
+        pieces[1],
+        "the branch points to the last piece"
+    );
+    assert_eq!(
+        repo.rev_parse_single("two~1")?.detach(),
+        pieces[1],
+        "descendants are rebased onto the last piece"
+    );
+    assert_eq!(message(&repo, pieces[0])?, "add gitignore");
+    assert_eq!(message(&repo, pieces[1])?, "add one.txt");
+
+    let first = repo.find_commit(pieces[0])?;
+    assert_eq!(
+        first.parent_ids().count(),
+        0,
+        "the first piece takes the place of the split commit"
+    );
+    let first_tree = first.tree()?;
+    assert!(first_tree.find_entry(".gitignore").is_some());
+    assert!(
+        first_tree.find_entry("one.txt").is_none(),
+        "changes selected by later pieces are removed from the first piece"
+    );
+    assert_eq!(
+        repo.find_commit(pieces[1])?.tree_id()?,
+        repo.find_commit(one)?.tree_id()?,
+        "the last piece has the tree of the split commit"
+    );
+
+    assert_eq!(
+        but_core::Commit::from_id(pieces[0].attach(&repo))?.change_id(),
+        original_change_id,
+        "the first piece keeps the change-id"
+    );
+    assert_ne!(
+        but_core::Commit::from_id(pieces[1].attach(&repo))?.change_id(),
+        original_change_id,
+        "other pieces get their own change-id"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn split_commit_rejects_empty_pieces() -> anyhow::Result<()> {
+    let (_tmp, graph, repo, mut meta, _description) =
+        writable_scenario("reword-three-commits", |_| {})?;
+    let mut workspace = graph.into_workspace()?;
+    let two = repo.rev_parse_single("two")?.detach();
+
+    let editor = Editor::create(&mut workspace, &mut meta, &repo)?;
+    let err = split_commit(
+        editor,
+        two,
+        vec![piece(&[], "rest"), piece(&["two.txt"], "everything")],
+        0,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Piece 1 of the split commit wouldn't contain any changes"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn split_commit_needs_at_least_two_pieces() -> anyhow::Result<()> {
+    let (_tmp, graph, repo, mut meta, _description) =
+        writable_scenario("reword-three-commits", |_| {})?;
+    let mut workspace = graph.into_workspace()?;
+    let two = repo.rev_parse_single("two")?.detach();
+
+    let editor = Editor::create(&mut workspace, &mut meta, &repo)?;
+    let err = split_commit(editor, two, vec![piece(&["two.txt"], "only")], 0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "A commit must be split into at least two pieces"
+    );
+
+    Ok(())
+}
@@ -7,6 +7,7 @@ mod flatten_diff_specs;
 mod graph;
 mod init;
 mod push;
+mod range_diff;
 mod ref_info;
 mod shallow_clone;
 mod target_history;
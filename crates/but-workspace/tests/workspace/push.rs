@@ -265,3 +265,38 @@ fn force_push_protection_is_observed_when_pushing_top_branch() -> anyhow::Result
 
     Ok(())
 }
+
+#[test]
+fn pushing_a_stack_updates_all_branches_or_none() -> anyhow::Result<()> {
+    let (tmp, repo, meta) = fixture("push-requiring-force")?;
+
+    let err = push(&repo, &meta, r("refs/heads/top"), false, false, false)
+        .expect_err("the rewritten bottom branch can't be pushed without force");
+    let err = format!("{err:#}");
+    assert!(
+        err.contains("refs/heads/top [rejected] (atomic push failed)"),
+        "the top branch is rejected along with the bottom branch: {err}"
+    );
+    let remote = gix::open(tmp.path().join("remote.git"))?;
+    assert!(
+        remote.try_find_reference("refs/heads/top")?.is_none(),
+        "nothing was pushed as the push is atomic"
+    );
+
+    let result = push(&repo, &meta, r("refs/heads/top"), true, false, false)?;
+    assert_eq!(
+        result
+            .ref_updates
+            .iter()
+            .map(|update| (update.destination.as_str(), update.kind))
+            .collect::<Vec<_>>(),
+        [
+            ("refs/heads/bottom", gitbutler_git::RefUpdateKind::Forced),
+            ("refs/heads/top", gitbutler_git::RefUpdateKind::Created),
+        ],
+        "the outcome of each ref is reported"
+    );
+    assert!(remote.try_find_reference("refs/heads/top")?.is_some());
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use bstr::ByteSlice as _;
+use but_workspace::range_diff::{RangeDiffPairing, RangeDiffStatus};
+
+use crate::utils::{CONTEXT_LINES, read_only_in_memory_scenario};
+
+#[test]
+fn pairs_by_change_id_then_by_similarity() -> anyhow::Result<()> {
+    let repo = read_only_in_memory_scenario("range-diff-rewritten-branch")?;
+    let old_tip = repo.rev_parse_single("origin/A")?.detach();
+    let new_tip = repo.rev_parse_single("A")?.detach();
+    let target = repo.rev_parse_single("main")?.detach();
+
+    let range_diff = but_workspace::range_diff(&repo, old_tip, new_tip, [target], CONTEXT_LINES)?;
+    let summary: Vec<_> = range_diff
+        .entries
+        .iter()
+        .map(|entry| {
+            (
+                entry.title.to_str_lossy().into_owned(),
+                entry.status,
+                entry.pairing,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                "add one, reworded".to_owned(),
+                RangeDiffStatus::Modified,
+                Some(RangeDiffPairing::Similarity)
+            ),
+            (
+                "add two, amended".to_owned(),
+                RangeDiffStatus::Modified,
+                Some(RangeDiffPairing::ChangeId)
+            ),
+            ("add dropped".to_owned(), RangeDiffStatus::Removed, None),
+            ("add new".to_owned(), RangeDiffStatus::Added, None),
+        ],
+        "removed commits stay after the commit they followed"
+    );
+
+    let reworded = range_diff.entries[0]
+        .interdiff
+        .as_ref()
+        .expect("modified entries have an interdiff");
+    assert_eq!(
+        reworded.to_str_lossy(),
+        "@@ -1,1 +1,1 @@\n-add one\n+add one, reworded\n",
+        "only the message changed"
+    );
+
+    let amended = range_diff.entries[1]
+        .interdiff
+        .as_ref()
+        .expect("modified entries have an interdiff");
+    assert!(
+        amended.contains_str("-add two\n+add two, amended\n"),
+        "the message change is part of the interdiff"
+    );
+    assert!(
+        amended.contains_str("++amended"),
+        "the added line shows up as added to the patch"
+    );
+    Ok(())
+}
+
+#[test]
+fn identical_ranges_are_unchanged() -> anyhow::Result<()> {
+    let repo = read_only_in_memory_scenario("range-diff-rewritten-branch")?;
+    let tip = repo.rev_parse_single("A")?.detach();
+    let target = repo.rev_parse_single("main")?.detach();
+
+    let range_diff = but_workspace::range_diff(&repo, tip, tip, [target], CONTEXT_LINES)?;
+    assert!(
+        range_diff.entries.is_empty(),
+        "commits reachable from both tips aren't part of either range"
+    );
+    Ok(())
+}
+
+#[test]
+fn moved_hunks_are_unchanged() -> anyhow::Result<()> {
+    let repo = read_only_in_memory_scenario("range-diff-rebased-over-insertion")?;
+    let old_tip = repo.rev_parse_single("origin/A")?.detach();
+    let new_tip = repo.rev_parse_single("A")?.detach();
+    let target = repo.rev_parse_single("main")?.detach();
+
+    let range_diff = but_workspace::range_diff(&repo, old_tip, new_tip, [target], CONTEXT_LINES)?;
+    let [entry] = range_diff.entries.as_slice() else {
+        panic!("expected a single entry, got {:?}", range_diff.entries);
+    };
+    assert_eq!(
+        entry.status,
+        RangeDiffStatus::Unchanged,
+        "lines inserted upstream move the hunk without changing the commit"
+    );
+    assert_eq!(entry.pairing, Some(RangeDiffPairing::ChangeId));
+    assert_eq!(entry.interdiff, None);
+    Ok(())
+}
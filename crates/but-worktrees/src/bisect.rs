@@ -0,0 +1,206 @@
+//! Find the first bad commit of a stack without touching the workspace.
+//!
+//! Each probed commit is checked out in a temporary linked worktree, so the `gitbutler/workspace`
+//! checkout and its uncommitted changes stay as they are while the test runs.
+
+use std::path::Path;
+
+use anyhow::{Result, bail};
+
+use crate::{
+    WorktreeId,
+    git::{git_checkout_detached, git_worktree_add, git_worktree_remove},
+};
+
+/// The commits to bisect, as returned by [`bisect_range()`].
+#[derive(Debug, Clone)]
+pub struct BisectRange {
+    /// The commit that is known to be good. It is never tested.
+    pub good: gix::ObjectId,
+    /// The commits on top of `good` along the first parent, oldest first.
+    /// The last one is the tip, which is expected to be bad.
+    pub commits: Vec<gix::ObjectId>,
+}
+
+/// What the test said about a single commit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BisectVerdict {
+    /// The commit doesn't have the problem.
+    Good,
+    /// The commit has the problem.
+    Bad,
+    /// The commit can't be tested, for instance because it doesn't build.
+    Skip,
+}
+
+/// A commit that was tested during [`bisect()`].
+#[derive(Debug, Clone, Copy)]
+pub struct BisectStep {
+    pub commit: gix::ObjectId,
+    pub verdict: BisectVerdict,
+}
+
+/// The result of [`bisect()`].
+#[derive(Debug, Clone)]
+pub struct BisectOutcome {
+    /// The first bad commit, or `None` if skipped commits made it impossible to tell.
+    pub first_bad: Option<gix::ObjectId>,
+    /// The commits that may be the first bad commit, oldest first.
+    /// It only contains `first_bad` if it is known.
+    pub candidates: Vec<gix::ObjectId>,
+    /// The tested commits, in the order they were tested.
+    pub steps: Vec<BisectStep>,
+}
+
+/// Return the commits of the stack containing the branch `refname`, from the tip of the branch
+/// down to `good`, or down to the base of the stack if `good` is `None`.
+///
+/// Branches stacked on top of `refname` are not included, but the ones below it are, so a
+/// problem introduced by a lower branch is found as well.
+pub fn bisect_range(
+    ws: &but_graph::Workspace,
+    refname: &gix::refs::FullNameRef,
+    good: Option<gix::ObjectId>,
+) -> Result<BisectRange> {
+    let Some((stack, segment)) = ws.find_segment_and_stack_by_refname(refname) else {
+        bail!("Branch not found in workspace");
+    };
+
+    let mut commits = Vec::new();
+    let mut found_good = false;
+    'segments: for stack_segment in stack
+        .segments
+        .iter()
+        .skip_while(|stack_segment| stack_segment.id != segment.id)
+    {
+        for commit in &stack_segment.commits {
+            if Some(commit.id) == good {
+                found_good = true;
+                break 'segments;
+            }
+            commits.push(commit.id);
+        }
+    }
+
+    let good = match good {
+        Some(good) if found_good || stack.base() == Some(good) => good,
+        Some(good) => bail!(
+            "Commit {good} is not below {} in its stack",
+            refname.shorten()
+        ),
+        None => match stack.base() {
+            Some(base) => base,
+            None => bail!(
+                "The stack of {} has no base, pass a known good commit instead",
+                refname.shorten()
+            ),
+        },
+    };
+    if commits.is_empty() {
+        bail!(
+            "There are no commits between {good} and {} to bisect",
+            refname.shorten()
+        );
+    }
+    commits.reverse();
+
+    Ok(BisectRange { good, commits })
+}
+
+/// Find the first bad commit in `range` by calling `test` with the worktree directory and the
+/// commit checked out in it, halving the range of candidates each time.
+///
+/// The commits are checked out one by one in a temporary worktree under `data_dir`, which is
+/// removed again when done, whether the bisection succeeded or not.
+/// The tip is tested first to be sure there is something to find, while `range.good` is trusted.
+/// Commits for which `test` returns [`BisectVerdict::Skip`] are avoided by testing their closest
+/// neighbours instead.
+pub fn bisect(
+    repo: &gix::Repository,
+    data_dir: &Path,
+    range: &BisectRange,
+    test: impl FnMut(&Path, gix::ObjectId) -> Result<BisectVerdict>,
+) -> Result<BisectOutcome> {
+    let Some(&tip) = range.commits.last() else {
+        bail!("There are no commits to bisect");
+    };
+
+    let id = WorktreeId::generate();
+    let path = data_dir.join("bisect").join(id.to_os_str());
+    git_worktree_add(repo.common_dir(), &path, tip)?;
+
+    let outcome = bisect_in_worktree(&path, range, test);
+    let removed = git_worktree_remove(repo.common_dir(), &id, true);
+    match (outcome, removed) {
+        (Ok(outcome), Ok(())) => Ok(outcome),
+        (Ok(_), Err(err)) => Err(err),
+        (Err(err), removed) => {
+            if let Err(remove_err) = removed {
+                tracing::warn!(?remove_err, "Failed to remove bisect worktree");
+            }
+            Err(err)
+        }
+    }
+}
+
+fn bisect_in_worktree(
+    path: &Path,
+    range: &BisectRange,
+    mut test: impl FnMut(&Path, gix::ObjectId) -> Result<BisectVerdict>,
+) -> Result<BisectOutcome> {
+    let commits = &range.commits;
+    let mut steps = Vec::new();
+    let mut run = |position: usize| -> Result<BisectVerdict> {
+        let commit = commits[position - 1];
+        git_checkout_detached(path, commit)?;
+        let verdict = test(path, commit)?;
+        steps.push(BisectStep { commit, verdict });
+        Ok(verdict)
+    };
+
+    // Positions are offsets into `commits` plus one, so that `0` is the good commit.
+    let mut good = 0;
+    let mut bad = commits.len();
+    match run(bad)? {
+        BisectVerdict::Bad => {}
+        BisectVerdict::Good => bail!(
+            "The tip {} is good, there is nothing to bisect",
+            commits[bad - 1]
+        ),
+        BisectVerdict::Skip => bail!(
+            "The tip {} can't be tested, there is nothing to bisect",
+            commits[bad - 1]
+        ),
+    }
+
+    let mut skipped = vec![false; commits.len() + 1];
+    while bad - good > 1 {
+        let Some(position) = next_position(good, bad, &skipped) else {
+            break;
+        };
+        match run(position)? {
+            BisectVerdict::Good => good = position,
+            BisectVerdict::Bad => bad = position,
+            BisectVerdict::Skip => skipped[position] = true,
+        }
+    }
+
+    let candidates = commits[good..bad].to_vec();
+    Ok(BisectOutcome {
+        first_bad: (bad - good == 1).then_some(commits[bad - 1]),
+        candidates,
+        steps,
+    })
+}
+
+/// Return the untested position between `good` and `bad` closest to their middle, or `None` if
+/// all of them were skipped.
+fn next_position(good: usize, bad: usize, skipped: &[bool]) -> Option<usize> {
+    let middle = good + (bad - good) / 2;
+    (0..bad - good).find_map(|distance| {
+        [middle.checked_sub(distance), Some(middle + distance)]
+            .into_iter()
+            .flatten()
+            .find(|&position| good < position && position < bad && !skipped[position])
+    })
+}
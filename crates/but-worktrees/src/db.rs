@@ -3,6 +3,8 @@
 //! Stores metadata in `.git/worktrees/<id>/` alongside Git's own worktree metadata:
 //! - `gitbutler-created-from`: The git reference this worktree was created from
 //! - `gitbutler-base`: The base commit OID for cherry-picking
+//! - `gitbutler-edited-commit`: The workspace commit edited in this worktree, if any
+//! - `gitbutler-checked-out-tree`: The tree that was checked out to edit the commit, if any
 
 use std::path::PathBuf;
 
@@ -14,6 +16,8 @@ use crate::{WorktreeId, WorktreeMeta};
 const CREATED_FROM_FILE: &str = "gitbutler-created-from";
 // TODO: this is a root-ref, capitalise it and use `gix::refs::file::Store` to read and write it, directly by opening the worktree repo.
 const BASE_FILE: &str = "gitbutler-base";
+const EDITED_COMMIT_FILE: &str = "gitbutler-edited-commit";
+const CHECKED_OUT_TREE_FILE: &str = "gitbutler-checked-out-tree";
 
 /// Get the possibly non-existing `.git/worktrees/<id>/` directory for a given worktree ID.
 fn worktree_gitdir(main_repo: &gix::Repository, id: &WorktreeId) -> PathBuf {
@@ -43,6 +47,14 @@ pub fn save_worktree_meta(main_repo: &gix::Repository, worktree: WorktreeMeta) -
     )
     .context("Failed to write gitbutler-base file")?;
 
+    if let Some(edited_commit) = worktree.edited_commit {
+        std::fs::write(
+            git_dir.join(EDITED_COMMIT_FILE),
+            edited_commit.to_hex().to_string().as_bytes(),
+        )
+        .context("Failed to write gitbutler-edited-commit file")?;
+    }
+
     Ok(())
 }
 
@@ -71,13 +83,53 @@ pub fn get_worktree_meta(repo: &gix::Repository, id: &WorktreeId) -> Result<Opti
         None
     };
 
+    // Read edited commit if present
+    let edited_commit_file = git_dir.join(EDITED_COMMIT_FILE);
+    let edited_commit = if edited_commit_file.exists() {
+        let commit_bytes = std::fs::read(&edited_commit_file)
+            .context("Failed to read gitbutler-edited-commit file")?;
+        Some(gix::ObjectId::from_hex(commit_bytes.trim()).context("Invalid edited commit OID")?)
+    } else {
+        None
+    };
+
     Ok(Some(WorktreeMeta {
         id: id.clone(),
         created_from_ref,
         base,
+        edited_commit,
     }))
 }
 
+/// Save `tree` as what was checked out into the worktree with `id`.
+pub fn save_checked_out_tree(
+    main_repo: &gix::Repository,
+    id: &WorktreeId,
+    tree: gix::ObjectId,
+) -> Result<()> {
+    std::fs::write(
+        worktree_gitdir(main_repo, id).join(CHECKED_OUT_TREE_FILE),
+        tree.to_hex().to_string().as_bytes(),
+    )
+    .context("Failed to write gitbutler-checked-out-tree file")
+}
+
+/// Read the tree saved with [`save_checked_out_tree()`], or `None` if there is none.
+pub fn get_checked_out_tree(
+    repo: &gix::Repository,
+    id: &WorktreeId,
+) -> Result<Option<gix::ObjectId>> {
+    let tree_file = worktree_gitdir(repo, id).join(CHECKED_OUT_TREE_FILE);
+    if !tree_file.exists() {
+        return Ok(None);
+    }
+    let tree_bytes =
+        std::fs::read(&tree_file).context("Failed to read gitbutler-checked-out-tree file")?;
+    Ok(Some(
+        gix::ObjectId::from_hex(tree_bytes.trim()).context("Invalid checked out tree OID")?,
+    ))
+}
+
 /// List all worktrees with GitButler metadata.
 pub fn list_worktree_meta(repo: &gix::Repository) -> Result<Vec<WorktreeMeta>> {
     let mut result = Vec::new();
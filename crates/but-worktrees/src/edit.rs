@@ -0,0 +1,87 @@
+//! Linked worktrees in which a single workspace commit is edited, so the `gitbutler/workspace`
+//! checkout and its uncommitted changes stay usable meanwhile.
+//!
+//! Only the worktree itself is managed here. What is checked out into it, and how the edit is
+//! brought back into the workspace, is up to the caller.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context as _, Result};
+
+use crate::{
+    Worktree, WorktreeId, WorktreeMeta,
+    db::{get_checked_out_tree, get_worktree_meta, save_checked_out_tree, save_worktree_meta},
+    git::git_worktree_add,
+};
+
+/// Create a linked worktree under `data_dir` to edit `edited_commit` in, with `base` checked out
+/// as detached `HEAD`.
+///
+/// `base` is typically `edited_commit` itself, or the commit to resolve its conflicts on top of
+/// if it is conflicted.
+pub fn edit_worktree_new(
+    repo: &gix::Repository,
+    data_dir: &Path,
+    edited_commit: gix::ObjectId,
+    base: gix::ObjectId,
+) -> Result<Worktree> {
+    let id = WorktreeId::generate();
+
+    let path = edit_workdir(data_dir, &id);
+    git_worktree_add(repo.common_dir(), &path, base)?;
+
+    let path = path.canonicalize()?;
+
+    save_worktree_meta(
+        repo,
+        WorktreeMeta {
+            id: id.clone(),
+            created_from_ref: None,
+            base,
+            edited_commit: Some(edited_commit),
+        },
+    )?;
+
+    Ok(Worktree {
+        id,
+        path,
+        created_from_ref: None,
+        base: Some(base),
+    })
+}
+
+/// Return the commit edited in the worktree with `id`, or `None` if the worktree wasn't
+/// created by [`edit_worktree_new()`].
+pub fn edited_commit(repo: &gix::Repository, id: &WorktreeId) -> Result<Option<gix::ObjectId>> {
+    Ok(get_worktree_meta(repo, id)?.and_then(|meta| meta.edited_commit))
+}
+
+/// Remember `tree` as what was checked out into the edit worktree with `id`, to later tell if the
+/// worktree was changed.
+pub fn set_checked_out_tree(
+    repo: &gix::Repository,
+    id: &WorktreeId,
+    tree: gix::ObjectId,
+) -> Result<()> {
+    save_checked_out_tree(repo, id, tree)
+}
+
+/// Return the tree set with [`set_checked_out_tree()`], or `None` if it wasn't set.
+pub fn checked_out_tree(repo: &gix::Repository, id: &WorktreeId) -> Result<Option<gix::ObjectId>> {
+    get_checked_out_tree(repo, id)
+}
+
+/// Open the repository of the linked worktree with `id`, which must be a worktree of `repo`.
+pub fn open_worktree_repo(repo: &gix::Repository, id: &WorktreeId) -> Result<gix::Repository> {
+    let proxy = repo
+        .worktrees()?
+        .into_iter()
+        .find(|proxy| proxy.id() == id.as_bstr())
+        .with_context(|| format!("Worktree {id} does not exist"))?;
+    Ok(proxy.into_repo()?)
+}
+
+/// The path at which the linked worktree to edit a commit in should be checked out to.
+fn edit_workdir(data_dir: &Path, id: &WorktreeId) -> PathBuf {
+    data_dir.join("edit").join(id.to_os_str())
+}
@@ -67,3 +67,31 @@ pub(crate) fn git_worktree_remove(project_path: &Path, id: &WorktreeId, force: b
         )
     }
 }
+
+/// Checks out `commit` with a detached `HEAD` in the worktree at `path`, discarding local changes.
+pub(crate) fn git_checkout_detached(path: &Path, commit: gix::ObjectId) -> Result<()> {
+    let output =
+        std::process::Command::from(gix::command::prepare(gix::path::env::exe_invocation()))
+            .current_dir(path)
+            .arg("checkout")
+            .arg("--detach")
+            .arg("--force")
+            .arg(commit.to_string())
+            .stderr(std::process::Stdio::piped())
+            .output()?;
+
+    tracing::debug!(
+        stdout = %String::from_utf8_lossy(&output.stdout),
+        stderr = %String::from_utf8_lossy(&output.stderr),
+        "git checkout"
+    );
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        bail!(
+            "Failed to check out {commit}\n\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        )
+    }
+}
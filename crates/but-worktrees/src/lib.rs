@@ -9,8 +9,10 @@ use anyhow::{Context as _, Result};
 use bstr::BString;
 use serde::{Deserialize, Serialize};
 
+pub mod bisect;
 pub(crate) mod db;
 pub mod destroy;
+pub mod edit;
 pub(crate) mod git;
 pub mod integrate;
 pub mod list;
@@ -42,7 +44,8 @@ impl WorktreeId {
         Ok(Self(gix::path::os_str_into_bstr(basename)?.to_owned()))
     }
 
-    fn as_bstr(&self) -> &bstr::BStr {
+    /// The name of the worktree, which is also the name of its directory in `.git/worktrees/`.
+    pub fn as_bstr(&self) -> &bstr::BStr {
         self.0.as_ref()
     }
 
@@ -67,6 +70,8 @@ pub(crate) struct WorktreeMeta {
     pub created_from_ref: Option<gix::refs::FullName>,
     /// The base which we will use in a cherry-pick.
     pub base: gix::ObjectId,
+    /// The workspace commit that is edited in this worktree, if it was created by [`edit::edit_worktree_new()`].
+    pub edited_commit: Option<gix::ObjectId>,
 }
 
 /// A struct representing worktrees for the frontend
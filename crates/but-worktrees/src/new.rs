@@ -38,6 +38,7 @@ pub fn worktree_new(
         id: id.clone(),
         created_from_ref: Some(refname.to_owned()),
         base: to_checkout.detach(),
+        edited_commit: None,
     };
 
     save_worktree_meta(repo, meta)?;
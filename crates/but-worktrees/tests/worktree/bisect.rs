@@ -0,0 +1,90 @@
+use but_worktrees::bisect::{BisectVerdict, bisect, bisect_range};
+
+use crate::util::test_ctx;
+
+#[test]
+fn finds_first_bad_commit_in_lower_branch() -> anyhow::Result<()> {
+    let test_ctx = test_ctx("stacked-and-parallel")?;
+    let ctx = &test_ctx.ctx;
+    let guard = ctx.shared_worktree_access();
+    let (repo, ws, _) = ctx.workspace_and_db_with_perm(guard.read_permission())?;
+
+    let feature_b = gix::refs::FullName::try_from("refs/heads/feature-b")?;
+    let range = bisect_range(&ws, feature_b.as_ref(), None)?;
+    assert_eq!(
+        range.commits.len(),
+        4,
+        "the range reaches down through feature-a to the stack base"
+    );
+    assert_eq!(range.good, repo.rev_parse_single("main")?.detach());
+
+    let outcome = bisect(&repo, &ctx.project_data_dir(), &range, |path, _commit| {
+        let content = std::fs::read_to_string(path.join("file.txt"))?;
+        Ok(if content.contains("feature-a line 2") {
+            BisectVerdict::Bad
+        } else {
+            BisectVerdict::Good
+        })
+    })?;
+
+    let expected = repo.rev_parse_single("feature-a")?.detach();
+    assert_eq!(outcome.first_bad, Some(expected));
+    assert_eq!(outcome.candidates, [expected]);
+    assert_eq!(
+        outcome.steps.first().map(|step| step.commit),
+        range.commits.last().copied(),
+        "the tip is tested first"
+    );
+    assert!(
+        repo.worktrees()?.is_empty(),
+        "the temporary worktree is removed"
+    );
+    Ok(())
+}
+
+#[test]
+fn skipped_commits_leave_candidates() -> anyhow::Result<()> {
+    let test_ctx = test_ctx("stacked-and-parallel")?;
+    let ctx = &test_ctx.ctx;
+    let guard = ctx.shared_worktree_access();
+    let (repo, ws, _) = ctx.workspace_and_db_with_perm(guard.read_permission())?;
+
+    let feature_b = gix::refs::FullName::try_from("refs/heads/feature-b")?;
+    let good = repo.rev_parse_single("feature-a")?.detach();
+    let range = bisect_range(&ws, feature_b.as_ref(), Some(good))?;
+    assert_eq!(range.commits.len(), 2, "only feature-b is bisected");
+
+    let tip = range.commits[1];
+    let outcome = bisect(&repo, &ctx.project_data_dir(), &range, |_path, commit| {
+        Ok(if commit == tip {
+            BisectVerdict::Bad
+        } else {
+            BisectVerdict::Skip
+        })
+    })?;
+
+    assert_eq!(outcome.first_bad, None);
+    assert_eq!(outcome.candidates, range.commits);
+    Ok(())
+}
+
+#[test]
+fn good_tip_is_an_error() -> anyhow::Result<()> {
+    let test_ctx = test_ctx("stacked-and-parallel")?;
+    let ctx = &test_ctx.ctx;
+    let guard = ctx.shared_worktree_access();
+    let (repo, ws, _) = ctx.workspace_and_db_with_perm(guard.read_permission())?;
+
+    let feature_c = gix::refs::FullName::try_from("refs/heads/feature-c")?;
+    let range = bisect_range(&ws, feature_c.as_ref(), None)?;
+    let err = bisect(&repo, &ctx.project_data_dir(), &range, |_, _| {
+        Ok(BisectVerdict::Good)
+    })
+    .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("is good, there is nothing to bisect")
+    );
+    assert!(repo.worktrees()?.is_empty());
+    Ok(())
+}
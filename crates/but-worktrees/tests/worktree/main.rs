@@ -79,6 +79,7 @@ mod util {
     }
 }
 
+mod bisect;
 mod worktree_new;
 
 mod worktree_list {
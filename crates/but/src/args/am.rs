@@ -0,0 +1,45 @@
+//! Arguments for `am`.
+
+#![deny(missing_docs)]
+
+use std::path::PathBuf;
+
+use crate::args::atoms::{AllowMergedArg, CliIdArg};
+
+/// Apply a series of patches from a mailbox to a branch.
+///
+/// Each patch of the mbox file is turned into a commit with its author and
+/// message, like `git am` does, without leaving the workspace. Patches that
+/// don't apply to the workspace are applied to the files they were created
+/// against, so they end up as conflicted commits to resolve later instead of
+/// stopping the import.
+///
+/// If there are no branches applied, a new branch is created for the commits.
+/// If there is only one stack of branches applied, the commits are placed at
+/// the tip of that stack. Otherwise, use `--branch` to pick the branch.
+///
+/// ## Examples
+///
+/// Apply patches onto a new branch:
+///
+/// ```text
+/// but am series.mbox --branch from-the-list
+/// ```
+#[derive(Debug, clap::Parser)]
+#[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+pub struct Platform {
+    /// The mbox file with the patches, as written by `git format-patch`.
+    pub mailbox: PathBuf,
+
+    /// Place the commits on the branch `BRANCH`.
+    ///
+    /// If `BRANCH` does not exist, it is created as an unstacked branch.
+    ///
+    /// If `BRANCH` is omitted, an unstacked branch with a generated name is created.
+    #[clap(short, long, value_name = "BRANCH")]
+    pub branch: Option<Option<CliIdArg>>,
+
+    #[clap(flatten)]
+    #[allow(missing_docs)]
+    pub allow_merged: AllowMergedArg,
+}
@@ -25,3 +25,14 @@ pub struct AllowMergedArg {
     #[clap(long)]
     pub allow_merged: bool,
 }
+
+/// Escape hatch for the commit message policy, shared by commands that write commit messages.
+#[derive(Debug, Clone, Copy, Default, clap::Args)]
+pub struct SkipMessagePolicyArg {
+    /// Bypass the commit message policy of the project.
+    ///
+    /// The policy is configured with the `gitbutler.commitPolicy.*` Git configuration keys, and
+    /// is otherwise checked before any commit is written.
+    #[clap(long)]
+    pub skip_message_policy: bool,
+}
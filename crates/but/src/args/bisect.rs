@@ -0,0 +1,56 @@
+//! Arguments for `bisect`.
+
+#![deny(missing_docs)]
+
+use std::ffi::OsString;
+
+use crate::args::atoms::CliIdArg;
+
+/// Find the commit of a stack that introduced a problem.
+///
+/// Runs a command on the commits of a branch and the branches below it in its
+/// stack, halving the range of candidates each time, until the first bad
+/// commit is found. Each commit is checked out in a temporary worktree, so the
+/// workspace and its uncommitted changes remain untouched.
+///
+/// Like with `git bisect run`, the exit code of the command decides:
+/// 0 means good, 125 means the commit can't be tested and is skipped, and any
+/// other code up to 127 means bad. Higher codes abort the bisection.
+///
+/// The tip of the branch is tested first and must be bad. The base of the
+/// stack is assumed to be good, unless another good commit is passed with
+/// `--good`.
+///
+/// ## Examples
+///
+/// Find the commit that broke the tests of a branch:
+///
+/// ```text
+/// but bisect my-feature -- cargo test
+/// ```
+///
+/// Only consider the commits above a known good commit:
+///
+/// ```text
+/// but bisect my-feature --good a1b2c3 -- ./check.sh
+/// ```
+#[derive(Debug, clap::Parser)]
+#[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+pub struct Platform {
+    /// The branch whose tip has the problem.
+    pub branch: CliIdArg,
+
+    /// A commit below the branch tip that is known to be good.
+    ///
+    /// Defaults to the base of the stack.
+    #[clap(long, value_name = "COMMIT")]
+    pub good: Option<CliIdArg>,
+
+    /// Show the output of the command for each tested commit.
+    #[clap(long, short = 'v')]
+    pub verbose: bool,
+
+    /// The command to run in the worktree, after `--`.
+    #[clap(last = true, required = true, value_name = "COMMAND")]
+    pub command: Vec<OsString>,
+}
@@ -95,8 +95,9 @@ pub enum Subcommands {
     ///
     /// You can also choose to fetch and display review information,
     /// show files modified in each commit with line counts, generate
-    /// an AI summary of the branch changes, and check if the branch
-    /// merges cleanly into upstream.
+    /// an AI summary of the branch changes, check if the branch
+    /// merges cleanly into upstream, and compare it with its
+    /// remote-tracking branch.
     ///
     #[cfg(feature = "legacy")]
     Show {
@@ -114,6 +115,13 @@ pub enum Subcommands {
         /// Check if the branch merges cleanly into upstream and identify conflicting commits
         #[clap(long)]
         check: bool,
+        /// Compare the branch with its remote-tracking branch, commit by commit
+        ///
+        /// Commits are paired by change-id, or by the similarity of their patches otherwise,
+        /// and the changes to each paired commit are shown as diff of their patches.
+        /// This shows what a force-push would change on the remote.
+        #[clap(long)]
+        range_diff: bool,
     },
 
     /// Deprecated: use `but move` instead
@@ -2,7 +2,7 @@
 
 #![deny(missing_docs)]
 
-use crate::args::atoms::{AllowMergedArg, CliIdArg};
+use crate::args::atoms::{AllowMergedArg, CliIdArg, SkipMessagePolicyArg};
 
 /// Create a commit.
 ///
@@ -92,6 +92,10 @@ pub struct Platform {
     #[clap(flatten)]
     #[allow(missing_docs)]
     pub allow_merged: AllowMergedArg,
+
+    #[clap(flatten)]
+    #[allow(missing_docs)]
+    pub skip_message_policy: SkipMessagePolicyArg,
 }
 
 /// Example invocations appended to a `but commit` parse error.
@@ -0,0 +1,44 @@
+use std::path::PathBuf;
+
+#[derive(Debug, clap::Parser)]
+pub struct Platform {
+    #[clap(subcommand)]
+    pub cmd: Subcommands,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Subcommands {
+    /// Collect anonymized diagnostics into a zip archive to attach to a bug report.
+    ///
+    /// The archive contains application settings and GitButler-specific git configuration
+    /// with identifying values and secrets redacted, the commit graph with all names,
+    /// messages and hashes anonymized, the kinds of the most recent operations, the
+    /// schema versions of the project database and the tail of the most recent logs.
+    /// A `manifest.json` lists every included file.
+    ///
+    /// Use `--preview` to see exactly which files would be shared, without writing anything.
+    ///
+    /// ## Examples
+    ///
+    /// List what would be included:
+    ///
+    /// ```text
+    /// but debug bundle --preview
+    /// ```
+    ///
+    /// Write the archive to a specific location:
+    ///
+    /// ```text
+    /// but debug bundle -o /tmp/bundle.zip
+    /// ```
+    ///
+    #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+    Bundle {
+        /// List the files that would be included, without writing the archive.
+        #[clap(long)]
+        preview: bool,
+        /// Where to write the archive. Defaults to a timestamped file in the current directory.
+        #[clap(long, short = 'o', conflicts_with = "preview")]
+        output: Option<PathBuf>,
+    },
+}
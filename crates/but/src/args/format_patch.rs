@@ -0,0 +1,55 @@
+//! Arguments for `format-patch`.
+
+#![deny(missing_docs)]
+
+use std::path::PathBuf;
+
+use crate::args::atoms::CliIdArg;
+
+/// Export a branch as a series of patches for email.
+///
+/// Writes each commit of the branch and of the branches below it in its stack
+/// as numbered patch in mbox format, like `git format-patch` does, oldest
+/// first. The patches can be sent with `git send-email` and applied with
+/// `git am` or `but am`.
+///
+/// ## Examples
+///
+/// Write the patches of a branch into the current directory:
+///
+/// ```text
+/// but format-patch my-feature
+/// ```
+///
+/// Write the patches along with a cover letter into `outgoing/`:
+///
+/// ```text
+/// but format-patch my-feature --cover-letter -o outgoing
+/// ```
+///
+/// Describe the series in the cover letter:
+///
+/// ```text
+/// but format-patch my-feature --description "Add my feature"
+/// ```
+#[derive(Debug, clap::Parser)]
+#[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+pub struct Platform {
+    /// The branch to export.
+    pub branch: CliIdArg,
+
+    /// Also write a cover letter, with placeholders for its subject and body unless
+    /// `--description` is given.
+    #[clap(long)]
+    pub cover_letter: bool,
+
+    /// The description of the series for the cover letter, its first line being the subject.
+    ///
+    /// Implies `--cover-letter`.
+    #[clap(long, value_name = "TEXT")]
+    pub description: Option<String>,
+
+    /// The directory to write the patches into, created if needed.
+    #[clap(short, long, value_name = "DIR", default_value = ".")]
+    pub output_directory: PathBuf,
+}
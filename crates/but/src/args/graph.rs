@@ -0,0 +1,84 @@
+#[derive(Debug, clap::Parser)]
+pub struct Platform {
+    #[clap(subcommand)]
+    pub cmd: Subcommands,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum Subcommands {
+    /// Export the branch topology of the repository.
+    ///
+    /// Writes the segment graph that `but` sees to stdout, ready to be pasted into
+    /// design documents or bug reports. With `--json`, the format-independent graph
+    /// document is written instead, regardless of `--format`.
+    ///
+    /// Use `--anonymize` to replace all branch and remote names with generated ones
+    /// before sharing the output. Commit hashes are kept.
+    ///
+    /// ## Examples
+    ///
+    /// Export the workspace as Mermaid flowchart:
+    ///
+    /// ```text
+    /// but graph export --workspace
+    /// ```
+    ///
+    /// Export every commit as Graphviz file and render it:
+    ///
+    /// ```text
+    /// but graph export --format dot --detail commits | dot -Tsvg > graph.svg
+    /// ```
+    ///
+    #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+    Export {
+        /// The format to write.
+        #[clap(long, short = 'f', value_enum, default_value_t = Format::Mermaid)]
+        format: Format,
+        /// Whether to show segments or individual commits.
+        #[clap(long, short = 'd', value_enum, default_value_t = Detail::Segments)]
+        detail: Detail,
+        /// Replace branch and remote names with generated ones.
+        #[clap(long)]
+        anonymize: bool,
+        /// Only export the segments that are part of the workspace.
+        #[clap(long, short = 'w')]
+        workspace: bool,
+    },
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Format {
+    /// The Graphviz `dot` language.
+    Dot,
+    /// A Mermaid flowchart.
+    Mermaid,
+    /// A JSON document describing segments and their connections.
+    Json,
+}
+
+impl From<Format> for but_graph::export::Format {
+    fn from(value: Format) -> Self {
+        match value {
+            Format::Dot => but_graph::export::Format::Dot,
+            Format::Mermaid => but_graph::export::Format::Mermaid,
+            Format::Json => but_graph::export::Format::Json,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Detail {
+    /// Show one node per segment.
+    Segments,
+    /// Show one node per commit, grouped by segment.
+    Commits,
+}
+
+impl From<Detail> for but_graph::export::Detail {
+    fn from(value: Detail) -> Self {
+        match value {
+            Detail::Segments => but_graph::export::Detail::Segments,
+            Detail::Commits => but_graph::export::Detail::Commits,
+        }
+    }
+}
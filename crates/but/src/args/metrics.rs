@@ -17,6 +17,7 @@ pub enum CommandName {
     Diff2,
     Edit,
     Show,
+    GraphExport,
     Commit,
     CommitEmpty,
     Push,
@@ -61,12 +62,21 @@ pub enum CommandName {
     UpdateCheck,
     UpdateSuppress,
     UpdateInstall,
+    UpdateRollback,
     Land,
     SkillInstall,
     SkillCheck,
     AgentSetup,
     Pick,
+    Revert,
+    Split,
+    Bisect,
+    Blame,
+    FormatPatch,
+    Am,
     Clean,
+    Doctor,
+    DebugBundle,
     External,
     #[default]
     Unknown,
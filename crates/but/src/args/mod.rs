@@ -292,6 +292,48 @@ pub enum Subcommands {
         verbose: bool,
     },
 
+    /// Shows where each line of a file in the worktree came from.
+    ///
+    /// Every line is attributed to one of:
+    ///
+    /// - the worktree, if the line isn't committed yet,
+    /// - the commit and branch in the workspace that introduced it,
+    /// - or the commit outside of the workspace that introduced it, typically on
+    ///   the target branch.
+    ///
+    /// Lines show the CLI ID of their commit, or of the uncommitted file, so they
+    /// can be acted on with other commands.
+    ///
+    /// ## Examples
+    ///
+    /// ```text
+    /// but blame src/main.rs
+    /// ```
+    ///
+    #[cfg(feature = "legacy")]
+    #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+    Blame {
+        /// The path of the file, relative to the root of the repository.
+        path: String,
+    },
+
+    /// Commands for inspecting the commit graph as GitButler sees it.
+    ///
+    /// The graph is made of segments, runs of commits that are owned by a single
+    /// branch, which are connected to each other. Stacks in the workspace are a view
+    /// of this graph.
+    ///
+    /// ## Examples
+    ///
+    /// Export the workspace topology as Mermaid flowchart:
+    ///
+    /// ```text
+    /// but graph export --workspace
+    /// ```
+    ///
+    #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+    Graph(graph::Platform),
+
     #[cfg(feature = "legacy")]
     #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
     Commit(commit::Platform),
@@ -494,6 +536,14 @@ pub enum Subcommands {
     #[clap(visible_alias = "mr")]
     Pr(forge::pr::Platform),
 
+    #[cfg(feature = "legacy")]
+    #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+    FormatPatch(format_patch::Platform),
+
+    #[cfg(feature = "legacy")]
+    #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+    Am(am::Platform),
+
     /// Amends changes into the appropriate commits where they belong.
     ///
     /// The semantic for finding "the appropriate commit" is as follows:
@@ -562,6 +612,8 @@ pub enum Subcommands {
         no_diff: bool,
         #[clap(flatten)]
         allow_merged: atoms::AllowMergedArg,
+        #[clap(flatten)]
+        skip_message_policy: atoms::SkipMessagePolicyArg,
     },
 
     #[cfg(feature = "legacy")]
@@ -572,6 +624,18 @@ pub enum Subcommands {
     #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
     Amend(amend::Platform),
 
+    #[cfg(feature = "legacy")]
+    #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+    Revert(revert::Platform),
+
+    #[cfg(feature = "legacy")]
+    #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+    Split(split::Platform),
+
+    #[cfg(feature = "legacy")]
+    #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+    Bisect(bisect::Platform),
+
     /// Commands for viewing and managing operation history.
     ///
     /// Displays a list of past operations performed in the repository,
@@ -660,6 +724,51 @@ pub enum Subcommands {
         checkout_to: Option<String>,
     },
 
+    /// Check that GitButler's metadata matches the repository, and repair it.
+    ///
+    /// This finds branches of stacks whose references were deleted, stacks without
+    /// branches, leftovers of edit mode, and uncommitted changes assigned to branches
+    /// that don't exist. Each problem is explained along with how it can be repaired.
+    ///
+    /// With `--fix`, all problems that can be repaired safely are repaired after an
+    /// oplog snapshot was taken, so `but undo` can restore the previous metadata.
+    ///
+    /// ## Examples
+    ///
+    /// List all problems:
+    ///
+    /// ```text
+    /// but doctor
+    /// ```
+    ///
+    /// Repair them:
+    ///
+    /// ```text
+    /// but doctor --fix
+    /// ```
+    ///
+    #[cfg(feature = "legacy")]
+    #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+    Doctor {
+        /// Repair all problems that can be repaired safely.
+        #[clap(long)]
+        fix: bool,
+    },
+
+    /// Commands to help with diagnosing problems and reporting bugs.
+    ///
+    /// ## Examples
+    ///
+    /// Collect anonymized diagnostics to attach to a bug report:
+    ///
+    /// ```text
+    /// but debug bundle
+    /// ```
+    ///
+    #[cfg(feature = "legacy")]
+    #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+    Debug(debug::Platform),
+
     /// Open the GitButler GUI for the current project.
     ///
     /// Running `but gui` will launch the GitButler graphical user interface
@@ -1067,17 +1176,26 @@ pub enum Subcommands {
 pub mod agent;
 pub mod alias;
 #[cfg(feature = "legacy")]
+pub mod am;
+#[cfg(feature = "legacy")]
 pub mod amend;
 #[cfg(feature = "legacy")]
 pub mod apply;
+#[cfg(feature = "legacy")]
+pub mod bisect;
 pub mod comment;
 #[cfg(feature = "legacy")]
 pub mod commit;
 pub mod config;
 #[cfg(feature = "legacy")]
+pub mod debug;
+#[cfg(feature = "legacy")]
 pub mod diff2;
 #[cfg(feature = "legacy")]
 pub mod discard;
+#[cfg(feature = "legacy")]
+pub mod format_patch;
+pub mod graph;
 pub mod mcp;
 #[cfg(feature = "legacy")]
 pub mod r#move;
@@ -1086,9 +1204,13 @@ pub mod pick;
 #[cfg(feature = "legacy")]
 pub mod redo;
 #[cfg(feature = "legacy")]
+pub mod revert;
+#[cfg(feature = "legacy")]
 pub mod reword2;
 pub mod skill;
 #[cfg(feature = "legacy")]
+pub mod split;
+#[cfg(feature = "legacy")]
 pub mod squash;
 #[cfg(feature = "legacy")]
 pub mod tui;
@@ -1116,13 +1238,41 @@ pub mod actions {
             /// Which handler is to be used for the operation. Different handles would have different behavior.
             #[clap(long, value_enum, default_value = "simple")]
             handler: Handler,
+            /// Only show where each change would be committed, without committing it.
+            ///
+            /// Only supported by the `rules` handler.
+            #[clap(long)]
+            dry_run: bool,
+        },
+        /// List recent actions, most recent first. This is the default.
+        List {
+            /// The maximum number of actions to show.
+            #[clap(long, short = 'n', default_value_t = 10)]
+            limit: i64,
+            /// The number of most recent actions to skip.
+            #[clap(long, default_value_t = 0)]
+            offset: i64,
+        },
+        /// Show an action along with the changes it made to the applied branches.
+        Show {
+            /// The ID of the action, or a unique prefix of it.
+            id: String,
+        },
+        /// Restore the workspace to the state it was in before an action ran.
+        ///
+        /// This restores the oplog snapshot taken right before the action, and can itself be undone with `but undo`.
+        Revert {
+            /// The ID of the action, or a unique prefix of it.
+            id: String,
         },
     }
 
-    #[derive(Debug, Clone, Copy, clap::ValueEnum)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
     pub enum Handler {
         /// Handles changes in a simple way.
         Simple,
+        /// Routes each change to a branch by the `gitbutler.route.<name>.*` rules of the project.
+        Rules,
     }
 }
 
@@ -1161,6 +1311,24 @@ pub mod worktree {
             #[clap(long)]
             dry: bool,
         },
+        /// Edit a commit, or resolve its conflicts, in a new worktree while the workspace stays usable
+        Edit {
+            /// The commit to edit, as CLI ID or commit hash
+            commit: String,
+        },
+        /// Apply the changes made in an edit worktree to its commit and remove the worktree
+        Finish {
+            /// The path or name of the edit worktree
+            path: String,
+        },
+        /// Remove an edit worktree without applying its changes to its commit
+        Abort {
+            /// The path or name of the edit worktree
+            path: String,
+            /// Discard the changes made in the worktree, which is refused otherwise
+            #[clap(long)]
+            force: bool,
+        },
         /// Destroy worktree(s)
         Destroy {
             /// The path to the worktree to destroy, or a reference to destroy all worktrees created from it
@@ -1208,6 +1376,7 @@ pub(crate) fn error_examples(subcommand: &str) -> Option<&'static str> {
             "amend" => amend::ERROR_EXAMPLES,
             "move" => r#move::ERROR_EXAMPLES,
             "squash" => squash::ERROR_EXAMPLES,
+            "split" => split::ERROR_EXAMPLES,
             _ => return None,
         })
     }
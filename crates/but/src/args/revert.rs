@@ -0,0 +1,40 @@
+//! Arguments for `revert`.
+
+#![deny(missing_docs)]
+
+use crate::args::atoms::{AllowMergedArg, CliIdArg, SkipMessagePolicyArg};
+
+/// Create a commit that undoes the changes of another commit.
+///
+/// The commit to revert may be in any applied branch, or anywhere else in the
+/// repository, like on the target branch.
+///
+/// By default, the revert is placed at the tip of the branch that contains the
+/// reverted commit. Commits that aren't in an applied branch are reverted into the
+/// only applied stack, or into a stack of your choice if there are several.
+/// Use `--into` to place the revert at the tip of a specific branch instead.
+///
+/// If the reverted changes don't apply cleanly, the revert is created as a
+/// conflicted commit which can be resolved with `but resolve`.
+///
+/// For more details about CLI IDs, see `but help cli-ids`.
+#[derive(Debug, clap::Parser)]
+#[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+pub struct Platform {
+    /// The commit to revert.
+    pub commit: CliIdArg,
+
+    /// Place the revert at the tip of the branch `BRANCH`.
+    ///
+    /// If `BRANCH` does not exist, it is created as an unstacked branch.
+    #[clap(long, value_name = "BRANCH")]
+    pub into: Option<CliIdArg>,
+
+    #[clap(flatten)]
+    #[allow(missing_docs)]
+    pub allow_merged: AllowMergedArg,
+
+    #[clap(flatten)]
+    #[allow(missing_docs)]
+    pub skip_message_policy: SkipMessagePolicyArg,
+}
@@ -0,0 +1,76 @@
+//! Arguments for `split`.
+
+#![deny(missing_docs)]
+
+use crate::args::atoms::{AllowMergedArg, CliIdArg, SkipMessagePolicyArg};
+
+/// Split committed changes out of a commit into new commits.
+///
+/// The selected changes are removed from their commit and placed in new commits directly on top
+/// of it, so the history of the branch is otherwise unchanged. The original commit keeps its
+/// message, its change ID and all changes that weren't selected.
+///
+/// Changes are committed files, or single hunks of them written as `<file>:#<index>`, where
+/// `<index>` counts the hunks of the file in `but diff <commit>` from 0.
+///
+/// The changes given as arguments go into the first new commit, and each `--piece` creates
+/// another one on top of the previous. All changes must come from the same commit.
+///
+/// For more details about CLI IDs, see `but help cli-ids`.
+#[derive(Debug, clap::Parser)]
+#[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
+pub struct Platform {
+    /// One or more committed files or hunks to split into the first new commit.
+    #[clap(required_unless_present = "pieces")]
+    pub changes: Vec<CliIdArg>,
+
+    /// Committed files or hunks, separated by commas, to split into another new commit.
+    ///
+    /// Can be given multiple times to create one commit per occurrence.
+    #[clap(short, long = "piece", value_name = "CHANGES")]
+    pub pieces: Vec<PieceArg>,
+
+    /// The message of a new commit.
+    ///
+    /// Give it once per new commit, in the order the commits are created. If omitted, the new
+    /// commits use the message of the split commit.
+    #[clap(short, long)]
+    pub message: Vec<String>,
+
+    #[clap(flatten)]
+    #[allow(missing_docs)]
+    pub allow_merged: AllowMergedArg,
+
+    #[clap(flatten)]
+    #[allow(missing_docs)]
+    pub skip_message_policy: SkipMessagePolicyArg,
+}
+
+/// The changes of one new commit, as comma-separated CLI IDs.
+#[derive(Debug, Clone)]
+pub struct PieceArg(pub Vec<CliIdArg>);
+
+impl std::str::FromStr for PieceArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let changes: Vec<_> = s
+            .split(',')
+            .map(str::trim)
+            .filter(|change| !change.is_empty())
+            .map(|change| CliIdArg(change.to_owned()))
+            .collect();
+        if changes.is_empty() {
+            return Err("a piece needs at least one committed file or hunk".into());
+        }
+        Ok(Self(changes))
+    }
+}
+
+/// Example invocations appended to a `but split` parse error.
+pub(crate) const ERROR_EXAMPLES: &str = "\
+Examples:
+  but split <commit>:<file> -m \"msg\"                            # split a file into a new commit
+  but split <commit>:<file>:#1 -m \"msg\"                         # split the second hunk of a file
+  but split <change> -m \"msg1\" -p <change>,<change> -m \"msg2\"   # split into two new commits
+";
@@ -2,7 +2,7 @@
 
 #![deny(missing_docs)]
 
-use crate::args::atoms::{AllowMergedArg, CliIdArg};
+use crate::args::atoms::{AllowMergedArg, CliIdArg, SkipMessagePolicyArg};
 
 /// Squash commits, branches, or changes.
 ///
@@ -96,6 +96,10 @@ pub struct Platform {
     #[clap(flatten)]
     #[allow(missing_docs)]
     pub allow_merged: AllowMergedArg,
+
+    #[clap(flatten)]
+    #[allow(missing_docs)]
+    pub skip_message_policy: SkipMessagePolicyArg,
 }
 
 /// Example invocations appended to a `but squash` parse error.
@@ -11,7 +11,7 @@ use crate::args::atoms::CliIdArg;
 ///
 /// **Environment variables:**
 ///
-/// **BUT_THEME**  Sets the theme for but. Options: dark, light. [default: detected from the terminal, falling back to dark]
+/// **BUT_THEME**  Sets the theme for but. Options: dark, light, high-contrast, colorblind-safe, or a theme file name. Falls back to the `but.theme` git config. [default: detected from the terminal, falling back to dark]
 ///
 #[derive(Debug, clap::Parser)]
 #[cfg_attr(feature = "raw-clap-docs", clap(verbatim_doc_comment))]
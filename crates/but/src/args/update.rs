@@ -22,7 +22,12 @@ pub enum Subcommands {
     /// Install or update the GitButler desktop application.
     ///
     /// By default, auto-detects your current channel (release/nightly) and installs the latest
-    /// version for that channel.
+    /// version for that channel. If the current project pins a version with
+    /// `gitbutler.cliVersion.pinned`, a target must be given instead.
+    ///
+    /// Releases are obtained from GitButler, or from the mirror or release directory given with
+    /// `--from`, the `appUpdatesMirror` setting or the `GITBUTLER_INSTALL_MIRROR` environment
+    /// variable, in that order. Downloads must be signed by GitButler in any case.
     ///
     /// macOS: Installs the full GitButler desktop application. The CLI (but) is included with the
     /// app and will also be updated.
@@ -40,5 +45,20 @@ pub enum Subcommands {
         ///   but update install release   Install latest stable release
         ///   but update install 0.18.7    Install specific version
         target: Option<String>,
+        /// A URL of a server mirroring the release API, or a local directory with releases
+        ///
+        /// Mirrors must use HTTPS, unless GITBUTLER_INSTALL_ALLOW_HTTP=1 is set.
+        ///
+        /// Examples:
+        ///   but update install --from https://mirror.example.com/gitbutler
+        ///   but update install 0.18.7 --from /mnt/gitbutler-releases
+        #[clap(long, value_name = "URL|DIR")]
+        from: Option<String>,
     },
+
+    /// Restore the installation that the last 'but update install' replaced.
+    ///
+    /// The replaced installation is kept in turn, so running this again undoes the rollback.
+    #[cfg(all(unix, not(feature = "packaged-but-distribution")))]
+    Rollback,
 }
@@ -562,9 +562,9 @@ fn format_commit_row(
 ) -> String {
     let t = theme::get();
     let dot = match style {
-        CommitRenderStyle::LocalOnly => "●".to_string(),
-        CommitRenderStyle::Upstream => t.attention.paint("●").to_string(),
-        CommitRenderStyle::Integrated => t.remote_branch.paint("●").to_string(),
+        CommitRenderStyle::LocalOnly => t.commit_local.paint("●").to_string(),
+        CommitRenderStyle::Upstream => t.commit_upstream.paint("●").to_string(),
+        CommitRenderStyle::Integrated => t.commit_integrated.paint("●").to_string(),
     };
     let refs = refs.map(|refs| format!(" ({refs})")).unwrap_or_default();
     let subject = subject
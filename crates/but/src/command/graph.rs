@@ -0,0 +1,35 @@
+use but_graph::export;
+
+use crate::{
+    args::graph::{Detail, Format},
+    utils::OutputChannel,
+};
+
+/// Write the graph of the current workspace in `format` to `out`.
+///
+/// JSON output always writes the format-independent document.
+pub fn export(
+    ctx: &but_ctx::Context,
+    out: &mut OutputChannel,
+    format: Format,
+    detail: Detail,
+    anonymize: bool,
+    workspace_only: bool,
+) -> anyhow::Result<()> {
+    let (_guard, repo, ws, _db) = ctx.workspace_and_db()?;
+    let mut graph = ws.graph.clone();
+    if anonymize {
+        graph.anonymize(&repo.remote_names())?;
+    }
+    let options = export::Options {
+        detail: detail.into(),
+        workspace_only,
+    };
+
+    if let Some(out) = out.for_json() {
+        out.write_value(graph.export_document(options)?)?;
+    } else if let Some(out) = out.for_human_or_shell() {
+        write!(out, "{}", graph.export(format.into(), options)?)?;
+    }
+    Ok(())
+}
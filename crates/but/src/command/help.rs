@@ -121,7 +121,12 @@ fn print_grouped_with_truncation(
                 SubcommandDiscriminant::_Diff2 => Group::Inspection,
                 #[cfg(feature = "legacy")]
                 SubcommandDiscriminant::Show => Group::Inspection,
+                #[cfg(feature = "legacy")]
+                SubcommandDiscriminant::Blame => Group::Inspection,
                 SubcommandDiscriminant::_Comment => Group::Inspection,
+                SubcommandDiscriminant::Graph => Group::Inspection,
+                #[cfg(feature = "legacy")]
+                SubcommandDiscriminant::Bisect => Group::Inspection,
 
                 #[cfg(feature = "legacy")]
                 SubcommandDiscriminant::Commit => Group::BranchingAndCommitting,
@@ -148,6 +153,10 @@ fn print_grouped_with_truncation(
                 SubcommandDiscriminant::Land => Group::ServerInteractions,
                 #[cfg(feature = "legacy")]
                 SubcommandDiscriminant::Pr => Group::ServerInteractions,
+                #[cfg(feature = "legacy")]
+                SubcommandDiscriminant::FormatPatch => Group::ServerInteractions,
+                #[cfg(feature = "legacy")]
+                SubcommandDiscriminant::Am => Group::ServerInteractions,
 
                 #[cfg(feature = "legacy")]
                 SubcommandDiscriminant::Absorb => Group::EditingCommits,
@@ -160,6 +169,10 @@ fn print_grouped_with_truncation(
                 #[cfg(feature = "legacy")]
                 SubcommandDiscriminant::Amend => Group::EditingCommits,
                 #[cfg(feature = "legacy")]
+                SubcommandDiscriminant::Revert => Group::EditingCommits,
+                #[cfg(feature = "legacy")]
+                SubcommandDiscriminant::Split => Group::EditingCommits,
+                #[cfg(feature = "legacy")]
                 SubcommandDiscriminant::Squash => Group::EditingCommits,
                 #[cfg(feature = "legacy")]
                 SubcommandDiscriminant::Move => Group::EditingCommits,
@@ -186,6 +199,10 @@ fn print_grouped_with_truncation(
                 #[cfg(feature = "legacy")]
                 SubcommandDiscriminant::Teardown => Group::OtherCommands,
                 #[cfg(feature = "legacy")]
+                SubcommandDiscriminant::Doctor => Group::OtherCommands,
+                #[cfg(feature = "legacy")]
+                SubcommandDiscriminant::Debug => Group::OtherCommands,
+                #[cfg(feature = "legacy")]
                 SubcommandDiscriminant::Tui => Group::OtherCommands,
 
                 SubcommandDiscriminant::Edit => continue,
@@ -373,6 +390,9 @@ Inspection:
   status       Overview of the project workspace state
   diff         Displays the diff of changes in the repo
   show         Shows detailed information about a commit or branch
+  blame        Shows where each line of a file in the worktree came from
+  graph        Commands for inspecting the commit graph as GitButler sees it
+  bisect       Find the commit of a stack that introduced a problem
 
 Branching and Committing:
   commit       Create a commit
@@ -391,6 +411,8 @@ Editing Commits:
   reword       Edit the commit message of the specified commit
   uncommit     Uncommit commits, branches, or committed files
   amend        Amend uncommitted changes into a commit or branch
+  revert       Create a commit that undoes the changes of another commit
+  split        Split committed changes out of a commit into new commits
 
 Operation History:
   oplog        Commands for viewing and managing operation history
@@ -402,6 +424,8 @@ Server Interactions:
   push         Push changes in a branch to remote
   pull         Updates all applied branches to be up to date with the target b…
   pr           Commands for creating and managing reviews on a forge, e.g. Git…
+  format-patch Export a branch as a series of patches for email
+  am           Apply a series of patches from a mailbox to a branch
 
 Other Commands:
   setup        Sets up a GitButler project from a git repository in the curren…
@@ -432,7 +456,7 @@ Options:
 Environment variables:
   BUT_OUTPUT_FORMAT  Sets the output format when --json is not passed. Options:…
   BUT_PAGER  Sets the pager for large outputs. [default: less]
-  BUT_THEME  Sets the theme for but. Options: dark, light. [default: detected f…
+  BUT_THEME  Sets the theme for but. Options: dark, light, high-contrast, color…
 
 "#]]
         );
@@ -477,6 +501,9 @@ commands (blame, log, etc) can also be used, as GitButler is fully Git compatibl
 
 Checkout the full docs here: https://docs.gitbutler.com/cli-overview
 
+Inspection:
+  graph        Commands for inspecting the commit graph as GitButler sees it
+
 Branching and Committing:
   branch       Commands for managing branches
 
@@ -506,7 +533,7 @@ Options:
 Environment variables:
   BUT_OUTPUT_FORMAT  Sets the output format when --json is not passed. Options:…
   BUT_PAGER  Sets the pager for large outputs. [default: less]
-  BUT_THEME  Sets the theme for but. Options: dark, light. [default: detected f…
+  BUT_THEME  Sets the theme for but. Options: dark, light, high-contrast, color…
 
 "#]]
         );
@@ -1,18 +1,30 @@
-use but_action::Source;
+use but_action::{ActionHandler, ButlerAction, Route, RoutedHunk, Source};
+use but_api::legacy::oplog::RestoreKind;
 use but_ctx::Context;
 use serde::Serialize;
 
-use crate::utils::OutputChannel;
+use crate::{
+    theme::{self, Paint},
+    utils::{OutputChannel, WriteWithUtils, shorten_hex_object_id, shorten_object_id},
+};
 
 pub(crate) fn handle_changes(
     ctx: &mut Context,
     out: &mut OutputChannel,
-    handler: impl Into<but_action::ActionHandler>,
+    handler: crate::args::actions::Handler,
+    dry_run: bool,
     change_description: &str,
 ) -> anyhow::Result<()> {
+    use crate::args::actions::Handler;
+    let handler = match handler {
+        Handler::Simple => ActionHandler::HandleChangesSimple,
+        Handler::Rules => ActionHandler::HandleChangesRules,
+    };
+    if dry_run && handler != ActionHandler::HandleChangesRules {
+        anyhow::bail!("--dry-run is only supported by the 'rules' handler");
+    }
     let mut guard = ctx.exclusive_worktree_access();
     let perm = guard.write_permission();
-    let handler = handler.into();
     let response = but_action::record_uncommitted_changes_with_perm(
         ctx,
         change_description,
@@ -20,17 +32,53 @@ pub(crate) fn handle_changes(
         handler,
         Source::ButCli,
         None,
+        dry_run,
         perm,
     )?;
-    print_json_or_human(&response, out)
-}
-
-impl From<crate::args::actions::Handler> for but_action::ActionHandler {
-    fn from(val: crate::args::actions::Handler) -> Self {
-        match val {
-            crate::args::actions::Handler::Simple => but_action::ActionHandler::HandleChangesSimple,
+    if let Some(out) = out.for_json() {
+        out.write_value(&response)?;
+    } else if let Some(out) = out.for_human() {
+        let (id, outcome) = &response;
+        if let Some(id) = id {
+            writeln!(out, "Recorded action {id}")?;
+        }
+        for hunk in &outcome.routed_hunks {
+            writeln!(out, "{}", format_routed_hunk(hunk))?;
+        }
+        for branch in &outcome.updated_branches {
+            writeln!(
+                out,
+                "Committed {} to {}",
+                branch.new_commits.join(", "),
+                branch.branch_name
+            )?;
         }
     }
+    Ok(())
+}
+
+/// Describe where `hunk` goes and why, on a single line.
+fn format_routed_hunk(hunk: &RoutedHunk) -> String {
+    let location = match hunk.hunk_header {
+        Some(header) => format!(
+            "{} @@ -{},{} +{},{} @@",
+            hunk.path, header.old_start, header.old_lines, header.new_start, header.new_lines
+        ),
+        None => hunk.path.clone(),
+    };
+    let reason = match &hunk.route {
+        Route::DependencyLock => "dependency lock".to_owned(),
+        Route::AmbiguousDependencyLock => "locked to multiple stacks".to_owned(),
+        Route::Assignment => "assigned".to_owned(),
+        Route::Rule { name, criterion } => format!("rule '{name}': {criterion}"),
+        Route::Default => "default".to_owned(),
+    };
+    let target = match (&hunk.branch_name, hunk.skipped) {
+        (Some(branch), false) => branch.as_str(),
+        (Some(_), true) => "(skipped)",
+        (None, _) => "(uncommitted)",
+    };
+    format!("{location} -> {target} ({reason})")
 }
 
 pub(crate) fn list_actions(
@@ -39,19 +87,207 @@ pub(crate) fn list_actions(
     offset: i64,
     limit: i64,
 ) -> anyhow::Result<()> {
-    let db = ctx.db.get_cache()?;
-    let response = but_action::list_actions(&db, offset, limit)?;
-    print_json_or_human(&response, out)
+    let listing = {
+        let db = ctx.db.get_cache()?;
+        but_action::list_actions(&db, offset, limit)?
+    };
+    if let Some(out) = out.for_json() {
+        out.write_value(&listing)?;
+    } else if let Some(out) = out.for_human() {
+        if listing.actions.is_empty() {
+            writeln!(out, "No actions found.")?;
+            return Ok(());
+        }
+        let repo = ctx.repo.get()?;
+        let t = theme::get();
+        writeln!(
+            out,
+            "{}",
+            t.important.paint(format!(
+                "Actions ({} of {})",
+                listing.actions.len(),
+                listing.total
+            ))
+        )?;
+        writeln!(out, "{}", t.hint.paint("─".repeat(50)))?;
+        for action in &listing.actions {
+            writeln!(
+                out,
+                "{} {} {} {}",
+                t.cli_id.paint(short_action_id(action)),
+                t.time
+                    .paint(action.created_at.format(TIME_FORMAT).to_string()),
+                action.handler,
+                t.hint.paint(format!("({})", source_name(&action.source)))
+            )?;
+            writeln!(
+                out,
+                "  {}",
+                action.external_summary.lines().next().unwrap_or_default()
+            )?;
+            write_result(out, &repo, action, "  ")?;
+        }
+    }
+    Ok(())
+}
+
+/// The JSON output of `but actions show`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ShowAction {
+    action: ButlerAction,
+    changes: Vec<crate::command::legacy::diff::JsonChange>,
 }
 
-pub(crate) fn print_json_or_human<T>(this: &T, out: &mut OutputChannel) -> anyhow::Result<()>
-where
-    T: ?Sized + Serialize + std::fmt::Debug,
-{
+pub(crate) fn show_action(ctx: &Context, out: &mut OutputChannel, id: &str) -> anyhow::Result<()> {
+    let action = {
+        let db = ctx.db.get_cache()?;
+        but_action::find_action(&db, id)?
+    };
+    let changes = but_api::legacy::oplog::snapshot_diff(
+        ctx,
+        action.snapshot_before,
+        Some(action.snapshot_after),
+    )?;
+
     if let Some(out) = out.for_json() {
-        out.write_value(this)?;
+        let changes = crate::command::legacy::diff::tree_changes_to_json(ctx, changes);
+        out.write_value(ShowAction { action, changes })?;
+        return Ok(());
+    }
+    if let Some(human) = out.for_human() {
+        let repo = ctx.repo.get()?;
+        let t = theme::get();
+        writeln!(
+            human,
+            "{} {}",
+            t.important.paint("Action"),
+            t.cli_id.paint(action.id.to_string())
+        )?;
+        writeln!(
+            human,
+            "Date:      {}",
+            t.time
+                .paint(action.created_at.format(TIME_FORMAT).to_string())
+        )?;
+        writeln!(human, "Handler:   {}", action.handler)?;
+        writeln!(human, "Source:    {}", source_name(&action.source))?;
+        writeln!(
+            human,
+            "Snapshots: {}..{}",
+            t.commit_id
+                .paint(shorten_object_id(&repo, action.snapshot_before)),
+            t.commit_id
+                .paint(shorten_object_id(&repo, action.snapshot_after))
+        )?;
+        write_result(human, &repo, &action, "")?;
+        if let Some(prompt) = &action.external_prompt {
+            writeln!(human, "\n{}", t.hint.paint("Prompt:"))?;
+            for line in prompt.lines() {
+                writeln!(human, "    {line}")?;
+            }
+        }
+        writeln!(human, "\n{}", t.hint.paint("Summary:"))?;
+        for line in action.external_summary.lines() {
+            writeln!(human, "    {line}")?;
+        }
+        writeln!(human)?;
+        if changes.is_empty() {
+            writeln!(human, "No changes to applied branches.")?;
+            return Ok(());
+        }
+    }
+    crate::command::legacy::diff::tree_changes(ctx, out, changes)
+}
+
+pub(crate) fn revert_action(
+    ctx: &mut Context,
+    out: &mut OutputChannel,
+    id: &str,
+) -> anyhow::Result<()> {
+    let action = {
+        let db = ctx.db.get_cache()?;
+        but_action::find_action(&db, id)?
+    };
+    but_api::legacy::oplog::restore_snapshot_with_kind(
+        ctx,
+        RestoreKind::ExplicitRestoreFromSnapshot,
+        action.snapshot_before,
+    )?;
+
+    if let Some(out) = out.for_json() {
+        out.write_value(serde_json::json!({
+            "revertedAction": action.id,
+            "restoredSnapshot": action.snapshot_before.to_string(),
+        }))?;
     } else if let Some(out) = out.for_human() {
-        writeln!(out, "{this:#?}")?;
+        let repo = ctx.repo.get()?;
+        let t = theme::get();
+        writeln!(
+            out,
+            "{} Reverted action {} by restoring snapshot {}",
+            t.sym().success,
+            t.cli_id.paint(short_action_id(&action)),
+            t.commit_id
+                .paint(shorten_object_id(&repo, action.snapshot_before))
+        )?;
+    }
+    Ok(())
+}
+
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Action IDs are UUIDs, of which the first group is unique enough for display.
+fn short_action_id(action: &ButlerAction) -> String {
+    action.id.simple().to_string()[..8].to_owned()
+}
+
+fn source_name(source: &Source) -> &'static str {
+    match source {
+        Source::ButCli => "cli",
+        Source::GitButler => "app",
+        Source::Mcp(_) => "mcp",
+        Source::ClaudeCode(_) => "claude-code",
+        Source::Cursor(_) => "cursor",
+        Source::Unknown => "unknown",
+    }
+}
+
+/// Write the branches and commits `action` produced, or its error, with each line starting with `indent`.
+fn write_result(
+    out: &mut dyn WriteWithUtils,
+    repo: &gix::Repository,
+    action: &ButlerAction,
+    indent: &str,
+) -> std::fmt::Result {
+    let t = theme::get();
+    if let Some(error) = &action.error {
+        return writeln!(out, "{indent}{} {}", t.error.paint("error:"), error);
+    }
+    let updated_branches = action
+        .response
+        .as_ref()
+        .map(|outcome| outcome.updated_branches.as_slice())
+        .unwrap_or_default();
+    if updated_branches.is_empty() {
+        return writeln!(out, "{indent}{}", t.hint.paint("No commits created"));
+    }
+    for branch in updated_branches {
+        let commits: Vec<_> = branch
+            .new_commits
+            .iter()
+            .map(|commit| {
+                t.commit_id
+                    .paint(shorten_hex_object_id(repo, commit))
+                    .to_string()
+            })
+            .collect();
+        writeln!(
+            out,
+            "{indent}{} {}",
+            t.local_branch.paint(&branch.branch_name),
+            commits.join(", ")
+        )?;
     }
     Ok(())
 }
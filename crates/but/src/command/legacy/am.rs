@@ -0,0 +1,186 @@
+use but_api::{
+    WorkspaceState,
+    json::{ChangeIdString, HexHash},
+};
+use but_ctx::Context;
+use gitbutler_repo::patch_series::MailboxCommit;
+use serde::Serialize;
+
+use crate::{
+    CliResult, IdMap,
+    args::am::Platform,
+    bad_input,
+    command::legacy::{
+        commit::{
+            BranchNameTarget, CommitOperationTargetIsh, RouteCommitOperationError,
+            route_commit_operation,
+        },
+        pick::{self, PickOperation},
+    },
+    id::CommitId,
+    theme::{self, Theme},
+    utils::{
+        CliOutput, CliOutputHuman, IntermediateChannel, WriteWithUtils,
+        merged_upstream::MergedUpstream,
+    },
+};
+
+#[derive(Debug)]
+pub struct AmOutcome {
+    pub patches: Vec<MailboxCommit>,
+    pub new_commits: Vec<CommitId>,
+    pub branch_name: Option<BranchNameTarget>,
+}
+
+impl CliOutputHuman for AmOutcome {
+    fn on_human(
+        self,
+        out: &mut dyn WriteWithUtils,
+        _agent: bool,
+        theme: &Theme,
+    ) -> anyhow::Result<()> {
+        let Self {
+            patches,
+            new_commits,
+            branch_name,
+        } = self;
+
+        let count = match patches.len() {
+            1 => "1 patch".to_owned(),
+            n => format!("{n} patches"),
+        };
+        match branch_name {
+            Some(BranchNameTarget::New(branch_name)) => writeln!(
+                out,
+                "Applied {count} onto new branch {}",
+                theme::Branch(branch_name)
+            )?,
+            Some(BranchNameTarget::Existing(branch_name)) => writeln!(
+                out,
+                "Applied {count} onto branch {}",
+                theme::Branch(branch_name)
+            )?,
+            None => writeln!(out, "Applied {count}")?,
+        }
+        for (patch, commit) in patches.iter().zip(new_commits) {
+            write!(out, "  {} {}", theme::Commit(commit), patch.subject)?;
+            if patch.three_way {
+                write!(out, " {}", theme.hint.paint("(applied three-way)"))?;
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+}
+
+impl CliOutput for AmOutcome {
+    fn on_json(self) -> impl serde::Serialize {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct AppliedPatch {
+            subject: String,
+            commit_id: HexHash,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            change_id: Option<ChangeIdString>,
+            three_way: bool,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Output {
+            commits: Vec<AppliedPatch>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            branch: Option<String>,
+        }
+
+        let Self {
+            patches,
+            new_commits,
+            branch_name,
+        } = self;
+
+        Output {
+            commits: patches
+                .into_iter()
+                .zip(new_commits)
+                .map(|(patch, commit)| AppliedPatch {
+                    subject: patch.subject,
+                    commit_id: commit.commit_id.into(),
+                    change_id: commit.change_id.map(Into::into),
+                    three_way: patch.three_way,
+                })
+                .collect(),
+            branch: branch_name.map(|branch_name| match branch_name {
+                BranchNameTarget::New(branch_name) | BranchNameTarget::Existing(branch_name) => {
+                    branch_name.shorten().to_string()
+                }
+            }),
+        }
+    }
+}
+
+pub fn am(
+    ctx: &mut Context,
+    mut out: IntermediateChannel<'_>,
+    args: Platform,
+) -> CliResult<(AmOutcome, WorkspaceState)> {
+    let Platform {
+        mailbox,
+        branch,
+        allow_merged,
+    } = args;
+
+    let mut guard = ctx.exclusive_worktree_access();
+    let mut meta = ctx.meta()?;
+    let id_map = IdMap::new_from_context(ctx, guard.read_permission())?;
+    let head_info = but_api::legacy::workspace::head_info(ctx)?;
+
+    let patches = {
+        let repo = ctx.repo.get()?;
+        // Apply the patches to what's visible in the workspace, so they have the best chance
+        // to apply cleanly no matter which of its stacks they are placed on.
+        let onto = repo.head_id()?.detach();
+        let mailbox = std::env::current_dir()?.join(mailbox);
+        gitbutler_repo::patch_series::commits_from_mailbox(&repo, &mailbox, onto)?
+    };
+
+    let target_ish = CommitOperationTargetIsh::resolve(branch, None, None)?;
+    let commit_op = {
+        let merged = MergedUpstream::new(&*ctx.repo.get()?, &head_info, allow_merged);
+        let (repo, ws, _db) = ctx.workspace_and_db_with_perm(guard.read_permission())?;
+        route_commit_operation(
+            &repo, &ws, &head_info, &mut out, &id_map, target_ish, &merged,
+        )
+        .map_err(|err| match err {
+            RouteCommitOperationError::NoStackToCommitTo => {
+                bad_input("Found no stack that the patches could be applied to").into()
+            }
+            RouteCommitOperationError::UnclearTargetCantPrompt => {
+                bad_input("Unclear where to apply the patches to. Found more than one stack")
+                    .hint("You can specify where to apply them with `--branch [<BRANCH>]`")
+                    .into()
+            }
+            RouteCommitOperationError::Other(cli_error) => cli_error,
+        })?
+    };
+
+    let (outcome, ws) = pick::run(
+        ctx,
+        &mut meta,
+        guard.write_permission(),
+        PickOperation {
+            sources: patches.iter().map(|patch| patch.commit_id).collect(),
+            commit_op,
+            order_commits_by_parentage: false,
+        },
+    )?;
+
+    Ok((
+        AmOutcome {
+            patches,
+            new_commits: outcome.new_commits,
+            branch_name: outcome.branch_name,
+        },
+        ws,
+    ))
+}
@@ -0,0 +1,215 @@
+use std::{
+    ffi::OsString,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use anyhow::{Context as _, bail};
+use bstr::ByteSlice as _;
+use but_api::json::{ChangeIdString, HexHash};
+use but_ctx::Context;
+use but_worktrees::bisect::{BisectOutcome, BisectVerdict};
+use serde::Serialize;
+
+use crate::{
+    CliResult, IdMap,
+    args::{
+        atoms::{CommitArg, Priority, Purpose, ResolvedCliIdArg},
+        bisect::Platform,
+    },
+    bad_input,
+    id::CommitId,
+    theme,
+    utils::OutputChannel,
+};
+
+pub fn bisect(ctx: &mut Context, out: &mut OutputChannel, args: Platform) -> CliResult<()> {
+    let Platform {
+        branch,
+        good,
+        verbose,
+        command,
+    } = args;
+
+    let (refname, range) = {
+        let guard = ctx.shared_worktree_access();
+        let id_map = IdMap::new_from_context(ctx, guard.read_permission())?;
+        let (repo, ws, _db) = ctx.workspace_and_db_with_perm(guard.read_permission())?;
+        let refname = branch.resolve_existing_local_branch(&repo, &id_map)?;
+        let good = match good {
+            Some(good) => Some(
+                match good.try_resolve(&repo, &id_map, Purpose::Source, Some(Priority::Commit))? {
+                    Some(ResolvedCliIdArg::Commit(commit_id)) => commit_id.commit_id,
+                    Some(resolved) => {
+                        return Err(bad_input(format!(
+                            "The good commit must be a commit. {} is {}",
+                            good,
+                            resolved.kind_for_humans()
+                        ))
+                        .into());
+                    }
+                    None => CommitArg(good.0).resolve(&repo)?,
+                },
+            ),
+            None => None,
+        };
+        let range = but_worktrees::bisect::bisect_range(&ws, refname.as_ref(), good)
+            .map_err(|err| bad_input(err.to_string()))?;
+        (refname, range)
+    };
+
+    let repo = ctx.repo.get()?.clone();
+    if let Some(out) = out.for_human() {
+        writeln!(
+            out,
+            "Bisecting {} commits of {} in a temporary worktree",
+            range.commits.len(),
+            theme::Branch(refname.as_ref())
+        )?;
+    }
+    let outcome = but_worktrees::bisect::bisect(
+        &repo,
+        &ctx.project_data_dir(),
+        &range,
+        |worktree, commit| {
+            let verdict = run_command(&command, worktree, verbose)?;
+            if let Some(out) = out.for_human() {
+                writeln!(
+                    out,
+                    "  {:<4} {} {}",
+                    verdict_str(verdict),
+                    theme::Commit(CommitId::try_from_commit_id(commit, &repo)?),
+                    commit_title(&repo, commit)?
+                )?;
+            }
+            Ok(verdict)
+        },
+    )?;
+
+    if let Some(out) = out.for_json() {
+        out.write_value(json_output(&repo, &outcome)?)?;
+    } else if let Some(out) = out.for_human() {
+        writeln!(out)?;
+        match outcome.first_bad {
+            Some(first_bad) => writeln!(
+                out,
+                "First bad commit: {} {}",
+                theme::Commit(CommitId::try_from_commit_id(first_bad, &repo)?),
+                commit_title(&repo, first_bad)?
+            )?,
+            None => {
+                writeln!(
+                    out,
+                    "Skipped commits prevent finding the first bad commit, it is one of:"
+                )?;
+                for candidate in outcome.candidates {
+                    writeln!(
+                        out,
+                        "  {} {}",
+                        theme::Commit(CommitId::try_from_commit_id(candidate, &repo)?),
+                        commit_title(&repo, candidate)?
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Run `command` in `worktree` and map its exit code like `git bisect run` does.
+fn run_command(
+    command: &[OsString],
+    worktree: &Path,
+    verbose: bool,
+) -> anyhow::Result<BisectVerdict> {
+    let Some((program, args)) = command.split_first() else {
+        bail!("No command to run");
+    };
+    let mut cmd = Command::new(program);
+    cmd.args(args).current_dir(worktree).stdin(Stdio::null());
+    if verbose {
+        // Keep standard output free for the result, which may be JSON.
+        cmd.stdout(std::io::stderr());
+    } else {
+        cmd.stdout(Stdio::null()).stderr(Stdio::null());
+    }
+    let status = cmd
+        .status()
+        .with_context(|| format!("Failed to run {}", program.to_string_lossy()))?;
+
+    Ok(match status.code() {
+        Some(0) => BisectVerdict::Good,
+        Some(125) => BisectVerdict::Skip,
+        Some(1..=127) => BisectVerdict::Bad,
+        Some(code) => bail!("The command exited with code {code}, aborting the bisection"),
+        None => bail!("The command was terminated by a signal, aborting the bisection"),
+    })
+}
+
+fn verdict_str(verdict: BisectVerdict) -> &'static str {
+    match verdict {
+        BisectVerdict::Good => "good",
+        BisectVerdict::Bad => "bad",
+        BisectVerdict::Skip => "skip",
+    }
+}
+
+fn commit_title(repo: &gix::Repository, commit: gix::ObjectId) -> anyhow::Result<String> {
+    let commit = repo.find_commit(commit)?;
+    Ok(commit.message()?.summary().to_str_lossy().into_owned())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonCommit {
+    commit_id: HexHash,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    change_id: Option<ChangeIdString>,
+    title: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonStep {
+    #[serde(flatten)]
+    commit: JsonCommit,
+    verdict: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonOutput {
+    first_bad: Option<JsonCommit>,
+    candidates: Vec<JsonCommit>,
+    steps: Vec<JsonStep>,
+}
+
+fn json_output(repo: &gix::Repository, outcome: &BisectOutcome) -> anyhow::Result<JsonOutput> {
+    let commit = |commit_id: gix::ObjectId| -> anyhow::Result<JsonCommit> {
+        let id = CommitId::try_from_commit_id(commit_id, repo)?;
+        Ok(JsonCommit {
+            commit_id: commit_id.into(),
+            change_id: id.change_id.map(Into::into),
+            title: commit_title(repo, commit_id)?,
+        })
+    };
+    Ok(JsonOutput {
+        first_bad: outcome.first_bad.map(&commit).transpose()?,
+        candidates: outcome
+            .candidates
+            .iter()
+            .copied()
+            .map(&commit)
+            .collect::<anyhow::Result<_>>()?,
+        steps: outcome
+            .steps
+            .iter()
+            .map(|step| {
+                Ok(JsonStep {
+                    commit: commit(step.commit)?,
+                    verdict: verdict_str(step.verdict),
+                })
+            })
+            .collect::<anyhow::Result<_>>()?,
+    })
+}
@@ -0,0 +1,95 @@
+use bstr::ByteSlice as _;
+use but_ctx::Context;
+use but_hunk_dependency::blame::{BlameLine, LineOrigin};
+use serde::Serialize;
+
+use crate::{
+    CliResult, IdMap, command::legacy::resolve::sanitize_terminal_text, theme, utils::OutputChannel,
+};
+
+pub fn blame(ctx: &mut Context, out: &mut OutputChannel, path: &str) -> CliResult<()> {
+    let lines = but_api::diff::blame_worktree_file(ctx, path.to_owned())?;
+
+    let guard = ctx.shared_worktree_access();
+    let id_map = IdMap::new_from_context(ctx, guard.read_permission())?;
+    let file_id = id_map
+        .uncommitted_files
+        .values()
+        .find(|file| file.path() == path)
+        .map(|file| file.short_id.clone());
+
+    let lines = lines
+        .into_iter()
+        .map(|line| {
+            let cli_id = match &line.origin {
+                LineOrigin::Uncommitted => file_id.clone(),
+                LineOrigin::Workspace { commit_id, .. } | LineOrigin::Target { commit_id } => Some(
+                    id_map
+                        .change_id_ref(*commit_id)
+                        .map(|change_id| change_id.padded_short_id())
+                        .unwrap_or_else(|| commit_id.to_hex_with_len(7).to_string()),
+                ),
+            };
+            (cli_id, line)
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(out) = out.for_json() {
+        out.write_value(
+            lines
+                .into_iter()
+                .map(|(cli_id, line)| JsonLine {
+                    cli_id,
+                    line: line.into(),
+                })
+                .collect::<Vec<_>>(),
+        )?;
+    } else if let Some(out) = out.for_human() {
+        let t = theme::get();
+        let line_number_width = lines.len().to_string().len();
+        let cli_id_width = lines
+            .iter()
+            .map(|(cli_id, _)| cli_id.as_deref().map_or(0, str::len))
+            .max()
+            .unwrap_or_default();
+        let origins: Vec<_> = lines
+            .iter()
+            .map(|(_, line)| match &line.origin {
+                LineOrigin::Uncommitted => "uncommitted".to_owned(),
+                LineOrigin::Workspace { branch, .. } => branch.as_ref().map_or_else(
+                    || "workspace".to_owned(),
+                    |branch| branch.shorten().to_string(),
+                ),
+                LineOrigin::Target { .. } => "target".to_owned(),
+            })
+            .collect();
+        let origin_width = origins.iter().map(String::len).max().unwrap_or_default();
+
+        for ((cli_id, line), origin) in lines.iter().zip(origins) {
+            let cli_id = format!("{:<cli_id_width$}", cli_id.as_deref().unwrap_or_default());
+            let origin = format!("{origin:<origin_width$}");
+            writeln!(
+                out,
+                "{} {} {:>line_number_width$} {}",
+                t.cli_id.paint(cli_id),
+                match line.origin {
+                    LineOrigin::Uncommitted => t.attention.paint(origin),
+                    LineOrigin::Workspace { .. } => t.local_branch.paint(origin),
+                    LineOrigin::Target { .. } => t.hint.paint(origin),
+                },
+                line.line_number,
+                sanitize_terminal_text(&line.content.to_str_lossy())
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonLine {
+    /// The CLI ID of the commit that introduced the line, or of the uncommitted file.
+    cli_id: Option<String>,
+    #[serde(flatten)]
+    line: but_api::diff::json::BlameLine,
+}
@@ -48,6 +48,7 @@ pub fn delete(
     )?)
 }
 
+#[expect(clippy::too_many_arguments)]
 pub fn show_branches(
     ctx: &mut but_ctx::Context,
     out: &mut OutputChannel,
@@ -56,8 +57,9 @@ pub fn show_branches(
     files: bool,
     ai: bool,
     check: bool,
+    range_diff: bool,
 ) -> CliResult<()> {
-    show::show(ctx, branch_arg, out, review, files, ai, check)
+    show::show(ctx, branch_arg, out, review, files, ai, check, range_diff)
 }
 
 #[expect(clippy::too_many_arguments)]
@@ -12,6 +12,7 @@ use crate::{
     utils::{OutputChannel, get_change_id_for_commit, shorten_object_id},
 };
 
+#[expect(clippy::too_many_arguments)]
 pub fn show(
     ctx: &mut Context,
     branch_arg: CliIdArg,
@@ -20,6 +21,7 @@ pub fn show(
     show_files: bool,
     generate_ai_summary: bool,
     check_merge: bool,
+    range_diff: bool,
 ) -> CliResult<()> {
     let (branch_arg, id_map) = {
         let guard = ctx.exclusive_worktree_access();
@@ -67,6 +69,16 @@ pub fn show(
         None
     };
 
+    // Compare with the remote-tracking branch if requested
+    let range_diff = if range_diff {
+        Some(but_api::branch::branch_range_diff(
+            ctx,
+            branch_name.to_owned(),
+        )?)
+    } else {
+        None
+    };
+
     if let Some(out) = out.for_json() {
         output_json(
             branch_name,
@@ -75,16 +87,20 @@ pub fn show(
             &reviews,
             ai_summary.as_deref(),
             merge_check.as_ref(),
+            range_diff,
             out,
         )?;
     } else if let Some(out) = out.for_human() {
+        let repo = ctx.repo.get()?;
         output_human(
+            &repo,
             branch_name,
             &commits,
             &uncommitted_files,
             &reviews,
             ai_summary.as_deref(),
             merge_check.as_ref(),
+            range_diff.as_ref(),
             out,
         )?;
     }
@@ -457,6 +473,7 @@ fn generate_branch_summary(
     Ok(summary)
 }
 
+#[expect(clippy::too_many_arguments)]
 fn output_json(
     branch_name: &str,
     commits: &[CommitInfo],
@@ -464,6 +481,7 @@ fn output_json(
     reviews: &[but_forge::ForgeReview],
     ai_summary: Option<&str>,
     merge_check: Option<&MergeCheck>,
+    range_diff: Option<but_api::branch::BranchRangeDiff>,
     out: &mut OutputChannel,
 ) -> anyhow::Result<()> {
     let reviews_json: Vec<_> = reviews
@@ -501,17 +519,26 @@ fn output_json(
         });
     }
 
+    // Add range diff if available
+    if let Some(range_diff) = range_diff {
+        output["rangeDiff"] =
+            serde_json::to_value(but_api::branch::json::BranchRangeDiff::from(range_diff))?;
+    }
+
     out.write_value(output)?;
     Ok(())
 }
 
+#[expect(clippy::too_many_arguments)]
 fn output_human(
+    repo: &gix::Repository,
     branch_name: &str,
     commits: &[CommitInfo],
     uncommitted_files: &[String],
     reviews: &[but_forge::ForgeReview],
     ai_summary: Option<&str>,
     merge_check: Option<&MergeCheck>,
+    range_diff: Option<&but_api::branch::BranchRangeDiff>,
     out: &mut dyn std::fmt::Write,
 ) -> anyhow::Result<()> {
     use std::fmt::Write;
@@ -749,10 +776,77 @@ fn output_human(
         }
     }
 
+    // Display range diff if available
+    if let Some(range_diff) = range_diff {
+        writeln!(buf)?;
+        writeln!(buf)?;
+        writeln!(
+            buf,
+            "{} {}",
+            t.important.paint("Range Diff:"),
+            t.remote_branch
+                .paint(range_diff.upstream.shorten().to_string())
+        )?;
+        if range_diff.range_diff.entries.is_empty() {
+            writeln!(buf, "  {}", t.hint.paint("No differences"))?;
+        }
+        for entry in &range_diff.range_diff.entries {
+            write_range_diff_entry(&mut buf, repo, entry)?;
+        }
+    }
+
     out.write_str(&buf)?;
     Ok(())
 }
 
+/// Write `entry` like `git range-diff` does, as the old commit, a marker for how it changed, and
+/// the new commit, followed by the interdiff of modified commits.
+fn write_range_diff_entry(
+    buf: &mut String,
+    repo: &gix::Repository,
+    entry: &but_workspace::range_diff::RangeDiffEntry,
+) -> anyhow::Result<()> {
+    use std::fmt::Write;
+
+    use but_workspace::range_diff::RangeDiffStatus;
+
+    let t = theme::get();
+    let short_id = |id: Option<gix::ObjectId>| match id {
+        Some(id) => t.commit_id.paint(shorten_object_id(repo, id)),
+        None => t.hint.paint("-".repeat(7)),
+    };
+    let marker = match entry.status {
+        RangeDiffStatus::Added => t.addition.paint(">"),
+        RangeDiffStatus::Removed => t.deletion.paint("<"),
+        RangeDiffStatus::Unchanged => t.success.paint("="),
+        RangeDiffStatus::Modified => t.attention.paint("!"),
+    };
+    writeln!(
+        buf,
+        "  {} {} {} {}",
+        short_id(entry.old),
+        marker,
+        short_id(entry.new),
+        entry.title.to_str_lossy()
+    )?;
+
+    if let Some(interdiff) = &entry.interdiff {
+        for line in interdiff.to_str_lossy().lines() {
+            let line = if line.starts_with("@@") {
+                t.hint.paint(line)
+            } else if line.starts_with('+') {
+                t.addition.paint(line)
+            } else if line.starts_with('-') {
+                t.deletion.paint(line)
+            } else {
+                t.default.paint(line)
+            };
+            writeln!(buf, "      {line}")?;
+        }
+    }
+    Ok(())
+}
+
 fn format_timestamp(timestamp: i64) -> String {
     use chrono::{DateTime, Local, Utc};
 
@@ -159,6 +159,8 @@ fn resolve(
         interactive,
         changes,
         allow_merged,
+        // Applied by the caller before the context is used.
+        skip_message_policy: _,
     } = args;
 
     let merged = MergedUpstream::new(&*ctx.repo.get()?, head_info, allow_merged);
@@ -0,0 +1,96 @@
+//! Commands to help with diagnosing problems and reporting bugs.
+
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use but_ctx::Context;
+use but_feedback::{Bundle, ManifestEntry};
+
+use crate::{
+    theme::{self, Paint},
+    utils::OutputChannel,
+};
+
+#[derive(serde::Serialize)]
+struct BundleOutput {
+    /// The path of the written archive, or `None` in preview mode.
+    path: Option<PathBuf>,
+    files: Vec<ManifestEntry>,
+}
+
+/// Collect anonymized diagnostics for `ctx` and write them to `output`, or just list them if `preview` is set.
+pub fn bundle(
+    ctx: &Context,
+    out: &mut OutputChannel,
+    preview: bool,
+    output: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let logs_dir = but_path::app_log_dir()?;
+    let mut bundle = Bundle::collect(ctx, &logs_dir);
+    bundle.push(
+        "version.txt",
+        "The version of `but` and the platform it runs on",
+        format!(
+            "{} ({} {})\n",
+            option_env!("VERSION").unwrap_or("dev"),
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )
+        .into_bytes(),
+    );
+
+    let path = if preview {
+        None
+    } else {
+        let path = output.unwrap_or_else(|| {
+            PathBuf::from(format!(
+                "gitbutler-bundle-{date}.zip",
+                date = but_feedback::filesafe_date_time()
+            ))
+        });
+        Some(
+            bundle
+                .write_zip(&path)
+                .with_context(|| format!("Failed to write '{}'", path.display()))?,
+        )
+    };
+
+    if let Some(out) = out.for_json() {
+        out.write_value(&BundleOutput {
+            path,
+            files: bundle.manifest(),
+        })?;
+    } else if let Some(out) = out.for_human() {
+        let t = theme::get();
+        let header = if path.is_some() {
+            "The bundle contains:"
+        } else {
+            "The bundle would contain:"
+        };
+        writeln!(out, "{header}")?;
+        writeln!(out, "  manifest.json")?;
+        for entry in bundle.manifest() {
+            writeln!(
+                out,
+                "  {} {}",
+                entry.name,
+                t.hint
+                    .paint(format!("({} bytes) {}", entry.size, entry.description))
+            )?;
+        }
+        match path {
+            Some(path) => writeln!(
+                out,
+                "{} Wrote diagnostics bundle to {}",
+                t.sym().success,
+                t.important.paint(path.display().to_string())
+            )?,
+            None => writeln!(
+                out,
+                "Run '{}' to write it.",
+                t.command_suggestion.paint("but debug bundle")
+            )?,
+        }
+    }
+    Ok(())
+}
@@ -101,6 +101,12 @@ fn format_patch(patch: &UnifiedPatch, render_header: impl Fn(&mut String)) -> St
                 ))
             ));
         }
+        UnifiedPatch::Submodule { .. } | UnifiedPatch::Lfs { .. } => {
+            render_header(&mut output);
+            for line in patch.summary_lines().unwrap_or_default() {
+                output.push_str(&format!("   {}\n", t.hint.paint(line)));
+            }
+        }
         UnifiedPatch::Patch {
             hunks,
             is_result_of_binary_to_text_conversion,
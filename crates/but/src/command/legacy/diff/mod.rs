@@ -10,6 +10,7 @@ use crate::{
 
 mod display;
 mod show;
+pub(crate) use show::{tree_changes, tree_changes_to_json};
 
 pub fn handle_tui(ctx: &mut Context, target_str: Option<&str>) -> anyhow::Result<()> {
     use crate::tui::diff_viewer::{DiffFileEntry, WorktreeFilter};
@@ -108,7 +109,7 @@ struct JsonDiffOutput {
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct JsonChange {
+pub(crate) struct JsonChange {
     #[serde(skip_serializing_if = "Option::is_none")]
     id: Option<String>,
     path: String,
@@ -130,6 +131,32 @@ enum JsonDiff {
         #[serde(skip_serializing_if = "std::ops::Not::not")]
         is_binary_to_text: bool,
     },
+    Submodule {
+        previous_commit: Option<String>,
+        commit: Option<String>,
+        commits_added: Vec<JsonSubmoduleCommit>,
+        commits_removed: Vec<JsonSubmoduleCommit>,
+    },
+    Lfs {
+        previous_pointer: Option<but_core::diff::lfs::Pointer>,
+        pointer: Option<but_core::diff::lfs::Pointer>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonSubmoduleCommit {
+    id: String,
+    title: String,
+}
+
+impl From<&but_core::unified_diff::SubmoduleCommit> for JsonSubmoduleCommit {
+    fn from(commit: &but_core::unified_diff::SubmoduleCommit) -> Self {
+        JsonSubmoduleCommit {
+            id: commit.id.to_string(),
+            title: commit.title.to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -133,23 +133,22 @@ pub(crate) fn branch(
     short_name: String,
 ) -> anyhow::Result<()> {
     let result = but_api::branch::branch_diff(ctx, short_name)?;
+    tree_changes(ctx, out, result.changes)
+}
 
+/// Print `changes` along with their patches, or write them as JSON.
+pub(crate) fn tree_changes(
+    ctx: &Context,
+    out: &mut OutputChannel,
+    changes: Vec<but_core::ui::TreeChange>,
+) -> anyhow::Result<()> {
     if let Some(json_out) = out.for_json() {
-        let changes: Vec<JsonChange> = result
-            .changes
-            .into_iter()
-            .map(|change| {
-                let patch = but_api::diff::tree_change_diffs(ctx, change.clone())
-                    .ok()
-                    .flatten();
-                tree_change_to_json(None, change, patch)
-            })
-            .collect();
-
-        let output = JsonDiffOutput { changes };
+        let output = JsonDiffOutput {
+            changes: tree_changes_to_json(ctx, changes),
+        };
         json_out.write_value(output)?;
     } else if let Some(out) = out.for_human_or_shell() {
-        for change in result.changes {
+        for change in changes {
             let patch = but_api::diff::tree_change_diffs(ctx, change.clone())
                 .ok()
                 .flatten();
@@ -161,6 +160,22 @@ pub(crate) fn branch(
     Ok(())
 }
 
+/// Convert `changes` along with their patches into their JSON representation.
+pub(crate) fn tree_changes_to_json(
+    ctx: &Context,
+    changes: Vec<but_core::ui::TreeChange>,
+) -> Vec<JsonChange> {
+    changes
+        .into_iter()
+        .map(|change| {
+            let patch = but_api::diff::tree_change_diffs(ctx, change.clone())
+                .ok()
+                .flatten();
+            tree_change_to_json(None, change, patch)
+        })
+        .collect()
+}
+
 // Helper functions for JSON conversion
 
 fn hunk_to_json(id: Option<&str>, hunk: &but_core::SingleHunk) -> JsonChange {
@@ -209,6 +224,24 @@ fn tree_change_to_json(
     let diff = match patch {
         Some(UnifiedPatch::Binary) => JsonDiff::Binary,
         Some(UnifiedPatch::TooLarge { size_in_bytes }) => JsonDiff::TooLarge { size_in_bytes },
+        Some(UnifiedPatch::Submodule {
+            previous_commit,
+            commit,
+            commits_added,
+            commits_removed,
+        }) => JsonDiff::Submodule {
+            previous_commit: previous_commit.map(|id| id.to_string()),
+            commit: commit.map(|id| id.to_string()),
+            commits_added: commits_added.iter().map(Into::into).collect(),
+            commits_removed: commits_removed.iter().map(Into::into).collect(),
+        },
+        Some(UnifiedPatch::Lfs {
+            previous_pointer,
+            pointer,
+        }) => JsonDiff::Lfs {
+            previous_pointer,
+            pointer,
+        },
         Some(UnifiedPatch::Patch {
             hunks,
             is_result_of_binary_to_text_conversion,
@@ -148,6 +148,32 @@ impl CliOutput for DiffOutcome<'_> {
                 #[serde(skip_serializing_if = "std::ops::Not::not")]
                 is_binary_to_text: bool,
             },
+            Submodule {
+                previous_commit: Option<String>,
+                commit: Option<String>,
+                commits_added: Vec<SubmoduleCommit>,
+                commits_removed: Vec<SubmoduleCommit>,
+            },
+            Lfs {
+                previous_pointer: Option<but_core::diff::lfs::Pointer>,
+                pointer: Option<but_core::diff::lfs::Pointer>,
+            },
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct SubmoduleCommit {
+            id: String,
+            title: String,
+        }
+
+        impl From<&but_core::unified_diff::SubmoduleCommit> for SubmoduleCommit {
+            fn from(commit: &but_core::unified_diff::SubmoduleCommit) -> Self {
+                SubmoduleCommit {
+                    id: commit.id.to_string(),
+                    title: commit.title.to_string(),
+                }
+            }
         }
 
         #[derive(Serialize)]
@@ -233,6 +259,24 @@ impl CliOutput for DiffOutcome<'_> {
             let diff = match patch {
                 Some(UnifiedPatch::Binary) => Diff::Binary,
                 Some(UnifiedPatch::TooLarge { size_in_bytes }) => Diff::TooLarge { size_in_bytes },
+                Some(UnifiedPatch::Submodule {
+                    previous_commit,
+                    commit,
+                    commits_added,
+                    commits_removed,
+                }) => Diff::Submodule {
+                    previous_commit: previous_commit.map(|id| id.to_string()),
+                    commit: commit.map(|id| id.to_string()),
+                    commits_added: commits_added.iter().map(Into::into).collect(),
+                    commits_removed: commits_removed.iter().map(Into::into).collect(),
+                },
+                Some(UnifiedPatch::Lfs {
+                    previous_pointer,
+                    pointer,
+                }) => Diff::Lfs {
+                    previous_pointer,
+                    pointer,
+                },
                 Some(UnifiedPatch::Patch {
                     hunks,
                     is_result_of_binary_to_text_conversion,
@@ -0,0 +1,458 @@
+//! Find inconsistencies between what GitButler stores about a project and the repository itself,
+//! and repair those for which it's safe to do so.
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use but_core::ref_metadata::StackId;
+use but_ctx::Context;
+use but_meta::{legacy_storage, virtual_branches_legacy_types::VirtualBranches};
+use gitbutler_operating_modes::{
+    EDIT_BRANCH_REF, INTEGRATION_BRANCH_REF, WORKSPACE_BRANCH_REF, delete_edit_mode_metadata,
+    has_edit_mode_metadata, read_edit_mode_metadata,
+};
+use gitbutler_oplog::{
+    OplogExt,
+    entry::{OperationKind, SnapshotDetails},
+};
+use gix::refs::transaction::PreviousValue;
+
+use crate::{
+    theme::{self, Paint},
+    utils::OutputChannel,
+};
+
+/// Something that doesn't match between GitButler metadata and the repository.
+#[derive(Debug, PartialEq)]
+enum Issue {
+    /// A branch of a stack has no reference anymore.
+    MissingBranchRef {
+        stack_id: StackId,
+        branch: String,
+        /// The commit the branch pointed to the last time we saw it, if it still exists.
+        restore_to: Option<gix::ObjectId>,
+    },
+    /// A stack without any branch, which can't be shown or applied.
+    EmptyStack { stack_id: StackId },
+    /// The edit branch exists even though edit mode isn't active.
+    OrphanedEditBranch {
+        /// The tip of the edit branch if it isn't reachable from the workspace, so deleting the
+        /// branch would lose it.
+        unsaved_commit: Option<gix::ObjectId>,
+    },
+    /// Edit mode metadata exists even though edit mode isn't active.
+    StaleEditMetadata,
+    /// Edit mode is active, but what's edited is unknown.
+    UnreadableEditMetadata { error: String },
+    /// Uncommitted changes are assigned to a branch or stack that doesn't exist.
+    DanglingHunkAssignment { path: String, assigned_to: String },
+    /// Stacks are applied, but the workspace reference they are merged into doesn't exist.
+    MissingWorkspaceRef,
+    /// Stacks are applied, but `HEAD` is detached, so the workspace isn't checked out.
+    DetachedHead { commit_id: gix::ObjectId },
+}
+
+impl Issue {
+    fn kind(&self) -> &'static str {
+        match self {
+            Issue::MissingBranchRef { .. } => "missingBranchRef",
+            Issue::EmptyStack { .. } => "emptyStack",
+            Issue::OrphanedEditBranch { .. } => "orphanedEditBranch",
+            Issue::StaleEditMetadata => "staleEditMetadata",
+            Issue::UnreadableEditMetadata { .. } => "unreadableEditMetadata",
+            Issue::DanglingHunkAssignment { .. } => "danglingHunkAssignment",
+            Issue::MissingWorkspaceRef => "missingWorkspaceRef",
+            Issue::DetachedHead { .. } => "detachedHead",
+        }
+    }
+
+    fn description(&self) -> String {
+        match self {
+            Issue::MissingBranchRef {
+                stack_id, branch, ..
+            } => format!("Branch '{branch}' of stack {stack_id} has no reference"),
+            Issue::EmptyStack { stack_id } => format!("Stack {stack_id} has no branches"),
+            Issue::OrphanedEditBranch {
+                unsaved_commit: None,
+            } => format!("'{EDIT_BRANCH_REF}' exists, but no commit is being edited"),
+            Issue::OrphanedEditBranch {
+                unsaved_commit: Some(commit_id),
+            } => format!(
+                "'{EDIT_BRANCH_REF}' exists, but no commit is being edited, and its commit {} isn't in the workspace",
+                commit_id.to_hex_with_len(7)
+            ),
+            Issue::StaleEditMetadata => {
+                "Edit mode metadata exists, but no commit is being edited".into()
+            }
+            Issue::UnreadableEditMetadata { error } => {
+                format!("A commit is being edited, but the edit mode metadata is unusable: {error}")
+            }
+            Issue::DanglingHunkAssignment { path, assigned_to } => {
+                format!("Changes in '{path}' are assigned to '{assigned_to}', which doesn't exist")
+            }
+            Issue::MissingWorkspaceRef => {
+                format!("Stacks are applied, but '{WORKSPACE_BRANCH_REF}' doesn't exist")
+            }
+            Issue::DetachedHead { commit_id } => format!(
+                "Stacks are applied, but HEAD is detached at {}",
+                commit_id.to_hex_with_len(7)
+            ),
+        }
+    }
+
+    /// Describe what `--fix` will do about this issue, or `None` if it must be fixed by hand.
+    fn repair(&self) -> Option<&'static str> {
+        Some(match self {
+            Issue::MissingBranchRef {
+                restore_to: Some(_),
+                ..
+            } => "recreate the reference at the commit it last pointed to",
+            Issue::MissingBranchRef {
+                restore_to: None, ..
+            } => "remove the branch from its stack, and the stack if it has no branches left",
+            Issue::EmptyStack { .. } => "remove the stack",
+            Issue::OrphanedEditBranch {
+                unsaved_commit: None,
+            } => "delete the edit branch and its metadata",
+            Issue::OrphanedEditBranch {
+                unsaved_commit: Some(_),
+            } => return None,
+            Issue::StaleEditMetadata => "delete the edit mode metadata",
+            Issue::UnreadableEditMetadata { .. } => return None,
+            Issue::DanglingHunkAssignment { .. } => "unassign the changes",
+            Issue::MissingWorkspaceRef | Issue::DetachedHead { .. } => return None,
+        })
+    }
+
+    fn hint(&self) -> Option<&'static str> {
+        match self {
+            Issue::UnreadableEditMetadata { .. } => Some(
+                "save the edited commit elsewhere, then check out 'gitbutler/workspace' to leave edit mode",
+            ),
+            Issue::OrphanedEditBranch {
+                unsaved_commit: Some(_),
+            } => Some("keep its commit on another branch, then delete 'gitbutler/edit'"),
+            Issue::MissingWorkspaceRef => Some("run 'but setup' to create the workspace again"),
+            Issue::DetachedHead { .. } => {
+                Some("keep new commits on a branch, then check out 'gitbutler/workspace'")
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A single issue in the JSON output.
+#[derive(Debug, serde::Serialize)]
+struct IssueOutput {
+    kind: &'static str,
+    description: String,
+    /// What `--fix` does about the issue, or `None` if it can't be repaired automatically.
+    repair: Option<&'static str>,
+}
+
+/// JSON output for the doctor command.
+#[derive(Debug, serde::Serialize)]
+struct DoctorResult {
+    issues: Vec<IssueOutput>,
+    /// The amount of issues that were repaired, always `0` unless `--fix` was given.
+    repaired: usize,
+    /// The amount of issues that are left after `--fix`, always `0` without it.
+    remaining: usize,
+}
+
+pub fn handle(ctx: &mut Context, out: &mut OutputChannel, fix: bool) -> anyhow::Result<()> {
+    let issues = diagnose(ctx)?;
+    let repairable = issues.iter().filter(|i| i.repair().is_some()).count();
+    let fixing = fix && repairable > 0;
+
+    let (repaired, remaining) = if fixing {
+        let mut guard = ctx.exclusive_worktree_access();
+        ctx.create_snapshot(
+            SnapshotDetails::new(OperationKind::RepairWorkspace),
+            guard.write_permission(),
+        )
+        .context("Refusing to repair the workspace as it couldn't be snapshotted first")?;
+        // Taking a snapshot restores some branch references by itself, so look again.
+        repair(ctx, &diagnose(ctx)?)?;
+        // Only what's gone now was repaired, whether by the snapshot or by us, while repairs
+        // may also have caused new issues.
+        let issues_after = diagnose(ctx)?;
+        let repaired = issues
+            .iter()
+            .filter(|issue| !issues_after.contains(issue))
+            .count();
+        (repaired, issues_after.len())
+    } else {
+        (0, 0)
+    };
+
+    if let Some(out) = out.for_json() {
+        out.write_value(&DoctorResult {
+            issues: issues
+                .iter()
+                .map(|issue| IssueOutput {
+                    kind: issue.kind(),
+                    description: issue.description(),
+                    repair: issue.repair(),
+                })
+                .collect(),
+            repaired,
+            remaining,
+        })?;
+    } else if let Some(out) = out.for_human() {
+        let t = theme::get();
+        if issues.is_empty() {
+            writeln!(out, "{} No problems found.", t.sym().success)?;
+            return Ok(());
+        }
+        for issue in &issues {
+            writeln!(out, "{} {}", t.sym().warning, issue.description())?;
+            match (issue.repair(), issue.hint()) {
+                (Some(repair), _) => {
+                    writeln!(out, "    {}", t.hint.paint(format!("fix: {repair}")))?
+                }
+                (None, Some(hint)) => {
+                    writeln!(out, "    {}", t.hint.paint(format!("hint: {hint}")))?
+                }
+                (None, None) => {}
+            }
+        }
+        if fixing {
+            writeln!(
+                out,
+                "{} Repaired {} problem(s)",
+                t.sym().success,
+                t.important.paint(repaired.to_string())
+            )?;
+            if remaining > 0 {
+                writeln!(
+                    out,
+                    "{} problem(s) remain, run '{}' to see them",
+                    t.important.paint(remaining.to_string()),
+                    t.command_suggestion.paint("but doctor")
+                )?;
+            }
+        } else if repairable > 0 {
+            writeln!(
+                out,
+                "Found {} problem(s), {} of which can be repaired with '{}'",
+                t.important.paint(issues.len().to_string()),
+                t.important.paint(repairable.to_string()),
+                t.command_suggestion.paint("but doctor --fix")
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn virtual_branches_path(ctx: &Context) -> std::path::PathBuf {
+    ctx.project_data_dir().join("virtual_branches.toml")
+}
+
+/// Find all issues, without changing anything.
+fn diagnose(ctx: &Context) -> anyhow::Result<Vec<Issue>> {
+    let repo = ctx.repo.get()?;
+    let vb = legacy_storage::read_synced_virtual_branches(&virtual_branches_path(ctx))?;
+    let mut issues = Vec::new();
+
+    let mut stacks: Vec<_> = vb.branches.values().collect();
+    stacks.sort_by_key(|stack| stack.order);
+    for stack in stacks {
+        if stack.heads.is_empty() {
+            issues.push(Issue::EmptyStack { stack_id: stack.id });
+        }
+        for head in &stack.heads {
+            if branch_ref(&head.name).is_some_and(|name| ref_exists(&repo, &name)) {
+                continue;
+            }
+            issues.push(Issue::MissingBranchRef {
+                stack_id: stack.id,
+                branch: head.name.clone(),
+                restore_to: (!head.head.is_null() && repo.find_commit(head.head).is_ok())
+                    .then_some(head.head),
+            });
+        }
+    }
+
+    if vb.branches.values().any(|stack| stack.in_workspace) {
+        if repo.try_find_reference(WORKSPACE_BRANCH_REF)?.is_none()
+            && repo.try_find_reference(INTEGRATION_BRANCH_REF)?.is_none()
+        {
+            issues.push(Issue::MissingWorkspaceRef);
+        }
+        let head = repo.head()?;
+        if head.is_detached()
+            && let Some(commit_id) = head.id()
+        {
+            issues.push(Issue::DetachedHead {
+                commit_id: commit_id.detach(),
+            });
+        }
+    }
+
+    let head_is_edit_branch = repo
+        .head_name()?
+        .is_some_and(|name| name.as_bstr() == EDIT_BRANCH_REF);
+    if head_is_edit_branch {
+        if let Err(err) = read_edit_mode_metadata(ctx) {
+            issues.push(Issue::UnreadableEditMetadata {
+                error: format!("{err:#}"),
+            });
+        }
+    } else if let Some(mut edit_branch) = repo.try_find_reference(EDIT_BRANCH_REF)? {
+        let tip = edit_branch.peel_to_id()?.detach();
+        let in_workspace = repo
+            .try_find_reference(WORKSPACE_BRANCH_REF)?
+            .and_then(|mut workspace| workspace.peel_to_id().ok())
+            .is_some_and(|workspace_tip| {
+                repo.merge_base(tip, workspace_tip)
+                    .is_ok_and(|base| base.detach() == tip)
+            });
+        issues.push(Issue::OrphanedEditBranch {
+            unsaved_commit: (!in_workspace).then_some(tip),
+        });
+    } else if has_edit_mode_metadata(ctx) {
+        issues.push(Issue::StaleEditMetadata);
+    }
+
+    let db = ctx.db.get_cache()?;
+    for assignment in db.hunk_assignments().list_all()? {
+        let assigned_to = match (&assignment.branch_ref_bytes, &assignment.stack_id) {
+            (Some(branch_ref), _) => {
+                if assigned_ref_exists(&repo, branch_ref) {
+                    continue;
+                }
+                String::from_utf8_lossy(branch_ref).into_owned()
+            }
+            (None, Some(stack_id)) => {
+                if vb.branches.keys().any(|id| id.to_string() == *stack_id) {
+                    continue;
+                }
+                format!("stack {stack_id}")
+            }
+            (None, None) => continue,
+        };
+        issues.push(Issue::DanglingHunkAssignment {
+            path: assignment.path,
+            assigned_to,
+        });
+    }
+
+    Ok(issues)
+}
+
+/// Repair all `issues` that can be repaired.
+fn repair(ctx: &Context, issues: &[Issue]) -> anyhow::Result<()> {
+    let repo = ctx.repo.get()?;
+    let vb_path = virtual_branches_path(ctx);
+    let mut vb = legacy_storage::read_synced_virtual_branches(&vb_path)?;
+    let mut vb_changed = false;
+    let mut unassign_changes = false;
+
+    for issue in issues {
+        match issue {
+            Issue::MissingBranchRef {
+                branch,
+                restore_to: Some(commit_id),
+                ..
+            } => {
+                let name = branch_ref(branch)
+                    .with_context(|| format!("'{branch}' isn't a valid branch name"))?;
+                repo.reference(
+                    name,
+                    *commit_id,
+                    PreviousValue::MustNotExist,
+                    "GitButler: restore branch",
+                )?;
+            }
+            Issue::MissingBranchRef {
+                stack_id,
+                branch,
+                restore_to: None,
+            } => {
+                if let Some(stack) = vb.branches.get_mut(stack_id) {
+                    stack.heads.retain(|head| head.name != *branch);
+                    vb_changed = true;
+                }
+            }
+            Issue::EmptyStack { stack_id } => {
+                vb_changed |= vb.branches.remove(stack_id).is_some();
+            }
+            Issue::OrphanedEditBranch {
+                unsaved_commit: None,
+            } => {
+                repo.find_reference(EDIT_BRANCH_REF)?.delete()?;
+                delete_edit_mode_metadata(ctx)?;
+            }
+            Issue::StaleEditMetadata => delete_edit_mode_metadata(ctx)?,
+            Issue::UnreadableEditMetadata { .. }
+            | Issue::OrphanedEditBranch { .. }
+            | Issue::MissingWorkspaceRef
+            | Issue::DetachedHead { .. } => {}
+            Issue::DanglingHunkAssignment { .. } => unassign_changes = true,
+        }
+    }
+
+    if vb_changed {
+        vb.branches.retain(|_, stack| !stack.heads.is_empty());
+        legacy_storage::write_virtual_branches_and_sync(&vb_path, &vb)?;
+    }
+    if unassign_changes {
+        unassign_dangling_changes(ctx, &repo, &vb)?;
+    }
+    ctx.invalidate_workspace_cache()?;
+    Ok(())
+}
+
+/// Drop all hunk assignments to branches or stacks that don't exist.
+///
+/// Assignments that are only known by their stack are kept by assigning them to the top-most
+/// branch of their stack, as rewriting them would otherwise drop the stack.
+fn unassign_dangling_changes(
+    ctx: &Context,
+    repo: &gix::Repository,
+    vb: &VirtualBranches,
+) -> anyhow::Result<()> {
+    let top_branch_by_stack: HashMap<String, &str> = vb
+        .branches
+        .values()
+        .filter_map(|stack| Some((stack.id.to_string(), stack.heads.last()?.name.as_str())))
+        .collect();
+
+    let mut db = ctx.db.get_cache_mut()?;
+    let assignments = db
+        .hunk_assignments()
+        .list_all()?
+        .into_iter()
+        .filter_map(|mut assignment| {
+            if assignment.branch_ref_bytes.is_none()
+                && let Some(stack_id) = &assignment.stack_id
+            {
+                let top_branch = top_branch_by_stack.get(stack_id)?;
+                assignment.branch_ref_bytes = Some(format!("refs/heads/{top_branch}").into_bytes());
+            }
+            let exists = assignment
+                .branch_ref_bytes
+                .as_ref()
+                .is_none_or(|branch_ref| assigned_ref_exists(repo, branch_ref));
+            exists.then_some(assignment)
+        })
+        .collect();
+    db.hunk_assignments_mut()?.set_all(assignments)?;
+    Ok(())
+}
+
+/// Return the full reference name of the local branch `name`, as stored in stack metadata.
+fn branch_ref(name: &str) -> Option<gix::refs::FullName> {
+    gix::refs::FullName::try_from(format!("refs/heads/{name}")).ok()
+}
+
+fn ref_exists(repo: &gix::Repository, name: &gix::refs::FullName) -> bool {
+    repo.try_find_reference(name.as_ref())
+        .is_ok_and(|r| r.is_some())
+}
+
+/// Return `true` if `branch_ref`, as stored with a hunk assignment, names an existing reference.
+fn assigned_ref_exists(repo: &gix::Repository, branch_ref: &[u8]) -> bool {
+    gix::refs::FullName::try_from(branch_ref).is_ok_and(|name| ref_exists(repo, &name))
+}
@@ -0,0 +1,63 @@
+use but_ctx::Context;
+use serde::Serialize;
+
+use crate::{CliResult, IdMap, args::format_patch::Platform, bad_input, utils::OutputChannel};
+
+pub fn format_patch(ctx: &mut Context, out: &mut OutputChannel, args: Platform) -> CliResult<()> {
+    let Platform {
+        branch,
+        cover_letter,
+        description,
+        output_directory,
+    } = args;
+
+    let (refname, base) = {
+        let guard = ctx.shared_worktree_access();
+        let id_map = IdMap::new_from_context(ctx, guard.read_permission())?;
+        let (repo, ws, _db) = ctx.workspace_and_db_with_perm(guard.read_permission())?;
+        let refname = branch.resolve_existing_local_branch(&repo, &id_map)?;
+        let Some((stack, _)) = ws.find_segment_and_stack_by_refname(refname.as_ref()) else {
+            return Err(bad_input(format!(
+                "Branch {} is not applied to the workspace",
+                refname.shorten()
+            ))
+            .into());
+        };
+        let Some(base) = stack.base() else {
+            return Err(bad_input(format!(
+                "The stack of {} has no base to create patches against",
+                refname.shorten()
+            ))
+            .into());
+        };
+        (refname, base)
+    };
+
+    let current_dir = std::env::current_dir()?;
+    let output_directory = current_dir.join(output_directory);
+    let repo = ctx.repo.get()?;
+    let files = gitbutler_repo::patch_series::format_patch(
+        &repo,
+        base,
+        refname.as_ref(),
+        description.as_deref().or(cover_letter.then_some("")),
+        &output_directory,
+    )?;
+    let files: Vec<_> = files
+        .iter()
+        .map(|path| path.strip_prefix(&current_dir).unwrap_or(path))
+        .collect();
+
+    if let Some(out) = out.for_json() {
+        #[derive(Serialize)]
+        struct Output<'a> {
+            patches: Vec<&'a std::path::Path>,
+        }
+        out.write_value(Output { patches: files })?;
+    } else if let Some(out) = out.for_human() {
+        for file in files {
+            writeln!(out, "{}", file.display())?;
+        }
+    }
+    Ok(())
+}
@@ -6,18 +6,24 @@ use bstr::ByteSlice;
 
 pub mod absorb;
 pub mod actions;
+pub mod am;
 pub mod amend;
 pub mod apply;
+pub mod bisect;
+pub mod blame;
 pub mod branch;
 pub mod clean;
 pub mod commit;
 pub mod commit_message_prep;
 pub(crate) mod conflict_notice;
+pub mod debug;
 pub mod diff;
 #[cfg(feature = "legacy")]
 pub mod diff2;
 pub mod discard;
+pub mod doctor;
 pub mod forge;
+pub mod format_patch;
 pub mod land;
 pub mod r#move;
 pub mod oplog;
@@ -26,10 +32,12 @@ pub mod pull;
 pub mod push;
 pub mod refresh;
 pub mod resolve;
+pub mod revert;
 pub mod reword;
 pub mod reword2;
 pub mod setup;
 pub mod show;
+pub mod split;
 pub mod squash;
 pub mod status;
 pub mod teardown;
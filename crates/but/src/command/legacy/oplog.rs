@@ -2,9 +2,10 @@ use but_api::legacy::oplog::RestoreKind;
 use but_core::RepositoryExt;
 use gitbutler_oplog::entry::{OperationKind, Snapshot, Trailer};
 use gix::{date::time::CustomFormat, prelude::ObjectIdExt};
+use ratatui::style::Style;
 
 use crate::{
-    theme::{self, Paint},
+    theme::{self, Paint, Theme},
     utils::{OutputChannel, shorten_object_id},
 };
 
@@ -155,6 +156,7 @@ pub(crate) fn show_oplog(
                     | OperationKind::DiscardCommit
                     | OperationKind::UnapplyBranch
                     | OperationKind::CherryPick
+                    | OperationKind::RevertCommit
                     | OperationKind::SquashCommit
                     | OperationKind::UpdateCommitMessage
                     | OperationKind::MoveCommit
@@ -175,7 +177,9 @@ pub(crate) fn show_oplog(
                     | OperationKind::AutoHandleChangesBefore
                     | OperationKind::AutoHandleChangesAfter
                     | OperationKind::SplitBranch
+                    | OperationKind::SplitCommit
                     | OperationKind::CleanWorkspace
+                    | OperationKind::RepairWorkspace
                     | OperationKind::Unknown => details.operation.title().to_owned(),
                 };
 
@@ -185,63 +189,8 @@ pub(crate) fn show_oplog(
                 (OperationKind::Unknown, "Unknown operation".to_string())
             };
 
-            let operation_colored = match operation_type {
-                OperationKind::CreateCommit => t.success.paint(operation_type.kind_str()),
-                OperationKind::UpdateCommitMessage
-                | OperationKind::AmendCommit
-                | OperationKind::ResolveConflictsAi => t.attention.paint(operation_type.kind_str()),
-                OperationKind::UndoCommit
-                | OperationKind::RestoreFromSnapshot
-                | OperationKind::RestoreFromSnapshotViaUndo
-                | OperationKind::RestoreFromSnapshotViaRedo => {
-                    t.error.paint(operation_type.kind_str())
-                }
-                OperationKind::DiscardChanges | OperationKind::Discard => {
-                    t.error.paint(operation_type.kind_str())
-                }
-                OperationKind::CreateBranch => t.local_branch.paint(operation_type.kind_str()),
-                OperationKind::MoveCommit
-                | OperationKind::ReorderCommit
-                | OperationKind::MoveHunk => t.info.paint(operation_type.kind_str()),
-                OperationKind::OnDemandSnapshot => t.hint.paint(operation_type.kind_str()),
-                OperationKind::StashIntoBranch
-                | OperationKind::SetBaseBranch
-                | OperationKind::MergeUpstream
-                | OperationKind::UpdateWorkspaceBase
-                | OperationKind::UpdateBranchName
-                | OperationKind::UpdateBranchNotes
-                | OperationKind::ReorderBranches
-                | OperationKind::UpdateBranchRemoteName
-                | OperationKind::GenericBranchUpdate
-                | OperationKind::DeleteBranch
-                | OperationKind::ApplyBranch
-                | OperationKind::DiscardLines
-                | OperationKind::DiscardHunk
-                | OperationKind::DiscardFile
-                | OperationKind::Absorb
-                | OperationKind::AutoCommit
-                | OperationKind::DiscardCommit
-                | OperationKind::UnapplyBranch
-                | OperationKind::CherryPick
-                | OperationKind::SquashCommit
-                | OperationKind::MoveBranch
-                | OperationKind::TearOffBranch
-                | OperationKind::InsertBlankCommit
-                | OperationKind::MoveCommitFile
-                | OperationKind::FileChanges
-                | OperationKind::EnterEditMode
-                | OperationKind::SyncWorkspace
-                | OperationKind::CreateDependentBranch
-                | OperationKind::RemoveDependentBranch
-                | OperationKind::UpdateDependentBranchName
-                | OperationKind::UpdateDependentBranchDescription
-                | OperationKind::UpdateDependentBranchPrNumber
-                | OperationKind::AutoHandleChangesBefore
-                | OperationKind::AutoHandleChangesAfter
-                | OperationKind::SplitBranch
-                | OperationKind::CleanWorkspace
-                | OperationKind::Unknown => t.default.paint(operation_type.kind_str()),
-            };
+            let operation_colored =
+                operation_kind_style(operation_type, t).paint(operation_type.kind_str());
 
             writeln!(
                 out,
@@ -257,7 +206,68 @@ pub(crate) fn show_oplog(
     Ok(())
 }
 
-fn snapshot_time_string(snapshot: &Snapshot) -> String {
+/// The style an operation kind is highlighted with when listing the oplog.
+pub(crate) fn operation_kind_style(kind: OperationKind, t: &Theme) -> Style {
+    match kind {
+        OperationKind::CreateCommit => t.success,
+        OperationKind::UpdateCommitMessage
+        | OperationKind::AmendCommit
+        | OperationKind::ResolveConflictsAi => t.attention,
+        OperationKind::UndoCommit
+        | OperationKind::RestoreFromSnapshot
+        | OperationKind::RestoreFromSnapshotViaUndo
+        | OperationKind::RestoreFromSnapshotViaRedo
+        | OperationKind::DiscardChanges
+        | OperationKind::Discard => t.error,
+        OperationKind::CreateBranch => t.local_branch,
+        OperationKind::MoveCommit | OperationKind::ReorderCommit | OperationKind::MoveHunk => {
+            t.info
+        }
+        OperationKind::OnDemandSnapshot => t.hint,
+        OperationKind::StashIntoBranch
+        | OperationKind::SetBaseBranch
+        | OperationKind::MergeUpstream
+        | OperationKind::UpdateWorkspaceBase
+        | OperationKind::UpdateBranchName
+        | OperationKind::UpdateBranchNotes
+        | OperationKind::ReorderBranches
+        | OperationKind::UpdateBranchRemoteName
+        | OperationKind::GenericBranchUpdate
+        | OperationKind::DeleteBranch
+        | OperationKind::ApplyBranch
+        | OperationKind::DiscardLines
+        | OperationKind::DiscardHunk
+        | OperationKind::DiscardFile
+        | OperationKind::Absorb
+        | OperationKind::AutoCommit
+        | OperationKind::DiscardCommit
+        | OperationKind::UnapplyBranch
+        | OperationKind::CherryPick
+        | OperationKind::RevertCommit
+        | OperationKind::SquashCommit
+        | OperationKind::MoveBranch
+        | OperationKind::TearOffBranch
+        | OperationKind::InsertBlankCommit
+        | OperationKind::MoveCommitFile
+        | OperationKind::FileChanges
+        | OperationKind::EnterEditMode
+        | OperationKind::SyncWorkspace
+        | OperationKind::CreateDependentBranch
+        | OperationKind::RemoveDependentBranch
+        | OperationKind::UpdateDependentBranchName
+        | OperationKind::UpdateDependentBranchDescription
+        | OperationKind::UpdateDependentBranchPrNumber
+        | OperationKind::AutoHandleChangesBefore
+        | OperationKind::AutoHandleChangesAfter
+        | OperationKind::SplitBranch
+        | OperationKind::SplitCommit
+        | OperationKind::CleanWorkspace
+        | OperationKind::RepairWorkspace
+        | OperationKind::Unknown => t.default,
+    }
+}
+
+pub(crate) fn snapshot_time_string(snapshot: &Snapshot) -> String {
     let time = snapshot.created_at;
     // TODO: use `format_or_unix`.
     time.format(ISO8601_NO_TZ)
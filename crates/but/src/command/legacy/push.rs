@@ -1300,6 +1300,7 @@ mod tests {
                 "refs/remotes/upstream/feature".try_into()?,
             )],
             branch_sha_updates: vec![],
+            ref_updates: vec![],
         };
 
         assert_eq!(
@@ -1315,6 +1316,7 @@ mod tests {
             remote: "origin".to_string(),
             branch_to_remote: vec![],
             branch_sha_updates: vec![],
+            ref_updates: vec![],
         };
 
         assert_eq!(
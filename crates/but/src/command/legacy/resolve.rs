@@ -4,11 +4,12 @@ use std::{
 };
 
 use anyhow::{Context as _, Result, bail};
-use bstr::ByteSlice;
+use bstr::{BString, ByteSlice};
 use but_api::legacy::modes::{
     abort_edit_and_return_to_workspace, edit_initial_index_state, enter_edit_mode, operating_mode,
     save_edit_and_return_to_workspace_with_output,
 };
+use but_core::ref_metadata::StackId;
 use but_ctx::Context;
 use gitbutler_commit::commit_ext::{CommitExt, CommitMessageBstr};
 use gitbutler_edit_mode::commands::changes_from_initial;
@@ -60,7 +61,10 @@ pub(crate) fn handle(
 
 /// Resolve a user-provided commit identifier (CLI ID or partial SHA) to an
 /// object id, along with its display ref rendered from the same map.
-fn parse_commit_id(ctx: &mut Context, commit_id_str: &str) -> Result<(gix::ObjectId, String)> {
+pub(crate) fn parse_commit_id(
+    ctx: &mut Context,
+    commit_id_str: &str,
+) -> Result<(gix::ObjectId, String)> {
     // Create an IdMap to resolve commit IDs (supports both CLI IDs and partial SHAs)
     let id_map = IdMap::legacy_new_from_context(ctx)?;
 
@@ -100,22 +104,61 @@ fn parse_commit_id(ctx: &mut Context, commit_id_str: &str) -> Result<(gix::Objec
 
 fn enter_resolution(ctx: &mut Context, out: &mut OutputChannel, commit_id_str: &str) -> Result<()> {
     let t = theme::get();
-    use gix::{prelude::ObjectIdExt as _, revision::walk::Sorting};
 
     let (commit_gix_oid, commit_ref) = parse_commit_id(ctx, commit_id_str)?;
+    enter_edit_mode_for_conflicted_commit(ctx, commit_gix_oid, &commit_ref)?;
 
-    // Get the commit and check if it's conflicted
-    let repo = ctx.repo.get()?;
-    let commit = repo
-        .find_commit(commit_gix_oid)
-        .context("Failed to find commit")?;
+    // Show checkout message
+    if let Some(out) = out.for_human() {
+        writeln!(
+            out,
+            "{} {}",
+            t.important.paint("Checking out conflicted commit"),
+            commit_ref
+        )?;
+    }
+
+    // Now show the same status as `but resolve status` would show
+    show_status(ctx, out)
+}
 
-    if !commit.is_conflicted() {
+/// Check out the conflicted commit `commit_id`, displayed as `commit_ref` in errors, in edit mode
+/// so its conflicts can be resolved in the worktree.
+pub(crate) fn enter_edit_mode_for_conflicted_commit(
+    ctx: &mut Context,
+    commit_id: gix::ObjectId,
+    commit_ref: &str,
+) -> Result<()> {
+    // Get the commit and check if it's conflicted
+    let is_conflicted = ctx
+        .repo
+        .get()?
+        .find_commit(commit_id)
+        .context("Failed to find commit")?
+        .is_conflicted();
+
+    if !is_conflicted {
         bail!(
             "Commit {commit_ref} is not in a conflicted state. Only conflicted commits can be resolved."
         );
     }
 
+    let stack_id = find_stack_containing_commit(ctx, commit_id, commit_ref)?;
+
+    // Enter edit mode
+    enter_edit_mode(ctx, commit_id, stack_id).context("Failed to enter edit mode")?;
+    Ok(())
+}
+
+/// Return the id of the applied stack that contains `commit_id`, displayed as `commit_ref` in errors.
+pub(crate) fn find_stack_containing_commit(
+    ctx: &mut Context,
+    commit_id: gix::ObjectId,
+    commit_ref: &str,
+) -> Result<StackId> {
+    use gix::{prelude::ObjectIdExt as _, revision::walk::Sorting};
+
+    let repo = ctx.repo.get()?;
     // Find which stack this commit belongs to
     let stacks = crate::legacy::workspace::applied_stacks(ctx)?;
     let mut found_stack_id = None;
@@ -134,7 +177,7 @@ fn enter_resolution(ctx: &mut Context, out: &mut OutputChannel, commit_id_str: &
 
             for info in traversal {
                 let info = info?;
-                if info.id == commit_gix_oid {
+                if info.id == commit_id {
                     found_stack_id = stack.id;
                     break 'outer;
                 }
@@ -142,27 +185,8 @@ fn enter_resolution(ctx: &mut Context, out: &mut OutputChannel, commit_id_str: &
         }
     }
 
-    let stack_id = found_stack_id
-        .ok_or_else(|| anyhow::anyhow!("Could not find stack containing commit {commit_ref}"))?;
-
-    drop(commit);
-    drop(repo);
-
-    // Enter edit mode
-    enter_edit_mode(ctx, commit_gix_oid, stack_id).context("Failed to enter edit mode")?;
-
-    // Show checkout message
-    if let Some(out) = out.for_human() {
-        writeln!(
-            out,
-            "{} {}",
-            t.important.paint("Checking out conflicted commit"),
-            commit_ref
-        )?;
-    }
-
-    // Now show the same status as `but resolve status` would show
-    show_status(ctx, out)
+    found_stack_id
+        .ok_or_else(|| anyhow::anyhow!("Could not find stack containing commit {commit_ref}"))
 }
 
 fn show_status(ctx: &mut Context, out: &mut OutputChannel) -> Result<()> {
@@ -346,7 +370,7 @@ fn show_conflicted_files(ctx: &mut Context, out: &mut OutputChannel) -> Result<b
 
 /// Check if a file contains git conflict markers
 /// Matches the logic from the GUI's looksConflicted() function
-fn has_conflict_markers(content: &str) -> bool {
+pub(crate) fn has_conflict_markers(content: &str) -> bool {
     content.lines().any(|line| line.starts_with("<<<<<<<"))
 }
 
@@ -412,18 +436,10 @@ fn finish_resolution(ctx: &mut Context, out: &mut OutputChannel) -> Result<()> {
         return show_workflow_help(out);
     }
 
-    // Capture conflicted commits BEFORE the rebase
-    let conflicts_before = find_conflicted_commits(ctx)?;
-
-    // Note files that still contain conflict markers, so the finish output can
-    // answer the "did I leave markers behind?" question without a re-scan.
-    // The scan may false-positive on legitimate content, so it warns rather
-    // than refusing to finalize.
-    let files_with_markers = files_with_conflict_markers(ctx)?;
-
-    // Save and return to workspace, capturing the rebase output
-    save_edit_and_return_to_workspace_with_output(ctx)
-        .context("Failed to save resolution and return to workspace")?;
+    let SavedResolution {
+        conflicts_before,
+        files_with_markers,
+    } = save_resolution(ctx)?;
 
     if let Some(human_out) = out.for_human() {
         writeln!(
@@ -478,18 +494,57 @@ fn finish_resolution(ctx: &mut Context, out: &mut OutputChannel) -> Result<()> {
     Ok(())
 }
 
+/// What [`save_resolution()`] observed before saving, for reporting.
+pub(crate) struct SavedResolution {
+    /// The conflicted commits as they were before the resolution was saved.
+    pub(crate) conflicts_before: BTreeMap<String, Vec<ConflictedCommit>>,
+    /// Initially-conflicted paths that still contained conflict markers when saving.
+    pub(crate) files_with_markers: Vec<String>,
+}
+
+/// Save the resolution of the commit checked out in edit mode, rebase its descendants and return
+/// to the workspace. This is `but resolve finish` without its output.
+pub(crate) fn save_resolution(ctx: &mut Context) -> Result<SavedResolution> {
+    // Capture conflicted commits BEFORE the rebase
+    let conflicts_before = find_conflicted_commits(ctx)?;
+
+    // Note files that still contain conflict markers, so the finish output can
+    // answer the "did I leave markers behind?" question without a re-scan.
+    // The scan may false-positive on legitimate content, so it warns rather
+    // than refusing to finalize.
+    let files_with_markers = files_with_conflict_markers(ctx)?;
+
+    // Save and return to workspace, capturing the rebase output
+    save_edit_and_return_to_workspace_with_output(ctx)
+        .context("Failed to save resolution and return to workspace")?;
+
+    Ok(SavedResolution {
+        conflicts_before,
+        files_with_markers,
+    })
+}
+
+/// Paths of the files that were conflicted when the commit was checked out in edit mode.
+pub(crate) fn initially_conflicted_paths(ctx: &mut Context) -> Result<Vec<BString>> {
+    Ok(edit_initial_index_state(ctx)
+        .context("Failed to get conflicted files")?
+        .into_iter()
+        .filter(|(_, conflict)| conflict.is_some())
+        .map(|(change, _)| change.path)
+        .collect())
+}
+
 /// Paths of initially-conflicted files that still contain conflict markers in
 /// the edit-mode worktree.
 fn files_with_conflict_markers(ctx: &mut Context) -> Result<Vec<String>> {
-    let conflicted_files = edit_initial_index_state(ctx)?;
+    let conflicted_paths = initially_conflicted_paths(ctx)?;
     let repo = ctx.repo.get()?;
     let repo_path = repo.workdir().context("No workdir")?;
 
-    Ok(conflicted_files
+    Ok(conflicted_paths
         .iter()
-        .filter(|(_, conflict)| conflict.is_some())
-        .filter_map(|(change, _)| {
-            let path = change.path.to_str_lossy().to_string();
+        .filter_map(|path| {
+            let path = path.to_str_lossy().to_string();
             let content = std::fs::read_to_string(repo_path.join(&path)).ok()?;
             has_conflict_markers(&content).then_some(path)
         })
@@ -724,7 +779,7 @@ fn resolve_one_with_ai(
 /// Strip control characters (keeping newlines and tabs) from text that will
 /// be written to the terminal but originates outside this program - model
 /// output or repository file content.
-fn sanitize_terminal_text(text: &str) -> String {
+pub(crate) fn sanitize_terminal_text(text: &str) -> String {
     text.chars()
         .filter(|c| !c.is_control() || matches!(c, '\n' | '\t'))
         .collect()
@@ -893,7 +948,7 @@ fn report_conflicts_after_rebase(
 /// A descendant branch's walk contains its conflicted ancestors, so preserving
 /// each list's order keeps dependencies bottom-up. The branch-map order only
 /// breaks ties between independent histories, where either order is valid.
-fn unique_conflict_queue(
+pub(crate) fn unique_conflict_queue(
     conflicts_by_branch: &BTreeMap<String, Vec<ConflictedCommit>>,
 ) -> Vec<&ConflictedCommit> {
     let mut seen = HashSet::new();
@@ -0,0 +1,301 @@
+use but_api::{
+    WorkspaceState,
+    json::{ChangeIdString, HexHash},
+};
+use but_core::{
+    DryRun, RefMetadata,
+    ref_metadata::StackId,
+    sync::{RepoExclusive, RepoShared},
+};
+use but_ctx::Context;
+use but_rebase::graph_rebase::mutate::{InsertSide, RelativeTo};
+use but_workspace::RefInfo;
+use gitbutler_oplog::entry::{OperationKind, SnapshotDetails, Trailer};
+use gix::ObjectId;
+use serde::Serialize;
+
+use crate::{
+    CliResult, IdMap,
+    args::{
+        atoms::{CommitArg, Priority, Purpose, ResolvedCliIdArg},
+        revert::Platform,
+    },
+    bad_input,
+    command::legacy::commit::{
+        BranchNameTarget, CommitAtOperation, CommitOperation, CommitOperationTargetIsh,
+        CommitRelativeToTarget, CommitToNewBranchOperation, RouteCommitOperationError,
+        route_commit_operation,
+    },
+    id::CommitId,
+    theme::{self, Theme},
+    utils::{
+        CliOutput, CliOutputHuman, IntermediateChannel, WriteWithUtils,
+        merged_upstream::MergedUpstream,
+    },
+};
+
+#[derive(Debug)]
+pub struct RevertOutcome {
+    pub reverted: ObjectId,
+    pub new_commit: CommitId,
+    pub branch_name: Option<BranchNameTarget>,
+}
+
+impl CliOutputHuman for RevertOutcome {
+    fn on_human(
+        self,
+        out: &mut dyn WriteWithUtils,
+        _agent: bool,
+        _theme: &Theme,
+    ) -> anyhow::Result<()> {
+        let Self {
+            reverted,
+            new_commit,
+            branch_name,
+        } = self;
+
+        let reverted = theme::Commit(CommitId {
+            commit_id: reverted,
+            change_id: None,
+        });
+        let new_commit = theme::Commit(new_commit);
+
+        match branch_name {
+            Some(BranchNameTarget::New(branch_name)) => writeln!(
+                out,
+                "Reverted {} onto new branch {} to create {}",
+                reverted,
+                theme::Branch(branch_name),
+                new_commit,
+            )?,
+            Some(BranchNameTarget::Existing(branch_name)) => writeln!(
+                out,
+                "Reverted {} onto branch {} to create {}",
+                reverted,
+                theme::Branch(branch_name),
+                new_commit,
+            )?,
+            None => writeln!(out, "Reverted {reverted} to create {new_commit}")?,
+        }
+
+        Ok(())
+    }
+}
+
+impl CliOutput for RevertOutcome {
+    fn on_json(self) -> impl serde::Serialize {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Output {
+            reverted_commit_id: HexHash,
+            new_commit_id: HexHash,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            new_change_id: Option<ChangeIdString>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            branch: Option<String>,
+        }
+
+        let Self {
+            reverted,
+            new_commit,
+            branch_name,
+        } = self;
+
+        Output {
+            reverted_commit_id: reverted.into(),
+            new_commit_id: new_commit.commit_id.into(),
+            new_change_id: new_commit.change_id.map(Into::into),
+            branch: branch_name.map(|branch_name| match branch_name {
+                BranchNameTarget::New(branch_name) | BranchNameTarget::Existing(branch_name) => {
+                    branch_name.shorten().to_string()
+                }
+            }),
+        }
+    }
+}
+
+pub fn revert(
+    ctx: &mut Context,
+    mut out: IntermediateChannel<'_>,
+    args: Platform,
+) -> CliResult<(RevertOutcome, WorkspaceState)> {
+    let mut guard = ctx.exclusive_worktree_access();
+    let mut meta = ctx.meta()?;
+    let id_map = IdMap::new_from_context(ctx, guard.read_permission())?;
+    let head_info = but_api::legacy::workspace::head_info(ctx)?;
+
+    let revert_op = resolve(
+        ctx,
+        &head_info,
+        &mut out,
+        guard.read_permission(),
+        &id_map,
+        args,
+    )?;
+
+    Ok(run(ctx, &mut meta, guard.write_permission(), revert_op)?)
+}
+
+fn resolve(
+    ctx: &Context,
+    head_info: &RefInfo,
+    out: &mut IntermediateChannel<'_>,
+    perm: &RepoShared,
+    id_map: &IdMap,
+    args: Platform,
+) -> CliResult<RevertOperation> {
+    let Platform {
+        commit,
+        into,
+        allow_merged,
+        skip_message_policy: _,
+    } = args;
+
+    let merged = MergedUpstream::new(&*ctx.repo.get()?, head_info, allow_merged);
+
+    let subject = {
+        let repo = ctx.repo.get()?;
+        if let Some(resolved) =
+            commit.try_resolve(&repo, id_map, Purpose::Source, Some(Priority::Commit))?
+        {
+            match resolved {
+                ResolvedCliIdArg::Commit(commit_id) => commit_id.commit_id,
+                ResolvedCliIdArg::Branch(..)
+                | ResolvedCliIdArg::UncommittedHunkOrFile(..)
+                | ResolvedCliIdArg::CommittedFile(..)
+                | ResolvedCliIdArg::Uncommitted
+                | ResolvedCliIdArg::PathPrefix { .. }
+                | ResolvedCliIdArg::Stack { .. } => {
+                    return Err(bad_input(format!(
+                        "Only commits can be reverted. {} is {}",
+                        commit,
+                        resolved.kind_for_humans()
+                    ))
+                    .into());
+                }
+            }
+        } else {
+            CommitArg(commit.0).resolve(&repo)?
+        }
+    };
+
+    // Without an explicit destination, the revert goes on top of the branch that holds the
+    // reverted commit, which is where it is most likely to apply cleanly.
+    if into.is_none()
+        && let Some(name) = containing_branch(head_info, subject, &merged)
+    {
+        return Ok(RevertOperation {
+            subject,
+            commit_op: CommitOperation::CommitAt(CommitAtOperation {
+                target: CommitRelativeToTarget::BranchTip { name },
+            }),
+        });
+    }
+
+    let target_ish = match into {
+        Some(branch) => CommitOperationTargetIsh::Branch(branch),
+        None => CommitOperationTargetIsh::Default,
+    };
+
+    let commit_op = {
+        let (repo, ws, _db) = ctx.workspace_and_db_with_perm(perm)?;
+        route_commit_operation(&repo, &ws, head_info, out, id_map, target_ish, &merged).map_err(
+            |err| match err {
+                RouteCommitOperationError::NoStackToCommitTo => {
+                    bad_input("Found no stack that the revert could be placed in").into()
+                }
+                RouteCommitOperationError::UnclearTargetCantPrompt => {
+                    bad_input("Unclear where to revert to. Found more than one stack")
+                        .hint("You can specify where to revert to with `--into <BRANCH>`")
+                        .into()
+                }
+                RouteCommitOperationError::Other(cli_error) => cli_error,
+            },
+        )?
+    };
+
+    Ok(RevertOperation { subject, commit_op })
+}
+
+/// The name of the applied, unmerged branch whose commits include `commit`, if any.
+fn containing_branch(
+    head_info: &RefInfo,
+    commit: ObjectId,
+    merged: &MergedUpstream,
+) -> Option<gix::refs::FullName> {
+    head_info
+        .stacks
+        .iter()
+        .flat_map(|stack| &stack.segments)
+        .filter(|segment| !merged.contains_segment(segment))
+        .find(|segment| segment.commits.iter().any(|local| local.inner.id == commit))
+        .and_then(|segment| segment.ref_info.as_ref())
+        .map(|ref_info| ref_info.ref_name.clone())
+}
+
+pub struct RevertOperation {
+    pub subject: ObjectId,
+    pub commit_op: CommitOperation,
+}
+
+pub fn run(
+    ctx: &mut Context,
+    meta: &mut impl RefMetadata,
+    perm: &mut RepoExclusive,
+    revert_op: RevertOperation,
+) -> anyhow::Result<(RevertOutcome, WorkspaceState)> {
+    let RevertOperation { subject, commit_op } = revert_op;
+
+    let snapshot_details =
+        SnapshotDetails::new(OperationKind::RevertCommit).with_trailers([Trailer::Sha(subject)]);
+    let ((new_commit, branch_name_target), ws) = but_transaction::with_transaction_with_perm(
+        ctx,
+        meta,
+        perm,
+        snapshot_details,
+        DryRun::No,
+        |mut tx| {
+            let (new_commit, branch_name_target) = match commit_op {
+                CommitOperation::CommitToNewBranch(CommitToNewBranchOperation { branch_name }) => {
+                    let branch_name = if let Some(branch_name) = branch_name {
+                        branch_name
+                    } else {
+                        but_core::branch::unique_canned_refname(tx.repo())?
+                    };
+
+                    tx.create_reference(
+                        branch_name.as_ref(),
+                        None,
+                        |_| StackId::generate(),
+                        Some(0),
+                    )?;
+
+                    let new_commit = tx.revert_commit(
+                        subject,
+                        RelativeTo::Reference(branch_name.clone()),
+                        InsertSide::Below,
+                    )?;
+
+                    (new_commit, Some(BranchNameTarget::New(branch_name)))
+                }
+                CommitOperation::CommitAt(op) => {
+                    let (relative_to, side, branch_name_target) = op.create_target(&mut tx)?;
+                    let new_commit = tx.revert_commit(subject, relative_to, side)?;
+
+                    (new_commit, branch_name_target)
+                }
+            };
+
+            Ok(but_transaction::Commit((new_commit, branch_name_target)))
+        },
+    )?;
+
+    Ok((
+        RevertOutcome {
+            reverted: subject,
+            new_commit: new_commit.into(),
+            branch_name: branch_name_target,
+        },
+        ws,
+    ))
+}
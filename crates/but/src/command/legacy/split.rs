@@ -0,0 +1,318 @@
+//! Implementation of the `but split` command.
+
+use bstr::{BString, ByteSlice as _};
+use but_api::{
+    WorkspaceState,
+    json::{ChangeIdString, HexHash},
+};
+use but_core::{DryRun, RefMetadata, sync::RepoExclusive};
+use but_ctx::Context;
+use but_workspace::commit::CommitSplitPiece;
+use gitbutler_oplog::entry::{OperationKind, SnapshotDetails, Trailer};
+use itertools::Itertools as _;
+use nonempty::NonEmpty;
+use serde::Serialize;
+
+use crate::{
+    CliResult, IdMap,
+    args::{
+        atoms::{CliIdArg, Purpose, ResolvedCliIdArg},
+        split::Platform,
+    },
+    bad_input,
+    id::{CommitId, CommittedFileId},
+    theme::{self, Theme},
+    utils::{
+        CliOutput, CliOutputHuman, CommitIdJson, IntermediateChannel, WriteWithUtils,
+        diff_specs::DiffSpecBuilder, merged_upstream::MergedUpstream,
+    },
+};
+
+#[derive(Debug)]
+pub struct SplitOutcome {
+    pub source: CommitId,
+    pub paths: NonEmpty<BString>,
+    pub new_commits: Vec<CommitId>,
+}
+
+impl CliOutputHuman for SplitOutcome {
+    fn on_human(
+        self,
+        out: &mut dyn WriteWithUtils,
+        _agent: bool,
+        _theme: &Theme,
+    ) -> anyhow::Result<()> {
+        let Self {
+            source,
+            paths,
+            new_commits,
+        } = self;
+
+        let paths = paths.iter().map(|path| path.as_bstr()).join(", ");
+        let new_commits = new_commits.into_iter().map(theme::Commit).join(", ");
+        writeln!(
+            out,
+            "Split {paths} out of {} to create {new_commits}",
+            theme::Commit(source)
+        )?;
+
+        Ok(())
+    }
+}
+
+impl CliOutput for SplitOutcome {
+    fn on_json(self) -> impl Serialize {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Output {
+            source_commit_id: HexHash,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            source_change_id: Option<ChangeIdString>,
+            paths: Vec<String>,
+            new_commits: Vec<CommitIdJson>,
+        }
+
+        let Self {
+            source,
+            paths,
+            new_commits,
+        } = self;
+
+        Output {
+            source_commit_id: source.commit_id.into(),
+            source_change_id: source.change_id.map(Into::into),
+            paths: paths
+                .into_iter()
+                .map(|path| path.to_str_lossy().into_owned())
+                .collect(),
+            new_commits: new_commits.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+pub fn split(
+    ctx: &mut Context,
+    _out: IntermediateChannel<'_>,
+    args: Platform,
+) -> CliResult<(SplitOutcome, WorkspaceState)> {
+    let mut guard = ctx.exclusive_worktree_access();
+    let mut meta = ctx.meta()?;
+    let id_map = IdMap::new_from_context(ctx, guard.read_permission())?;
+    let merged = MergedUpstream::from_ctx(ctx, args.allow_merged)?;
+    let operation = {
+        let repo = ctx.repo.get()?;
+        resolve(&repo, &id_map, &merged, args)?
+    };
+
+    Ok(run(ctx, &mut meta, guard.write_permission(), operation)?)
+}
+
+/// Separates a committed file from the index of one of its hunks, like in `<file>:#1`.
+const HUNK_INDEX_SEPARATOR: &str = ":#";
+
+fn resolve(
+    repo: &gix::Repository,
+    id_map: &IdMap,
+    merged: &MergedUpstream,
+    args: Platform,
+) -> CliResult<SplitOperation> {
+    let Platform {
+        changes,
+        pieces,
+        message: messages,
+        allow_merged: _,
+        skip_message_policy: _,
+    } = args;
+
+    let pieces: Vec<_> = std::iter::once(changes)
+        .filter(|changes| !changes.is_empty())
+        .chain(pieces.into_iter().map(|piece| piece.0))
+        .collect();
+    if !messages.is_empty() && messages.len() != pieces.len() {
+        return Err(bad_input(format!(
+            "Got {} messages for {} new commits",
+            messages.len(),
+            pieces.len()
+        ))
+        .arg_name("--message")
+        .hint("Give one message per new commit, or none to reuse the message of the split commit")
+        .into());
+    }
+    let mut messages = messages.into_iter();
+
+    let mut source = None::<CommitId>;
+    let mut selections = Vec::with_capacity(pieces.len());
+    for piece in pieces {
+        let mut changes = Vec::with_capacity(piece.len());
+        for change in piece {
+            let (file, hunk) = resolve_change(repo, id_map, &change)?;
+            match &source {
+                Some(source) if source.commit_id != file.commit_id => {
+                    return Err(
+                        bad_input("All committed files must come from the same commit")
+                            .arg_name("<CHANGES>")
+                            .hint("Split changes out of each commit separately")
+                            .into(),
+                    );
+                }
+                Some(_) => {}
+                None => {
+                    source = Some(CommitId {
+                        commit_id: file.commit_id,
+                        change_id: file.change_id,
+                    })
+                }
+            }
+            changes.push(SplitChange {
+                path: file.path,
+                hunk,
+            });
+        }
+        // Selecting a whole file includes all of its hunks.
+        let whole_files: Vec<_> = changes
+            .iter()
+            .filter(|change| change.hunk.is_none())
+            .map(|change| change.path.clone())
+            .collect();
+        changes.retain(|change| change.hunk.is_none() || !whole_files.contains(&change.path));
+
+        selections.push(SplitPieceSelection {
+            changes: NonEmpty::from_vec(changes.into_iter().unique().collect())
+                .expect("pieces have at least one change"),
+            message: messages.next(),
+        });
+    }
+
+    let source = source.expect("at least one piece with at least one change");
+    merged.ensure_commit_not_merged(source.commit_id)?;
+    Ok(SplitOperation {
+        source,
+        pieces: NonEmpty::from_vec(selections).expect("clap requires at least one piece"),
+    })
+}
+
+/// Resolve `change` to a committed file, and the index of the hunk it selects if it selects one.
+fn resolve_change(
+    repo: &gix::Repository,
+    id_map: &IdMap,
+    change: &CliIdArg,
+) -> CliResult<(CommittedFileId, Option<usize>)> {
+    let (file, hunk) = match change.0.rsplit_once(HUNK_INDEX_SEPARATOR) {
+        Some((file, index)) if let Ok(index) = index.parse::<usize>() => {
+            (CliIdArg(file.to_owned()), Some(index))
+        }
+        _ => (change.clone(), None),
+    };
+    match file.resolve_in_workspace(repo, id_map, Purpose::Source, None)? {
+        ResolvedCliIdArg::CommittedFile(committed_file) => Ok((committed_file, hunk)),
+        other => Err(bad_input(format!(
+            "Only committed files and their hunks can be split out of a commit. {change} is {}",
+            other.kind_for_humans()
+        ))
+        .arg_name("<CHANGES>")
+        .hint("Run `but status -f` to show committed files")
+        .into()),
+    }
+}
+
+/// A committed file, or one of its hunks, to split out of a commit.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SplitChange {
+    pub path: BString,
+    /// The index of the hunk in the changes of the file, or `None` for all of them.
+    pub hunk: Option<usize>,
+}
+
+/// The changes that go into one of the new commits.
+#[derive(Debug)]
+pub struct SplitPieceSelection {
+    pub changes: NonEmpty<SplitChange>,
+    /// The message of the new commit, or `None` to use the message of the split commit.
+    pub message: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct SplitOperation {
+    pub source: CommitId,
+    /// The new commits to create on top of `source`, parent first.
+    pub pieces: NonEmpty<SplitPieceSelection>,
+}
+
+pub fn run(
+    ctx: &mut Context,
+    meta: &mut impl RefMetadata,
+    perm: &mut RepoExclusive,
+    operation: SplitOperation,
+) -> anyhow::Result<(SplitOutcome, WorkspaceState)> {
+    let SplitOperation { source, pieces } = operation;
+
+    let paths = NonEmpty::from_vec(
+        pieces
+            .iter()
+            .flat_map(|piece| piece.changes.iter())
+            .map(|change| change.path.clone())
+            .unique()
+            .collect(),
+    )
+    .expect("pieces are non-empty");
+
+    let split_pieces = {
+        let context_lines = ctx.settings.context_lines;
+        let repo = ctx.repo.get()?;
+        let source_message: BString = repo
+            .find_commit(source.commit_id)?
+            .message_raw()?
+            .to_owned();
+
+        let mut split_pieces = vec![CommitSplitPiece {
+            changes: Vec::new(),
+            message: source_message.clone(),
+        }];
+        for piece in pieces {
+            let mut builder = DiffSpecBuilder::new(&repo, context_lines);
+            for SplitChange { path, hunk } in piece.changes.iter() {
+                match hunk {
+                    Some(index) => builder.push_hunk_from_committed_file(
+                        source.commit_id,
+                        path.as_bstr(),
+                        *index,
+                    )?,
+                    None => builder
+                        .push_changes_from_committed_file(source.commit_id, path.as_bstr())?,
+                }
+            }
+            let changes = builder.into_diff_specs();
+            anyhow::ensure!(!changes.is_empty(), "No committed changes to split");
+            split_pieces.push(CommitSplitPiece {
+                changes,
+                message: piece
+                    .message
+                    .map(Into::into)
+                    .unwrap_or_else(|| source_message.clone()),
+            });
+        }
+        split_pieces
+    };
+
+    let (new_commits, ws) = but_transaction::with_transaction_with_perm(
+        ctx,
+        meta,
+        perm,
+        SnapshotDetails::new(OperationKind::SplitCommit)
+            .with_trailers([Trailer::Sha(source.commit_id)]),
+        DryRun::No,
+        |mut tx| {
+            let new_commits = tx.split_commit(source.commit_id, split_pieces)?;
+            Ok(but_transaction::Commit(new_commits))
+        },
+    )?;
+
+    Ok((
+        SplitOutcome {
+            source,
+            paths,
+            new_commits: new_commits.into_iter().map(Into::into).collect(),
+        },
+        ws,
+    ))
+}
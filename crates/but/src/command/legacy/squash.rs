@@ -187,6 +187,8 @@ fn resolve_args(
         use_source_message,
         // Consumed by the caller when building the `MergedUpstream` guard.
         allow_merged: _,
+        // Applied by the caller before the context is used.
+        skip_message_policy: _,
     } = args;
 
     let reword = resolve_reword(message, no_message, use_target_message, use_source_message)?;
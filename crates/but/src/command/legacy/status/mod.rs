@@ -1655,11 +1655,11 @@ fn print_commit(
 ) -> anyhow::Result<()> {
     let t = crate::theme::get();
     let dot = match classification {
-        CommitClassification::Upstream => Span::styled("●", t.attention),
-        CommitClassification::LocalOnly => Span::raw("●"),
-        CommitClassification::Pushed => Span::styled("●", t.success),
-        CommitClassification::Modified => Span::styled("◐", t.success),
-        CommitClassification::Integrated => Span::styled("●", t.remote_branch),
+        CommitClassification::Upstream => Span::styled("●", t.commit_upstream),
+        CommitClassification::LocalOnly => Span::styled("●", t.commit_local),
+        CommitClassification::Pushed => Span::styled("●", t.commit_pushed),
+        CommitClassification::Modified => Span::styled("◐", t.commit_pushed),
+        CommitClassification::Integrated => Span::styled("●", t.commit_integrated),
     };
 
     let upstream_commit = matches!(commit_changes, CommitChanges::Remote(_));
@@ -117,6 +117,8 @@ impl App {
                 | Mode::MoveStack(..)
                 | Mode::PickChanges(..)
                 | Mode::CherryPick(..)
+                | Mode::Conflict(..)
+                | Mode::Oplog(..)
                 | Mode::Jump(..) => CommandReturnMode::Normal(NormalMode::default()),
             };
             backstack.push_leave_command_mode();
@@ -0,0 +1,1018 @@
+//! Resolve conflicted commits without leaving the status TUI.
+//!
+//! The mode first lists the conflicted commits. Resolving one checks it out in edit mode and lists
+//! its conflicted paths, where each conflict can be taken from one side, both sides, or fixed up in
+//! the editor before the resolution is finished or cancelled.
+
+use std::path::Path;
+
+use anyhow::{Context as _, bail};
+use bstr::{BString, ByteSlice};
+use but_api::legacy::modes::{abort_edit_and_return_to_workspace, operating_mode};
+use but_ctx::Context;
+use gitbutler_operating_modes::OperatingMode;
+use nonempty::NonEmpty;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+
+use crate::{
+    command::legacy::{
+        resolve::{self, ConflictedCommit, SavedResolution, sanitize_terminal_text},
+        status::tui::{
+            Message, ReloadCause,
+            app::{App, Modal},
+            confirm::Confirm,
+            mode::Mode,
+            render::ModeRender,
+            toast::ToastKind,
+        },
+    },
+    tui::{TerminalGuard, get_text},
+};
+
+#[derive(Debug, Clone)]
+pub enum ConflictMode {
+    /// Choose which conflicted commit to check out for resolution.
+    Commits {
+        commits: Vec<ConflictedCommit>,
+        selected: usize,
+    },
+    /// A conflicted commit is checked out in edit mode and its paths are being resolved.
+    Paths {
+        paths: Vec<ConflictedPath>,
+        selected: usize,
+        /// The index of the conflict shown for the selected path.
+        hunk: usize,
+    },
+}
+
+impl ConflictMode {
+    pub fn is_resolving_paths(&self) -> bool {
+        matches!(self, Self::Paths { .. })
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        match self {
+            Self::Commits { commits, selected } => {
+                *selected = offset_index(*selected, offset, commits.len());
+            }
+            Self::Paths {
+                paths,
+                selected,
+                hunk,
+            } => {
+                let new_selected = offset_index(*selected, offset, paths.len());
+                if new_selected != *selected {
+                    *selected = new_selected;
+                    *hunk = 0;
+                }
+            }
+        }
+    }
+
+    fn move_hunk(&mut self, offset: isize) {
+        if let Self::Paths {
+            paths,
+            selected,
+            hunk,
+        } = self
+        {
+            let hunk_count = paths.get(*selected).map_or(0, ConflictedPath::hunk_count);
+            *hunk = offset_index(*hunk, offset, hunk_count);
+        }
+    }
+
+    fn selected_path(&self) -> Option<(&ConflictedPath, usize)> {
+        match self {
+            Self::Commits { .. } => None,
+            Self::Paths {
+                paths,
+                selected,
+                hunk,
+            } => paths.get(*selected).map(|path| (path, *hunk)),
+        }
+    }
+
+    pub fn render(&self, app: &App, area: Rect, frame: &mut Frame) {
+        let layout = Layout::horizontal([Constraint::Percentage(35), Constraint::Percentage(65)])
+            .spacing(1)
+            .split(area);
+        let (list_area, view_area) = (layout[0], layout[1]);
+
+        let theme = app.theme;
+        let (title, items, selected) = match self {
+            Self::Commits { commits, selected } => (
+                "Conflicted commits",
+                commits
+                    .iter()
+                    .map(|commit| {
+                        ListItem::new(Line::from_iter([
+                            theme.sym().error.span(),
+                            Span::raw(" "),
+                            Span::styled(commit.commit_short_id.clone(), theme.commit_id),
+                            Span::raw(" "),
+                            Span::raw(sanitize_terminal_text(&commit.commit_message)),
+                        ]))
+                    })
+                    .collect::<Vec<_>>(),
+                *selected,
+            ),
+            Self::Paths {
+                paths, selected, ..
+            } => (
+                "Conflicted files",
+                paths
+                    .iter()
+                    .map(|path| {
+                        let symbol = if path.is_resolved() {
+                            theme.sym().success.span()
+                        } else {
+                            theme.sym().error.span()
+                        };
+                        ListItem::new(Line::from_iter([
+                            symbol,
+                            Span::raw(" "),
+                            Span::raw(sanitize_terminal_text(&path.path.to_str_lossy())),
+                        ]))
+                    })
+                    .collect::<Vec<_>>(),
+                *selected,
+            ),
+        };
+
+        let list = List::new(items)
+            .block(Block::new().title(Span::styled(title, theme.important)))
+            .highlight_style(theme.selection_highlight);
+        let mut list_state = ListState::default().with_selected(Some(selected));
+        frame.render_stateful_widget(list, list_area, &mut list_state);
+
+        match self {
+            Self::Commits { commits, selected } => {
+                let Some(commit) = commits.get(*selected) else {
+                    return;
+                };
+                let text = Text::from_iter([
+                    Line::from_iter([
+                        Span::styled(commit.commit_short_id.clone(), theme.commit_id),
+                        Span::raw(" "),
+                        Span::raw(sanitize_terminal_text(&commit.commit_message)),
+                    ]),
+                    Line::default(),
+                    Line::styled(
+                        "Check out this commit to resolve its conflicts.",
+                        theme.hint,
+                    ),
+                ]);
+                frame.render_widget(Paragraph::new(text), view_area);
+            }
+            Self::Paths { .. } => render_selected_path(self, app, view_area, frame),
+        }
+    }
+}
+
+fn render_selected_path(mode: &ConflictMode, app: &App, area: Rect, frame: &mut Frame) {
+    let theme = app.theme;
+    let Some((path, hunk_index)) = mode.selected_path() else {
+        frame.render_widget(
+            Paragraph::new(Line::styled("No conflicted files", theme.hint)),
+            area,
+        );
+        return;
+    };
+
+    let message = match &path.content {
+        ConflictedContent::Deleted => "The file was deleted, which resolves it.",
+        ConflictedContent::Binary { .. } => "This is not a text file, resolve it in the editor.",
+        ConflictedContent::Text(file) => match file.hunks().nth(hunk_index) {
+            Some(hunk) => {
+                return render_hunk(app, file, hunk, hunk_index, area, frame);
+            }
+            None if file.has_conflict_markers => {
+                "The conflict markers couldn't be parsed, resolve the file in the editor."
+            }
+            None => "No conflict markers left.",
+        },
+    };
+    frame.render_widget(Paragraph::new(Line::styled(message, theme.hint)), area);
+}
+
+fn render_hunk(
+    app: &App,
+    file: &ConflictedFile,
+    hunk: &ConflictHunk,
+    hunk_index: usize,
+    area: Rect,
+    frame: &mut Frame,
+) {
+    let theme = app.theme;
+    let layout = Layout::vertical([Constraint::Length(1), Constraint::Min(1)]).split(area);
+    frame.render_widget(
+        Line::styled(
+            format!("conflict {} of {}", hunk_index + 1, file.hunks().count()),
+            theme.attention,
+        ),
+        layout[0],
+    );
+
+    let sides = [Some(&hunk.ours), hunk.base.as_ref(), Some(&hunk.theirs)]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let columns = Layout::horizontal(vec![Constraint::Ratio(1, sides.len() as u32); sides.len()])
+        .split(layout[1]);
+    for (side, area) in sides.into_iter().zip(columns.iter()) {
+        let block = Block::bordered()
+            .border_style(theme.border)
+            .title(Span::styled(
+                sanitize_terminal_text(&side.label),
+                theme.important,
+            ));
+        let text = if side.text.is_empty() {
+            Text::styled("(empty)", theme.hint)
+        } else {
+            Text::raw(sanitize_terminal_text(&side.text).replace('\t', "    "))
+        };
+        frame.render_widget(Paragraph::new(text).block(block), *area);
+    }
+}
+
+fn offset_index(index: usize, offset: isize, len: usize) -> usize {
+    index
+        .saturating_add_signed(offset)
+        .min(len.saturating_sub(1))
+}
+
+impl ModeRender for ConflictMode {}
+
+#[derive(Debug, Clone)]
+pub struct ConflictedPath {
+    pub path: BString,
+    pub content: ConflictedContent,
+}
+
+impl ConflictedPath {
+    fn read(workdir: &Path, path: BString) -> anyhow::Result<Self> {
+        let content = match std::fs::read(workdir.join(gix::path::from_bstr(path.as_bstr()))) {
+            Ok(bytes) => match String::from_utf8(bytes) {
+                Ok(text) => ConflictedContent::Text(ConflictedFile::parse(&text)),
+                Err(err) => ConflictedContent::Binary {
+                    has_conflict_markers: resolve::has_conflict_markers(&String::from_utf8_lossy(
+                        err.as_bytes(),
+                    )),
+                },
+            },
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => ConflictedContent::Deleted,
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to read {path}"));
+            }
+        };
+        Ok(Self { path, content })
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        match &self.content {
+            ConflictedContent::Deleted => true,
+            ConflictedContent::Binary {
+                has_conflict_markers,
+            } => !has_conflict_markers,
+            ConflictedContent::Text(file) => !file.has_conflict_markers,
+        }
+    }
+
+    fn hunk_count(&self) -> usize {
+        match &self.content {
+            ConflictedContent::Text(file) => file.hunks().count(),
+            ConflictedContent::Deleted | ConflictedContent::Binary { .. } => 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConflictedContent {
+    /// The file was deleted in the worktree.
+    Deleted,
+    /// The file isn't valid UTF-8 and can only be resolved in the editor.
+    Binary {
+        has_conflict_markers: bool,
+    },
+    Text(ConflictedFile),
+}
+
+/// A text file split into the conflicts written by a diff3-style checkout and the text around them.
+#[derive(Debug, Clone)]
+pub struct ConflictedFile {
+    chunks: Vec<Chunk>,
+    /// Whether the file still looks conflicted, using the same check as `but resolve`.
+    pub has_conflict_markers: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Chunk {
+    Text(String),
+    Conflict(ConflictHunk),
+}
+
+#[derive(Debug, Clone)]
+pub struct ConflictHunk {
+    /// The hunk including its markers, exactly as it appears in the file.
+    raw: String,
+    pub ours: ConflictHunkSide,
+    /// Only present for diff3-style conflicts.
+    pub base: Option<ConflictHunkSide>,
+    pub theirs: ConflictHunkSide,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConflictHunkSide {
+    /// The label following the marker, like `Common ancestor`.
+    pub label: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictSide {
+    Ours,
+    Theirs,
+    /// Ours followed by theirs.
+    Both,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Section {
+    Ours,
+    Base,
+    Theirs,
+}
+
+impl ConflictedFile {
+    /// Split `text` into its conflicts. Markers that don't form a complete conflict are kept as
+    /// plain text.
+    pub fn parse(text: &str) -> Self {
+        let mut chunks = Vec::new();
+        let mut plain = String::new();
+        let mut open: Option<(ConflictHunk, Section)> = None;
+
+        for line in text.split_inclusive('\n') {
+            let content = line.trim_end_matches(['\n', '\r']);
+            if let Some(label) = marker_label(content, "<<<<<<<") {
+                if let Some((unclosed, _)) = open.take() {
+                    plain.push_str(&unclosed.raw);
+                }
+                open = Some((
+                    ConflictHunk {
+                        raw: line.to_owned(),
+                        ours: ConflictHunkSide {
+                            label: label.to_owned(),
+                            text: String::new(),
+                        },
+                        base: None,
+                        theirs: ConflictHunkSide::default(),
+                    },
+                    Section::Ours,
+                ));
+                continue;
+            }
+
+            let Some((hunk, section)) = open.as_mut() else {
+                plain.push_str(line);
+                continue;
+            };
+            hunk.raw.push_str(line);
+            match *section {
+                Section::Ours if marker_label(content, "|||||||").is_some() => {
+                    hunk.base = Some(ConflictHunkSide {
+                        label: marker_label(content, "|||||||")
+                            .unwrap_or_default()
+                            .to_owned(),
+                        text: String::new(),
+                    });
+                    *section = Section::Base;
+                }
+                Section::Ours | Section::Base if content == SEPARATOR => {
+                    *section = Section::Theirs;
+                }
+                Section::Theirs if marker_label(content, ">>>>>>>").is_some() => {
+                    hunk.theirs.label = marker_label(content, ">>>>>>>")
+                        .unwrap_or_default()
+                        .to_owned();
+                    if !plain.is_empty() {
+                        chunks.push(Chunk::Text(std::mem::take(&mut plain)));
+                    }
+                    if let Some((hunk, _)) = open.take() {
+                        chunks.push(Chunk::Conflict(hunk));
+                    }
+                }
+                Section::Ours => hunk.ours.text.push_str(line),
+                Section::Base => {
+                    if let Some(base) = hunk.base.as_mut() {
+                        base.text.push_str(line);
+                    }
+                }
+                Section::Theirs => hunk.theirs.text.push_str(line),
+            }
+        }
+
+        if let Some((unclosed, _)) = open {
+            plain.push_str(&unclosed.raw);
+        }
+        if !plain.is_empty() {
+            chunks.push(Chunk::Text(plain));
+        }
+
+        ConflictedFile {
+            chunks,
+            has_conflict_markers: resolve::has_conflict_markers(text),
+        }
+    }
+
+    pub fn hunks(&self) -> impl Iterator<Item = &ConflictHunk> {
+        self.chunks.iter().filter_map(|chunk| match chunk {
+            Chunk::Text(_) => None,
+            Chunk::Conflict(hunk) => Some(hunk),
+        })
+    }
+
+    /// Return the file's text with the conflict at `index` replaced by `side`, leaving all other
+    /// conflicts untouched.
+    pub fn resolve(&self, index: usize, side: ConflictSide) -> String {
+        let mut out = String::new();
+        let mut hunk_index = 0;
+        for chunk in &self.chunks {
+            match chunk {
+                Chunk::Text(text) => out.push_str(text),
+                Chunk::Conflict(hunk) => {
+                    if hunk_index == index {
+                        match side {
+                            ConflictSide::Ours => out.push_str(&hunk.ours.text),
+                            ConflictSide::Theirs => out.push_str(&hunk.theirs.text),
+                            ConflictSide::Both => {
+                                out.push_str(&hunk.ours.text);
+                                out.push_str(&hunk.theirs.text);
+                            }
+                        }
+                    } else {
+                        out.push_str(&hunk.raw);
+                    }
+                    hunk_index += 1;
+                }
+            }
+        }
+        out
+    }
+}
+
+/// The line between the sides of a conflict, which unlike the other markers never has a label.
+const SEPARATOR: &str = "=======";
+
+/// The label after `marker` if `line` is a conflict marker line of that kind.
+fn marker_label<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(marker)?;
+    if rest.is_empty() {
+        Some(rest)
+    } else {
+        rest.strip_prefix(' ')
+    }
+}
+
+#[derive(Debug)]
+pub enum ConflictMessage {
+    Start,
+    Up,
+    Down,
+    NextHunk,
+    PrevHunk,
+    /// Check out the selected commit to resolve its conflicts.
+    Resolve,
+    Pick(ConflictSide),
+    OpenEditor,
+    Finish,
+    Cancel,
+    /// Return to normal mode after the resolution was finished or cancelled.
+    Leave,
+}
+
+impl App {
+    pub fn handle_conflict<T>(
+        &mut self,
+        message: ConflictMessage,
+        ctx: &mut Context,
+        terminal_guard: &mut T,
+        messages: &mut Vec<Message>,
+    ) -> anyhow::Result<()>
+    where
+        T: TerminalGuard,
+        anyhow::Error: From<<T::Backend as Backend>::Error>,
+    {
+        let result = match message {
+            ConflictMessage::Start => self.handle_conflict_start(ctx),
+            ConflictMessage::Up => {
+                self.update_conflict_mode(|mode| mode.move_selection(-1));
+                Ok(())
+            }
+            ConflictMessage::Down => {
+                self.update_conflict_mode(|mode| mode.move_selection(1));
+                Ok(())
+            }
+            ConflictMessage::NextHunk => {
+                self.update_conflict_mode(|mode| mode.move_hunk(1));
+                Ok(())
+            }
+            ConflictMessage::PrevHunk => {
+                self.update_conflict_mode(|mode| mode.move_hunk(-1));
+                Ok(())
+            }
+            ConflictMessage::Resolve => self.handle_conflict_resolve(ctx),
+            ConflictMessage::Pick(side) => self.handle_conflict_pick(ctx, side),
+            ConflictMessage::OpenEditor => self.handle_conflict_open_editor(ctx, terminal_guard),
+            ConflictMessage::Finish => self.handle_conflict_finish(ctx, messages),
+            ConflictMessage::Cancel => {
+                self.handle_conflict_cancel();
+                Ok(())
+            }
+            ConflictMessage::Leave => self.handle_conflict_leave(ctx, messages),
+        };
+
+        match result {
+            // Don't drop back to normal mode while the commit is still checked out, the
+            // resolution would have to be continued with `but resolve` otherwise.
+            Err(err)
+                if matches!(&*self.mode, Mode::Conflict(mode) if mode.is_resolving_paths())
+                    && matches!(self.operating_mode, OperatingMode::Edit(_)) =>
+            {
+                self.push_transient_error(err);
+                Ok(())
+            }
+            result => result,
+        }
+    }
+
+    fn update_conflict_mode(&mut self, f: impl FnOnce(&mut ConflictMode)) {
+        if let Mode::Conflict(conflict_mode) = self
+            .mode
+            .get_mut_and_i_promise_not_to_switch_to_a_different_state()
+        {
+            f(conflict_mode);
+        }
+    }
+
+    fn handle_conflict_start(&mut self, ctx: &mut Context) -> anyhow::Result<()> {
+        self.operating_mode = operating_mode(ctx)?.operating_mode;
+        let conflict_mode = if matches!(self.operating_mode, OperatingMode::Edit(_)) {
+            ConflictMode::Paths {
+                paths: conflicted_paths(ctx)?,
+                selected: 0,
+                hunk: 0,
+            }
+        } else {
+            let conflicts = resolve::find_conflicted_commits(ctx)?;
+            let commits = resolve::unique_conflict_queue(&conflicts)
+                .into_iter()
+                .cloned()
+                .collect::<Vec<_>>();
+            if commits.is_empty() {
+                self.toasts
+                    .insert(ToastKind::Info, "No conflicted commits to resolve");
+                return Ok(());
+            }
+            let selected = self
+                .selected_commit_id()
+                .and_then(|selected| {
+                    commits
+                        .iter()
+                        .position(|commit| commit.commit_oid == selected.commit_id)
+                })
+                .unwrap_or_default();
+            ConflictMode::Commits { commits, selected }
+        };
+
+        self.mode
+            .update_and_push_leave_normal_mode(&mut self.backstack, |mode| {
+                *mode = Mode::Conflict(conflict_mode);
+            });
+        Ok(())
+    }
+
+    fn handle_conflict_resolve(&mut self, ctx: &mut Context) -> anyhow::Result<()> {
+        let Mode::Conflict(ConflictMode::Commits { commits, selected }) = &*self.mode else {
+            return Ok(());
+        };
+        let Some(commit) = commits.get(*selected) else {
+            return Ok(());
+        };
+        let (commit_id, commit_ref) = (commit.commit_oid, commit.commit_short_id.clone());
+
+        resolve::enter_edit_mode_for_conflicted_commit(ctx, commit_id, &commit_ref)?;
+        self.operating_mode = operating_mode(ctx)?.operating_mode;
+
+        let paths = conflicted_paths(ctx)?;
+        if let Mode::Conflict(conflict_mode) = self
+            .mode
+            .get_mut_and_i_promise_not_to_switch_to_a_different_state()
+        {
+            *conflict_mode = ConflictMode::Paths {
+                paths,
+                selected: 0,
+                hunk: 0,
+            };
+        }
+        Ok(())
+    }
+
+    fn handle_conflict_pick(
+        &mut self,
+        ctx: &mut Context,
+        side: ConflictSide,
+    ) -> anyhow::Result<()> {
+        let Some((path, hunk)) = self.selected_conflicted_path() else {
+            return Ok(());
+        };
+        let abs_path = ctx
+            .workdir_or_fail()?
+            .join(gix::path::from_bstr(path.as_bstr()));
+
+        let resolved = resolve_hunk_in_file(&abs_path, hunk, side)
+            .with_context(|| format!("Failed to resolve a conflict in {path}"))?;
+        if !resolved {
+            self.toasts.insert(
+                ToastKind::Info,
+                "The conflict is gone, the file was changed since it was shown",
+            );
+        }
+
+        self.reload_conflicted_paths(ctx)
+    }
+
+    fn handle_conflict_open_editor<T>(
+        &mut self,
+        ctx: &mut Context,
+        terminal_guard: &mut T,
+    ) -> anyhow::Result<()>
+    where
+        T: TerminalGuard,
+        anyhow::Error: From<<T::Backend as Backend>::Error>,
+    {
+        let Some((path, _)) = self.selected_conflicted_path() else {
+            return Ok(());
+        };
+        let abs_path = ctx
+            .workdir_or_fail()?
+            .join(gix::path::from_bstr(path.as_bstr()));
+        {
+            let _suspend_guard = terminal_guard.suspend()?;
+            get_text::edit_file_in_editor(&abs_path)?;
+        }
+        self.reload_conflicted_paths(ctx)
+    }
+
+    fn handle_conflict_finish(
+        &mut self,
+        ctx: &mut Context,
+        messages: &mut Vec<Message>,
+    ) -> anyhow::Result<()> {
+        let Mode::Conflict(ConflictMode::Paths { paths, .. }) = &*self.mode else {
+            return Ok(());
+        };
+
+        let unresolved = paths.iter().filter(|path| !path.is_resolved()).count();
+        if unresolved == 0 {
+            return finish_resolution(ctx, messages);
+        }
+
+        let prompt = if unresolved == 1 {
+            "1 file still contains conflict markers. Finish anyway?".to_owned()
+        } else {
+            format!("{unresolved} files still contain conflict markers. Finish anyway?")
+        };
+        self.modal = Some(Modal::Confirm {
+            confirm: Confirm::new(NonEmpty::new(prompt.into()), self.theme, |ctx, messages| {
+                toast_on_error(finish_resolution(ctx, messages), messages);
+                Ok(())
+            }),
+        });
+        Ok(())
+    }
+
+    fn handle_conflict_cancel(&mut self) {
+        if !matches!(&*self.mode, Mode::Conflict(mode) if mode.is_resolving_paths()) {
+            return;
+        }
+
+        self.modal = Some(Modal::Confirm {
+            confirm: Confirm::new(
+                NonEmpty::new("Cancel the resolution and drop all changes to the commit?".into()),
+                self.theme,
+                |ctx, messages| {
+                    let result = abort_edit_and_return_to_workspace(ctx, true).map(|()| {
+                        messages.extend([
+                            Message::ShowToast {
+                                kind: ToastKind::Info,
+                                text: "Resolution cancelled".into(),
+                            },
+                            Message::Conflict(ConflictMessage::Leave),
+                        ]);
+                    });
+                    toast_on_error(result, messages);
+                    Ok(())
+                },
+            ),
+        });
+    }
+
+    fn handle_conflict_leave(
+        &mut self,
+        ctx: &mut Context,
+        messages: &mut Vec<Message>,
+    ) -> anyhow::Result<()> {
+        self.operating_mode = operating_mode(ctx)?.operating_mode;
+        messages.extend([
+            Message::EnterNormalModeAfterConfirmingOperation,
+            Message::Reload(None, ReloadCause::Mutation),
+        ]);
+        Ok(())
+    }
+
+    fn selected_conflicted_path(&self) -> Option<(BString, usize)> {
+        let Mode::Conflict(conflict_mode) = &*self.mode else {
+            return None;
+        };
+        conflict_mode
+            .selected_path()
+            .map(|(path, hunk)| (path.path.clone(), hunk))
+    }
+
+    /// Re-read the conflicted paths of the commit that is checked out for resolution.
+    pub(super) fn reload_conflicted_paths(&mut self, ctx: &mut Context) -> anyhow::Result<()> {
+        self.operating_mode = operating_mode(ctx)?.operating_mode;
+        if !matches!(self.operating_mode, OperatingMode::Edit(_)) {
+            bail!("The conflicted commit is no longer checked out");
+        }
+
+        let new_paths = conflicted_paths(ctx)?;
+        if let Mode::Conflict(ConflictMode::Paths {
+            paths,
+            selected,
+            hunk,
+        }) = self
+            .mode
+            .get_mut_and_i_promise_not_to_switch_to_a_different_state()
+        {
+            *selected = (*selected).min(new_paths.len().saturating_sub(1));
+            let hunk_count = new_paths
+                .get(*selected)
+                .map_or(0, ConflictedPath::hunk_count);
+            *hunk = (*hunk).min(hunk_count.saturating_sub(1));
+            *paths = new_paths;
+        }
+        Ok(())
+    }
+}
+
+/// Save the resolution of the checked out commit and return to the workspace.
+fn finish_resolution(ctx: &mut Context, messages: &mut Vec<Message>) -> anyhow::Result<()> {
+    let SavedResolution {
+        conflicts_before: _,
+        files_with_markers,
+    } = resolve::save_resolution(ctx)?;
+    let remaining = resolve::unique_conflict_queue(&resolve::find_conflicted_commits(ctx)?).len();
+
+    let mut lines = Vec::from(["Resolution finished".to_owned()]);
+    lines.extend(files_with_markers.iter().map(|path| {
+        format!(
+            "{} still contains conflict markers",
+            sanitize_terminal_text(path)
+        )
+    }));
+    match remaining {
+        0 => {}
+        1 => lines.push("1 conflicted commit left".to_owned()),
+        n => lines.push(format!("{n} conflicted commits left")),
+    }
+
+    messages.extend([
+        Message::ShowToast {
+            kind: ToastKind::Info,
+            text: lines.join("\n").into(),
+        },
+        Message::Conflict(ConflictMessage::Leave),
+    ]);
+    Ok(())
+}
+
+/// Replace the conflict at `hunk` in the file at `path` with `side`, and return `false` if there
+/// is no such conflict.
+///
+/// The file is parsed again in case it was changed since it was last read.
+fn resolve_hunk_in_file(path: &Path, hunk: usize, side: ConflictSide) -> anyhow::Result<bool> {
+    let text = std::fs::read_to_string(path)?;
+    let file = ConflictedFile::parse(&text);
+    if hunk >= file.hunks().count() {
+        return Ok(false);
+    }
+    std::fs::write(path, file.resolve(hunk, side))?;
+    Ok(true)
+}
+
+/// Show errors of confirmed operations as toasts, as returning them would leave conflict mode
+/// while the commit is still checked out.
+fn toast_on_error(result: anyhow::Result<()>, messages: &mut Vec<Message>) {
+    if let Err(err) = result {
+        messages.push(Message::ShowToast {
+            kind: ToastKind::Error,
+            text: format!("{err:#}").into(),
+        });
+    }
+}
+
+fn conflicted_paths(ctx: &mut Context) -> anyhow::Result<Vec<ConflictedPath>> {
+    let workdir = ctx.workdir_or_fail()?;
+    resolve::initially_conflicted_paths(ctx)?
+        .into_iter()
+        .map(|path| ConflictedPath::read(&workdir, path))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF3: &str = "\
+before
+<<<<<<< New base: abc
+ours
+||||||| Common ancestor
+base
+=======
+theirs
+>>>>>>> Current commit: def
+between
+<<<<<<< New base: abc
+second ours
+||||||| Common ancestor
+=======
+second theirs
+>>>>>>> Current commit: def
+after
+";
+
+    #[test]
+    fn parse_diff3_conflicts() {
+        let file = ConflictedFile::parse(DIFF3);
+        assert!(file.has_conflict_markers);
+
+        let hunks = file.hunks().collect::<Vec<_>>();
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].ours.label, "New base: abc");
+        assert_eq!(hunks[0].ours.text, "ours\n");
+        let base = hunks[0].base.as_ref().expect("diff3 conflicts have a base");
+        assert_eq!(base.label, "Common ancestor");
+        assert_eq!(base.text, "base\n");
+        assert_eq!(hunks[0].theirs.label, "Current commit: def");
+        assert_eq!(hunks[0].theirs.text, "theirs\n");
+        assert_eq!(
+            hunks[1].base.as_ref().map(|base| base.text.as_str()),
+            Some("")
+        );
+    }
+
+    #[test]
+    fn parse_conflicts_without_base() {
+        let file = ConflictedFile::parse("<<<<<<< ours\na\n=======\nb\n>>>>>>> theirs\n");
+        let hunk = file.hunks().next().expect("one conflict");
+        assert!(hunk.base.is_none());
+        assert_eq!(hunk.ours.text, "a\n");
+        assert_eq!(hunk.theirs.text, "b\n");
+    }
+
+    #[test]
+    fn unterminated_conflicts_are_plain_text() {
+        let text = "<<<<<<< ours\na\n=======\nb\n";
+        let file = ConflictedFile::parse(text);
+        assert_eq!(file.hunks().count(), 0);
+        assert!(file.has_conflict_markers);
+        assert_eq!(file.resolve(0, ConflictSide::Ours), text);
+    }
+
+    #[test]
+    fn resolving_one_conflict_keeps_the_others() {
+        let file = ConflictedFile::parse(DIFF3);
+
+        let resolved = file.resolve(0, ConflictSide::Theirs);
+        assert!(resolved.starts_with("before\ntheirs\nbetween\n<<<<<<< New base: abc\n"));
+        assert!(resolved.ends_with(">>>>>>> Current commit: def\nafter\n"));
+
+        let resolved = ConflictedFile::parse(&resolved).resolve(0, ConflictSide::Both);
+        assert_eq!(
+            resolved,
+            "before\ntheirs\nbetween\nsecond ours\nsecond theirs\nafter\n"
+        );
+        assert!(!ConflictedFile::parse(&resolved).has_conflict_markers);
+    }
+
+    #[test]
+    fn marker_labels() {
+        assert_eq!(marker_label("<<<<<<<", "<<<<<<<"), Some(""));
+        assert_eq!(marker_label("<<<<<<< HEAD", "<<<<<<<"), Some("HEAD"));
+        assert_eq!(
+            marker_label("||||||| Common ancestor", "|||||||"),
+            Some("Common ancestor")
+        );
+        assert_eq!(marker_label(">>>>>>> theirs", ">>>>>>>"), Some("theirs"));
+        assert_eq!(
+            marker_label("<<<<<<<<", "<<<<<<<"),
+            None,
+            "longer markers are text"
+        );
+        assert_eq!(marker_label("<<<<<<<HEAD", "<<<<<<<"), None);
+        assert_eq!(marker_label(" <<<<<<< HEAD", "<<<<<<<"), None);
+    }
+
+    #[test]
+    fn separators_must_be_bare() {
+        let text = "<<<<<<< ours\na\n======= label\nb\n=======\nc\n>>>>>>> theirs\n";
+        let hunk = ConflictedFile::parse(text)
+            .hunks()
+            .next()
+            .cloned()
+            .expect("one conflict");
+        assert_eq!(
+            hunk.ours.text, "a\n======= label\nb\n",
+            "a separator with a label is part of our side"
+        );
+        assert_eq!(hunk.theirs.text, "c\n");
+    }
+
+    #[test]
+    fn diff3_base_is_not_part_of_any_resolution() {
+        let text = "<<<<<<< ours\na\n||||||| base\nbase\n=======\nb\n>>>>>>> theirs\n";
+        let file = ConflictedFile::parse(text);
+        let hunk = file.hunks().next().expect("one conflict");
+        assert_eq!(hunk.ours.text, "a\n");
+        assert_eq!(
+            hunk.base.as_ref().map(|base| base.text.as_str()),
+            Some("base\n")
+        );
+        assert_eq!(hunk.theirs.text, "b\n");
+
+        assert_eq!(file.resolve(0, ConflictSide::Ours), "a\n");
+        assert_eq!(file.resolve(0, ConflictSide::Theirs), "b\n");
+        assert_eq!(file.resolve(0, ConflictSide::Both), "a\nb\n");
+    }
+
+    #[test]
+    fn each_hunk_can_be_resolved_with_a_different_side() {
+        let mut text = DIFF3.to_owned();
+        for (index, side) in [(1, ConflictSide::Both), (0, ConflictSide::Ours)] {
+            text = ConflictedFile::parse(&text).resolve(index, side);
+        }
+        assert_eq!(
+            text,
+            "before\nours\nbetween\nsecond ours\nsecond theirs\nafter\n"
+        );
+
+        let text = ConflictedFile::parse(DIFF3).resolve(1, ConflictSide::Theirs);
+        let text = ConflictedFile::parse(&text).resolve(0, ConflictSide::Both);
+        assert_eq!(
+            text,
+            "before\nours\ntheirs\nbetween\nsecond theirs\nafter\n"
+        );
+    }
+
+    #[test]
+    fn resolved_hunks_are_written_back() -> anyhow::Result<()> {
+        let tmp = tempfile::tempdir()?;
+        let path = tmp.path().join("file");
+        std::fs::write(&path, DIFF3)?;
+
+        assert!(resolve_hunk_in_file(&path, 0, ConflictSide::Theirs)?);
+        let text = std::fs::read_to_string(&path)?;
+        assert!(text.starts_with("before\ntheirs\nbetween\n<<<<<<< New base: abc\n"));
+        assert!(ConflictedFile::parse(&text).has_conflict_markers);
+
+        assert!(
+            !resolve_hunk_in_file(&path, 1, ConflictSide::Ours)?,
+            "only one conflict is left"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&path)?,
+            text,
+            "the file is untouched without a conflict to resolve"
+        );
+
+        assert!(resolve_hunk_in_file(&path, 0, ConflictSide::Ours)?);
+        let text = std::fs::read_to_string(&path)?;
+        assert_eq!(text, "before\ntheirs\nbetween\nsecond ours\nafter\n");
+        let path = ConflictedPath::read(tmp.path(), "file".into())?;
+        assert!(path.is_resolved());
+        Ok(())
+    }
+
+    #[test]
+    fn text_without_conflicts_round_trips() {
+        let text = "a\n======= not a marker\nb";
+        let file = ConflictedFile::parse(text);
+        assert_eq!(file.hunks().count(), 0);
+        assert_eq!(file.resolve(0, ConflictSide::Ours), text);
+    }
+}
@@ -95,6 +95,8 @@ impl App {
                 | Mode::MoveStack(..)
                 | Mode::Jump(..)
                 | Mode::CherryPick(..)
+                | Mode::Conflict(..)
+                | Mode::Oplog(..)
                 | Mode::Stack(..) => {}
             }
         }
@@ -149,6 +151,8 @@ impl App {
                     | Mode::MoveStack(..)
                     | Mode::Jump(..)
                     | Mode::CherryPick(..)
+                    | Mode::Conflict(..)
+                    | Mode::Oplog(..)
                     | Mode::Stack(..) => DetailsReturnMode::Normal(NormalMode::default()),
                 };
                 *mode = Mode::Details(DetailsMode {
@@ -199,6 +203,8 @@ impl App {
             | Mode::MoveStack(..)
             | Mode::Jump(..)
             | Mode::CherryPick(..)
+            | Mode::Conflict(..)
+            | Mode::Oplog(..)
             | Mode::Move(..) => {}
         }
     }
@@ -162,7 +162,7 @@ impl App {
         }
 
         let previous_mode = match &*self.mode {
-            Mode::Details(..) => return,
+            Mode::Details(..) | Mode::Conflict(..) | Mode::Oplog(..) => return,
             mode @ (Mode::Normal(..)
             | Mode::Squash(..)
             | Mode::InlineReword(..)
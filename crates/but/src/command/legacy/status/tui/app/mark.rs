@@ -692,6 +692,8 @@ impl App {
                     | Mode::MoveStack(..)
                     | Mode::Jump(..)
                     | Mode::CherryPick(..)
+                    | Mode::Conflict(..)
+                    | Mode::Oplog(..)
                     | Mode::Stack(..) => {}
                 }
             }
@@ -740,6 +742,8 @@ impl App {
             | Mode::Stack(..)
             | Mode::MoveStack(..)
             | Mode::CherryPick(..)
+            | Mode::Conflict(..)
+            | Mode::Oplog(..)
             | Mode::Jump(..) => false,
         };
 
@@ -777,6 +781,8 @@ fn handle_mark_cli_id(commit: &CliId, mode: &mut Mode) -> anyhow::Result<bool> {
         | Mode::MoveStack(..)
         | Mode::Jump(..)
         | Mode::CherryPick(..)
+        | Mode::Conflict(..)
+        | Mode::Oplog(..)
         | Mode::Details(..) => {
             return Ok(false);
         }
@@ -62,8 +62,8 @@ use super::{
     help::{Help, HelpMessage},
     highlight::Highlights,
     key_bind::{
-        KeyBinds, confirm_key_binds, default_key_binds, fuzzy_picker_key_binds, help_key_binds,
-        normal_with_marks_key_binds,
+        KeyBindOverrides, KeyBinds, confirm_key_binds, default_key_binds, fuzzy_picker_key_binds,
+        help_key_binds, normal_with_marks_key_binds,
     },
     mode::{DetailsMode, DetailsReturnMode, Mode},
     operations,
@@ -74,6 +74,7 @@ mod details_layout;
 mod discard;
 pub(super) use discard::run_discard;
 pub mod mark;
+mod split;
 mod undo_redo;
 
 mod cherry_pick_mode;
@@ -85,6 +86,9 @@ pub use command_mode::*;
 mod commit_mode;
 pub use commit_mode::*;
 
+mod conflict_mode;
+pub use conflict_mode::*;
+
 mod reword;
 pub use reword::*;
 
@@ -97,6 +101,9 @@ pub use move_mode::*;
 mod normal_mode;
 pub use normal_mode::*;
 
+mod oplog_mode;
+pub use oplog_mode::*;
+
 mod pick_changes_mode;
 pub use pick_changes_mode::*;
 
@@ -369,10 +376,16 @@ impl App {
         }
         let is_details_visible = launch_options.show_diff || initial_hunk.is_some();
 
-        let app_key_binds = AppKeyBinds {
-            key_binds: default_key_binds(),
-            normal_with_marks_key_binds: normal_with_marks_key_binds(),
-            confirm_key_binds: confirm_key_binds(),
+        let mut toasts = Toasts::default();
+        let app_key_binds = match KeyBindOverrides::load().and_then(|o| AppKeyBinds::new(&o)) {
+            Ok(app_key_binds) => app_key_binds,
+            Err(err) => {
+                toasts.insert(
+                    ToastKind::Error,
+                    format!("Using default key binds: {err:#}"),
+                );
+                AppKeyBinds::new(&KeyBindOverrides::default())?
+            }
         };
 
         let mode = RememberToUpdateBackstack::new(match (run_options, initial_hunk.is_some()) {
@@ -410,7 +423,7 @@ impl App {
             outcome: None,
             should_render: true,
             mode,
-            toasts: Default::default(),
+            toasts,
             renders: 0,
             updates: 0,
             app_key_binds,
@@ -625,6 +638,10 @@ impl App {
             Message::CherryPick(cherry_pick_message) => {
                 self.handle_cherry_pick(cherry_pick_message, ctx, messages)?
             }
+            Message::Conflict(conflict_message) => {
+                self.handle_conflict(conflict_message, ctx, terminal_guard, messages)?
+            }
+            Message::Oplog(oplog_message) => self.handle_oplog(oplog_message, ctx)?,
             Message::NewBranch => {
                 self.handle_new_branch(ctx, messages)?;
             }
@@ -730,6 +747,9 @@ impl App {
             Message::Discard => {
                 self.handle_discard(messages)?;
             }
+            Message::Split => {
+                self.handle_split()?;
+            }
             Message::DropToBeDiscarded => {
                 self.to_be_discarded.clear();
             }
@@ -810,6 +830,8 @@ impl App {
                 | Mode::Stack(..)
                 | Mode::Jump(..)
                 | Mode::CherryPick(..)
+                | Mode::Conflict(..)
+                | Mode::Oplog(..)
                 | Mode::MoveStack(..) => return,
                 Mode::Details(details_mode) => match &details_mode.return_mode {
                     DetailsReturnMode::PickChanges(PickChangesMode { marks }) => {
@@ -920,6 +942,8 @@ impl App {
                 | Mode::Stack(..)
                 | Mode::MoveStack(..)
                 | Mode::CherryPick(..)
+                | Mode::Conflict(..)
+                | Mode::Oplog(..)
                 | Mode::Jump(..) => {}
             },
             BackstackEntry::OpenSplitDetailsView | BackstackEntry::OpenFullScreenDetailsView => {
@@ -1231,6 +1255,14 @@ impl App {
     ) -> anyhow::Result<()> {
         tracing::debug!("handle_reload");
 
+        if let Mode::Conflict(conflict_mode) = &*self.mode
+            && conflict_mode.is_resolving_paths()
+        {
+            // The workspace isn't checked out while resolving, so only the conflicted paths of
+            // the checked out commit are refreshed.
+            return self.reload_conflicted_paths(ctx);
+        }
+
         let close_empty_global_file_list_after_reload = matches!(
             (&self.flags.show_files, &select_after_reload),
             (FilesStatusFlag::All, Some(SelectAfterReload::Commit(_)))
@@ -1971,6 +2003,17 @@ pub struct AppKeyBinds {
     confirm_key_binds: KeyBinds,
 }
 
+impl AppKeyBinds {
+    /// Create all key binds of the app, with the user-defined `overrides` applied.
+    fn new(overrides: &KeyBindOverrides) -> anyhow::Result<Self> {
+        Ok(Self {
+            key_binds: default_key_binds().with_overrides(overrides)?,
+            normal_with_marks_key_binds: normal_with_marks_key_binds().with_overrides(overrides)?,
+            confirm_key_binds: confirm_key_binds(),
+        })
+    }
+}
+
 #[derive(Debug)]
 pub enum Modal {
     Confirm {
@@ -0,0 +1,258 @@
+//! Browse the operations log without leaving the status TUI.
+//!
+//! Lists the most recent snapshots like `but oplog`, previews what restoring the selected snapshot
+//! would change, and restores it after confirmation.
+
+use but_api::legacy::oplog::{self, RestoreKind};
+use but_core::ui;
+use but_ctx::Context;
+use gitbutler_oplog::entry::Snapshot;
+use nonempty::NonEmpty;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, List, ListItem, ListState, Paragraph},
+};
+
+use crate::command::legacy::{
+    oplog::{operation_kind_style, snapshot_time_string},
+    resolve::sanitize_terminal_text,
+    status::{
+        path_with_color_ui, status_letter_ui,
+        tui::{
+            Message, ReloadCause,
+            app::{App, Modal},
+            confirm::Confirm,
+            mode::Mode,
+            render::ModeRender,
+            toast::ToastKind,
+        },
+    },
+};
+
+/// The number of snapshots loaded when the oplog is opened.
+const SNAPSHOT_LIMIT: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct OplogMode {
+    snapshots: Vec<Snapshot>,
+    selected: usize,
+    /// What restoring the selected snapshot would change, or why that couldn't be determined.
+    preview: Result<Vec<ui::TreeChange>, String>,
+}
+
+impl OplogMode {
+    fn selected_snapshot(&self) -> Option<&Snapshot> {
+        self.snapshots.get(self.selected)
+    }
+
+    pub fn render(&self, app: &App, area: Rect, frame: &mut Frame) {
+        let layout = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .spacing(1)
+            .split(area);
+        let (list_area, view_area) = (layout[0], layout[1]);
+
+        let theme = app.theme;
+        let items = self
+            .snapshots
+            .iter()
+            .map(|snapshot| {
+                let mut line = Line::from_iter([
+                    Span::styled(
+                        snapshot.commit_id.to_hex_with_len(7).to_string(),
+                        theme.commit_id,
+                    ),
+                    Span::raw(" "),
+                    Span::styled(snapshot_time_string(snapshot), theme.time),
+                    Span::raw(" "),
+                ]);
+                match &snapshot.details {
+                    Some(details) => line.extend([
+                        Span::styled(
+                            format!("[{}]", details.operation.kind_str()),
+                            operation_kind_style(details.operation, theme),
+                        ),
+                        Span::raw(" "),
+                        Span::raw(sanitize_terminal_text(&details.title)),
+                    ]),
+                    None => line.push_span(Span::styled("Unknown operation", theme.hint)),
+                }
+                ListItem::new(line)
+            })
+            .collect::<Vec<_>>();
+
+        let list = List::new(items)
+            .block(Block::new().title(Span::styled("Operations log", theme.important)))
+            .highlight_style(theme.selection_highlight);
+        let mut list_state = ListState::default().with_selected(Some(self.selected));
+        frame.render_stateful_widget(list, list_area, &mut list_state);
+
+        self.render_selected_snapshot(app, view_area, frame);
+    }
+
+    fn render_selected_snapshot(&self, app: &App, area: Rect, frame: &mut Frame) {
+        let theme = app.theme;
+        let Some(snapshot) = self.selected_snapshot() else {
+            return;
+        };
+
+        let mut lines = Vec::new();
+        match &snapshot.details {
+            Some(details) => {
+                lines.push(Line::from_iter([
+                    Span::styled(
+                        details.operation.kind_str(),
+                        operation_kind_style(details.operation, theme),
+                    ),
+                    Span::raw(" "),
+                    Span::styled(sanitize_terminal_text(&details.title), theme.important),
+                ]));
+                if let Some(body) = details.body.as_deref().filter(|body| !body.is_empty()) {
+                    lines.push(Line::default());
+                    lines.extend(sanitize_terminal_text(body).lines().map(Line::raw));
+                }
+                if !details.trailers.is_empty() {
+                    lines.push(Line::default());
+                    lines.extend(details.trailers.iter().map(|trailer| {
+                        Line::styled(sanitize_terminal_text(&trailer.to_string()), theme.hint)
+                    }));
+                }
+            }
+            None => lines.push(Line::styled("Unknown operation", theme.hint)),
+        }
+
+        lines.push(Line::default());
+        lines.push(Line::styled("Restoring would change", theme.attention));
+        match &self.preview {
+            Ok(changes) if changes.is_empty() => {
+                lines.push(Line::styled("no changes to committed files", theme.hint));
+            }
+            Ok(changes) => lines.extend(changes.iter().map(|change| {
+                Line::from_iter([
+                    Span::raw(format!("{} ", status_letter_ui(&change.status))),
+                    path_with_color_ui(
+                        &change.status,
+                        sanitize_terminal_text(&change.path_bytes.to_string()),
+                    ),
+                ])
+            })),
+            Err(err) => lines.push(Line::styled(err.clone(), theme.error)),
+        }
+
+        frame.render_widget(Paragraph::new(Text::from(lines)), area);
+    }
+}
+
+impl ModeRender for OplogMode {}
+
+#[derive(Debug)]
+pub enum OplogMessage {
+    Start,
+    Up,
+    Down,
+    /// Restore the selected snapshot after confirming.
+    Restore,
+}
+
+impl App {
+    pub fn handle_oplog(&mut self, message: OplogMessage, ctx: &mut Context) -> anyhow::Result<()> {
+        match message {
+            OplogMessage::Start => self.handle_oplog_start(ctx),
+            OplogMessage::Up => self.move_oplog_selection(ctx, -1),
+            OplogMessage::Down => self.move_oplog_selection(ctx, 1),
+            OplogMessage::Restore => {
+                self.handle_oplog_restore();
+                Ok(())
+            }
+        }
+    }
+
+    fn handle_oplog_start(&mut self, ctx: &mut Context) -> anyhow::Result<()> {
+        let snapshots = oplog::list_snapshots(ctx, SNAPSHOT_LIMIT, None, None, None)?;
+        let Some(first) = snapshots.first() else {
+            self.toasts.insert(ToastKind::Info, "No oplog entries yet");
+            return Ok(());
+        };
+        let preview = restore_preview(ctx, first);
+
+        self.mode
+            .update_and_push_leave_normal_mode(&mut self.backstack, |mode| {
+                *mode = Mode::Oplog(OplogMode {
+                    snapshots,
+                    selected: 0,
+                    preview,
+                });
+            });
+        Ok(())
+    }
+
+    fn move_oplog_selection(&mut self, ctx: &mut Context, offset: isize) -> anyhow::Result<()> {
+        if let Mode::Oplog(oplog_mode) = self
+            .mode
+            .get_mut_and_i_promise_not_to_switch_to_a_different_state()
+        {
+            let selected = oplog_mode
+                .selected
+                .saturating_add_signed(offset)
+                .min(oplog_mode.snapshots.len().saturating_sub(1));
+            if selected != oplog_mode.selected {
+                oplog_mode.selected = selected;
+                oplog_mode.preview = restore_preview(ctx, &oplog_mode.snapshots[selected]);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_oplog_restore(&mut self) {
+        let Mode::Oplog(oplog_mode) = &*self.mode else {
+            return;
+        };
+        let Some(snapshot) = oplog_mode.selected_snapshot() else {
+            return;
+        };
+
+        let snapshot_id = snapshot.commit_id;
+        let short_id = snapshot_id.to_hex_with_len(7).to_string();
+        let title = snapshot
+            .details
+            .as_ref()
+            .map(|details| sanitize_terminal_text(&details.title))
+            .unwrap_or_else(|| "Unknown operation".to_owned());
+        let theme = self.theme;
+
+        let prompt = Line::from_iter([
+            Span::raw("Restore the workspace to "),
+            Span::styled(short_id.clone(), theme.cli_id),
+            Span::raw(" "),
+            Span::styled(title.clone(), theme.attention),
+            Span::raw("?"),
+        ]);
+        self.modal = Some(Modal::Confirm {
+            confirm: Confirm::new(NonEmpty::new(prompt), theme, move |ctx, messages| {
+                oplog::restore_snapshot_with_kind(
+                    ctx,
+                    RestoreKind::ExplicitRestoreFromSnapshot,
+                    snapshot_id,
+                )?;
+                let text = Line::from_iter([
+                    Span::raw("Restored "),
+                    Span::styled(short_id, theme.cli_id),
+                    Span::raw(" "),
+                    Span::styled(title, theme.attention),
+                ]);
+                messages.extend([
+                    Message::EnterNormalModeAfterConfirmingOperation,
+                    Message::Reload(None, ReloadCause::Mutation),
+                    Message::ShowToast {
+                        kind: ToastKind::Info,
+                        text: text.into(),
+                    },
+                ]);
+                Ok(())
+            }),
+        });
+    }
+}
+
+fn restore_preview(ctx: &Context, snapshot: &Snapshot) -> Result<Vec<ui::TreeChange>, String> {
+    oplog::restore_diff(ctx, snapshot.commit_id).map_err(|err| format!("{err:#}"))
+}
@@ -0,0 +1,102 @@
+use bstr::BString;
+use but_ctx::Context;
+use nonempty::NonEmpty;
+
+use crate::{
+    CliId,
+    command::legacy::{
+        split::{self, SplitChange, SplitOperation, SplitOutcome, SplitPieceSelection},
+        status::tui::{
+            Message, ReloadCause, SelectAfterReload,
+            app::{App, Modal},
+            confirm::Confirm,
+            mode::Mode,
+        },
+    },
+    id::CommitId,
+    theme,
+};
+
+use super::mark::Marks;
+
+impl App {
+    /// Split the selected committed file, or the marked committed files, out of their commit into
+    /// a new commit on top of it.
+    pub fn handle_split(&mut self) -> anyhow::Result<()> {
+        let Some((source, paths)) = self.split_source_and_paths() else {
+            return Ok(());
+        };
+        let from_marks = !self.marks_ref().is_empty();
+
+        let prompt = match paths.tail.as_slice() {
+            [] => format!("Split {} out of {}?", paths.head, theme::Commit(&source)),
+            tail => format!(
+                "Split {} files out of {}?",
+                tail.len() + 1,
+                theme::Commit(&source)
+            ),
+        };
+
+        self.modal = Some(Modal::Confirm {
+            confirm: Confirm::new(
+                NonEmpty::new(prompt.into()),
+                self.theme,
+                move |ctx, messages| {
+                    let outcome = run_split(
+                        ctx,
+                        SplitOperation {
+                            source,
+                            pieces: NonEmpty::new(SplitPieceSelection {
+                                changes: paths.map(|path| SplitChange { path, hunk: None }),
+                                message: None,
+                            }),
+                        },
+                    )?;
+                    let select_after_reload = outcome
+                        .new_commits
+                        .last()
+                        .map(|commit| SelectAfterReload::Commit(commit.commit_id));
+                    if from_marks {
+                        messages.push(Message::ClearMarks);
+                    }
+                    messages.push(Message::Reload(select_after_reload, ReloadCause::Mutation));
+                    Ok(())
+                },
+            ),
+        });
+
+        Ok(())
+    }
+
+    fn split_source_and_paths(&self) -> Option<(CommitId, NonEmpty<BString>)> {
+        if let Mode::Normal(normal_mode) = &*self.mode
+            && let Marks::CommittedFiles(files) = &normal_mode.marks
+        {
+            let source = CommitId {
+                commit_id: files.head.commit_id,
+                change_id: files.head.change_id.clone(),
+            };
+            return Some((source, files.clone().map(|file| file.path)));
+        }
+        if !self.marks_ref().is_empty() {
+            return None;
+        }
+
+        let selection = self.cursor.selected_line(&self.status_lines)?;
+        let CliId::CommittedFile { committed_file, .. } = &**selection.data.cli_id()? else {
+            return None;
+        };
+        let source = CommitId {
+            commit_id: committed_file.commit_id,
+            change_id: committed_file.change_id.clone(),
+        };
+        Some((source, NonEmpty::new(committed_file.path.to_owned())))
+    }
+}
+
+fn run_split(ctx: &mut Context, operation: SplitOperation) -> anyhow::Result<SplitOutcome> {
+    let mut guard = ctx.exclusive_worktree_access();
+    let mut meta = ctx.meta()?;
+    let (outcome, _ws) = split::run(ctx, &mut meta, guard.write_permission(), operation)?;
+    Ok(outcome)
+}
@@ -894,6 +894,8 @@ fn is_section_header(line: &StatusOutputLine, mode: &Mode) -> bool {
         | Mode::Jump(..)
         | Mode::Squash(..)
         | Mode::CherryPick(..)
+        | Mode::Conflict(..)
+        | Mode::Oplog(..)
         | Mode::Details(..) => {
             matches!(
                 line.data,
@@ -1004,6 +1006,8 @@ pub fn is_selectable_in_mode(
         | ModeRef::PickChanges(..)
         | ModeRef::Details(..)
         | ModeRef::Jump(..)
+        | ModeRef::Conflict(..)
+        | ModeRef::Oplog(..)
         | ModeRef::Stack(..) => {}
     }
 
@@ -1071,6 +1075,8 @@ pub fn is_selectable_in_mode(
         | ModeRef::MoveStack(..)
         | ModeRef::Jump(..)
         | ModeRef::CherryPick(..)
+        | ModeRef::Conflict(..)
+        | ModeRef::Oplog(..)
         | ModeRef::Stack(..) => {}
     }
 
@@ -1121,7 +1127,7 @@ pub fn is_selectable_in_mode(
         ModeRef::Command(command_mode) => {
             is_selectable_in_mode(line, command_mode.return_mode.as_ref(), show_files_flag)
         }
-        ModeRef::InlineReword(..) => true,
+        ModeRef::InlineReword(..) | ModeRef::Conflict(..) | ModeRef::Oplog(..) => true,
         ModeRef::Jump(jump_mode) => prefix_match(
             jump_mode.query(),
             line,
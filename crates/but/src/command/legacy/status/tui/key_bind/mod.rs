@@ -8,7 +8,10 @@ use crate::{
     command::legacy::status::tui::{
         CommandMessage, ConfirmMessage, DetailsLayoutMessage, FuzzyPickerMessage, JumpMessage,
         Message, StackMessage,
-        app::{CherryPickMessage, CommitMessageComposer, RewordMessage, SquashMessage},
+        app::{
+            CherryPickMessage, CommitMessageComposer, ConflictMessage, ConflictSide, OplogMessage,
+            RewordMessage, SquashMessage,
+        },
         details::DetailsMessage,
         help::HelpMessage,
         mode::{Mode, ModeDiscriminant},
@@ -19,6 +22,9 @@ use super::{
     CommandModeKind, CommitMessage, FilesMessage, MoveMessage, ReloadCause, mode::DetailsReturnMode,
 };
 
+mod overrides;
+pub use overrides::KeyBindOverrides;
+
 #[cfg(test)]
 mod tests;
 
@@ -90,6 +96,80 @@ pub fn default_key_binds() -> KeyBinds {
                 builder.cherry_pick_to_new_branch().register();
                 register_non_mode_specific_key_binds(&mut builder, WithFocusDetails::No);
             }
+            ModeDiscriminant::Conflict => {
+                builder
+                    .up_with(|| Message::Conflict(ConflictMessage::Up))
+                    .register();
+                builder
+                    .down_with(|| Message::Conflict(ConflictMessage::Down))
+                    .register();
+                builder.conflict_next_hunk().register();
+                builder.conflict_prev_hunk().register();
+
+                builder
+                    .conflict_resolve()
+                    .condition(KeyBindCondition::Not(
+                        &KeyBindCondition::ConflictModeIsResolvingPaths,
+                    ))
+                    .register();
+
+                builder
+                    .conflict_pick_ours()
+                    .condition(KeyBindCondition::ConflictModeIsResolvingPaths)
+                    .register();
+                builder
+                    .conflict_pick_theirs()
+                    .condition(KeyBindCondition::ConflictModeIsResolvingPaths)
+                    .register();
+                builder
+                    .conflict_pick_both()
+                    .condition(KeyBindCondition::ConflictModeIsResolvingPaths)
+                    .register();
+                builder
+                    .conflict_open_editor()
+                    .condition(KeyBindCondition::ConflictModeIsResolvingPaths)
+                    .register();
+                builder
+                    .conflict_finish()
+                    .condition(KeyBindCondition::ConflictModeIsResolvingPaths)
+                    .register();
+                builder
+                    .conflict_cancel()
+                    .condition(KeyBindCondition::ConflictModeIsResolvingPaths)
+                    .register();
+
+                builder.help().register();
+                builder.quit().register();
+
+                // Leaving while paths are being resolved would leave the commit checked out.
+                builder
+                    .normal_mode()
+                    .condition(KeyBindCondition::Not(
+                        &KeyBindCondition::ConflictModeIsResolvingPaths,
+                    ))
+                    .register();
+                builder
+                    .back()
+                    .condition(KeyBindCondition::Not(
+                        &KeyBindCondition::ConflictModeIsResolvingPaths,
+                    ))
+                    .register();
+            }
+            ModeDiscriminant::Oplog => {
+                builder
+                    .up_with(|| Message::Oplog(OplogMessage::Up))
+                    .register();
+                builder
+                    .down_with(|| Message::Oplog(OplogMessage::Down))
+                    .register();
+                builder.oplog_restore().register();
+
+                builder.help().register();
+                builder.quit().register();
+
+                builder.normal_mode().register();
+                builder.back().register();
+            }
             ModeDiscriminant::Details => {
                 builder
                     .confirm_and_quit()
@@ -783,6 +863,14 @@ impl KeyBindsBuilder<'_> {
         .long_description("Discard the selection")
     }
 
+    fn split(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind("split", press().shift().code(KeyCode::Char('S')), || {
+            Message::Split
+        })
+        .long_description("Split the selected committed files out into a new commit")
+        .hide_from_hotbar()
+    }
+
     fn command(&mut self) -> KeyBindsInModesBuilder<'_> {
         self.key_bind("but command", press().code(KeyCode::Char(':')), || {
             Message::Command(CommandMessage::Start(CommandModeKind::But))
@@ -981,6 +1069,98 @@ impl KeyBindsBuilder<'_> {
         .long_description("Create a new branch, then pick to it")
     }
 
+    fn resolve_conflicts(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind(
+            "resolve conflicts",
+            press().shift().code(KeyCode::Char('C')),
+            || Message::Conflict(ConflictMessage::Start),
+        )
+        .hide_from_hotbar()
+        .long_description("Resolve conflicted commits")
+    }
+
+    fn oplog(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind("oplog", press().shift().code(KeyCode::Char('H')), || {
+            Message::Oplog(OplogMessage::Start)
+        })
+        .hide_from_hotbar()
+        .long_description("Browse the operations log")
+    }
+
+    fn oplog_restore(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind("restore", press().code(KeyCode::Enter), || {
+            Message::Oplog(OplogMessage::Restore)
+        })
+        .long_description("Restore the workspace to the selected snapshot")
+    }
+
+    fn conflict_next_hunk(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind(
+            "next conflict",
+            press().shift().code(KeyCode::Char('J')),
+            || Message::Conflict(ConflictMessage::NextHunk),
+        )
+        .hide_from_hotbar()
+    }
+
+    fn conflict_prev_hunk(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind(
+            "prev conflict",
+            press().shift().code(KeyCode::Char('K')),
+            || Message::Conflict(ConflictMessage::PrevHunk),
+        )
+        .hide_from_hotbar()
+    }
+
+    fn conflict_resolve(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind("resolve", press().code(KeyCode::Enter), || {
+            Message::Conflict(ConflictMessage::Resolve)
+        })
+        .long_description("Check out the commit to resolve its conflicts")
+    }
+
+    fn conflict_pick_ours(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind("ours", press().code(KeyCode::Char('o')), || {
+            Message::Conflict(ConflictMessage::Pick(ConflictSide::Ours))
+        })
+        .long_description("Resolve the conflict with the new base's side")
+    }
+
+    fn conflict_pick_theirs(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind("theirs", press().code(KeyCode::Char('t')), || {
+            Message::Conflict(ConflictMessage::Pick(ConflictSide::Theirs))
+        })
+        .long_description("Resolve the conflict with the commit's side")
+    }
+
+    fn conflict_pick_both(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind("both", press().code(KeyCode::Char('b')), || {
+            Message::Conflict(ConflictMessage::Pick(ConflictSide::Both))
+        })
+        .long_description("Resolve the conflict by keeping both sides")
+    }
+
+    fn conflict_open_editor(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind("edit", press().code(KeyCode::Char('e')), || {
+            Message::Conflict(ConflictMessage::OpenEditor)
+        })
+        .long_description("Resolve the file in your editor")
+    }
+
+    fn conflict_finish(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind("finish", press().code(KeyCode::Char('f')), || {
+            Message::Conflict(ConflictMessage::Finish)
+        })
+        .long_description("Save the resolution and return to the workspace")
+    }
+
+    fn conflict_cancel(&mut self) -> KeyBindsInModesBuilder<'_> {
+        self.key_bind("cancel", press().code(KeyCode::Char('x')), || {
+            Message::Conflict(ConflictMessage::Cancel)
+        })
+        .long_description("Drop the resolution and return to the workspace")
+    }
+
     fn details_next_hunk(&mut self) -> KeyBindsInModesBuilder<'_> {
         self.key_bind(
             "next hunk",
@@ -1113,6 +1293,10 @@ fn register_normal_mode_key_binds(builder: &mut KeyBindsBuilder<'_>, without_mar
 
     builder.cherry_pick().register();
 
+    if without_marks {
+        builder.resolve_conflicts().register();
+    }
+
     builder.toggle_details().register();
     builder.toggle_full_screen_details().register();
     builder.focus_details().register();
@@ -1128,12 +1312,17 @@ fn register_normal_mode_key_binds(builder: &mut KeyBindsBuilder<'_>, without_mar
     builder.all_files().register();
 
     builder.discard().register();
+    builder.split().register();
 
     builder.mark().register();
 
     builder.undo().register();
     builder.redo().register();
 
+    if without_marks {
+        builder.oplog().register();
+    }
+
     builder.jump_enter().register();
     builder.branch_picker().register();
     builder.uncommitted_area().register();
@@ -1483,6 +1672,7 @@ fn normalize_char_for_display(ch: char, shifted: bool) -> char {
 pub enum KeyBindCondition {
     SelectionIsUncommitted,
     DetailsReturnModeIsPickChanges,
+    ConflictModeIsResolvingPaths,
     And {
         lhs: &'static Self,
         rhs: &'static Self,
@@ -1524,6 +1714,9 @@ impl KeyBindCondition {
                     false
                 }
             }
+            KeyBindCondition::ConflictModeIsResolvingPaths => {
+                matches!(mode, Mode::Conflict(conflict_mode) if conflict_mode.is_resolving_paths())
+            }
         }
     }
 }
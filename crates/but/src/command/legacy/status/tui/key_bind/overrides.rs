@@ -0,0 +1,268 @@
+//! User-defined key binds, loaded from [`KEY_BINDS_FILENAME`] in the application config directory.
+//!
+//! The file maps the [config name](ModeDiscriminant::config_name) of a mode to the key binds to
+//! change in that mode. Key binds are identified by the short description shown in the hotbar and
+//! help overlay, and are bound to a chord in the same representation that is displayed there.
+//!
+//! ```json
+//! {
+//!   "normal": { "down": "ctrl+n", "up": "ctrl+p", "commit": "alt+c" },
+//!   "details": { "scroll down": "ctrl+↓/j" }
+//! }
+//! ```
+
+use std::{collections::BTreeMap, str::FromStr};
+
+use anyhow::{Context as _, bail};
+use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
+use strum::IntoEnumIterator;
+
+use super::{KeyBindId, KeyBinds, KeyMatcher, default_key_binds};
+use crate::command::legacy::status::tui::mode::ModeDiscriminant;
+
+/// Name of the user-defined key binds file.
+pub const KEY_BINDS_FILENAME: &str = "key-binds.json";
+
+/// Validated key bind overrides, ready to be applied with [`KeyBinds::with_overrides()`].
+#[derive(Debug, Default)]
+pub struct KeyBindOverrides {
+    overrides: Vec<KeyBindOverride>,
+}
+
+#[derive(Debug)]
+struct KeyBindOverride {
+    mode: ModeDiscriminant,
+    short_description: String,
+    key_matcher: KeyMatcher,
+}
+
+impl KeyBindOverrides {
+    /// Load the overrides from the user-defined key binds file, which is fine to be missing.
+    pub fn load() -> anyhow::Result<Self> {
+        let path = but_path::app_config_dir()?.join(KEY_BINDS_FILENAME);
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to read '{}'", path.display()));
+            }
+        };
+        Self::from_json(&content)
+            .with_context(|| format!("Invalid key binds in '{}'", path.display()))
+    }
+
+    /// Parse overrides from `json`, and validate that all modes and key binds in it exist.
+    pub fn from_json(json: &str) -> anyhow::Result<Self> {
+        let modes: BTreeMap<String, BTreeMap<String, String>> = serde_json::from_str(json)?;
+        let defaults = default_key_binds();
+
+        let mut overrides = Vec::new();
+        for (mode_name, key_binds) in modes {
+            let Some(mode) = ModeDiscriminant::iter().find(|mode| mode.config_name() == mode_name)
+            else {
+                bail!(
+                    "Unknown mode '{mode_name}', expected one of {}",
+                    ModeDiscriminant::iter()
+                        .map(|mode| format!("'{}'", mode.config_name()))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            };
+            for (short_description, chord) in key_binds {
+                if !defaults
+                    .iter_key_binds_available_in_mode_regardless_of_conditions(mode)
+                    .any(|key_bind| key_bind.short_description == short_description)
+                {
+                    bail!("There is no key bind named '{short_description}' in {mode_name} mode");
+                }
+                let key_matcher = chord.parse().with_context(|| {
+                    format!("Invalid chord for '{short_description}' in {mode_name} mode")
+                })?;
+                overrides.push(KeyBindOverride {
+                    mode,
+                    short_description,
+                    key_matcher,
+                });
+            }
+        }
+        Ok(KeyBindOverrides { overrides })
+    }
+}
+
+impl KeyBinds {
+    /// Rebind all key binds named in `overrides`, and fail if a rebound key bind now shares a chord
+    /// with another key bind of the same mode, whether that one was rebound as well or not.
+    ///
+    /// All conflicts are reported at once, regardless of the conditions under which the key binds
+    /// are available. Overrides for key binds that aren't registered here are ignored.
+    pub fn with_overrides(mut self, overrides: &KeyBindOverrides) -> anyhow::Result<Self> {
+        let mut overridden = Vec::new();
+        for KeyBindOverride {
+            mode,
+            short_description,
+            key_matcher,
+        } in &overrides.overrides
+        {
+            for id in self.key_bind_ids_in_mode(*mode) {
+                let key_bind = &mut self.all_key_binds[id.0];
+                if key_bind.short_description == *short_description {
+                    key_bind.key_matcher = *key_matcher;
+                    key_bind.chord_display = key_matcher.chord_display();
+                    overridden.push((*mode, id.0));
+                }
+            }
+        }
+
+        let mut conflicts = Vec::new();
+        for &(mode, idx) in &overridden {
+            let key_bind = &self.all_key_binds[idx];
+            for KeyBindId(other_idx) in self.key_bind_ids_in_mode(mode) {
+                let other = &self.all_key_binds[other_idx];
+                if other.short_description == key_bind.short_description
+                    || !other.key_matcher.overlaps(&key_bind.key_matcher)
+                {
+                    continue;
+                }
+                let conflict = if overridden.contains(&(mode, other_idx)) {
+                    // Both are overrides, so the pair is seen from either side.
+                    if key_bind.short_description > other.short_description {
+                        continue;
+                    }
+                    format!(
+                        "'{}' and '{}' are both bound to {} in {} mode",
+                        key_bind.short_description,
+                        other.short_description,
+                        key_bind.chord_display,
+                        mode.config_name(),
+                    )
+                } else {
+                    format!(
+                        "'{}' is bound to {} in {} mode, which is already used by '{}'",
+                        key_bind.short_description,
+                        key_bind.chord_display,
+                        mode.config_name(),
+                        other.short_description,
+                    )
+                };
+                if !conflicts.contains(&conflict) {
+                    conflicts.push(conflict);
+                }
+            }
+        }
+        if !conflicts.is_empty() {
+            bail!("{}", conflicts.join("\n"));
+        }
+
+        Ok(self)
+    }
+
+    fn key_bind_ids_in_mode(&self, mode: ModeDiscriminant) -> Vec<KeyBindId> {
+        self.mode_to_key_binds
+            .get(&mode)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+impl KeyMatcher {
+    /// Return `true` if there is a key event that would be matched by both `self` and `other`.
+    pub(super) fn overlaps(&self, other: &KeyMatcher) -> bool {
+        self.kind == other.kind
+            && self.modifiers == other.modifiers
+            && self
+                .codes
+                .into_iter()
+                .flatten()
+                .any(|code| other.codes.contains(&Some(code)))
+    }
+}
+
+/// Parse the chord display format, like `ctrl+r`, `shift+↓/j` or `esc/n`, back into a matcher.
+///
+/// Arrow keys may also be spelled out as `up`, `down`, `left` and `right`.
+impl FromStr for KeyMatcher {
+    type Err = anyhow::Error;
+
+    fn from_str(chord: &str) -> Result<Self, Self::Err> {
+        let mut rest = chord.trim();
+        let mut modifiers = KeyModifiers::NONE;
+        while let Some((prefix, tail)) = rest.split_once('+')
+            && !tail.is_empty()
+        {
+            modifiers |= match prefix {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => break,
+            };
+            rest = tail;
+        }
+
+        let keys: Vec<_> = if rest == "/" {
+            vec![rest]
+        } else {
+            rest.split('/').collect()
+        };
+        if keys.len() > 2 {
+            bail!("'{chord}' has more than two alternative keys");
+        }
+
+        let mut codes = [None, None];
+        for (slot, key) in codes.iter_mut().zip(keys) {
+            let code = parse_key_code(key).with_context(|| format!("Invalid chord '{chord}'"))?;
+            if let KeyCode::Char(ch) = code
+                && ch.is_ascii_uppercase()
+            {
+                modifiers |= KeyModifiers::SHIFT;
+            }
+            *slot = Some(code);
+        }
+
+        // Terminals report shifted letters in uppercase, which is also how built-in key binds are defined.
+        if modifiers.contains(KeyModifiers::SHIFT) {
+            for code in codes.iter_mut().flatten() {
+                if let KeyCode::Char(ch) = code {
+                    *ch = ch.to_ascii_uppercase();
+                }
+            }
+        }
+
+        Ok(KeyMatcher {
+            kind: KeyEventKind::Press,
+            modifiers,
+            codes,
+        })
+    }
+}
+
+/// The inverse of [`super::format_key_code()`].
+fn parse_key_code(key: &str) -> anyhow::Result<KeyCode> {
+    Ok(match key {
+        "backspace" => KeyCode::Backspace,
+        "enter" => KeyCode::Enter,
+        "←" | "left" => KeyCode::Left,
+        "→" | "right" => KeyCode::Right,
+        "↑" | "up" => KeyCode::Up,
+        "↓" | "down" => KeyCode::Down,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "del" => KeyCode::Delete,
+        "ins" => KeyCode::Insert,
+        "esc" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        _ => {
+            let mut chars = key.chars();
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) => KeyCode::Char(ch),
+                _ => match key.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                    Some(number) => KeyCode::F(number),
+                    None => bail!("Unknown key '{key}'"),
+                },
+            }
+        }
+    })
+}
@@ -1,6 +1,10 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use strum::IntoEnumIterator;
 
-use crate::command::legacy::status::tui::key_bind::press;
+use crate::command::legacy::status::tui::{
+    key_bind::{KeyBind, KeyBindOverrides, KeyBinds, KeyMatcher, default_key_binds, press},
+    mode::ModeDiscriminant,
+};
 
 #[test]
 fn chord_display_for_plain_and_modified_keys() {
@@ -96,3 +100,131 @@ fn matcher_still_matches_primary_and_alternate_codes() {
     assert!(matcher.matches(&KeyEvent::new(KeyCode::Down, KeyModifiers::NONE)));
     assert!(!matcher.matches(&KeyEvent::new(KeyCode::Char('j'), KeyModifiers::SHIFT)));
 }
+
+#[test]
+fn chords_of_all_default_key_binds_parse_back_into_the_same_chord() {
+    let key_binds = default_key_binds();
+    for mode in ModeDiscriminant::iter() {
+        for key_bind in key_binds.iter_key_binds_available_in_mode_regardless_of_conditions(mode) {
+            let parsed: KeyMatcher = key_bind.chord_display().parse().unwrap();
+            assert_eq!(parsed.chord_display(), key_bind.chord_display());
+            assert!(parsed.overlaps(&key_bind.key_matcher));
+        }
+    }
+}
+
+#[test]
+fn chords_can_use_spelled_out_arrows_and_uppercase_letters() {
+    assert_eq!(
+        "ctrl+down/j".parse::<KeyMatcher>().unwrap().chord_display(),
+        "ctrl+↓/j"
+    );
+    assert_eq!(
+        "J".parse::<KeyMatcher>().unwrap().chord_display(),
+        "shift+j"
+    );
+    assert_eq!(
+        "ctrl++".parse::<KeyMatcher>().unwrap().chord_display(),
+        "ctrl++"
+    );
+    assert_eq!("/".parse::<KeyMatcher>().unwrap().chord_display(), "/");
+    assert_eq!("f5".parse::<KeyMatcher>().unwrap().chord_display(), "f5");
+}
+
+#[test]
+fn invalid_chords_are_rejected() {
+    for chord in ["", "hyper+x", "a/b/c", "fx", "ctrl+"] {
+        assert!(
+            chord.parse::<KeyMatcher>().is_err(),
+            "{chord:?} should not parse"
+        );
+    }
+}
+
+#[test]
+fn overrides_rebind_key_binds_of_a_mode() {
+    let overrides =
+        KeyBindOverrides::from_json(r#"{ "normal": { "down": "ctrl+n", "up": "ctrl+p" } }"#)
+            .unwrap();
+    let key_binds = default_key_binds().with_overrides(&overrides).unwrap();
+
+    let down = key_bind(&key_binds, ModeDiscriminant::Normal, "down");
+    assert_eq!(down.chord_display(), "ctrl+n");
+    assert!(down.matches(&KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL)));
+    assert!(!down.matches(&KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE)));
+
+    assert_eq!(
+        key_bind(&key_binds, ModeDiscriminant::Commit, "down").chord_display(),
+        "↓/j",
+        "other modes keep their key binds"
+    );
+}
+
+#[test]
+fn overrides_may_swap_chords() {
+    let overrides =
+        KeyBindOverrides::from_json(r#"{ "normal": { "commit": "m", "move": "c" } }"#).unwrap();
+    let key_binds = default_key_binds().with_overrides(&overrides).unwrap();
+
+    assert_eq!(
+        key_bind(&key_binds, ModeDiscriminant::Normal, "commit").chord_display(),
+        "m"
+    );
+    assert_eq!(
+        key_bind(&key_binds, ModeDiscriminant::Normal, "move").chord_display(),
+        "c"
+    );
+}
+
+#[test]
+fn overrides_conflicting_with_other_key_binds_are_rejected() {
+    let overrides = KeyBindOverrides::from_json(r#"{ "normal": { "down": "c" } }"#).unwrap();
+    let err = default_key_binds().with_overrides(&overrides).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "'down' is bound to c in normal mode, which is already used by 'commit'"
+    );
+}
+
+#[test]
+fn overrides_conflicting_with_each_other_are_rejected() {
+    let overrides =
+        KeyBindOverrides::from_json(r#"{ "normal": { "down": "f12", "up": "f12" } }"#).unwrap();
+    let err = default_key_binds().with_overrides(&overrides).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "'down' and 'up' are both bound to f12 in normal mode"
+    );
+}
+
+#[test]
+fn overrides_conflicting_with_conditional_key_binds_are_rejected() {
+    let overrides =
+        KeyBindOverrides::from_json(r#"{ "conflict": { "up": "o", "down": "t" } }"#).unwrap();
+    let err = default_key_binds().with_overrides(&overrides).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "'down' is bound to t in conflict mode, which is already used by 'theirs'\n\
+         'up' is bound to o in conflict mode, which is already used by 'ours'",
+        "key binds that are only available while resolving paths conflict as well, and all conflicts are reported"
+    );
+}
+
+#[test]
+fn overrides_of_unknown_modes_or_key_binds_are_rejected() {
+    let err = KeyBindOverrides::from_json(r#"{ "normal": { "frobnicate": "x" } }"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "There is no key bind named 'frobnicate' in normal mode"
+    );
+
+    let err = KeyBindOverrides::from_json(r#"{ "insert": { "down": "x" } }"#).unwrap_err();
+    assert!(err.to_string().starts_with("Unknown mode 'insert'"));
+}
+
+fn key_bind<'a>(key_binds: &'a KeyBinds, mode: ModeDiscriminant, name: &str) -> &'a KeyBind {
+    key_binds
+        .iter_key_binds_available_in_mode_regardless_of_conditions(mode)
+        .find(|key_bind| key_bind.short_description() == name)
+        .unwrap_or_else(|| panic!("key bind '{name}' should exist in {mode:?} mode"))
+}
@@ -20,9 +20,9 @@ use crate::{
             StatusFlags, StatusOutputLine, TuiLaunchOptions, TuiOutcome, TuiRunOptions,
             tui::{
                 app::{
-                    CherryPickMessage, CommandMessage, CommandModeKind, CommitMessage, JumpMessage,
-                    MoveMessage, NormalMode, PickChangesMode, RewordMessage, SquashMessage,
-                    StackMessage, UpdateContext,
+                    CherryPickMessage, CommandMessage, CommandModeKind, CommitMessage,
+                    ConflictMessage, JumpMessage, MoveMessage, NormalMode, OplogMessage,
+                    PickChangesMode, RewordMessage, SquashMessage, StackMessage, UpdateContext,
                 },
                 backstack::{Backstack, BackstackEntry},
                 confirm::ConfirmMessage,
@@ -264,6 +264,8 @@ fn event_to_messages(ev: Event, app: &App, terminal_area: Rect, messages: &mut V
                         | Mode::PickChanges(..)
                         | Mode::MoveStack(..)
                         | Mode::CherryPick(..)
+                        | Mode::Conflict(..)
+                        | Mode::Oplog(..)
                         | Mode::Move(..) => {}
                     }
                 }
@@ -300,6 +302,8 @@ fn event_to_messages(ev: Event, app: &App, terminal_area: Rect, messages: &mut V
                 | Mode::PickChanges(..)
                 | Mode::MoveStack(..)
                 | Mode::CherryPick(..)
+                | Mode::Conflict(..)
+                | Mode::Oplog(..)
                 | Mode::Move(..) => {
                     messages.push(Message::JustRender);
                 }
@@ -405,6 +409,7 @@ pub enum Message {
     Confirm(ConfirmMessage),
     Discard,
     DropToBeDiscarded,
+    Split,
     GrowDetails,
     ShrinkDetails,
     DebugScrollUp(usize),
@@ -438,6 +443,8 @@ pub enum Message {
     Help(HelpMessage),
     Jump(JumpMessage),
     CherryPick(CherryPickMessage),
+    Conflict(ConflictMessage),
+    Oplog(OplogMessage),
     NewBranch,
     ToggleHelp,
     Mark,
@@ -629,6 +636,25 @@ fn dedup_mutation_messages(messages: &mut Vec<Message>, other_messages: &mut Vec
                 CherryPickMessage::Confirm | CherryPickMessage::CherryPickToNewBranch => true,
                 CherryPickMessage::Start | CherryPickMessage::ToggleInsertSide => false,
             },
+            Message::Conflict(message) => match message {
+                ConflictMessage::Resolve
+                | ConflictMessage::Pick(_)
+                | ConflictMessage::OpenEditor
+                | ConflictMessage::Finish
+                | ConflictMessage::Leave => true,
+                ConflictMessage::Start
+                | ConflictMessage::Up
+                | ConflictMessage::Down
+                | ConflictMessage::NextHunk
+                | ConflictMessage::PrevHunk
+                | ConflictMessage::Cancel => false,
+            },
+            Message::Oplog(message) => match message {
+                OplogMessage::Start
+                | OplogMessage::Up
+                | OplogMessage::Down
+                | OplogMessage::Restore => false,
+            },
             Message::Stack(message) => match message {
                 StackMessage::Unapply | StackMessage::MoveConfirm => true,
                 StackMessage::Enter | StackMessage::ShowApplyPicker | StackMessage::MoveStart => {
@@ -686,7 +712,11 @@ fn dedup_mutation_messages(messages: &mut Vec<Message>, other_messages: &mut Vec
                 | Modal::ProgramPicker { .. }
                 | Modal::Help { .. } => false,
             },
-            Message::Undo | Message::Redo | Message::Discard | Message::NewBranch => true,
+            Message::Undo
+            | Message::Redo
+            | Message::Discard
+            | Message::Split
+            | Message::NewBranch => true,
             Message::JustRender
             | Message::Quit
             | Message::ConfirmAndQuit
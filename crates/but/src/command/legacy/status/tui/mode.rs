@@ -4,9 +4,9 @@ use crate::{
     command::legacy::status::tui::{
         InlineRewordMode,
         app::{
-            CherryPickMode, CommandMode, CommandReturnMode, CommitMode, CommitSource, JumpMode,
-            MoveMode, MoveSource, MoveStackMode, NormalMode, PickChangesMode, SquashMode,
-            StackMode,
+            CherryPickMode, CommandMode, CommandReturnMode, CommitMode, CommitSource, ConflictMode,
+            JumpMode, MoveMode, MoveSource, MoveStackMode, NormalMode, OplogMode, PickChangesMode,
+            SquashMode, StackMode,
             mark::{Marks, MarksRef},
         },
         render::ModeRender,
@@ -32,6 +32,8 @@ pub enum Mode {
     PickChanges(PickChangesMode),
     Jump(JumpMode),
     CherryPick(CherryPickMode),
+    Conflict(ConflictMode),
+    Oplog(OplogMode),
 }
 
 impl Default for Mode {
@@ -69,6 +71,8 @@ impl Mode {
             Mode::PickChanges(inner) => ModeRef::PickChanges(inner),
             Mode::Jump(inner) => ModeRef::Jump(inner),
             Mode::CherryPick(inner) => ModeRef::CherryPick(inner),
+            Mode::Conflict(inner) => ModeRef::Conflict(inner),
+            Mode::Oplog(inner) => ModeRef::Oplog(inner),
         }
     }
 }
@@ -82,7 +86,9 @@ impl ModeDiscriminant {
             Self::InlineReword | Self::Stack => {
                 theme.tui_mode_inline_reword.bg.unwrap_or(Color::Magenta)
             }
-            Self::Command => theme.tui_mode_command.bg.unwrap_or(Color::Yellow),
+            Self::Command | Self::Conflict | Self::Oplog => {
+                theme.tui_mode_command.bg.unwrap_or(Color::Yellow)
+            }
             Self::Move | Self::MoveStack | Self::CherryPick => {
                 theme.tui_mode_move.bg.unwrap_or(Color::Cyan)
             }
@@ -101,7 +107,9 @@ impl ModeDiscriminant {
             Self::InlineReword | Self::Stack => {
                 theme.tui_mode_inline_reword.fg.unwrap_or(Color::Black)
             }
-            Self::Command => theme.tui_mode_command.fg.unwrap_or(Color::Black),
+            Self::Command | Self::Conflict | Self::Oplog => {
+                theme.tui_mode_command.fg.unwrap_or(Color::Black)
+            }
             Self::Move | Self::MoveStack | Self::CherryPick => {
                 theme.tui_mode_move.fg.unwrap_or(Color::Black)
             }
@@ -123,6 +131,28 @@ impl ModeDiscriminant {
             Self::MoveStack => "  move stack  ",
             Self::Jump => "  jump  ",
             Self::CherryPick => "  pick  ",
+            Self::Conflict => "  conflict  ",
+            Self::Oplog => "  oplog  ",
+        }
+    }
+
+    /// The name of the mode as used in configuration files.
+    pub fn config_name(self) -> &'static str {
+        match self {
+            Self::Normal => "normal",
+            Self::Squash => "squash",
+            Self::InlineReword => "inline-reword",
+            Self::Command => "command",
+            Self::Commit => "commit",
+            Self::PickChanges => "pick-changes",
+            Self::Move => "move",
+            Self::Details => "details",
+            Self::Stack => "stack",
+            Self::MoveStack => "move-stack",
+            Self::Jump => "jump",
+            Self::CherryPick => "cherry-pick",
+            Self::Conflict => "conflict",
+            Self::Oplog => "oplog",
         }
     }
 }
@@ -142,6 +172,8 @@ pub enum ModeRef<'a> {
     PickChanges(&'a PickChangesMode),
     Jump(&'a JumpMode),
     CherryPick(&'a CherryPickMode),
+    Conflict(&'a ConflictMode),
+    Oplog(&'a OplogMode),
 }
 
 impl<'a> ModeRef<'a> {
@@ -177,7 +209,9 @@ impl<'a> ModeRef<'a> {
             ModeRef::InlineReword(..)
             | ModeRef::Stack(..)
             | ModeRef::MoveStack(..)
-            | ModeRef::Jump(..) => MarksRef::Empty,
+            | ModeRef::Jump(..)
+            | ModeRef::Conflict(..)
+            | ModeRef::Oplog(..) => MarksRef::Empty,
         }
     }
 }
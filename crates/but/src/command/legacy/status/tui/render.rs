@@ -85,7 +85,13 @@ pub fn render_app(app: &App, frame: &mut Frame) {
             let status_block = pane_block(app, Borders::NONE);
             let status_inner_area = status_block.inner(layout.status_area);
             frame.render_widget(status_block, layout.status_area);
-            render_status(app, status_inner_area, frame);
+            if let Mode::Conflict(conflict_mode) = &*app.mode {
+                conflict_mode.render(app, status_inner_area, frame);
+            } else if let Mode::Oplog(oplog_mode) = &*app.mode {
+                oplog_mode.render(app, status_inner_area, frame);
+            } else {
+                render_status(app, status_inner_area, frame);
+            }
         }
     }
 
@@ -280,8 +286,10 @@ pub(crate) fn debug_content_area_for_app(app: &App, terminal_area: Rect) -> Opti
 }
 
 pub fn status_layout(app: &App, area: Rect) -> StatusLayout {
-    if let Mode::Details(details_mode) = &*app.mode
-        && details_mode.full_screen
+    // Conflict and oplog mode take up the whole area as there is no selection to show details
+    // for.
+    if matches!(&*app.mode, Mode::Details(details_mode) if details_mode.full_screen)
+        || matches!(&*app.mode, Mode::Conflict(..) | Mode::Oplog(..))
     {
         return StatusLayout {
             status_area: area,
@@ -1506,6 +1514,8 @@ impl Mode {
             Mode::PickChanges(mode) => mode,
             Mode::Jump(mode) => mode,
             Mode::CherryPick(mode) => mode,
+            Mode::Conflict(mode) => mode,
+            Mode::Oplog(mode) => mode,
         }
     }
 }
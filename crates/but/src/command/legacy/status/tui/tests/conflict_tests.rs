@@ -0,0 +1,75 @@
+use but_core::DryRun;
+use but_testsupport::Sandbox;
+use crossterm::event::KeyCode;
+
+use crate::{command::legacy::status::tui::tests::test_status_tui, tui::test_utils::Shift};
+
+/// Integrate an upstream change that conflicts with the only commit of branch `A`.
+fn sandbox_with_conflicted_commit() -> Sandbox {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("upstream-conflicted");
+    env.setup_metadata(&["A"]);
+    env.set_target_sha("refs/heads/base");
+
+    let mut ctx = env.context();
+    let head_info = but_api::legacy::workspace::head_info(&ctx).unwrap();
+    let updates = but_api::workspace::rebase_stack_bottoms(&head_info);
+    let mut guard = ctx.exclusive_worktree_access();
+    but_api::workspace::workspace_integrate_upstream_with_perm(
+        &mut ctx,
+        updates,
+        DryRun::No,
+        guard.write_permission(),
+    )
+    .unwrap();
+    env
+}
+
+#[test]
+fn resolve_conflicts_without_conflicted_commits() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+
+    let mut tui = test_status_tui(env);
+
+    tui.input(Shift('C'))
+        .assert_rendered_contains("No conflicted commits to resolve")
+        .assert_rendered_contains("normal");
+}
+
+#[test]
+fn pick_a_side_of_a_conflict() {
+    let mut tui = test_status_tui(sandbox_with_conflicted_commit());
+
+    tui.input(Shift('C'))
+        .assert_rendered_contains("Conflicted commits")
+        .assert_rendered_contains("A-change");
+
+    tui.input(KeyCode::Enter)
+        .assert_rendered_contains("Conflicted files")
+        .assert_rendered_contains("conflict 1 of 1");
+
+    tui.input('o')
+        .assert_rendered_contains("No conflict markers left.");
+
+    let content = tui.env().read_file("file.txt").unwrap();
+    assert_eq!(content, "change-on-main\n", "the new base's side was taken");
+}
+
+#[test]
+fn pick_a_side_of_a_conflict_that_is_gone() {
+    let mut tui = test_status_tui(sandbox_with_conflicted_commit());
+
+    tui.input(Shift('C'));
+    tui.input(KeyCode::Enter)
+        .assert_rendered_contains("conflict 1 of 1");
+
+    tui.env().file("file.txt", "resolved by hand\n");
+
+    tui.input('t')
+        .assert_rendered_contains("The conflict is gone, the file was changed since it was shown")
+        .assert_rendered_contains("No conflict markers left.")
+        .assert_rendered_contains("Conflicted files");
+
+    let content = tui.env().read_file("file.txt").unwrap();
+    assert_eq!(content, "resolved by hand\n", "the file is left untouched");
+}
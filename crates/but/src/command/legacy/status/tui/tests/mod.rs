@@ -21,6 +21,7 @@ mod branch_picker_tests;
 mod branch_tests;
 mod command_tests;
 mod commit_tests;
+mod conflict_tests;
 mod copy_tests;
 mod details_tests;
 mod discard_tests;
@@ -28,7 +29,9 @@ mod jump_tests;
 mod marking_tests;
 mod move_tests;
 mod open_tests;
+mod oplog_tests;
 mod pick_tests;
+mod split_tests;
 mod squash_tests;
 mod stack_tests;
 mod utils;
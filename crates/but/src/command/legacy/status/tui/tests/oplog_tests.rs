@@ -0,0 +1,32 @@
+use but_testsupport::Sandbox;
+use crossterm::event::KeyCode;
+
+use crate::{command::legacy::status::tui::tests::test_status_tui, tui::test_utils::Shift};
+
+#[test]
+fn oplog_lists_snapshots_and_asks_before_restoring() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+    let mut tui = test_status_tui(env);
+
+    tui.env().file("test.txt", "content");
+    tui.input('c');
+    tui.input(KeyCode::Down);
+    tui.input('i');
+    tui.input(KeyCode::Enter);
+    tui.input("commit for oplog test");
+    tui.input(KeyCode::Enter);
+
+    tui.input(Shift('H'))
+        .assert_rendered_contains("Operations log")
+        .assert_rendered_contains("[COMMIT]")
+        .assert_rendered_contains("Restoring would change")
+        .assert_rendered_contains("oplog");
+
+    tui.input(KeyCode::Enter)
+        .assert_rendered_contains("Restore the workspace to");
+
+    tui.input('n').assert_rendered_contains("Operations log");
+
+    tui.input(KeyCode::Esc).assert_rendered_contains("normal");
+}
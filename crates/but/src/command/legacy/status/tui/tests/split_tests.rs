@@ -0,0 +1,45 @@
+use but_testsupport::Sandbox;
+
+use crate::{command::legacy::status::tui::tests::utils::test_status_tui, tui::test_utils::Shift};
+
+#[test]
+fn split_committed_file_into_new_commit() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("zero-stacks");
+    env.setup_metadata(&[]);
+
+    env.file("one", "");
+    env.file("two", "");
+
+    let mut tui = test_status_tui(env);
+
+    tui.input('c');
+    tui.input('e');
+    tui.input('b');
+
+    tui.input('f');
+    tui.input(Shift('S'))
+        .assert_rendered_contains("Split one out of");
+
+    tui.input('n');
+    assert_eq!(
+        tui.env()
+            .invoke_git("diff-tree --no-commit-id --name-only -r c-branch-1"),
+        "one\ntwo",
+        "cancelling leaves the commit alone"
+    );
+
+    tui.input(Shift('S'));
+    tui.input('y');
+
+    let env = tui.env();
+    assert_eq!(
+        env.invoke_git("diff-tree --no-commit-id --name-only -r c-branch-1"),
+        "one",
+        "the split out file lands in a new commit on top"
+    );
+    assert_eq!(
+        env.invoke_git("diff-tree --no-commit-id --name-only -r c-branch-1~1"),
+        "two",
+        "the original commit keeps the remaining files"
+    );
+}
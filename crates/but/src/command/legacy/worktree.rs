@@ -166,6 +166,51 @@ pub fn handle(cmd: Subcommands, ctx: &mut Context, out: &mut OutputChannel) -> R
 
             Ok(())
         }
+        Subcommands::Edit { commit } => {
+            let (commit_id, commit_ref) = super::resolve::parse_commit_id(ctx, &commit)?;
+            let stack_id =
+                super::resolve::find_stack_containing_commit(ctx, commit_id, &commit_ref)?;
+            let worktree =
+                but_api::legacy::modes::enter_edit_mode_in_worktree(ctx, commit_id, stack_id)?;
+
+            if let Some(out) = out.for_json() {
+                out.write_value(worktree)?;
+            } else if let Some(out) = out.for_human() {
+                writeln!(
+                    out,
+                    "Editing {commit_ref} in worktree at: {}",
+                    worktree.path.display()
+                )?;
+                writeln!(
+                    out,
+                    "Run `but worktree finish {}` to apply the changes to the commit",
+                    worktree.id
+                )?;
+            }
+            Ok(())
+        }
+        Subcommands::Finish { path } => {
+            let id = parse_worktree_identifier(&path)?;
+            but_api::legacy::modes::save_worktree_edit_and_return(ctx, id.clone())?;
+
+            if let Some(out) = out.for_json() {
+                out.write_value(serde_json::json!({"status": "success"}))?;
+            } else if let Some(out) = out.for_human() {
+                writeln!(out, "Applied the edit from worktree: {id}")?;
+            }
+            Ok(())
+        }
+        Subcommands::Abort { path, force } => {
+            let id = parse_worktree_identifier(&path)?;
+            but_api::legacy::modes::abort_worktree_edit(ctx, id.clone(), force)?;
+
+            if let Some(out) = out.for_json() {
+                out.write_value(serde_json::json!({"status": "success"}))?;
+            } else if let Some(out) = out.for_human() {
+                writeln!(out, "Removed the edit worktree without applying it: {id}")?;
+            }
+            Ok(())
+        }
         Subcommands::Destroy { target, reference } => {
             if reference {
                 // Treat target as a reference - parse it
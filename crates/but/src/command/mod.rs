@@ -11,6 +11,7 @@ pub mod config;
 pub mod expand;
 pub(crate) mod external;
 pub(crate) mod git_config;
+pub mod graph;
 pub mod gui;
 pub mod help;
 pub mod mcp;
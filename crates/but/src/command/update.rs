@@ -5,7 +5,7 @@ use crate::{
 };
 use anyhow::Result;
 #[cfg(all(unix, not(feature = "packaged-but-distribution")))]
-use but_installer::VersionRequest;
+use but_installer::{ReleaseSource, VersionRequest};
 use but_settings::AppSettings;
 use but_update::{AppName, CheckUpdateStatus, check_status};
 
@@ -13,12 +13,19 @@ pub fn handle(
     cmd: update::Subcommands,
     out: &mut OutputChannel,
     app_settings: &AppSettings,
+    current_dir: &std::path::Path,
 ) -> Result<()> {
+    #[cfg(not(all(unix, not(feature = "packaged-but-distribution"))))]
+    let _ = current_dir;
     match cmd {
         update::Subcommands::Check => check_for_updates(out, app_settings),
         update::Subcommands::Suppress { days } => suppress_updates(out, days),
         #[cfg(all(unix, not(feature = "packaged-but-distribution")))]
-        update::Subcommands::Install { target } => install(out, target),
+        update::Subcommands::Install { target, from } => {
+            install(out, target, from, app_settings, current_dir)
+        }
+        #[cfg(all(unix, not(feature = "packaged-but-distribution")))]
+        update::Subcommands::Rollback => rollback(out),
     }
 }
 
@@ -131,7 +138,13 @@ fn suppress_updates(out: &mut OutputChannel, days: u32) -> Result<()> {
 }
 
 #[cfg(all(unix, not(feature = "packaged-but-distribution")))]
-fn install(out: &mut OutputChannel, target: Option<String>) -> Result<()> {
+fn install(
+    out: &mut OutputChannel,
+    target: Option<String>,
+    from: Option<String>,
+    app_settings: &AppSettings,
+    current_dir: &std::path::Path,
+) -> Result<()> {
     // Installation requires interactive output and cannot be used with JSON mode
     // because the installer writes directly to stdout/stderr
     if out.for_json().is_some() {
@@ -157,9 +170,19 @@ fn install(out: &mut OutputChannel, target: Option<String>) -> Result<()> {
             })?
         }
         None => {
+            // Installing the latest version would fight the project's pin, so make it explicit.
+            if let Some(pinned) = crate::utils::version_policy::discover_at(current_dir)
+                .and_then(|policy| policy.pinned)
+            {
+                anyhow::bail!(
+                    "This project pins `but` to version {pinned}.\n\n\
+                     Run 'but update install {pinned}' to install it, or name another target:\n  \
+                     nightly          Install latest nightly build\n  \
+                     release          Install latest stable release"
+                );
+            }
             // Auto-detect from current channel
-            let current_channel = but_path::AppChannel::new();
-            match current_channel {
+            match but_path::AppChannel::new() {
                 but_path::AppChannel::Nightly => VersionRequest::Nightly,
                 but_path::AppChannel::Release => VersionRequest::Release,
                 but_path::AppChannel::Dev => VersionRequest::Release, // Dev installs release
@@ -167,9 +190,17 @@ fn install(out: &mut OutputChannel, target: Option<String>) -> Result<()> {
         }
     };
 
+    // An explicit source takes precedence over the configured one, which takes precedence over the environment.
+    let source = match from.as_deref().or_else(|| {
+        Some(app_settings.app_updates_mirror.trim()).filter(|mirror| !mirror.is_empty())
+    }) {
+        Some(from) => ReleaseSource::parse(from)?,
+        None => ReleaseSource::from_env()?,
+    };
+
     // Call installer directly (handles all user-facing output)
     // Don't print usage info since user is already using the CLI
-    but_installer::run_installation_with_version(version_request, false)?;
+    but_installer::run_installation_with_version(version_request, source, false)?;
 
     // Show change log link
     if let Some(writer) = out.for_human() {
@@ -197,3 +228,19 @@ fn install(out: &mut OutputChannel, target: Option<String>) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(all(unix, not(feature = "packaged-but-distribution")))]
+fn rollback(out: &mut OutputChannel) -> Result<()> {
+    // Like installation, the rollback writes directly to stdout/stderr
+    if out.for_json().is_some() {
+        anyhow::bail!("JSON output is not supported for 'but update rollback'.");
+    }
+
+    but_installer::run_rollback()?;
+
+    let mut cache = but_ctx::Context::app_cache();
+    if let Err(err) = cache.update_check_mut().and_then(|handle| handle.delete()) {
+        tracing::warn!(?err, "Failed to invalidate update check cache");
+    }
+    Ok(())
+}
@@ -22,10 +22,7 @@
 #![deny(unsafe_code)]
 #![cfg_attr(not(feature = "legacy"), expect(unused))]
 
-use std::{
-    ffi::{OsStr, OsString},
-    io::IsTerminal as _,
-};
+use std::{ffi::OsString, io::IsTerminal as _};
 
 use anyhow::{Context as _, Result};
 use but_api::WorkspaceState;
@@ -173,13 +170,15 @@ fn should_detect_terminal_theme(
         )
 }
 
-fn resolve_theme_preset(
-    theme_name_from_env: Option<&OsStr>,
+fn resolve_theme(
+    theme_name: Option<&str>,
     detected_preset: Option<theme::ThemePreset>,
-) -> anyhow::Result<theme::ThemePreset> {
-    match theme_name_from_env {
-        Some(theme_name) => theme_name.to_string_lossy().parse(),
-        None => Ok(detected_preset.unwrap_or(theme::ThemePreset::Dark)),
+) -> anyhow::Result<theme::Theme> {
+    match theme_name {
+        Some(theme_name) => theme::load_named(theme_name),
+        None => Ok(theme::Theme::default_for(
+            detected_preset.unwrap_or(theme::ThemePreset::Dark),
+        )),
     }
 }
 
@@ -213,33 +212,41 @@ pub async fn handle_args(args: impl Iterator<Item = OsString>) -> Result<()> {
 
     let agent_detected = utils::detect_agent::detect().is_some();
     let (args, alias_expansion_warning) = expand_aliases(args);
-    let theme_name_from_env = std::env::var_os(envs::BUT_THEME);
-    let detected_preset = if theme_name_from_env.is_none()
+    let theme_name = std::env::var_os(envs::BUT_THEME)
+        .map(|theme_name| theme_name.to_string_lossy().into_owned())
+        .or_else(|| {
+            // Respect `-C` even though the arguments are fully parsed only later.
+            let current_dir = Args::try_parse_from(&args)
+                .map(|args| args.current_dir)
+                .unwrap_or_else(|_| ".".into());
+            theme::configured_name(&current_dir)
+        });
+    let detected_preset = if theme_name.is_none()
         && should_detect_terminal_theme(&args, agent_detected, std::io::stdout().is_terminal())
     {
         theme::detect_terminal_preset()
     } else {
         None
     };
-    let theme_preset_result = resolve_theme_preset(theme_name_from_env.as_deref(), detected_preset);
+    let theme_result = resolve_theme(theme_name.as_deref(), detected_preset);
 
     {
-        let theme_preset = match &theme_preset_result {
-            Ok(theme_preset) => theme_preset.clone(),
+        let theme = match &theme_result {
+            Ok(theme) => theme.clone(),
             Err(_) => {
                 // ignore for now, we print a warning once the output channel has been initialized
-                theme::ThemePreset::Dark
+                theme::Theme::default_for(theme::ThemePreset::Dark)
             }
         };
 
         // Note: Overrides in but-theme.json are hardwired to apply to the Dark theme at present.
-        // This is only for internal testing at the moment so it's not worthwhile to go through the
-        // motions of merging overrides with a configurable theme.
+        // This is only for internal testing at the moment, and named themes take precedence.
         let theme = dirs::config_dir()
+            .filter(|_| theme_name.is_none())
             .map(|dir| dir.join("gitbutler").join("but-theme.json"))
             .filter(|p| p.exists())
             .and_then(|p| theme::load(&p).ok())
-            .unwrap_or_else(|| theme::Theme::default_for(theme_preset));
+            .unwrap_or(theme);
         theme::init(theme);
     }
 
@@ -306,10 +313,10 @@ pub async fn handle_args(args: impl Iterator<Item = OsString>) -> Result<()> {
         out.request_pager();
     }
 
-    if let (Err(theme_preset_err), Some(out)) = (theme_preset_result, out.for_human_ui()) {
+    if let (Err(theme_err), Some(out)) = (theme_result, out.for_human_ui()) {
         writeln!(
             out,
-            "{}: {theme_preset_err}",
+            "{}: {theme_err:#}",
             theme::get().attention.paint("Failed to set theme")
         )?;
     }
@@ -321,6 +328,7 @@ pub async fn handle_args(args: impl Iterator<Item = OsString>) -> Result<()> {
         return run_agentlog_command(&args.current_dir, cmd, &mut out);
     }
     let app_settings = app_settings()?.clone();
+    but_secret::secret::setup_from_settings(&app_settings.secrets);
 
     let result = match args.cmd.take() {
         Some(cmd @ Subcommands::External(_)) => {
@@ -610,7 +618,7 @@ async fn match_subcommand(
                 .map_err(CliError::from);
         }
         Subcommands::Update(update_args::Platform { cmd }) => {
-            return command::update::handle(cmd, out, &app_settings)
+            return command::update::handle(cmd, out, &app_settings, &args.current_dir)
                 .emit_metrics(metrics_ctx)
                 .map_err(CliError::from);
         }
@@ -791,12 +799,15 @@ async fn match_subcommand(
             },
             out,
         )?,
-        Subcommands::_Comment(..) => setup::init_ctx(&args, InitCtxOptions::default(), out)?,
+        Subcommands::_Comment(..) | Subcommands::Graph(..) => {
+            setup::init_ctx(&args, InitCtxOptions::default(), out)?
+        }
         #[cfg(feature = "legacy")]
         Subcommands::Actions { .. }
         | Subcommands::Pull { .. }
         | Subcommands::Fetch
         | Subcommands::Worktree(..)
+        | Subcommands::Bisect(..)
         | Subcommands::Push(..)
         | Subcommands::Oplog(..)
         | Subcommands::Undo(..)
@@ -808,6 +819,8 @@ async fn match_subcommand(
         | Subcommands::Status { .. }
         | Subcommands::Diff { .. }
         | Subcommands::Show { .. }
+        | Subcommands::Blame { .. }
+        | Subcommands::FormatPatch(..)
         | Subcommands::Commit(..)
         | Subcommands::Squash(..)
         | Subcommands::Move(..)
@@ -821,6 +834,9 @@ async fn match_subcommand(
         | Subcommands::Uncommit(..)
         | Subcommands::Amend(..)
         | Subcommands::Pick(..)
+        | Subcommands::Am(..)
+        | Subcommands::Revert(..)
+        | Subcommands::Split(..)
         | Subcommands::Unapply(..)
         | Subcommands::Apply(..) => setup::init_ctx(
             &args,
@@ -840,15 +856,17 @@ async fn match_subcommand(
             out,
         )?,
         #[cfg(feature = "legacy")]
-        Subcommands::Teardown { .. } => setup::init_ctx(
-            &args,
-            InitCtxOptions {
-                workspace_check: setup::WorkspaceCheck::Disabled,
-                target_requirement: TargetRequirement::Optional,
-                ..Default::default()
-            },
-            out,
-        )?,
+        Subcommands::Teardown { .. } | Subcommands::Doctor { .. } | Subcommands::Debug(..) => {
+            setup::init_ctx(
+                &args,
+                InitCtxOptions {
+                    workspace_check: setup::WorkspaceCheck::Disabled,
+                    target_requirement: TargetRequirement::Optional,
+                    ..Default::default()
+                },
+                out,
+            )?
+        }
         Subcommands::AgentLog { .. } => {
             unreachable!("agentlog command is handled before metrics setup")
         }
@@ -857,6 +875,25 @@ async fn match_subcommand(
         }
     };
 
+    // The project's version requirements matter to all commands working with it. Commands without
+    // a repository returned above, so checking them doesn't cost another repository discovery.
+    let version_policy_notice = (!is_expand)
+        .then(|| {
+            ctx.repo
+                .get()
+                .ok()
+                .and_then(|repo| utils::version_policy::notice(&repo))
+        })
+        .flatten();
+    if let Some(notice) = version_policy_notice.as_ref() {
+        if let Some(human) = out.for_human() {
+            writeln!(human, "{notice}").ok();
+            writeln!(human).ok();
+        } else {
+            print_err_infallible(format!("{notice}\n"));
+        }
+    }
+
     // If `Some`, and if result is `Ok`, this is passed to
     // `report_newly_conflicted()`.
     #[cfg(feature = "legacy")]
@@ -952,9 +989,10 @@ async fn match_subcommand(
                 files,
                 ai,
                 check,
+                range_diff,
             }) => {
                 command::legacy::branch::show_branches(
-                    &mut ctx, out, branch, review, files, ai, check,
+                    &mut ctx, out, branch, review, files, ai, check, range_diff,
                 )
                 .emit_metrics(metrics_ctx)?;
                 None
@@ -1033,14 +1071,42 @@ async fn match_subcommand(
                 .emit_metrics(metrics_ctx)?;
             None
         }
+        Subcommands::Graph(args::graph::Platform { cmd }) => match cmd {
+            args::graph::Subcommands::Export {
+                format,
+                detail,
+                anonymize,
+                workspace,
+            } => {
+                command::graph::export(&ctx, out, format, detail, anonymize, workspace)
+                    .emit_metrics(metrics_ctx)?;
+                None
+            }
+        },
         #[cfg(feature = "legacy")]
         Subcommands::Actions(actions::Platform { cmd }) => {
             match cmd {
                 Some(actions::Subcommands::HandleChanges {
                     description,
                     handler,
+                    dry_run,
                 }) => {
-                    command::legacy::actions::handle_changes(&mut ctx, out, handler, &description)?;
+                    command::legacy::actions::handle_changes(
+                        &mut ctx,
+                        out,
+                        handler,
+                        dry_run,
+                        &description,
+                    )?;
+                }
+                Some(actions::Subcommands::List { limit, offset }) => {
+                    command::legacy::actions::list_actions(&ctx, out, offset, limit)?;
+                }
+                Some(actions::Subcommands::Show { id }) => {
+                    command::legacy::actions::show_action(&ctx, out, &id)?;
+                }
+                Some(actions::Subcommands::Revert { id }) => {
+                    command::legacy::actions::revert_action(&mut ctx, out, &id)?;
                 }
                 None => {
                     command::legacy::actions::list_actions(&ctx, out, 0, 10)?;
@@ -1104,6 +1170,17 @@ async fn match_subcommand(
             None
         }
         #[cfg(feature = "legacy")]
+        Subcommands::Blame { path } => {
+            command::legacy::blame::blame(&mut ctx, out, &path).emit_metrics(metrics_ctx)?;
+            None
+        }
+        #[cfg(feature = "legacy")]
+        Subcommands::Bisect(bisect_args) => {
+            command::legacy::bisect::bisect(&mut ctx, out, bisect_args)
+                .emit_metrics(metrics_ctx)?;
+            None
+        }
+        #[cfg(feature = "legacy")]
         Subcommands::Status {
             show_files,
             verbose,
@@ -1211,11 +1288,13 @@ async fn match_subcommand(
             status_after_data = Some(status_after);
 
             newly_conflicted_data = Some(command::legacy::conflict_notice::snapshot(&ctx));
+            utils::message_policy::apply(&mut ctx, commit_args.skip_message_policy)?;
             let (outcome, ws) = command::legacy::commit::commit(
                 &mut ctx,
                 IntermediateChannel::new(out),
                 commit_args,
             )
+            .map_err(utils::message_policy::violations_as_bad_input)
             .emit_metrics(metrics_ctx)?;
             out.print_cli_output(outcome)?;
             Some(ws)
@@ -1229,11 +1308,13 @@ async fn match_subcommand(
             status_after_data = Some(status_after);
 
             newly_conflicted_data = Some(command::legacy::conflict_notice::snapshot(&ctx));
+            utils::message_policy::apply(&mut ctx, squash_args.skip_message_policy)?;
             let (outcome, ws) = command::legacy::squash::squash(
                 &mut ctx,
                 IntermediateChannel::new(out),
                 squash_args,
             )
+            .map_err(utils::message_policy::violations_as_bad_input)
             .emit_metrics(metrics_ctx)?;
             out.print_cli_output(outcome)?;
             ws
@@ -1295,10 +1376,12 @@ async fn match_subcommand(
             diff,
             no_diff,
             allow_merged,
+            skip_message_policy,
         } => {
             let status_after = args.status_after;
             out.begin_status_after(status_after);
             status_after_data = Some(status_after);
+            utils::message_policy::apply(&mut ctx, skip_message_policy)?;
             command::legacy::reword::reword_target(
                 &mut ctx,
                 out,
@@ -1310,6 +1393,7 @@ async fn match_subcommand(
                 ShowDiffInEditor::from_args(diff, no_diff).unwrap_or(ShowDiffInEditor::Unspecified),
                 allow_merged,
             )
+            .map_err(utils::message_policy::violations_as_bad_input)
             .emit_metrics(metrics_ctx)?;
             None
         }
@@ -1433,6 +1517,19 @@ async fn match_subcommand(
             None
         }
         #[cfg(feature = "legacy")]
+        Subcommands::Doctor { fix } => {
+            command::legacy::doctor::handle(&mut ctx, out, fix).emit_metrics(metrics_ctx)?;
+            None
+        }
+        #[cfg(feature = "legacy")]
+        Subcommands::Debug(args::debug::Platform { cmd }) => match cmd {
+            args::debug::Subcommands::Bundle { preview, output } => {
+                command::legacy::debug::bundle(&ctx, out, preview, output)
+                    .emit_metrics(metrics_ctx)?;
+                None
+            }
+        },
+        #[cfg(feature = "legacy")]
         Subcommands::Pr(forge::pr::Platform {
             cmd,
             draft: top_level_draft,
@@ -1642,6 +1739,64 @@ async fn match_subcommand(
             Some(ws)
         }
         #[cfg(feature = "legacy")]
+        Subcommands::Am(am_args) => {
+            use crate::utils::IntermediateChannel;
+
+            let status_after = args.status_after;
+            out.begin_status_after(status_after);
+            status_after_data = Some(status_after);
+
+            newly_conflicted_data = Some(command::legacy::conflict_notice::snapshot(&ctx));
+            let (outcome, ws) =
+                command::legacy::am::am(&mut ctx, IntermediateChannel::new(out), am_args)
+                    .emit_metrics(metrics_ctx)?;
+            out.print_cli_output(outcome)?;
+            Some(ws)
+        }
+        #[cfg(feature = "legacy")]
+        Subcommands::FormatPatch(format_patch_args) => {
+            command::legacy::format_patch::format_patch(&mut ctx, out, format_patch_args)
+                .emit_metrics(metrics_ctx)?;
+            None
+        }
+        #[cfg(feature = "legacy")]
+        Subcommands::Revert(revert_args) => {
+            use crate::utils::IntermediateChannel;
+
+            let status_after = args.status_after;
+            out.begin_status_after(status_after);
+            status_after_data = Some(status_after);
+
+            newly_conflicted_data = Some(command::legacy::conflict_notice::snapshot(&ctx));
+            utils::message_policy::apply(&mut ctx, revert_args.skip_message_policy)?;
+            let (outcome, ws) = command::legacy::revert::revert(
+                &mut ctx,
+                IntermediateChannel::new(out),
+                revert_args,
+            )
+            .map_err(utils::message_policy::violations_as_bad_input)
+            .emit_metrics(metrics_ctx)?;
+            out.print_cli_output(outcome)?;
+            Some(ws)
+        }
+        #[cfg(feature = "legacy")]
+        Subcommands::Split(split_args) => {
+            use crate::utils::IntermediateChannel;
+
+            let status_after = args.status_after;
+            out.begin_status_after(status_after);
+            status_after_data = Some(status_after);
+
+            newly_conflicted_data = Some(command::legacy::conflict_notice::snapshot(&ctx));
+            utils::message_policy::apply(&mut ctx, split_args.skip_message_policy)?;
+            let (outcome, ws) =
+                command::legacy::split::split(&mut ctx, IntermediateChannel::new(out), split_args)
+                    .map_err(utils::message_policy::violations_as_bad_input)
+                    .emit_metrics(metrics_ctx)?;
+            out.print_cli_output(outcome)?;
+            Some(ws)
+        }
+        #[cfg(feature = "legacy")]
         Subcommands::Unapply(unapply_args) => {
             use crate::utils::IntermediateChannel;
 
@@ -1900,23 +2055,62 @@ mod tests {
     #[test]
     fn explicit_theme_overrides_detection_and_detection_falls_back_to_dark() {
         assert_eq!(
-            resolve_theme_preset(Some(OsStr::new("light")), Some(theme::ThemePreset::Dark))
-                .unwrap(),
-            theme::ThemePreset::Light,
+            resolve_theme(Some("light"), Some(theme::ThemePreset::Dark)).unwrap(),
+            theme::Theme::default_for(theme::ThemePreset::Light),
             "BUT_THEME should override the detected terminal theme"
         );
         assert_eq!(
-            resolve_theme_preset(None, Some(theme::ThemePreset::Light)).unwrap(),
-            theme::ThemePreset::Light,
+            resolve_theme(Some("high-contrast"), Some(theme::ThemePreset::Dark)).unwrap(),
+            theme::Theme::default_for(theme::ThemePreset::HighContrast),
+            "built-in themes beyond dark and light can be selected by name"
+        );
+        assert_eq!(
+            resolve_theme(None, Some(theme::ThemePreset::Light)).unwrap(),
+            theme::Theme::default_for(theme::ThemePreset::Light),
             "the detected terminal theme should be used without an override"
         );
         assert_eq!(
-            resolve_theme_preset(None, None).unwrap(),
-            theme::ThemePreset::Dark,
+            resolve_theme(None, None).unwrap(),
+            theme::Theme::default_for(theme::ThemePreset::Dark),
             "failed terminal detection should fall back to the dark preset"
         );
     }
 
+    #[test]
+    fn named_themes_are_loaded_from_the_themes_directory() {
+        let app_data_dir = tempfile::TempDir::new().unwrap();
+        let themes_dir = app_data_dir
+            .path()
+            .join("gitbutler")
+            .join(theme::THEMES_DIR);
+        std::fs::create_dir_all(&themes_dir).unwrap();
+        std::fs::write(
+            themes_dir.join("ocean.json"),
+            r#"{ "base": "light", "cli_id": { "fg": "Cyan" } }"#,
+        )
+        .unwrap();
+
+        temp_env::with_var("E2E_TEST_APP_DATA_DIR", Some(app_data_dir.path()), || {
+            let theme = resolve_theme(Some("ocean"), None).unwrap();
+            assert_eq!(
+                theme.cli_id,
+                ratatui::style::Style::new().fg(ratatui::style::Color::Cyan)
+            );
+            assert_eq!(
+                theme.addition_rich,
+                theme::Theme::default_for(theme::ThemePreset::Light).addition_rich
+            );
+
+            let err = resolve_theme(Some("desert"), None).unwrap_err();
+            assert!(
+                err.to_string().starts_with(
+                    "Unknown theme 'desert', expected one of dark, light, high-contrast, colorblind-safe"
+                ),
+                "{err}"
+            );
+        });
+    }
+
     #[test]
     fn detected_agent_defaults_to_agent_output() {
         let (_, format) = temp_env::with_var(envs::BUT_OUTPUT_FORMAT, None::<&str>, || {
@@ -29,6 +29,7 @@
 
 use std::{fmt::Display, path::Path, str::FromStr, sync::OnceLock};
 
+use anyhow::Context as _;
 use bstr::ByteSlice as _;
 use but_core::ChangeId;
 use colored::{ColoredString, Colorize as _};
@@ -42,6 +43,7 @@ use ratatui::{
     text::Span,
 };
 use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator as _;
 use syntect::highlighting::{self, ThemeSet};
 
 use crate::id::{CommitId, CommitIdRef};
@@ -214,13 +216,115 @@ fn apply_modifiers(mut styled: ColoredString, modifiers: Modifier) -> ColoredStr
     styled
 }
 
+/// Load a user-defined theme from a JSON file.
+///
+/// The file may name a built-in preset as `"base"`, which provides all styles that are missing in
+/// the file. It defaults to the dark preset.
+pub fn load_custom(path: &Path) -> anyhow::Result<Theme> {
+    let contents = std::fs::read_to_string(path)?;
+    let mut overrides: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&contents)?;
+    let preset = match overrides.remove("base") {
+        Some(serde_json::Value::String(base)) => base.parse()?,
+        Some(other) => anyhow::bail!("\"base\" must be the name of a built-in theme, got {other}"),
+        None => ThemePreset::Dark,
+    };
+
+    let base = Theme::default_for(preset);
+    let serde_json::Value::Object(mut styles) = serde_json::to_value(&base)? else {
+        unreachable!("themes always serialize into objects")
+    };
+    styles.extend(overrides);
+
+    let mut theme: Theme = serde_json::from_value(serde_json::Value::Object(styles))?;
+    theme.syntax_highlighting_theme_raw = base.syntax_highlighting_theme_raw;
+    theme.symbols = Some(ThemeSymbols::new(&theme));
+    Ok(theme)
+}
+
+/// The directory within the application config directory that contains user-defined themes, one
+/// `<name>.json` file per theme.
+pub const THEMES_DIR: &str = "themes";
+
+/// The Git configuration key to select a theme by name.
+pub const THEME_CONFIG_KEY: &str = "but.theme";
+
+/// Load the theme called `name`, which is either a built-in [`ThemePreset`] or a user-defined theme
+/// in [`THEMES_DIR`].
+pub fn load_named(name: &str) -> anyhow::Result<Theme> {
+    if let Ok(preset) = name.parse() {
+        return Ok(Theme::default_for(preset));
+    }
+
+    let name = name.trim();
+    let themes_dir = but_path::app_config_dir()?.join(THEMES_DIR);
+    let path = themes_dir.join(format!("{name}.json"));
+    if name.is_empty() || name.contains(['/', '\\']) || !path.is_file() {
+        anyhow::bail!(
+            "Unknown theme '{name}', expected one of {} or the name of a theme file in '{}'",
+            ThemePreset::iter()
+                .map(|preset| preset.name())
+                .collect::<Vec<_>>()
+                .join(", "),
+            themes_dir.display()
+        );
+    }
+    load_custom(&path).with_context(|| format!("Failed to load theme from '{}'", path.display()))
+}
+
+/// Return the theme name configured with [`THEME_CONFIG_KEY`], as seen from `current_dir`.
+///
+/// This happens way before we have a context, and for every command, so instead of opening the
+/// repository only its local configuration file is read, falling back to the user-global Git
+/// configuration.
+pub(crate) fn configured_name(current_dir: &Path) -> Option<String> {
+    let read = |config: gix::config::File<'static>| {
+        config
+            .string(THEME_CONFIG_KEY)
+            .map(|value| value.to_string())
+            .filter(|name| !name.trim().is_empty())
+    };
+    repo_local_config(current_dir)
+        .and_then(read)
+        .or_else(|| read(but_core::git_config::open_global_config_for_reading().ok()?))
+}
+
+/// Read the local configuration of the repository containing `current_dir` without opening it.
+fn repo_local_config(current_dir: &Path) -> Option<gix::config::File<'static>> {
+    let (path, _trust) = gix::discover::upwards(current_dir).ok()?;
+    let (git_dir, _work_dir) = path.into_repository_and_work_tree_directories();
+    // Linked worktrees share the configuration of the main repository.
+    let common_dir = match std::fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common_dir) => git_dir.join(common_dir.trim()),
+        Err(_) => git_dir,
+    };
+    gix::config::File::from_path_no_includes(common_dir.join("config"), gix::config::Source::Local)
+        .ok()
+}
+
 /// Identifiers for the theme presets.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, strum::EnumIter)]
 pub enum ThemePreset {
     /// The dark preset.
     Dark,
     /// The light preset.
     Light,
+    /// A dark preset with bright, bold colors and without dimmed text.
+    HighContrast,
+    /// A dark preset which doesn't rely on telling red and green apart, using the Okabe-Ito palette.
+    ColorblindSafe,
+}
+
+impl ThemePreset {
+    /// The name by which the preset can be selected.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ThemePreset::Dark => "dark",
+            ThemePreset::Light => "light",
+            ThemePreset::HighContrast => "high-contrast",
+            ThemePreset::ColorblindSafe => "colorblind-safe",
+        }
+    }
 }
 
 impl FromStr for ThemePreset {
@@ -228,11 +332,10 @@ impl FromStr for ThemePreset {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let normalized = s.to_lowercase();
-        match normalized.trim() {
-            "dark" => Ok(ThemePreset::Dark),
-            "light" => Ok(ThemePreset::Light),
-            unknown => Err(anyhow::anyhow!("Unknown theme preset: {unknown}")),
-        }
+        let normalized = normalized.trim();
+        ThemePreset::iter()
+            .find(|preset| preset.name() == normalized)
+            .ok_or_else(|| anyhow::anyhow!("Unknown theme preset: {normalized}"))
     }
 }
 
@@ -312,6 +415,16 @@ pub struct Theme {
     /// Highlight something that is purely informational
     pub info: Style,
 
+    // Commit states
+    /// Commits that only exist on the remote tracking branch
+    pub commit_upstream: Style,
+    /// Commits that were not pushed yet
+    pub commit_local: Style,
+    /// Commits that were pushed
+    pub commit_pushed: Style,
+    /// Commits that are integrated into the target branch
+    pub commit_integrated: Style,
+
     // TUI modes
     pub tui_mode_normal: Style,
     pub tui_mode_commit: Style,
@@ -397,6 +510,8 @@ impl Theme {
         let mut t = match preset {
             ThemePreset::Light => Self::default_light(),
             ThemePreset::Dark => Self::default_dark(),
+            ThemePreset::HighContrast => Self::default_high_contrast(),
+            ThemePreset::ColorblindSafe => Self::default_colorblind_safe(),
         };
         t.symbols = Some(ThemeSymbols::new(&t));
         t
@@ -452,6 +567,12 @@ impl Theme {
             error: style_fg(Color::Red),
             info: style_fg(Color::Cyan),
 
+            // Commit states
+            commit_upstream: style_fg(Color::Yellow),
+            commit_local: Style::new(),
+            commit_pushed: style_fg(Color::Green),
+            commit_integrated: style_fg(Color::Magenta),
+
             // TUI modes
             tui_mode_normal: Style::new().bg(Color::DarkGray).fg(Color::White),
             tui_mode_commit: Style::new().bg(Color::Green).fg(Color::Black),
@@ -538,6 +659,127 @@ impl Theme {
             ..dark_t
         }
     }
+
+    fn default_high_contrast() -> Self {
+        let dark_t = Self::default_dark();
+
+        Self {
+            // Concrete "things"
+            local_branch: style_fg_bold(Color::LightGreen),
+            remote_branch: style_fg_bold(Color::LightMagenta),
+            commit_id: style_fg(Color::LightCyan),
+            change_id: style_fg_bold(Color::LightMagenta),
+            cli_id: Style::new()
+                .fg(Color::LightCyan)
+                .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            pr_number: style_fg(Color::LightBlue),
+            link: Style::new()
+                .fg(Color::LightBlue)
+                .add_modifier(Modifier::UNDERLINED),
+            config_value: style_fg(Color::LightCyan),
+            config_key: style_fg(Color::LightGreen),
+
+            // Modifications
+            addition: style_fg_bold(Color::LightGreen),
+            deletion: style_fg_bold(Color::LightRed),
+            modification: style_fg_bold(Color::LightYellow),
+            renaming: style_fg_bold(Color::LightMagenta),
+            context: style_fg(Color::Gray),
+            addition_rich: Style::new().bg(Color::Rgb(0, 80, 0)),
+            addition_rich_subsection: Style::new().bg(Color::Rgb(0, 140, 0)),
+            deletion_rich: Style::new().bg(Color::Rgb(100, 0, 0)),
+            deletion_rich_subsection: Style::new().bg(Color::Rgb(170, 0, 0)),
+
+            // State signals
+            success: style_fg_bold(Color::LightGreen),
+            attention: style_fg_bold(Color::LightYellow),
+            error: style_fg_bold(Color::LightRed),
+            info: style_fg_bold(Color::LightCyan),
+
+            // Commit states
+            commit_upstream: style_fg_bold(Color::LightYellow),
+            commit_local: style_fg_bold(Color::White),
+            commit_pushed: style_fg_bold(Color::LightGreen),
+            commit_integrated: style_fg_bold(Color::LightMagenta),
+
+            // TUI modes
+            tui_mode_normal: Style::new().bg(Color::White).fg(Color::Black),
+            tui_mode_commit: Style::new().bg(Color::LightGreen).fg(Color::Black),
+            tui_mode_squash: Style::new().bg(Color::LightBlue).fg(Color::Black),
+            tui_mode_inline_reword: Style::new().bg(Color::LightMagenta).fg(Color::Black),
+            tui_mode_command: Style::new().bg(Color::LightYellow).fg(Color::Black),
+            tui_mode_move: Style::new().bg(Color::LightCyan).fg(Color::Black),
+            tui_mark: Style::new().bg(Color::LightBlue).fg(Color::Black),
+            tui_details_context_lines_marked: Rgb(60, 60, 100).into_bg_style(),
+
+            // General purpose
+            hint: style_fg(Color::Gray),
+            command_suggestion: style_fg(Color::LightBlue),
+            progress: style_fg(Color::Gray),
+
+            // Layout
+            border: style_fg(Color::Gray),
+            border_active: style_fg_bold(Color::LightYellow),
+            selection_highlight: Style::new()
+                .bg(Color::White)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+            discrete_selection_highlight: Rgb(70, 70, 70).into_bg_style(),
+            legend: style_fg_bold(Color::LightCyan),
+
+            ..dark_t
+        }
+    }
+
+    fn default_colorblind_safe() -> Self {
+        // The Okabe-Ito palette, which stays distinguishable with all common forms of color
+        // blindness. Additions and deletions use blue and orange instead of green and red.
+        const ORANGE: Color = Color::Rgb(230, 159, 0);
+        const SKY_BLUE: Color = Color::Rgb(86, 180, 233);
+        const BLUISH_GREEN: Color = Color::Rgb(0, 158, 115);
+        const YELLOW: Color = Color::Rgb(240, 228, 66);
+        const VERMILLION: Color = Color::Rgb(213, 94, 0);
+        const REDDISH_PURPLE: Color = Color::Rgb(204, 121, 167);
+        let dark_t = Self::default_dark();
+
+        Self {
+            // Concrete "things"
+            local_branch: style_fg(SKY_BLUE),
+            remote_branch: style_fg(REDDISH_PURPLE),
+            change_id: style_fg(REDDISH_PURPLE),
+            config_key: style_fg(SKY_BLUE),
+
+            // Modifications
+            addition: style_fg(SKY_BLUE),
+            deletion: style_fg(ORANGE),
+            modification: style_fg(YELLOW),
+            renaming: style_fg(REDDISH_PURPLE),
+            addition_rich: Rgb(0, 45, 80).into_bg_style(),
+            addition_rich_subsection: Rgb(0, 85, 140).into_bg_style(),
+            deletion_rich: Rgb(75, 45, 0).into_bg_style(),
+            deletion_rich_subsection: Rgb(130, 80, 0).into_bg_style(),
+
+            // State signals
+            success: style_fg(SKY_BLUE),
+            attention: style_fg(YELLOW),
+            error: style_fg(VERMILLION),
+
+            // Commit states
+            commit_upstream: style_fg(YELLOW),
+            commit_pushed: style_fg(SKY_BLUE),
+            commit_integrated: style_fg(REDDISH_PURPLE),
+
+            // TUI modes
+            tui_mode_commit: Style::new().bg(SKY_BLUE).fg(Color::Black),
+            tui_mode_squash: Style::new().bg(Color::Blue).fg(Color::White),
+            tui_mode_inline_reword: Style::new().bg(REDDISH_PURPLE).fg(Color::Black),
+            tui_mode_command: Style::new().bg(YELLOW).fg(Color::Black),
+            tui_mode_move: Style::new().bg(BLUISH_GREEN).fg(Color::Black),
+            tui_mode_details: Style::new().bg(ORANGE).fg(Color::Black),
+
+            ..dark_t
+        }
+    }
 }
 
 /// Symbols styled with a [`Theme`].
@@ -810,4 +1052,95 @@ mod tests {
         let theme: Theme = serde_json::from_str("{}").unwrap();
         assert_eq!(theme, Theme::default());
     }
+
+    #[test]
+    fn presets_can_be_selected_by_name() {
+        for preset in ThemePreset::iter() {
+            assert_eq!(preset.name().parse::<ThemePreset>().unwrap(), preset);
+        }
+        assert_eq!(
+            " High-Contrast ".parse::<ThemePreset>().unwrap(),
+            ThemePreset::HighContrast,
+            "names are normalized"
+        );
+        assert!("solarized".parse::<ThemePreset>().is_err());
+    }
+
+    #[test]
+    fn colorblind_safe_preset_does_not_use_red_or_green_for_diffs() {
+        let theme = Theme::default_for(ThemePreset::ColorblindSafe);
+        for style in [theme.addition, theme.deletion, theme.success, theme.error] {
+            assert!(
+                !matches!(
+                    style.fg,
+                    Some(Color::Red | Color::LightRed | Color::Green | Color::LightGreen)
+                ),
+                "{style:?} should avoid red and green"
+            );
+        }
+    }
+
+    #[test]
+    fn custom_theme_fills_missing_fields_from_its_base() -> anyhow::Result<()> {
+        let file = tempfile::NamedTempFile::new()?;
+        std::fs::write(
+            file.path(),
+            r#"{ "base": "light", "addition": { "fg": "Blue" }, "commit_pushed": { "fg": "Blue" } }"#,
+        )?;
+        let theme = load_custom(file.path())?;
+
+        let light = Theme::default_for(ThemePreset::Light);
+        assert_eq!(
+            theme,
+            Theme {
+                addition: style_fg(Color::Blue),
+                commit_pushed: style_fg(Color::Blue),
+                ..light
+            },
+            "syntax highlighting and everything not in the file follows the base"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn configured_name_is_read_from_the_repository_containing_the_directory() -> anyhow::Result<()>
+    {
+        let tmp = tempfile::tempdir()?;
+        let repo = gix::init(tmp.path())?;
+        let subdir = tmp.path().join("sub/dir");
+        std::fs::create_dir_all(&subdir)?;
+        std::fs::write(
+            repo.git_dir().join("config"),
+            "[core]\n\tbare = false\n[but]\n\ttheme = light\n",
+        )?;
+        assert_eq!(configured_name(&subdir).as_deref(), Some("light"));
+
+        // Linked worktrees use the configuration of the main repository.
+        let worktree_git_dir = repo.git_dir().join("worktrees/wt");
+        std::fs::create_dir_all(&worktree_git_dir)?;
+        std::fs::write(worktree_git_dir.join("commondir"), "../..\n")?;
+        std::fs::write(worktree_git_dir.join("HEAD"), "ref: refs/heads/main\n")?;
+        let worktree = tmp.path().join("wt");
+        std::fs::create_dir_all(&worktree)?;
+        std::fs::write(
+            worktree.join(".git"),
+            format!("gitdir: {}\n", worktree_git_dir.display()),
+        )?;
+        assert_eq!(configured_name(&worktree).as_deref(), Some("light"));
+        Ok(())
+    }
+
+    #[test]
+    fn custom_theme_defaults_to_dark_base_and_rejects_unknown_bases() -> anyhow::Result<()> {
+        let file = tempfile::NamedTempFile::new()?;
+        std::fs::write(file.path(), "{}")?;
+        assert_eq!(load_custom(file.path())?, Theme::default());
+
+        std::fs::write(file.path(), r#"{ "base": "sepia" }"#)?;
+        assert_eq!(
+            load_custom(file.path()).unwrap_err().to_string(),
+            "Unknown theme preset: sepia"
+        );
+        Ok(())
+    }
 }
@@ -190,6 +190,12 @@ fn parse_unified_patch(patch: &but_core::UnifiedPatch) -> Vec<DiffLine> {
                 "File too large ({size_in_bytes} bytes) - no diff available"
             ))]
         }
+        but_core::UnifiedPatch::Submodule { .. } | but_core::UnifiedPatch::Lfs { .. } => patch
+            .summary_lines()
+            .unwrap_or_default()
+            .into_iter()
+            .map(DiffLine::Info)
+            .collect(),
         but_core::UnifiedPatch::Patch {
             hunks,
             is_result_of_binary_to_text_conversion,
@@ -82,6 +82,27 @@ pub fn from_editor(
     }
 }
 
+/// Launches the user's preferred text editor to edit the file at `path` in place.
+/// Like [`from_editor`], this falls back to the built-in TUI editor if no external editor is configured.
+pub fn edit_file_in_editor(path: &std::path::Path) -> Result<()> {
+    let Some(editor_cmd) = get_editor_command() else {
+        return super::editor::edit_file(path);
+    };
+    let status = gix::command::prepare(editor_cmd)
+        .arg(path)
+        .stdin(std::process::Stdio::inherit())
+        .stdout(std::process::Stdio::inherit())
+        .with_shell()
+        .spawn()?
+        .wait()?;
+
+    if !status.success() {
+        return Err(anyhow::anyhow!("Editor exited with non-zero status")
+            .context(Code::EditorExitedWithNonZeroStatus));
+    }
+    Ok(())
+}
+
 /// Launch an external editor (vim, code, etc.) to edit text via a temporary file.
 fn from_external_editor(
     editor_cmd: &str,
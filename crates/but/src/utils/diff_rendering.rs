@@ -830,7 +830,10 @@ fn compute_line_stats_from_tree_changes(
                 line_stats.lines_added += (*lines_added) as u64;
                 line_stats.lines_removed += (*lines_removed) as u64;
             }
-            UnifiedPatch::Binary | UnifiedPatch::TooLarge { .. } => {}
+            UnifiedPatch::Binary
+            | UnifiedPatch::TooLarge { .. }
+            | UnifiedPatch::Submodule { .. }
+            | UnifiedPatch::Lfs { .. } => {}
         }
     }
 }
@@ -908,6 +911,26 @@ fn render_tree_changes(
                     out.write_section_separator()?;
                 }
             }
+            UnifiedPatch::Submodule { .. } | UnifiedPatch::Lfs { .. } => {
+                let patch_id = id_gen.new_id("summary");
+
+                render_hunk_path_header(
+                    patch_id,
+                    None,
+                    tree_change.path.as_ref(),
+                    Some(ShortIdOrTreeStatus::TreeStatus(&tree_change.status)),
+                    out,
+                    theme,
+                )?;
+
+                for line in patch.summary_lines().unwrap_or_default() {
+                    out.write_selectable_text(patch_id, None, line.into())?;
+                }
+
+                if tree_change_pos.needs_padding_below() {
+                    out.write_section_separator()?;
+                }
+            }
             UnifiedPatch::TooLarge { size_in_bytes } => {
                 let patch_id = id_gen.new_id("too_large");
 
@@ -1,6 +1,6 @@
 use anyhow::Context as _;
 use bstr::{BStr, BString};
-use but_core::{DiffSpec, HunkHeader};
+use but_core::{DiffSpec, HunkHeader, UnifiedPatch};
 
 use crate::{
     CliId,
@@ -82,6 +82,47 @@ impl<'a> DiffSpecBuilder<'a> {
         self.push_changes_from_path_in_commit(path, commit_id, "First parent")
     }
 
+    /// Push the hunk at `index` of the changes to `path` in `commit_id`, counting hunks from 0 in
+    /// the order `but diff` shows them.
+    pub fn push_hunk_from_committed_file(
+        &mut self,
+        commit_id: gix::ObjectId,
+        path: &BStr,
+        index: usize,
+    ) -> anyhow::Result<()> {
+        let source_commit = self.repo.find_commit(commit_id)?;
+        let source_commit_parent_id = source_commit.parent_ids().next().context("First parent")?;
+        let tree_change = but_core::diff::tree_changes(
+            self.repo,
+            Some(source_commit_parent_id.detach()),
+            commit_id,
+        )?
+        .into_iter()
+        .find(|tc| tc.path == path)
+        .with_context(|| format!("{path} isn't changed in commit {commit_id}"))?;
+
+        let Some(UnifiedPatch::Patch {
+            hunks,
+            is_result_of_binary_to_text_conversion: false,
+            ..
+        }) = tree_change.unified_patch(self.repo, self.context_lines)?
+        else {
+            anyhow::bail!("The changes to {path} can't be selected by hunk");
+        };
+        let hunk = hunks.get(index).with_context(|| {
+            format!(
+                "{path} has {count} hunk{s} in commit {commit_id}, there is no hunk #{index}",
+                count = hunks.len(),
+                s = if hunks.len() == 1 { "" } else { "s" },
+            )
+        })?;
+
+        let mut spec = DiffSpec::from(&tree_change);
+        spec.hunk_headers.push(hunk.into());
+        self.diff_specs.push(spec);
+        Ok(())
+    }
+
     pub fn push_changes_from_path_in_commit(
         &mut self,
         path: &BStr,
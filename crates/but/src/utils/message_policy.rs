@@ -0,0 +1,30 @@
+//! Integration of the project's commit message policy with commands that write commit messages.
+//!
+//! The policy itself is enforced by the commit engine, see [`but_core::commit::message_policy`].
+//! Commands only decide whether it should be skipped, and turn violations into user errors.
+
+use but_core::commit::message_policy::{self, PolicyViolations};
+
+use crate::{CliError, args::atoms::SkipMessagePolicyArg, bad_input};
+
+/// Skip the commit message policy for all operations performed with `ctx` if
+/// `--skip-message-policy` was passed.
+pub fn apply(ctx: &mut but_ctx::Context, skip: SkipMessagePolicyArg) -> anyhow::Result<()> {
+    if skip.skip_message_policy {
+        message_policy::disable(&mut *ctx.repo.get_mut()?)?;
+    }
+    Ok(())
+}
+
+/// Present violations of the commit message policy in `err` as bad input, leave all other errors unchanged.
+pub fn violations_as_bad_input(err: CliError) -> CliError {
+    match err {
+        CliError::Internal(err) => match err.downcast_ref::<PolicyViolations>() {
+            Some(violations) => bad_input(violations.to_string())
+                .hint("Change the message to comply, or bypass the policy with `--skip-message-policy`")
+                .into(),
+            None => CliError::Internal(err),
+        },
+        err => err,
+    }
+}
@@ -116,6 +116,7 @@ impl Subcommands {
             Subcommands::_Diff2(..) => Diff2,
             #[cfg(feature = "legacy")]
             Subcommands::Show { .. } => Show,
+            Subcommands::Graph(..) => GraphExport,
             #[cfg(feature = "legacy")]
             Subcommands::Pull { .. } => Pull,
             #[cfg(feature = "legacy")]
@@ -208,6 +209,8 @@ impl Subcommands {
                 update::Subcommands::Suppress { .. } => UpdateSuppress,
                 #[cfg(all(unix, not(feature = "packaged-but-distribution")))]
                 update::Subcommands::Install { .. } => UpdateInstall,
+                #[cfg(all(unix, not(feature = "packaged-but-distribution")))]
+                update::Subcommands::Rollback => UpdateRollback,
             },
             #[cfg(feature = "legacy")]
             Subcommands::RefreshRemoteData { .. } => RefreshRemoteData,
@@ -225,6 +228,18 @@ impl Subcommands {
             Subcommands::Land { .. } => Land,
             #[cfg(feature = "legacy")]
             Subcommands::Pick(..) => Pick,
+            #[cfg(feature = "legacy")]
+            Subcommands::Revert(..) => Revert,
+            #[cfg(feature = "legacy")]
+            Subcommands::Split(..) => Split,
+            #[cfg(feature = "legacy")]
+            Subcommands::Bisect(..) => Bisect,
+            #[cfg(feature = "legacy")]
+            Subcommands::Blame { .. } => Blame,
+            #[cfg(feature = "legacy")]
+            Subcommands::FormatPatch(..) => FormatPatch,
+            #[cfg(feature = "legacy")]
+            Subcommands::Am(..) => Am,
             Subcommands::Skill(skill::Platform { cmd }) => match cmd {
                 skill::Subcommands::Install { .. } => SkillInstall,
                 skill::Subcommands::Check { .. } => SkillCheck,
@@ -236,6 +251,10 @@ impl Subcommands {
             Subcommands::Edit { .. } => Edit,
             #[cfg(feature = "legacy")]
             Subcommands::Clean { .. } => Clean,
+            #[cfg(feature = "legacy")]
+            Subcommands::Doctor { .. } => Doctor,
+            #[cfg(feature = "legacy")]
+            Subcommands::Debug(..) => DebugBundle,
             Subcommands::Onboarding => Unknown,
             Subcommands::AgentLog { .. } => Unknown,
             Subcommands::External(_) => External,
@@ -865,10 +884,18 @@ mod tests {
             Subcommands::Update(update::Platform {
                 cmd: update::Subcommands::Install {
                     target: Some("0.20.0".into()),
+                    from: None,
                 },
             }),
             "updateInstall",
         );
+        #[cfg(all(unix, not(feature = "packaged-but-distribution")))]
+        assert_command(
+            Subcommands::Update(update::Platform {
+                cmd: update::Subcommands::Rollback,
+            }),
+            "updateRollback",
+        );
 
         #[cfg(feature = "legacy")]
         {
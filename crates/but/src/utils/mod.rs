@@ -29,8 +29,11 @@ pub(crate) mod diff_specs;
 #[cfg(feature = "legacy")]
 pub(crate) mod merged_upstream;
 #[cfg(feature = "legacy")]
+pub(crate) mod message_policy;
+#[cfg(feature = "legacy")]
 pub(crate) mod rejection;
 pub(crate) mod targeting;
+pub(crate) mod version_policy;
 
 pub mod diff_rendering;
 pub mod string_interning;
@@ -518,5 +518,8 @@ fn reason_summary(reason: RejectionReason) -> &'static str {
         RejectionReason::MissingDiffSpecAssociation => {
             "the selected hunks no longer match the worktree"
         }
+        RejectionReason::LfsCleanFilterNotApplied => {
+            "the file is tracked by Git LFS, but git-lfs didn't convert it to a pointer"
+        }
     }
 }
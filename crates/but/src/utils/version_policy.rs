@@ -0,0 +1,92 @@
+//! The versions of `but` a project accepts, configured in Git configuration:
+//!
+//! ```ini
+//! [gitbutler "cliVersion"]
+//!     minimum = 0.18.0
+//!     pinned = 0.18.7
+//! ```
+//!
+//! To share them with everyone who clones the project, commit them to `.gitbutler/config` at the
+//! root of the worktree, which uses the same format. Values in the repository's own Git
+//! configuration, `.git/config` or the worktree configuration, take precedence, so they can still be
+//! overridden locally. Global and system configuration is ignored, as it would apply to every project.
+//!
+//! `but` warns if it doesn't satisfy them, and `but update install` refuses to install another
+//! version than the pinned one unless asked for it explicitly.
+//! See [`but_update::version_policy`] for how versions are compared.
+
+use std::path::Path;
+
+use but_update::VersionPolicy;
+
+const MINIMUM: &str = "gitbutler.cliVersion.minimum";
+const PINNED: &str = "gitbutler.cliVersion.pinned";
+/// The tracked file with the version policy, relative to the root of the worktree.
+const TRACKED_CONFIG: &str = ".gitbutler/config";
+
+/// Read the version policy of `repo`, or return `None` if it doesn't configure one.
+pub(crate) fn discover(repo: &gix::Repository) -> Option<VersionPolicy> {
+    let tracked = repo.workdir().and_then(|workdir| {
+        gix::config::File::from_path_no_includes(
+            workdir.join(TRACKED_CONFIG),
+            gix::config::Source::Local,
+        )
+        .ok()
+    });
+    let config = repo.config_snapshot();
+    let value = |key: &str| {
+        config
+            .plumbing()
+            .string_filter(key, |metadata| {
+                matches!(
+                    metadata.source,
+                    gix::config::Source::Local | gix::config::Source::Worktree
+                )
+            })
+            .or_else(|| tracked.as_ref()?.string(key))
+            .map(|value| value.to_string().trim().to_owned())
+            .filter(|value| !value.is_empty())
+    };
+    let policy = VersionPolicy {
+        minimum: value(MINIMUM),
+        pinned: value(PINNED),
+    };
+    (!policy.is_empty()).then_some(policy)
+}
+
+/// Read the version policy of the repository at or above `current_dir`, or return `None`
+/// if there is no repository or it doesn't configure one.
+pub(crate) fn discover_at(current_dir: &Path) -> Option<VersionPolicy> {
+    discover(&gix::discover(current_dir).ok()?)
+}
+
+/// Return a warning if this build of `but` doesn't satisfy the version policy of `repo`.
+///
+/// Development builds have no version, and are never warned about.
+pub(crate) fn notice(repo: &gix::Repository) -> Option<String> {
+    let current = option_env!("VERSION")?;
+    let mismatch = discover(repo)?.check(current)?;
+
+    let install_hint = {
+        #[cfg(feature = "packaged-but-distribution")]
+        {
+            "Install it with your package manager"
+        }
+        #[cfg(all(unix, not(feature = "packaged-but-distribution")))]
+        {
+            match &mismatch {
+                but_update::PolicyMismatch::NotPinned { pinned, .. } => {
+                    format!("Install it with 'but update install {pinned}'")
+                }
+                but_update::PolicyMismatch::BelowMinimum { .. } => {
+                    "Install it with 'but update install'".to_owned()
+                }
+            }
+        }
+        #[cfg(all(not(unix), not(feature = "packaged-but-distribution")))]
+        {
+            "Download it from https://gitbutler.com/downloads"
+        }
+    };
+    Some(format!("warning: {mismatch}. {install_hint}."))
+}
@@ -0,0 +1,192 @@
+use crate::{
+    command::util::{self, commit_file_with_worktree_changes_as_two_hunks},
+    utils::{CommandExt, Sandbox},
+};
+
+/// Return the `(route type, branch name)` of all routed hunks of `path` in the `handle-changes` JSON `output`.
+fn routes_of(output: &serde_json::Value, path: &str) -> Vec<(String, Option<String>)> {
+    output[1]["routedHunks"]
+        .as_array()
+        .expect("rules handler reports routes")
+        .iter()
+        .filter(|hunk| hunk["path"] == path)
+        .map(|hunk| {
+            (
+                hunk["route"]["type"].as_str().unwrap().to_owned(),
+                hunk["branchName"].as_str().map(ToOwned::to_owned),
+            )
+        })
+        .collect()
+}
+
+fn commit_count(status: &serde_json::Value) -> usize {
+    ["A", "B"]
+        .into_iter()
+        .map(|branch| util::branch_commit_cli_ids(status, branch).len())
+        .sum()
+}
+
+#[test]
+fn rules_route_hunks_to_configured_branches() -> anyhow::Result<()> {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata_at_target(&["A", "B"], "origin/main");
+    commit_file_with_worktree_changes_as_two_hunks(&env, "A", "a.txt");
+
+    env.invoke_git("config --local gitbutler.route.docs.branch B");
+    env.invoke_git("config --local gitbutler.route.docs.path docs/");
+    // Hunks locked to commits in `A` must not be routed elsewhere.
+    env.invoke_git("config --local gitbutler.route.docs.extension txt");
+    env.invoke_git("config --local gitbutler.route.style.branch A");
+    env.invoke_git("config --local gitbutler.route.style.extension css");
+    env.file("docs/guide.md", "guide\n");
+    env.file("style.css", "body {}\n");
+    let oplog_before = env.but("oplog").output()?.stdout;
+
+    let output = env
+        .but("--json actions handle-changes -d 'routed' --handler rules --dry-run")
+        .allow_json()
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    let dry_run: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let rule_to = |branch: &str| ("rule".to_owned(), Some(branch.to_owned()));
+    assert_eq!(routes_of(&dry_run, "docs/guide.md"), [rule_to("B")]);
+    assert_eq!(routes_of(&dry_run, "style.css"), [rule_to("A")]);
+    assert_eq!(
+        routes_of(&dry_run, "a.txt"),
+        [
+            ("dependencyLock".to_owned(), Some("A".to_owned())),
+            ("dependencyLock".to_owned(), Some("A".to_owned()))
+        ],
+        "dependency locks take precedence over rules"
+    );
+    assert_eq!(
+        dry_run[1]["updatedBranches"],
+        serde_json::json!([]),
+        "nothing is committed in a dry run"
+    );
+    assert!(dry_run[0].is_null(), "a dry run isn't recorded as action");
+    assert_eq!(
+        env.but("oplog").output()?.stdout,
+        oplog_before,
+        "a dry run takes no snapshots"
+    );
+    assert_eq!(
+        util::status_json(&env)["uncommittedChanges"]
+            .as_array()
+            .map(Vec::len),
+        Some(3)
+    );
+
+    let output = env
+        .but("--json actions handle-changes -d 'routed' --handler rules")
+        .allow_json()
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    let outcome: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let mut updated: Vec<_> = outcome[1]["updatedBranches"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|branch| branch["branchName"].as_str().unwrap().to_owned())
+        .collect();
+    updated.sort();
+    assert_eq!(updated, ["A", "B"]);
+    assert_eq!(
+        util::status_json(&env)["uncommittedChanges"]
+            .as_array()
+            .map(Vec::len),
+        Some(0),
+        "all changes were committed"
+    );
+
+    let listing = env.but("--json actions").allow_json().output()?;
+    let listing: serde_json::Value = serde_json::from_slice(&listing.stdout)?;
+    let mut handlers: Vec<_> = listing["actions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|action| action["handler"].as_str().unwrap())
+        .collect();
+    handlers.sort();
+    assert_eq!(
+        handlers,
+        ["handleChangesRules"],
+        "only the run that committed is recorded"
+    );
+    Ok(())
+}
+
+#[test]
+fn list_show_and_revert() -> anyhow::Result<()> {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata_at_target(&["A", "B"], "origin/main");
+    env.file("new-file", "content\n");
+    let commits_before = commit_count(&util::status_json(&env));
+
+    let output = env
+        .but("--json actions handle-changes -d 'Add a new file'")
+        .allow_json()
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    let outcome: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    let id = outcome[0].as_str().unwrap().to_owned();
+    assert_eq!(
+        util::status_json(&env)["uncommittedChanges"]
+            .as_array()
+            .map(Vec::len),
+        Some(0)
+    );
+
+    env.but("actions list")
+        .assert()
+        .success()
+        .stdout_eq(snapbox::str![[r#"
+Actions (1 of 1)
+──────────────────────────────────────────────────
+[..] [..] [..] HandleChangesSimple (cli)
+  Add a new file
+  [..]
+
+"#]]);
+
+    let output = env
+        .but(format!("--json actions show {}", &id[..8]))
+        .allow_json()
+        .output()?;
+    assert!(output.status.success(), "{output:?}");
+    let show: serde_json::Value = serde_json::from_slice(&output.stdout)?;
+    assert_eq!(show["action"]["id"], id.as_str());
+    assert_eq!(show["action"]["externalSummary"], "Add a new file");
+    let changed_paths: Vec<_> = show["changes"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|change| change["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        changed_paths,
+        ["new-file"],
+        "the diff between the snapshots shows what was committed"
+    );
+
+    env.but("actions show 0000")
+        .assert()
+        .failure()
+        .stderr_eq(snapbox::str![[r#"
+...No action found with ID '0000'...
+"#]]);
+
+    env.but(format!("actions revert {id}")).assert().success();
+    let status = util::status_json(&env);
+    assert_eq!(
+        status["uncommittedChanges"].as_array().map(Vec::len),
+        Some(1),
+        "the change is uncommitted again"
+    );
+    assert_eq!(
+        commit_count(&status),
+        commits_before,
+        "the commit created by the action is gone"
+    );
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use snapbox::str;
+
+use crate::utils::{CommandExt as _, Sandbox};
+
+const NEW_FILE_PATCH: &str = r#"From 0000000000000000000000000000000000000000 Mon Sep 17 00:00:00 2001
+From: Patch Author <patch@example.com>
+Date: Mon, 1 Jan 2024 00:00:00 +0000
+Subject: [PATCH 1/1] add new-file
+
+Sent to the list.
+---
+ new-file | 1 +
+ 1 file changed, 1 insertion(+)
+ create mode 100644 new-file
+
+diff --git a/new-file b/new-file
+new file mode 100644
+index 0000000..3e75765
+--- /dev/null
++++ b/new-file
+@@ -0,0 +1 @@
++new
+-- 
+2.40.0
+
+"#;
+
+const CHANGE_A_PATCH: &str = r#"From 0000000000000000000000000000000000000000 Mon Sep 17 00:00:00 2001
+From: Patch Author <patch@example.com>
+Date: Mon, 1 Jan 2024 00:00:00 +0000
+Subject: [PATCH 1/1] change A
+
+---
+ A | 2 +-
+ 1 file changed, 1 insertion(+), 1 deletion(-)
+
+diff --git a/A b/A
+--- a/A
++++ b/A
+@@ -1 +1 @@
+-A
++changed
+-- 
+2.40.0
+
+"#;
+
+#[test]
+fn applies_patches_onto_a_new_branch() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+    env.file("../series.mbox", NEW_FILE_PATCH);
+
+    env.but("am ../series.mbox --branch from-the-list")
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+Applied 1 patch onto new branch 'from-the-list'
+  [..] add new-file
+
+"#]]);
+
+    assert_eq!(
+        env.invoke_git("log -1 --format=%an%n%B from-the-list"),
+        "Patch Author\nadd new-file\n\nSent to the list."
+    );
+    assert_eq!(
+        env.invoke_git("show from-the-list:new-file"),
+        "new",
+        "the change is committed"
+    );
+}
+
+#[test]
+fn applies_patches_onto_an_existing_branch() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+    env.file("../series.mbox", NEW_FILE_PATCH);
+
+    env.but("--json am ../series.mbox --branch A")
+        .allow_json()
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+{
+  "commits": [
+    {
+      "subject": "add new-file",
+      "commitId": "[..]",
+      "changeId": "[..]",
+      "threeWay": false
+    }
+  ],
+  "branch": "A"
+}
+
+"#]]);
+
+    assert_eq!(
+        env.invoke_git("show A:new-file"),
+        "new",
+        "the change is committed on top of the branch"
+    );
+}
+
+#[test]
+fn records_patches_that_do_not_apply_as_conflicted_commits() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+    env.file("../series.mbox", CHANGE_A_PATCH);
+
+    // `A` only exists on branch `A`, so the change can't apply cleanly to a new branch.
+    env.but("--json am ../series.mbox --branch from-the-list")
+        .allow_json()
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+{
+  "commits": [
+    {
+      "subject": "change A",
+      "commitId": "[..]",
+      "changeId": "[..]",
+      "threeWay": false
+    }
+  ],
+  "branch": "from-the-list"
+}
+
+"#]]);
+
+    env.but("status").assert().success().stdout_eq(str![[r#"
+...
+[..]{conflicted}[..]
+...
+"#]]);
+}
+
+#[test]
+fn fails_for_an_empty_mailbox() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+    env.file("../empty.mbox", "");
+
+    env.but("am ../empty.mbox")
+        .assert()
+        .failure()
+        .stderr_eq(str![[r#"
+Error: Failed to read mailbox at '[..]empty.mbox'
+...
+"#]]);
+}
@@ -0,0 +1,99 @@
+use snapbox::str;
+
+use crate::utils::{CommandExt as _, Sandbox};
+
+#[test]
+fn finds_first_bad_commit_in_a_lower_branch() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings(
+        "one-stack-three-dependent-branches",
+    );
+    env.setup_metadata(&["A", "B", "C"]);
+    let head_before = env.invoke_git("rev-parse HEAD");
+
+    env.but("bisect C -- test ! -f B")
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+Bisecting 3 commits of 'C' in a temporary worktree
+  bad  [..] add C
+  good [..] add A
+  bad  [..] add B
+
+First bad commit: [..] add B
+
+"#]]);
+
+    assert_eq!(
+        env.invoke_git("rev-parse HEAD"),
+        head_before,
+        "the workspace checkout is left alone"
+    );
+    assert_eq!(
+        env.invoke_git("worktree list --porcelain")
+            .lines()
+            .filter(|line| line.starts_with("worktree "))
+            .count(),
+        1,
+        "the temporary worktree is removed"
+    );
+}
+
+#[test]
+fn outputs_json() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings(
+        "one-stack-three-dependent-branches",
+    );
+    env.setup_metadata(&["A", "B", "C"]);
+
+    env.but("--json bisect C --good A -- test ! -f B")
+        .allow_json()
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+{
+  "firstBad": {
+    "commitId": "[..]",
+    "changeId": "[..]",
+    "title": "add B"
+  },
+  "candidates": [
+    {
+      "commitId": "[..]",
+      "changeId": "[..]",
+      "title": "add B"
+    }
+  ],
+  "steps": [
+    {
+      "commitId": "[..]",
+      "changeId": "[..]",
+      "title": "add C",
+      "verdict": "bad"
+    },
+    {
+      "commitId": "[..]",
+      "changeId": "[..]",
+      "title": "add B",
+      "verdict": "bad"
+    }
+  ]
+}
+
+"#]]);
+}
+
+#[test]
+fn fails_if_the_tip_is_good() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings(
+        "one-stack-three-dependent-branches",
+    );
+    env.setup_metadata(&["A", "B", "C"]);
+
+    env.but("bisect C -- true")
+        .assert()
+        .failure()
+        .stderr_eq(str![[r#"
+Error: The tip [..] is good, there is nothing to bisect
+
+"#]]);
+}
@@ -0,0 +1,103 @@
+use snapbox::str;
+
+use crate::utils::{CommandExt as _, Sandbox};
+
+#[test]
+fn attributes_lines_to_the_stack_branch() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+
+    env.but("blame A").assert().success().stdout_eq(str![[r#"
+[..] A 1 A
+
+"#]]);
+}
+
+#[test]
+fn attributes_lines_to_the_target_and_the_worktree() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+    env.file("M", "M\nnew\n");
+
+    env.but("blame M").assert().success().stdout_eq(str![[r#"
+[..] target      1 M
+[..] uncommitted 2 new
+
+"#]]);
+
+    env.but("--json blame M")
+        .allow_json()
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+[
+  {
+    "cliId": "[..]",
+    "lineNumber": 1,
+    "content": "M",
+    "origin": {
+      "type": "target",
+      "subject": {
+        "commitId": "[..]"
+      }
+    }
+  },
+  {
+    "cliId": "[..]",
+    "lineNumber": 2,
+    "content": "new",
+    "origin": {
+      "type": "uncommitted"
+    }
+  }
+]
+
+"#]]);
+}
+
+#[test]
+fn control_characters_in_lines_are_not_printed() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+    env.file("escapes", "\u{1b}]0;title\u{7}plain\u{1b}[2J\tkept\n");
+
+    env.but("blame escapes")
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+[..] uncommitted 1 ]0;titleplain[2J	kept
+
+"#]]);
+
+    env.but("--json blame escapes")
+        .allow_json()
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+[
+  {
+    "cliId": "[..]",
+    "lineNumber": 1,
+    "content": "\u001b]0;title\u0007plain\u001b[2J\tkept",
+    "origin": {
+      "type": "uncommitted"
+    }
+  }
+]
+
+"#]]);
+}
+
+#[test]
+fn fails_for_missing_files() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+
+    env.but("blame does-not-exist")
+        .assert()
+        .failure()
+        .stderr_eq(str![[r#"
+Error: Cannot read 'does-not-exist' in the worktree
+...
+"#]]);
+}
@@ -254,3 +254,75 @@ Hint: run `but help` for all commands
 
     env.but("branch show A --check").assert().success();
 }
+
+#[test]
+fn range_diff_compares_with_remote_tracking_branch() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+
+    env.invoke_bash("git update-ref refs/remotes/origin/A A");
+    env.but("branch show A --range-diff")
+        .assert()
+        .success()
+        .stdout_eq(snapbox::str![[r#"
+Branch: A (1 commits ahead)
+
+tpm add A
+    2000-01-02 00:00:00 by author
+    1 file changed, 1 insertion, 0 deletions
+
+
+Range Diff: origin/A
+  No differences
+
+"#]]);
+
+    // The pushed version of the commit had different content.
+    env.invoke_bash(
+        r#"
+git checkout A~1
+echo "pushed" >A
+git add A
+git commit -m 'add A'
+git update-ref refs/remotes/origin/A HEAD
+git checkout gitbutler/workspace
+"#,
+    );
+
+    let result = env
+        .but("--json branch show A --range-diff")
+        .allow_json()
+        .output()
+        .unwrap();
+    assert!(result.status.success());
+    let json: serde_json::Value =
+        serde_json::from_str(String::from_utf8_lossy(&result.stdout).trim()).unwrap();
+
+    let range_diff = &json["rangeDiff"];
+    assert_eq!(range_diff["upstream"], "refs/remotes/origin/A");
+    let entries = range_diff["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["title"], "add A");
+    assert_eq!(entries[0]["status"], "modified");
+    assert!(
+        entries[0]["interdiff"]
+            .as_str()
+            .unwrap()
+            .contains("-+pushed"),
+        "the pushed line is removed from the patch"
+    );
+}
+
+#[test]
+fn range_diff_needs_a_remote_tracking_branch() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+
+    env.but("branch show A --range-diff")
+        .assert()
+        .failure()
+        .stderr_eq(snapbox::str![[r#"
+Error: Branch 'refs/heads/A' has no tracking branch
+...
+"#]]);
+}
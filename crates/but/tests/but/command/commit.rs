@@ -1,4 +1,4 @@
-use crate::utils::Sandbox;
+use crate::{command::util, utils::Sandbox};
 
 #[test]
 fn no_message_nothing_to_commit() {
@@ -2340,3 +2340,55 @@ For more information, try '--help'.
 
 "#]]);
 }
+
+#[test]
+fn message_policy_is_enforced_unless_bypassed() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+    env.invoke_git("config --local gitbutler.commitPolicy.conventional true");
+    env.invoke_git("config --local gitbutler.commitPolicy.forbiddenWord WIP");
+
+    env.file("one", "one content");
+    env.but("commit -m 'WIP add one'")
+        .assert()
+        .failure()
+        .stderr_eq(snapbox::str![[r#"
+Error: The commit message violates the commit message policy of this project:
+  - the subject must follow Conventional Commits, like 'type(scope): description'
+  - the word 'WIP' isn't allowed
+
+Hint: Change the message to comply, or bypass the policy with `--skip-message-policy`
+
+"#]]);
+    env.but("commit -m 'WIP add one' --skip-message-policy")
+        .assert()
+        .success();
+    env.file("two", "two content");
+    env.but("commit -m 'feat: add two'").assert().success();
+
+    let status = util::status_json(&env);
+    let [two, one, ..] = &util::branch_commit_cli_ids(&status, "A")[..] else {
+        panic!("expected the two new commits on top of 'A'");
+    };
+
+    env.but(format!("reword {one} -m 'fix one'"))
+        .assert()
+        .failure()
+        .stderr_eq(snapbox::str![[r#"
+Error: The commit message violates the commit message policy of this project:
+  - the subject must follow Conventional Commits, like 'type(scope): description'
+...
+"#]]);
+
+    env.but(format!("squash {one} -t {two} --use-source-message"))
+        .assert()
+        .failure()
+        .stderr_eq(snapbox::str![[r#"
+...the word 'WIP' isn't allowed
+...
+"#]]);
+    // Squashing combines both messages first, but only the final message has to comply.
+    env.but(format!("squash {one} -t {two} -m 'feat: add one and two'"))
+        .assert()
+        .success();
+}
@@ -0,0 +1,55 @@
+use snapbox::str;
+
+use crate::utils::{CommandExt, Sandbox};
+
+#[test]
+fn bundle_preview_writes_nothing() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+
+    env.but("debug bundle --preview")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+The bundle would contain:
+  manifest.json
+  settings.json ([..] bytes) Application settings, with identifying values redacted
+  git-config.txt ([..] bytes) GitButler-specific git configuration of the repository, with secrets redacted
+  graph.dot ([..] bytes) The commit graph with all names, messages and hashes anonymized
+  oplog.txt ([..] bytes) Time and kind of the most recent operations, without titles or descriptions
+...
+  version.txt ([..] bytes) The version of `but` and the platform it runs on
+Run 'but debug bundle' to write it.
+
+"#]]);
+
+    let zips = std::fs::read_dir(env.projects_root())
+        .unwrap()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "zip"))
+        .count();
+    assert_eq!(zips, 0, "nothing is written in preview mode");
+}
+
+#[test]
+fn bundle_to_output_path() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+
+    env.but("debug bundle -o bundle.zip")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+The bundle contains:
+  manifest.json
+...
+✓ Wrote diagnostics bundle to bundle.zip
+
+"#]]);
+
+    let archive =
+        std::fs::metadata(env.projects_root().join("bundle.zip")).expect("the archive was written");
+    assert!(archive.len() > 0);
+}
@@ -0,0 +1,272 @@
+use but_db::{DbHandle, HunkAssignment};
+use but_meta::legacy_storage;
+use snapbox::str;
+
+use crate::utils::{CommandExt, Sandbox};
+
+#[test]
+fn no_problems() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+
+    env.but("doctor")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+✓ No problems found.
+
+"#]]);
+}
+
+#[test]
+fn missing_branch_ref() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+    env.invoke_git("update-ref -d refs/heads/B");
+
+    env.but("doctor")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+⚠ Branch 'B' of stack [..] has no reference
+    fix: [..]
+Found 1 problem(s), 1 of which can be repaired with 'but doctor --fix'
+
+"#]]);
+}
+
+#[test]
+fn missing_branch_ref_is_restored() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+    let tip = env.invoke_git("rev-parse B");
+    env.invoke_git("update-ref -d refs/heads/B");
+
+    env.but("doctor --fix")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+⚠ Branch 'B' of stack [..] has no reference
+    fix: recreate the reference at the commit it last pointed to
+✓ Repaired 1 problem(s)
+
+"#]]);
+
+    assert_eq!(env.invoke_git("rev-parse B"), tip);
+    env.but("doctor").assert().success().stdout_eq(str![[r#"
+✓ No problems found.
+
+"#]]);
+}
+
+#[test]
+fn empty_stack_is_removed() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    let stack_ids = env.setup_metadata(&["A", "B"]);
+    let vb_path = env
+        .projects_root()
+        .join(".git/gitbutler/virtual_branches.toml");
+    let mut vb = legacy_storage::read_synced_virtual_branches(&vb_path).unwrap();
+    vb.branches.get_mut(&stack_ids[1]).unwrap().heads.clear();
+    legacy_storage::write_virtual_branches_and_sync(&vb_path, &vb).unwrap();
+
+    env.but("doctor --fix")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+⚠ Stack [..] has no branches
+    fix: remove the stack
+✓ Repaired 1 problem(s)
+
+"#]]);
+
+    let vb = legacy_storage::read_synced_virtual_branches(&vb_path).unwrap();
+    assert!(
+        !vb.branches.contains_key(&stack_ids[1]),
+        "the empty stack was removed"
+    );
+}
+
+#[test]
+fn problems_caused_by_repairs_remain() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    let stack_ids = env.setup_metadata(&["A", "B"]);
+    let vb_path = env
+        .projects_root()
+        .join(".git/gitbutler/virtual_branches.toml");
+    let mut vb = legacy_storage::read_synced_virtual_branches(&vb_path).unwrap();
+    vb.branches.get_mut(&stack_ids[1]).unwrap().heads.clear();
+    legacy_storage::write_virtual_branches_and_sync(&vb_path, &vb).unwrap();
+    // Changes assigned to the empty stack are dangling once it's removed.
+    let mut db = DbHandle::new_in_directory(env.projects_root().join(".git/gitbutler")).unwrap();
+    db.hunk_assignments_mut()
+        .unwrap()
+        .set_all(vec![HunkAssignment {
+            id: None,
+            hunk_header: None,
+            path: "file".into(),
+            path_bytes: b"file".into(),
+            stack_id: Some(stack_ids[1].to_string()),
+            branch_ref_bytes: None,
+        }])
+        .unwrap();
+
+    env.but("doctor --fix")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+⚠ Stack [..] has no branches
+    fix: remove the stack
+✓ Repaired 1 problem(s)
+1 problem(s) remain, run 'but doctor' to see them
+
+"#]]);
+
+    env.but("doctor").assert().success().stdout_eq(str![[r#"
+⚠ Changes in 'file' are assigned to 'stack [..]', which doesn't exist
+    fix: unassign the changes
+Found 1 problem(s), 1 of which can be repaired with 'but doctor --fix'
+
+"#]]);
+}
+
+#[test]
+fn dangling_hunk_assignment_is_unassigned() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+    let mut db = DbHandle::new_in_directory(env.projects_root().join(".git/gitbutler")).unwrap();
+    db.hunk_assignments_mut()
+        .unwrap()
+        .set_all(vec![HunkAssignment {
+            id: None,
+            hunk_header: None,
+            path: "file".into(),
+            path_bytes: b"file".into(),
+            stack_id: None,
+            branch_ref_bytes: Some(b"refs/heads/missing".into()),
+        }])
+        .unwrap();
+
+    env.but("doctor --fix")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+⚠ Changes in 'file' are assigned to 'refs/heads/missing', which doesn't exist
+    fix: unassign the changes
+✓ Repaired 1 problem(s)
+
+"#]]);
+
+    assert_eq!(db.hunk_assignments().list_all().unwrap(), []);
+}
+
+#[test]
+fn stale_edit_metadata_is_deleted() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+    let metadata_path = env
+        .projects_root()
+        .join(".git/gitbutler/edit_mode_metadata.toml");
+    std::fs::write(&metadata_path, "").unwrap();
+
+    env.but("doctor --fix")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+⚠ Edit mode metadata exists, but no commit is being edited
+    fix: delete the edit mode metadata
+✓ Repaired 1 problem(s)
+
+"#]]);
+
+    assert!(!metadata_path.exists(), "the stale metadata was deleted");
+}
+
+#[test]
+fn detached_head_needs_manual_repair() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+    env.invoke_git("checkout -q --detach gitbutler/workspace");
+
+    env.but("doctor --fix")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+⚠ Stacks are applied, but HEAD is detached at [..]
+    hint: keep new commits on a branch, then check out 'gitbutler/workspace'
+
+"#]]);
+}
+
+#[test]
+fn orphaned_edit_branch_is_deleted() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+    env.invoke_git("branch gitbutler/edit A");
+
+    env.but("doctor")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+⚠ 'refs/heads/gitbutler/edit' exists, but no commit is being edited
+    fix: delete the edit branch and its metadata
+Found 1 problem(s), 1 of which can be repaired with 'but doctor --fix'
+
+"#]]);
+
+    env.but("doctor --fix")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+⚠ 'refs/heads/gitbutler/edit' exists, but no commit is being edited
+    fix: delete the edit branch and its metadata
+✓ Repaired 1 problem(s)
+
+"#]]);
+
+    env.invoke_git_fails(
+        "rev-parse --verify refs/heads/gitbutler/edit",
+        "the orphaned edit branch was deleted",
+    );
+    env.but("doctor").assert().success().stdout_eq(str![[r#"
+✓ No problems found.
+
+"#]]);
+}
+
+#[test]
+fn json_output() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+    env.invoke_git("branch gitbutler/edit A");
+
+    env.but("--json doctor")
+        .allow_json()
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+{
+  "issues": [
+    {
+      "kind": "orphanedEditBranch",
+      "description": "'refs/heads/gitbutler/edit' exists, but no commit is being edited",
+      "repair": "delete the edit branch and its metadata"
+    }
+  ],
+  "repaired": 0,
+  "remaining": 0
+}
+
+"#]]);
+}
@@ -0,0 +1,91 @@
+use snapbox::str;
+
+use crate::utils::{CommandExt as _, Sandbox};
+
+#[test]
+fn writes_the_commits_of_a_branch_and_the_branches_below_it() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings(
+        "one-stack-three-dependent-branches",
+    );
+    env.setup_metadata(&["A", "B", "C"]);
+
+    env.but("format-patch B -o patches")
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+patches/0001-add-A.patch
+patches/0002-add-B.patch
+
+"#]]);
+
+    let patch = std::fs::read_to_string(env.projects_root().join("patches/0002-add-B.patch"))
+        .expect("patch was written");
+    assert!(
+        patch.contains("Subject: [PATCH 2/2] add B\n"),
+        "patches are numbered: {patch}"
+    );
+}
+
+#[test]
+fn writes_a_cover_letter() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings(
+        "one-stack-three-dependent-branches",
+    );
+    env.setup_metadata(&["A", "B", "C"]);
+    env.invoke_git("config branch.A.description Ignored-Git-description");
+
+    env.but("--json format-patch A --description 'Describe the series\n\nIn detail.' -o patches")
+        .allow_json()
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+{
+  "patches": [
+    "patches/0000-cover-letter.patch",
+    "patches/0001-add-A.patch"
+  ]
+}
+
+"#]]);
+
+    let cover_letter =
+        std::fs::read_to_string(env.projects_root().join("patches/0000-cover-letter.patch"))
+            .expect("cover letter was written");
+    assert!(
+        cover_letter.contains("Subject: [PATCH 0/1] Describe the series\n"),
+        "the first line of the description is the subject: {cover_letter}"
+    );
+    assert!(
+        cover_letter.contains("\nIn detail.\n"),
+        "the rest of the description is the body: {cover_letter}"
+    );
+    assert!(
+        !cover_letter.contains("Ignored-Git-description"),
+        "the Git description of the branch isn't used: {cover_letter}"
+    );
+}
+
+#[test]
+fn writes_a_cover_letter_with_placeholders() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings(
+        "one-stack-three-dependent-branches",
+    );
+    env.setup_metadata(&["A", "B", "C"]);
+    env.invoke_git("config branch.A.description Ignored-Git-description");
+
+    env.but("format-patch A --cover-letter -o patches")
+        .assert()
+        .success();
+
+    let cover_letter =
+        std::fs::read_to_string(env.projects_root().join("patches/0000-cover-letter.patch"))
+            .expect("cover letter was written");
+    assert!(
+        cover_letter.contains("Subject: [PATCH 0/1] *** SUBJECT HERE ***\n"),
+        "the subject is left to be filled in: {cover_letter}"
+    );
+    assert!(
+        !cover_letter.contains("Ignored-Git-description"),
+        "the Git description of the branch isn't used: {cover_letter}"
+    );
+}
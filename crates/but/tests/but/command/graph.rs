@@ -0,0 +1,45 @@
+use snapbox::str;
+
+use crate::utils::{CommandExt as _, Sandbox};
+
+#[test]
+fn export_workspace_as_json() {
+    let env = Sandbox::open_or_init_scenario_with_target_and_default_settings("one-stack");
+
+    let output = env
+        .but("--json graph export --workspace")
+        .allow_json()
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .get_output()
+        .stdout
+        .clone();
+    let json: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let names: Vec<_> = json["segments"]
+        .as_array()
+        .expect("segments should be an array")
+        .iter()
+        .filter_map(|segment| segment["name"].as_str())
+        .collect();
+    assert!(
+        names.contains(&"A"),
+        "the stack segment is part of the workspace export: {names:?}"
+    );
+}
+
+#[test]
+fn export_as_mermaid() {
+    let env = Sandbox::open_or_init_scenario_with_target_and_default_settings("one-stack");
+
+    let output = env
+        .but("graph export --format mermaid")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .get_output()
+        .stdout
+        .clone();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.starts_with("flowchart TD\n"), "{output}");
+}
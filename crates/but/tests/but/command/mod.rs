@@ -5,11 +5,19 @@
 
 #[cfg(feature = "legacy")]
 mod absorb;
+#[cfg(feature = "legacy")]
+mod actions;
 mod agent;
 mod alias;
 #[cfg(feature = "legacy")]
+mod am;
+#[cfg(feature = "legacy")]
 mod amend;
 #[cfg(feature = "legacy")]
+mod bisect;
+#[cfg(feature = "legacy")]
+mod blame;
+#[cfg(feature = "legacy")]
 mod branch;
 #[cfg(feature = "legacy")]
 mod clean;
@@ -19,16 +27,23 @@ mod comment;
 mod commit;
 mod config;
 #[cfg(feature = "legacy")]
+mod debug;
+#[cfg(feature = "legacy")]
 mod diff;
 #[cfg(feature = "legacy")]
 mod diff2;
 #[cfg(feature = "legacy")]
 mod discard;
 #[cfg(feature = "legacy")]
+mod doctor;
+#[cfg(feature = "legacy")]
 mod expand;
 #[cfg(unix)]
 mod external;
 mod format;
+#[cfg(feature = "legacy")]
+mod format_patch;
+mod graph;
 mod gui;
 mod help;
 #[cfg(feature = "legacy")]
@@ -47,6 +62,8 @@ mod push;
 #[cfg(feature = "legacy")]
 mod resolve;
 #[cfg(feature = "legacy")]
+mod revert;
+#[cfg(feature = "legacy")]
 mod reword;
 #[cfg(feature = "legacy")]
 mod reword2;
@@ -54,6 +71,8 @@ mod reword2;
 mod setup;
 mod skill;
 #[cfg(feature = "legacy")]
+mod split;
+#[cfg(feature = "legacy")]
 mod squash;
 #[cfg(feature = "legacy")]
 mod status;
@@ -64,6 +83,8 @@ mod teardown;
 mod uncommit;
 #[cfg(feature = "legacy")]
 mod undo;
+#[cfg(all(unix, not(feature = "packaged-but-distribution")))]
+mod update;
 #[cfg(feature = "legacy")]
 mod worktree;
 
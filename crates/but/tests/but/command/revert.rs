@@ -0,0 +1,108 @@
+use snapbox::str;
+
+use crate::utils::{CommandExt as _, Sandbox};
+
+#[test]
+fn revert_commit_onto_its_own_branch() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+
+    env.but("revert 9477ae7")
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+Reverted 9477ae7 onto branch 'A' to create [..]
+
+"#]]);
+
+    assert_eq!(
+        env.invoke_git("log -1 --format=%B A"),
+        "Revert \"add A\"\n\nThis reverts commit 9477ae721ab521d9d0174f70e804ce3ff9f6fb56."
+    );
+    assert_eq!(
+        env.invoke_git("rev-parse A~1"),
+        "9477ae721ab521d9d0174f70e804ce3ff9f6fb56",
+        "the revert is placed on top of the reverted commit's branch"
+    );
+    assert_eq!(
+        env.invoke_git("rev-parse A^{tree}"),
+        env.invoke_git("rev-parse A~2^{tree}"),
+        "the revert undoes the changes of the reverted commit"
+    );
+}
+
+#[test]
+fn revert_commit_into_another_branch_outputs_json() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+
+    env.but("--json revert 9477ae7 --into B")
+        .allow_json()
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+{
+  "revertedCommitId": "9477ae721ab521d9d0174f70e804ce3ff9f6fb56",
+  "newCommitId": "[..]",
+  "newChangeId": "[..]",
+  "branch": "B"
+}
+
+"#]]);
+
+    assert_eq!(env.invoke_git("log -1 --format=%s B"), "Revert \"add A\"");
+    assert_eq!(
+        env.invoke_git("rev-parse A"),
+        "9477ae721ab521d9d0174f70e804ce3ff9f6fb56",
+        "the branch of the reverted commit is left alone"
+    );
+}
+
+#[test]
+fn revert_rejects_branches() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+
+    env.but("revert A").assert().failure().stderr_eq(str![[r#"
+Error: Only commits can be reverted. A is a branch
+
+"#]]);
+}
+
+#[test]
+fn revert_uses_a_conventional_subject_if_the_message_policy_requires_it() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+    env.invoke_git("config --local gitbutler.commitPolicy.conventional true");
+
+    env.but("revert 9477ae7").assert().success();
+    assert_eq!(env.invoke_git("log -1 --format=%s A"), "revert: add A");
+}
+
+#[test]
+fn revert_enforces_the_message_policy_unless_bypassed() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+    env.invoke_git("config --local gitbutler.commitPolicy.requiredTrailer Ticket");
+
+    env.but("revert 9477ae7")
+        .assert()
+        .failure()
+        .stderr_eq(str![[r#"
+Error: The commit message violates the commit message policy of this project:
+  - the trailer 'Ticket: <value>' is required
+
+Hint: Change the message to comply, or bypass the policy with `--skip-message-policy`
+
+"#]]);
+    assert_eq!(
+        env.invoke_git("rev-parse A"),
+        "9477ae721ab521d9d0174f70e804ce3ff9f6fb56",
+        "nothing is reverted"
+    );
+
+    env.but("revert 9477ae7 --skip-message-policy")
+        .assert()
+        .success();
+    assert_eq!(env.invoke_git("log -1 --format=%s A"), "Revert \"add A\"");
+}
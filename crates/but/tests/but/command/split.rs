@@ -0,0 +1,195 @@
+use snapbox::str;
+
+use crate::utils::{CommandExt as _, Sandbox};
+
+#[test]
+fn split_committed_file_into_new_commit_on_top() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+
+    env.file("kept-in-commit.txt", "keep me\n");
+    env.file("split-out.txt", "split me\n");
+    env.but("commit -b A -m 'two unrelated files'")
+        .assert()
+        .success();
+    let source = env.invoke_git("rev-parse refs/heads/A");
+
+    env.but(format!("split {source}:split-out.txt -m 'split out'"))
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+Split split-out.txt out of [..] to create [..], [..]
+
+"#]]);
+
+    assert_eq!(env.invoke_git("log -1 --format=%s A"), "split out");
+    assert_eq!(
+        env.invoke_git("log -1 --format=%s A~1"),
+        "two unrelated files"
+    );
+    assert_eq!(
+        env.invoke_git("diff-tree --no-commit-id --name-only -r A"),
+        "split-out.txt",
+        "the new commit holds the split out file"
+    );
+    assert_eq!(
+        env.invoke_git("diff-tree --no-commit-id --name-only -r A~1"),
+        "kept-in-commit.txt",
+        "the original commit keeps everything else"
+    );
+    assert_eq!(
+        env.invoke_git("rev-parse A^{tree}"),
+        env.invoke_git(&format!("rev-parse {source}^{{tree}}")),
+        "splitting doesn't change the result of the branch"
+    );
+}
+
+#[test]
+fn split_into_three_commits_by_file_and_hunk() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+
+    let lines = |first: &str, last: &str| {
+        let middle: String = (2..20).map(|n| format!("line {n}\n")).collect();
+        format!("{first}\n{middle}{last}\n")
+    };
+    env.file("two-hunks.txt", lines("line 1", "line 20"));
+    env.but("commit -b A -m 'base'").assert().success();
+    env.file("two-hunks.txt", lines("first hunk", "second hunk"));
+    env.file("other.txt", "other\n");
+    env.but("commit -b A -m 'mixed changes'").assert().success();
+    let source = env.invoke_git("rev-parse refs/heads/A");
+
+    env.but(format!(
+        "split {source}:two-hunks.txt:#1 -m 'second hunk' --piece {source}:other.txt -m 'other file'"
+    ))
+    .assert()
+    .success()
+    .stdout_eq(str![[r#"
+Split two-hunks.txt, other.txt out of [..] to create [..], [..], [..]
+
+"#]]);
+
+    assert_eq!(
+        env.invoke_git("log -3 --format=%s A"),
+        "other file\nsecond hunk\nmixed changes",
+        "pieces are stacked in order on top of the original commit"
+    );
+    assert_eq!(
+        env.invoke_git("show A~2:two-hunks.txt"),
+        lines("first hunk", "line 20").trim_end(),
+        "the original commit keeps the hunk that wasn't selected"
+    );
+    assert_eq!(
+        env.invoke_git("diff-tree --no-commit-id --name-only -r A~2"),
+        "two-hunks.txt"
+    );
+    assert_eq!(
+        env.invoke_git("diff-tree --no-commit-id --name-only -r A~1"),
+        "two-hunks.txt",
+        "the selected hunk lands in the first new commit"
+    );
+    assert_eq!(
+        env.invoke_git("show A~1:two-hunks.txt"),
+        lines("first hunk", "second hunk").trim_end()
+    );
+    assert_eq!(
+        env.invoke_git("diff-tree --no-commit-id --name-only -r A"),
+        "other.txt",
+        "the second piece lands in the second new commit"
+    );
+    assert_eq!(
+        env.invoke_git("rev-parse A^{tree}"),
+        env.invoke_git(&format!("rev-parse {source}^{{tree}}")),
+        "splitting doesn't change the result of the branch"
+    );
+}
+
+#[test]
+fn split_needs_one_message_per_new_commit() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+
+    env.file("one.txt", "one\n");
+    env.file("two.txt", "two\n");
+    env.file("three.txt", "three\n");
+    env.but("commit -b A -m 'three files'").assert().success();
+    let source = env.invoke_git("rev-parse refs/heads/A");
+
+    env.but(format!(
+        "split {source}:one.txt -p {source}:two.txt -m 'only one message'"
+    ))
+    .assert()
+    .failure()
+    .stderr_eq(str![[r#"
+Error: Got 1 messages for 2 new commits
+...
+
+"#]]);
+    assert_eq!(
+        env.invoke_git("rev-parse refs/heads/A"),
+        source,
+        "nothing is split"
+    );
+}
+
+#[test]
+fn split_enforces_the_message_policy_for_new_messages_unless_bypassed() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+
+    env.file("one.txt", "one\n");
+    env.file("two.txt", "two\n");
+    env.but("commit -b A -m 'WIP two files'").assert().success();
+    env.invoke_git("config --local gitbutler.commitPolicy.forbiddenWord WIP");
+    let source = env.invoke_git("rev-parse refs/heads/A");
+
+    env.but(format!("split {source}:one.txt -m 'WIP one'"))
+        .assert()
+        .failure()
+        .stderr_eq(str![[r#"
+Error: The commit message violates the commit message policy of this project:
+  - the word 'WIP' isn't allowed
+
+Hint: Change the message to comply, or bypass the policy with `--skip-message-policy`
+
+"#]]);
+    assert_eq!(
+        env.invoke_git("rev-parse refs/heads/A"),
+        source,
+        "nothing is split"
+    );
+
+    env.but(format!("split {source}:one.txt"))
+        .assert()
+        .success();
+    assert_eq!(
+        env.invoke_git("log -2 --format=%s A"),
+        "WIP two files\nWIP two files",
+        "the message of the split commit is reused without being checked"
+    );
+
+    env.but("undo").assert().success();
+    env.but(format!(
+        "split {source}:one.txt -m 'WIP one' --skip-message-policy"
+    ))
+    .assert()
+    .success();
+    assert_eq!(env.invoke_git("log -1 --format=%s A"), "WIP one");
+}
+
+#[test]
+fn split_rejects_commits() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.setup_metadata(&["A"]);
+    let source = env.invoke_git("rev-parse refs/heads/A");
+
+    env.but(format!("split {source}"))
+        .assert()
+        .failure()
+        .stderr_eq(str![[r#"
+Error: Only committed files and their hunks can be split out of a commit. [..] is a commit
+...
+
+"#]]);
+}
@@ -2790,3 +2790,39 @@ Hint: run `but branch new` to create a new branch to work on
 
 "#]]);
 }
+
+#[test]
+fn squash_copies_notes_and_runs_post_rewrite_hook() {
+    let env = one_branch_three_commits();
+    env.invoke_bash(
+        "printf '#!/bin/sh\\necho \"$1\" >>.git/post-rewrite.args\\ncat >>.git/post-rewrite.input\\n' >.git/hooks/post-rewrite && \
+         chmod +x .git/hooks/post-rewrite && \
+         git config notes.rewriteRef refs/notes/commits && \
+         git notes add -m 'reviewed' a-branch-1~1",
+    );
+    let old_target = env.invoke_git("rev-parse a-branch-1~1");
+
+    env.but("squash 1#0 --target 1#1 --message 'squashed'")
+        .assert()
+        .success();
+
+    let new_target = env.invoke_git("rev-parse a-branch-1");
+    assert_eq!(
+        env.invoke_git("notes show a-branch-1"),
+        "reviewed",
+        "the note of the target is copied to its replacement"
+    );
+    let git_dir = env.projects_root().join(".git");
+    assert_eq!(
+        std::fs::read_to_string(git_dir.join("post-rewrite.args")).unwrap(),
+        "rebase\n",
+        "the hook is told about a rebase as descendants are rewritten too"
+    );
+    let input = std::fs::read_to_string(git_dir.join("post-rewrite.input")).unwrap();
+    assert!(
+        input
+            .lines()
+            .any(|line| line == format!("{old_target} {new_target}")),
+        "the hook receives the rewritten target, got: {input}"
+    );
+}
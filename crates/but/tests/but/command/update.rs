@@ -0,0 +1,67 @@
+use snapbox::str;
+
+use crate::utils::{CommandExt, Sandbox};
+
+#[test]
+fn install_needs_a_target_if_the_project_pins_a_version() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.file(
+        ".gitbutler/config",
+        "[gitbutler \"cliVersion\"]\n\tpinned = 0.18.7\n",
+    );
+
+    env.but("update install")
+        .assert()
+        .failure()
+        .stderr_eq(str![[r#"
+Error: This project pins `but` to version 0.18.7.
+
+Run 'but update install 0.18.7' to install it, or name another target:
+  nightly          Install latest nightly build
+  release          Install latest stable release
+
+"#]]);
+
+    env.invoke_git("config gitbutler.cliVersion.pinned 0.19.0");
+    env.but("update install")
+        .assert()
+        .failure()
+        .stderr_eq(str![[r#"
+Error: This project pins `but` to version 0.19.0.
+...
+"#]]);
+}
+
+#[test]
+fn global_configuration_does_not_override_the_version_policy() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("one-stack");
+    env.file(
+        ".gitbutler/config",
+        "[gitbutler \"cliVersion\"]\n\tpinned = 0.18.7\n",
+    );
+    let global_config = env.projects_root().join("global.gitconfig");
+    std::fs::write(
+        &global_config,
+        "[gitbutler \"cliVersion\"]\n\tpinned = 0.20.0\n",
+    )
+    .unwrap();
+
+    env.but("update install")
+        .env("GIT_CONFIG_GLOBAL", &global_config)
+        .assert()
+        .failure()
+        .stderr_eq(str![[r#"
+Error: This project pins `but` to version 0.18.7.
+...
+"#]]);
+
+    env.invoke_git("config gitbutler.cliVersion.pinned 0.19.0");
+    env.but("update install")
+        .env("GIT_CONFIG_GLOBAL", &global_config)
+        .assert()
+        .failure()
+        .stderr_eq(str![[r#"
+Error: This project pins `but` to version 0.19.0.
+...
+"#]]);
+}
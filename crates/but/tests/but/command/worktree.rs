@@ -162,6 +162,112 @@ Status: Nothing to integrate - the worktree has no changes
 "#]]);
 }
 
+/// Edit a commit in its own worktree while the workspace keeps its uncommitted changes.
+#[test]
+fn edit_and_finish() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+
+    env.but("worktree edit 9477ae7")
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+Editing [..] in worktree at: [..]
+Run `but worktree finish [..]` to apply the changes to the commit
+
+"#]]);
+
+    let edit_worktree = single_edit_worktree(&env);
+    std::fs::write(edit_worktree.join("A"), "edited A\n").unwrap();
+    env.file("uncommitted.txt", "workspace change\n");
+
+    env.but(format!("worktree finish {}", edit_worktree.display()))
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+Applied the edit from worktree: [..]
+
+"#]]);
+
+    assert_eq!(
+        env.invoke_git("show A:A"),
+        "edited A",
+        "the edit replaced the commit on its branch"
+    );
+    assert_eq!(
+        std::fs::read_to_string(env.projects_root().join("A")).unwrap(),
+        "edited A\n",
+        "the edited commit is checked out in the workspace"
+    );
+    assert_eq!(
+        std::fs::read_to_string(env.projects_root().join("uncommitted.txt")).unwrap(),
+        "workspace change\n",
+        "uncommitted changes in the workspace are kept"
+    );
+    assert!(!edit_worktree.exists(), "the edit worktree is removed");
+}
+
+/// Abort editing a commit in its own worktree, which only discards changes when forced.
+#[test]
+fn edit_and_abort() {
+    let env = Sandbox::init_scenario_with_target_and_default_settings("two-stacks");
+    env.setup_metadata(&["A", "B"]);
+
+    env.but("worktree edit 9477ae7").assert().success();
+    let edit_worktree = single_edit_worktree(&env);
+    env.but(format!("worktree abort {}", edit_worktree.display()))
+        .assert()
+        .success()
+        .stderr_eq(str![])
+        .stdout_eq(str![[r#"
+Removed the edit worktree without applying it: [..]
+
+"#]]);
+    assert!(
+        !edit_worktree.exists(),
+        "the unchanged edit worktree is removed"
+    );
+
+    env.but("worktree edit 9477ae7").assert().success();
+    let edit_worktree = single_edit_worktree(&env);
+    std::fs::write(edit_worktree.join("A"), "edited A\n").unwrap();
+
+    env.but(format!("worktree abort {}", edit_worktree.display()))
+        .assert()
+        .failure()
+        .stderr_eq(str![[r#"
+Error: The edit worktree [..] differs from the original commit. A forced abort is necessary to discard its changes.
+...
+"#]]);
+    assert!(edit_worktree.exists(), "changes are kept without --force");
+
+    env.but(format!(
+        "worktree abort --force {}",
+        edit_worktree.display()
+    ))
+    .assert()
+    .success()
+    .stderr_eq(str![]);
+    assert!(!edit_worktree.exists(), "the edit worktree is removed");
+    assert_eq!(
+        env.invoke_git("show A:A"),
+        "A",
+        "the commit on its branch is unchanged"
+    );
+}
+
+/// The path of the only edit worktree that currently exists.
+fn single_edit_worktree(env: &Sandbox) -> std::path::PathBuf {
+    let mut entries: Vec<_> = std::fs::read_dir(env.projects_root().join(".git/gitbutler/edit"))
+        .expect("edit worktrees directory exists")
+        .map(|e| e.expect("readable directory entry").path())
+        .collect();
+    assert_eq!(entries.len(), 1, "exactly one edit worktree is expected");
+    entries.pop().expect("one entry")
+}
+
 fn worktrees_dir(env: &Sandbox) -> std::path::PathBuf {
     env.projects_root().join(".git/gitbutler/worktrees")
 }
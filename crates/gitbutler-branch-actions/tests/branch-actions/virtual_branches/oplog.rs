@@ -1020,3 +1020,50 @@ fn first_snapshot_diff_works() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn restore_diff_shows_what_a_restore_would_change() -> anyhow::Result<()> {
+    let Test { repo, ctx, .. } = &mut Test::default();
+
+    let mut guard = ctx.exclusive_worktree_access();
+    gitbutler_branch_actions::set_base_branch(
+        ctx,
+        &"refs/remotes/origin/master".parse()?,
+        guard.write_permission(),
+    )?;
+    drop(guard);
+
+    let mut guard = ctx.exclusive_worktree_access();
+    let stack_entry = ctx
+        .branch_manager()
+        .create_virtual_branch(&BranchCreateRequest::default(), guard.write_permission())?;
+    drop(guard);
+
+    fs::write(repo.path().join("file.txt"), "content")?;
+    let _commit_id = super::create_commit(ctx, stack_entry.id, "first commit")?;
+
+    let snapshots = ctx
+        .snapshots_iter(None, Vec::new(), None)?
+        .take(10)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let before_commit = snapshots
+        .iter()
+        .find(|snapshot| {
+            snapshot
+                .details
+                .as_ref()
+                .is_some_and(|details| details.operation == OperationKind::CreateCommit)
+        })
+        .context("the commit was recorded")?;
+
+    let diff = ctx.restore_diff(before_commit.commit_id)?;
+    assert_eq!(
+        diff.iter()
+            .map(|change| change.path.to_str_lossy())
+            .collect::<Vec<_>>(),
+        ["file.txt"],
+        "restoring to before the commit removes the committed file"
+    );
+
+    Ok(())
+}
@@ -4,6 +4,7 @@ use but_ctx::{
     Context,
     access::{RepoExclusive, RepoShared},
 };
+use but_worktrees::{Worktree, WorktreeId};
 use gitbutler_operating_modes::{
     EditModeMetadata, ensure_edit_mode, ensure_open_workspace_mode, in_edit_mode,
 };
@@ -56,6 +57,57 @@ pub fn abort_and_return_to_workspace(
     crate::abort_and_return_to_workspace(ctx, force, perm)
 }
 
+/// Check out `commit_oid` into a new linked worktree to edit it or resolve its conflicts there,
+/// while the workspace stays usable.
+///
+/// Use [`save_worktree_edit_and_return()`] to apply the edit to the workspace, or
+/// [`abort_worktree_edit()`] to discard it.
+pub fn enter_edit_mode_in_worktree(
+    ctx: &mut Context,
+    commit_oid: gix::ObjectId,
+    stack_id: StackId,
+    perm: &mut RepoExclusive,
+) -> Result<Worktree> {
+    ensure_open_workspace_mode(ctx, perm.read_permission())
+        .context("Editing in a worktree may only be done when the workspace is open")?;
+
+    crate::worktree::enter_edit_mode_in_worktree(ctx, commit_oid, stack_id, perm)
+}
+
+/// Replace the edited commit with the state of the edit worktree `id`, and remove the worktree.
+pub fn save_worktree_edit_and_return(
+    ctx: &mut Context,
+    id: &WorktreeId,
+    perm: &mut RepoExclusive,
+) -> Result<()> {
+    ensure_open_workspace_mode(ctx, perm.read_permission())
+        .context("Edits in a worktree may only be saved when the workspace is open")?;
+
+    let snapshot = ctx
+        .prepare_snapshot(perm.read_permission())
+        .context("Failed to prepare snapshot")?;
+
+    crate::worktree::save_worktree_edit_and_return(ctx, id, perm)?;
+
+    let _ = ctx.commit_snapshot(
+        snapshot,
+        SnapshotDetails::new(OperationKind::AmendCommit),
+        perm,
+    );
+
+    Ok(())
+}
+
+/// Remove the edit worktree `id` without applying its changes, which requires `force` if there are any.
+pub fn abort_worktree_edit(
+    ctx: &mut Context,
+    id: &WorktreeId,
+    force: bool,
+    perm: &mut RepoExclusive,
+) -> Result<()> {
+    crate::worktree::abort_worktree_edit(ctx, id, force, perm)
+}
+
 pub fn starting_index_state(
     ctx: &Context,
     perm: &RepoShared,
@@ -26,6 +26,7 @@ use gix::prelude::ObjectIdExt as _;
 use serde::Serialize;
 
 pub mod commands;
+mod worktree;
 
 const UNCOMMITTED_CHANGES_REF: &str = "refs/gitbutler/edit-uncommitted-changes";
 
@@ -142,11 +143,9 @@ fn checkout_edit_branch(ctx: &Context, commit_id: gix::ObjectId) -> Result<()> {
     let repo = &*ctx.repo.get()?;
     #[expect(deprecated, reason = "checkout/index materialization boundary")]
     let git2_repo = &*ctx.git2_repo.get()?;
-    let commit = commit_id.attach(repo).object()?.try_into_commit()?;
 
     // Checkout commits's parent
     let base_commit_id = find_or_create_base_commit(repo, commit_id)?;
-    let base_commit = base_commit_id.attach(repo).object()?.try_into_commit()?;
     let edit_branch_ref: gix::refs::FullName = EDIT_BRANCH_REF.try_into()?;
     repo.reference(
         edit_branch_ref.as_ref(),
@@ -163,6 +162,21 @@ fn checkout_edit_branch(ctx: &Context, commit_id: gix::ObjectId) -> Result<()> {
         0,
     )?;
 
+    checkout_commit_index(ctx, git2_repo, commit_id, base_commit_id)
+}
+
+/// Write the tree of `commit_id` into the worktree of `git2_repo` as unstaged changes on top of
+/// `base_commit_id`, with its conflicts, if any, written as conflict markers.
+fn checkout_commit_index(
+    ctx: &Context,
+    git2_repo: &git2::Repository,
+    commit_id: gix::ObjectId,
+    base_commit_id: gix::ObjectId,
+) -> Result<()> {
+    let repo = &*ctx.repo.get()?;
+    let commit = commit_id.attach(repo).object()?.try_into_commit()?;
+    let base_commit = base_commit_id.attach(repo).object()?.try_into_commit()?;
+
     // Checkout the commit as unstaged changes
     // TODO this may not be necessary if the commit is unconflicted
     let mut index = get_commit_index(ctx, commit_id)?;
@@ -396,19 +410,25 @@ pub(crate) fn save_and_return_to_workspace(ctx: &Context, perm: &mut RepoExclusi
     index.read_tree(&git2_repo.head()?.peel_to_tree()?)?;
     index.write()?;
 
-    // Write the commit evolution.
+    write_evolution_parent(repo, new_commit_oid, edit_mode_metadata.commit_oid)?;
+
+    cleanup_edit_mode(ctx, repo)?;
+
+    Ok(())
+}
+
+/// Record that `new_commit_id` is the result of editing `edited_commit_id`.
+fn write_evolution_parent(
+    repo: &gix::Repository,
+    new_commit_id: gix::ObjectId,
+    edited_commit_id: gix::ObjectId,
+) -> Result<()> {
     let session = git_meta_lib::Session::open(repo.path())?;
     session
         .target(&git_meta_lib::Target::commit(
-            &new_commit_oid.to_hex().to_string(),
+            &new_commit_id.to_hex().to_string(),
         )?)
-        .set_add(
-            "evolution-parent",
-            &edit_mode_metadata.commit_oid.to_hex().to_string(),
-        )?;
-
-    cleanup_edit_mode(ctx, repo)?;
-
+        .set_add("evolution-parent", &edited_commit_id.to_hex().to_string())?;
     Ok(())
 }
 
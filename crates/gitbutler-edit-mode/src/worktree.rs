@@ -0,0 +1,158 @@
+//! Editing a commit in a linked worktree, leaving the `gitbutler/workspace` checkout untouched.
+//!
+//! Unlike edit mode, this doesn't change the operating mode, so the workspace stays usable while
+//! the commit is edited. Finishing the edit applies it to the workspace like
+//! [`save_and_return_to_workspace()`](crate::save_and_return_to_workspace) would.
+
+use anyhow::{Context as _, Result, bail};
+use but_core::{RepositoryExt as _, commit::SignCommit, ref_metadata::StackId};
+use but_ctx::{Context, access::RepoExclusive};
+use but_rebase::graph_rebase::{Editor, Pick, Step};
+use but_worktrees::{Worktree, WorktreeId, edit::open_worktree_repo};
+use gitbutler_cherry_pick::GixRepositoryExt as _;
+use gitbutler_operating_modes::WORKSPACE_BRANCH_REF;
+
+use crate::{
+    checkout_commit_index, ensure_stack_in_workspace, find_or_create_base_commit,
+    write_evolution_parent,
+};
+
+/// Return the commit that is edited in the worktree with `id`, or fail if it isn't an edit worktree.
+fn edited_commit(repo: &gix::Repository, id: &WorktreeId) -> Result<gix::ObjectId> {
+    but_worktrees::edit::edited_commit(repo, id)?
+        .with_context(|| format!("Worktree {id} is not used to edit a commit"))
+}
+
+pub(crate) fn enter_edit_mode_in_worktree(
+    ctx: &Context,
+    commit_oid: gix::ObjectId,
+    stack_id: StackId,
+    _perm: &mut RepoExclusive,
+) -> Result<Worktree> {
+    ensure_stack_in_workspace(ctx, stack_id)?;
+
+    let worktree = {
+        let repo = &*ctx.repo.get()?;
+        let base_commit_id = find_or_create_base_commit(repo, commit_oid)?;
+        let worktree = but_worktrees::edit::edit_worktree_new(
+            repo,
+            &ctx.project_data_dir(),
+            commit_oid,
+            base_commit_id,
+        )?;
+        let git2_repo = git2::Repository::open(&worktree.path)?;
+        checkout_commit_index(ctx, &git2_repo, commit_oid, base_commit_id)
+            .context("Failed to checkout the commit into the edit worktree")?;
+        // Conflicted commits are checked out with conflict markers, which aren't in any of their
+        // trees, so remember what the worktree looked like to know if it was changed.
+        #[expect(deprecated)]
+        let checked_out_tree = open_worktree_repo(repo, &worktree.id)?.create_wd_tree(0)?;
+        but_worktrees::edit::set_checked_out_tree(repo, &worktree.id, checked_out_tree)?;
+        worktree
+    };
+
+    // Keep the edit worktree out of graph traversals so rewrites in the workspace don't move its
+    // `HEAD`, and with that the changes made in it.
+    ctx.set_worktree_archived(worktree.id.as_bstr(), true)?;
+
+    Ok(worktree)
+}
+
+pub(crate) fn abort_worktree_edit(
+    ctx: &Context,
+    id: &WorktreeId,
+    force: bool,
+    _perm: &mut RepoExclusive,
+) -> Result<()> {
+    let repo = &*ctx.repo.get()?;
+    let edited_commit_id = edited_commit(repo, id)?;
+
+    if !force {
+        let worktree_repo = open_worktree_repo(repo, id)?;
+        let base = match but_worktrees::edit::checked_out_tree(repo, id)? {
+            Some(checked_out_tree) => checked_out_tree,
+            None => {
+                let commit = repo.find_commit(edited_commit_id)?;
+                repo.find_real_tree(&commit, Default::default())?.into()
+            }
+        };
+        #[expect(deprecated)]
+        let head = worktree_repo.create_wd_tree(0)?;
+        if !but_core::diff::tree_changes(repo, Some(base), head)?.is_empty() {
+            bail!(
+                "The edit worktree {id} differs from the original commit. A forced abort is necessary to discard its changes."
+            );
+        }
+    }
+
+    but_worktrees::destroy::worktree_destroy_by_id(repo, id)?;
+    Ok(())
+}
+
+pub(crate) fn save_worktree_edit_and_return(
+    ctx: &Context,
+    id: &WorktreeId,
+    _perm: &mut RepoExclusive,
+) -> Result<()> {
+    let repo = &*ctx.repo.get()?;
+    let edited_commit_id = edited_commit(repo, id)?;
+    let worktree_repo = open_worktree_repo(repo, id)?;
+
+    let head_commit = worktree_repo.head_commit()?;
+    let decoded_head_commit = head_commit.decode()?;
+    // Write out all the changes, including unstaged changes to a tree for re-committing
+    #[expect(deprecated)]
+    let tree_id = worktree_repo.create_wd_tree(0)?;
+    let new_commit_oid = if decoded_head_commit.tree() == tree_id {
+        head_commit.id
+    } else {
+        let commit = gix::objs::Commit::try_from(decoded_head_commit.clone())?;
+        but_rebase::commit::create(
+            repo,
+            gix::objs::Commit {
+                tree: tree_id,
+                ..commit
+            },
+            but_rebase::commit::DateMode::CommitterUpdateAuthorKeep,
+            SignCommit::IfSignCommitsEnabled,
+            None,
+        )?
+    };
+
+    let workspace_commit = repo
+        .find_reference(WORKSPACE_BRANCH_REF)?
+        .peel_to_commit()?;
+    let mut meta = ctx.meta()?;
+    let mut workspace = but_graph::Graph::from_commit_traversal(
+        workspace_commit.id(),
+        Some(gix::refs::FullName::try_from(WORKSPACE_BRANCH_REF)?),
+        &meta,
+        ctx.project_meta()?,
+        but_graph::init::Options::limited(),
+    )?
+    .into_workspace()?;
+    let mut editor = Editor::create(&mut workspace, &mut meta, repo)?;
+    let (target_selector, _commit) = editor
+        .find_selectable_commit(edited_commit_id)
+        .with_context(|| {
+            format!(
+                "The edited commit {edited_commit_id} was changed in the workspace in the meantime, the edit remains in worktree {id}"
+            )
+        })?;
+
+    let mut pick = Pick::new_pick(new_commit_oid);
+    // Do not replace new_commit_oid's parents with the parents of edited_commit_id
+    pick.preserved_parents = Some(decoded_head_commit.parents().collect());
+
+    editor.replace(target_selector, Step::Pick(pick))?;
+    let outcome = editor.rebase()?;
+    // The workspace is still checked out, so update it while keeping its uncommitted changes.
+    outcome.materialize(Default::default())?;
+    ctx.invalidate_workspace_cache()?;
+
+    write_evolution_parent(repo, new_commit_oid, edited_commit_id)?;
+
+    but_worktrees::destroy::worktree_destroy_by_id(repo, id)?;
+
+    Ok(())
+}
@@ -10,7 +10,8 @@ use but_ctx::Context;
 use but_meta::VirtualBranchesTomlMetadata;
 use but_testsupport::{gix_testtools, open_repo, visualize_commit_graph};
 use gitbutler_edit_mode::commands::{
-    abort_and_return_to_workspace, enter_edit_mode, save_and_return_to_workspace,
+    abort_and_return_to_workspace, abort_worktree_edit, enter_edit_mode,
+    enter_edit_mode_in_worktree, save_and_return_to_workspace, save_worktree_edit_and_return,
 };
 use gitbutler_operating_modes::{
     EditModeMetadata, INTEGRATION_BRANCH_REF, read_edit_mode_metadata, write_edit_mode_metadata,
@@ -600,3 +601,202 @@ Some(
 
     Ok(())
 }
+
+#[test]
+fn edit_in_worktree_keeps_workspace_usable() -> Result<()> {
+    let (mut ctx, _tempdir) = command_ctx("conficted_entries_get_written_when_leaving_edit_mode")?;
+    let repo = ctx.repo.get()?;
+    let foobar = repo.rev_parse_single(b"HEAD^{/foobar}")?.detach();
+    let workspace_dir = repo.workdir().unwrap().to_owned();
+    drop(repo);
+
+    let mut guard = ctx.exclusive_worktree_access();
+    let stack_id = {
+        let (_repo, ws, _db) = ctx.workspace_and_db_with_perm(guard.read_permission())?;
+        stack_id(&ws)?
+    };
+    let worktree =
+        enter_edit_mode_in_worktree(&mut ctx, foobar, stack_id, guard.write_permission())?;
+
+    // The workspace stays checked out and usable while the commit is edited elsewhere.
+    assert_edit_mode_cleaned_up(&ctx)?;
+    snapbox::assert_data_eq!(
+        ctx.repo.get()?.head_name()?.to_debug(),
+        snapbox::str![[r#"
+Some(
+    FullName(
+        "refs/heads/gitbutler/workspace",
+    ),
+)
+
+"#]]
+    );
+    std::fs::write(
+        workspace_dir.join("untouched"),
+        "uncommitted in workspace\n",
+    )?;
+
+    snapbox::assert_data_eq!(
+        std::fs::read_to_string(worktree.path.join("file"))?,
+        snapbox::str![[r#"
+b
+
+"#]]
+    );
+    std::fs::write(worktree.path.join("file"), "edited in worktree\n")?;
+
+    save_worktree_edit_and_return(&mut ctx, &worktree.id, guard.write_permission())?;
+
+    let repo = ctx.repo.get()?;
+    let blob = repo.rev_parse_single(b"HEAD^{/foobar}:file")?.object()?;
+    snapbox::assert_data_eq!(
+        &*blob.data,
+        snapbox::str![[r#"
+edited in worktree
+
+"#]]
+    );
+    snapbox::assert_data_eq!(
+        std::fs::read_to_string(workspace_dir.join("file"))?,
+        snapbox::str![[r#"
+edited in worktree
+
+"#]]
+    );
+    snapbox::assert_data_eq!(
+        std::fs::read_to_string(workspace_dir.join("untouched"))?,
+        snapbox::str![[r#"
+uncommitted in workspace
+
+"#]]
+    );
+    assert!(
+        !worktree.path.exists(),
+        "the edit worktree is removed once its changes are applied"
+    );
+    assert!(repo.worktrees()?.is_empty());
+
+    let session = git_meta_lib::Session::open(repo.path())?;
+    let evolution_parent = session
+        .target(&git_meta_lib::Target::commit(
+            &repo
+                .rev_parse_single(b"HEAD^{/foobar}")?
+                .to_hex()
+                .to_string(),
+        )?)
+        .get_value("evolution-parent")?;
+    snapbox::assert_data_eq!(
+        evolution_parent.to_debug(),
+        snapbox::str![[r#"
+Some(
+    Set(
+        {
+            "26804c33bfc7bf602e778b8dd847283bbf886b6a",
+        },
+    ),
+)
+
+"#]]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn abort_worktree_edit_of_conflicted_commit() -> Result<()> {
+    let (mut ctx, _tempdir) = command_ctx("enter_edit_mode_with_conflicted_commit")?;
+    let conflicted_commit = ctx
+        .repo
+        .get()?
+        .find_reference("refs/tags/conflicted-target")?
+        .peel_to_commit()?
+        .id()
+        .detach();
+
+    let mut guard = ctx.exclusive_worktree_access();
+    let stack_id = {
+        let (_repo, ws, _db) = ctx.workspace_and_db_with_perm(guard.read_permission())?;
+        stack_id(&ws)?
+    };
+    let worktree = enter_edit_mode_in_worktree(
+        &mut ctx,
+        conflicted_commit,
+        stack_id,
+        guard.write_permission(),
+    )?;
+
+    let conflict_path = worktree.path.join("conflict");
+    let checked_out_conflict = std::fs::read_to_string(&conflict_path)?;
+    snapbox::assert_data_eq!(
+        &checked_out_conflict,
+        snapbox::str![[r#"
+<<<<<<< New base: foobar
+left
+||||||| Common ancestor
+base
+=======
+right
+>>>>>>> Current commit: Changes to make millions
+
+"#]]
+    );
+    snapbox::assert_data_eq!(
+        ctx.repo.get()?.head_name()?.to_debug(),
+        snapbox::str![[r#"
+Some(
+    FullName(
+        "refs/heads/gitbutler/workspace",
+    ),
+)
+
+"#]]
+    );
+
+    std::fs::write(&conflict_path, "resolved\n")?;
+    let err = abort_worktree_edit(&mut ctx, &worktree.id, false, guard.write_permission())
+        .err()
+        .ok_or_else(|| anyhow!("expected abort to fail without force"))?;
+    assert!(
+        err.to_string().contains("A forced abort is necessary"),
+        "{err}"
+    );
+    assert!(worktree.path.exists(), "nothing is discarded without force");
+
+    std::fs::write(&conflict_path, checked_out_conflict)?;
+    abort_worktree_edit(&mut ctx, &worktree.id, false, guard.write_permission())?;
+    assert!(
+        !worktree.path.exists(),
+        "the conflict markers that were checked out are no change"
+    );
+    assert!(ctx.repo.get()?.worktrees()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn abort_worktree_edit_with_changes_requires_force() -> Result<()> {
+    let (mut ctx, _tempdir) = command_ctx("conficted_entries_get_written_when_leaving_edit_mode")?;
+    let foobar = ctx
+        .repo
+        .get()?
+        .rev_parse_single(b"HEAD^{/foobar}")?
+        .detach();
+
+    let mut guard = ctx.exclusive_worktree_access();
+    let stack_id = {
+        let (_repo, ws, _db) = ctx.workspace_and_db_with_perm(guard.read_permission())?;
+        stack_id(&ws)?
+    };
+    let worktree =
+        enter_edit_mode_in_worktree(&mut ctx, foobar, stack_id, guard.write_permission())?;
+    std::fs::write(worktree.path.join("file"), "edited in worktree\n")?;
+
+    assert!(
+        abort_worktree_edit(&mut ctx, &worktree.id, false, guard.write_permission()).is_err(),
+        "changes are not discarded without force"
+    );
+    abort_worktree_edit(&mut ctx, &worktree.id, true, guard.write_permission())?;
+    assert!(!worktree.path.exists());
+
+    Ok(())
+}
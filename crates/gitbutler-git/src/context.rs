@@ -1,6 +1,6 @@
-use std::{path::Path, time::UNIX_EPOCH};
+use std::{path::Path, sync::atomic::AtomicBool, time::UNIX_EPOCH};
 
-use anyhow::{Context as _, Result, anyhow};
+use anyhow::{Context as _, Result, anyhow, bail};
 use but_askpass as askpass;
 use but_core::{extract_remote_name_and_short_name, ref_metadata::StackId};
 use but_ctx::Context;
@@ -20,6 +20,10 @@ pub struct PushResult {
     ///
     /// Format: `(branch_name, before_sha, after_sha)`.
     pub branch_sha_updates: Vec<(String, String, String)>,
+    /// The outcome of each pushed ref as reported by Git.
+    ///
+    /// Empty if the refs were pushed one at a time, as is the case for Gerrit.
+    pub ref_updates: Vec<crate::RefUpdate>,
 }
 
 /// Higher-level fetch and push helpers implemented for [`Context`].
@@ -134,12 +138,21 @@ impl GitContextExt for Context {
 /// when it is enabled.
 ///
 /// The fetch runs on its own thread and runtime so synchronous API callers don't block the runtime
-/// responsible for delivering askpass responses.
+/// responsible for delivering askpass responses. It uses the [`FetchBackend`](crate::FetchBackend)
+/// configured for the repository.
 pub fn fetch_with_askpass(
     repo_path: impl AsRef<Path>,
     remote_name: &str,
     action: Option<String>,
 ) -> Result<()> {
+    // If the repository can't be opened, the CLI reports it just like it would without a backend.
+    let backend = gix::open(repo_path.as_ref())
+        .map(|repo| crate::FetchBackend::from_repo(&repo))
+        .unwrap_or_default();
+    if backend == crate::FetchBackend::InProcess {
+        return fetch_in_process_with_askpass(repo_path, remote_name, action);
+    }
+
     let on_prompt = if askpass::get_broker().is_some() {
         Some(move |prompt: String| handle_git_prompt_fetch(prompt, action.clone()))
     } else {
@@ -161,20 +174,66 @@ pub fn fetch_with_askpass(
         )))
     })
     .join()
-    .map_err(|panic| {
-        let reason = if let Some(message) = panic.downcast_ref::<String>() {
-            message.clone()
-        } else if let Some(message) = panic.downcast_ref::<&'static str>() {
-            (*message).to_owned()
-        } else {
-            "unknown panic payload".to_owned()
+    .map_err(worker_panicked("fetch"))??;
+    result.map_err(map_needs_authorization)
+}
+
+/// Like [`fetch_with_askpass()`], but fetch with `gitoxide` in-process.
+fn fetch_in_process_with_askpass(
+    repo_path: impl AsRef<Path>,
+    remote_name: &str,
+    action: Option<String>,
+) -> Result<()> {
+    let repo_path = repo_path.as_ref().to_owned();
+    let remote = remote_name.to_owned();
+    let result = std::thread::spawn(move || -> Result<_> {
+        // Only needed to drive the askpass broker, the fetch itself is blocking.
+        let runtime = tokio::runtime::Runtime::new().context(
+            but_error::Context::new("failed to initialize async runtime for git fetch")
+                .with_code(Code::Unknown),
+        )?;
+        let on_prompt = askpass::get_broker().is_some().then(|| {
+            let handle = runtime.handle().clone();
+            move |prompt: String| handle.block_on(handle_git_prompt_fetch(prompt, action.clone()))
+        });
+        let progress = prodash::tree::Root::new();
+        let result = crate::in_process::fetch(
+            &repo_path,
+            &remote,
+            on_prompt,
+            progress.add_child(format!("fetch {remote}")),
+            &AtomicBool::new(false),
+        );
+        log_fetch_messages(&progress, &repo_path, result.is_err());
+        Ok(result)
+    })
+    .join()
+    .map_err(worker_panicked("fetch"))??;
+    result.map_err(|err| {
+        let crate::Error::Backend(crate::in_process::Error::NeedsAuthorization(ref prompt)) = err
+        else {
+            return err.into();
         };
+        let context = but_error::Context::new(needs_authorization_message(prompt, &err))
+            .with_code(Code::ProjectGitAuth);
+        anyhow::Error::from(err).context(context)
+    })
+}
 
-        anyhow!("git fetch worker thread panicked: {reason}").context(
-            but_error::Context::new("git fetch failed unexpectedly").with_code(Code::Unknown),
-        )
-    })??;
-    result.map_err(map_needs_authorization)
+/// Log what the in-process fetch reported to `progress`, including the messages of the remote,
+/// like the Git CLI executor logs the output of failed invocations.
+fn log_fetch_messages(progress: &prodash::tree::Root, repo_path: &Path, failed: bool) {
+    let mut messages = Vec::new();
+    progress.copy_messages(&mut messages);
+    let messages: Vec<_> = messages
+        .iter()
+        .map(|message| format!("{}: {}", message.origin, message.message))
+        .collect();
+    if failed {
+        tracing::error!(?repo_path, ?messages, "In-process fetch failed");
+    } else {
+        tracing::debug!(?repo_path, ?messages, "In-process fetch succeeded");
+    }
 }
 
 /// The concrete error type produced by fetch/push through the tokio executor.
@@ -264,19 +323,7 @@ where
         )))
     })
     .join()
-    .map_err(|panic| {
-        let reason = if let Some(message) = panic.downcast_ref::<String>() {
-            message.clone()
-        } else if let Some(message) = panic.downcast_ref::<&'static str>() {
-            (*message).to_owned()
-        } else {
-            "unknown panic payload".to_owned()
-        };
-
-        anyhow!("git push worker thread panicked: {reason}").context(
-            but_error::Context::new("git push failed unexpectedly").with_code(Code::Unknown),
-        )
-    })??;
+    .map_err(worker_panicked("push"))??;
     match result {
             Ok(stderr) => Ok(stderr),
             Err(err) => match err {
@@ -294,6 +341,114 @@ where
         }
 }
 
+/// Push each commit in `updates` to the remote branch of the remote tracking branch it is paired
+/// with, atomically, so either all remote branches are updated or none of them are.
+/// Remotes that don't support atomic pushes get a regular push instead, which may update some
+/// remote branches even if others are rejected, see [`crate::push_atomic`].
+///
+/// All remote tracking branches must belong to the same remote.
+/// Returns the outcome of each ref as reported by Git, in the order of `updates`.
+pub fn push_atomic_with_askpass(
+    repo: &gix::Repository,
+    updates: Vec<(gix::ObjectId, gix::refs::FullName)>,
+    with_force: bool,
+    force_push_protection: bool,
+    askpass_broker: Option<Option<but_core::Id<'S'>>>,
+    push_opts: Vec<String>,
+) -> Result<Vec<crate::RefUpdate>> {
+    let mut remote = None::<String>;
+    let mut refspecs = Vec::with_capacity(updates.len());
+    for (head, branch) in updates {
+        let (branch_remote, branch_name) = remote_tracking_branch_parts(repo, branch.as_ref())?;
+        match &remote {
+            Some(remote) if *remote != branch_remote => bail!(
+                "cannot push to `{remote}` and `{branch_remote}` at once, all branches must share the same remote"
+            ),
+            Some(_) => {}
+            None => remote = Some(branch_remote),
+        }
+        refspecs.push(crate::RefSpec {
+            update_non_fastforward: false,
+            source: Some(head.to_string()),
+            destination: Some(format!("refs/heads/{branch_name}")),
+        });
+    }
+    let Some(remote) = remote else {
+        return Ok(Vec::new());
+    };
+
+    let on_prompt = if askpass::get_broker().is_some() {
+        Some(move |prompt: String| handle_git_prompt_push(prompt, askpass_broker))
+    } else {
+        None
+    };
+
+    let repo_path = repo.git_dir().to_owned();
+    let result = std::thread::spawn(move || -> Result<_> {
+        let runtime = tokio::runtime::Runtime::new().context(
+            but_error::Context::new("failed to initialize async runtime for git push")
+                .with_code(Code::Unknown),
+        )?;
+        Ok(runtime.block_on(crate::push_atomic(
+            repo_path,
+            crate::tokio::TokioExecutor,
+            &remote,
+            refspecs,
+            with_force,
+            force_push_protection,
+            on_prompt,
+            push_opts,
+        )))
+    })
+    .join()
+    .map_err(worker_panicked("push"))??;
+
+    let err = match result {
+        Ok(updates) => return Ok(updates),
+        Err(err) => err,
+    };
+    match err {
+        crate::Error::PushRejected(ref updates, ref e)
+        | crate::Error::PushPartlyRejected(ref updates, ref e)
+            if with_force
+                && force_push_protection
+                && updates.iter().any(crate::RefUpdate::is_stale) =>
+        {
+            Err(anyhow!(
+                "The force push was blocked because a remote branch contains commits that would be overwritten.\n\n{e}"
+            )
+            .context(Code::GitForcePushProtection))
+        }
+        crate::Error::PushRejected(ref updates, _)
+        | crate::Error::PushPartlyRejected(ref updates, _)
+            if updates.iter().any(crate::RefUpdate::is_non_fast_forward) =>
+        {
+            Err(err).context(Code::GitNonFastForward)
+        }
+        _ => Err(map_needs_authorization(err)),
+    }
+}
+
+/// Return a function to turn the panic of a thread running the Git `operation` into an error.
+fn worker_panicked(
+    operation: &'static str,
+) -> impl FnOnce(Box<dyn std::any::Any + Send>) -> anyhow::Error {
+    move |panic| {
+        let reason = if let Some(message) = panic.downcast_ref::<String>() {
+            message.clone()
+        } else if let Some(message) = panic.downcast_ref::<&'static str>() {
+            (*message).to_owned()
+        } else {
+            "unknown panic payload".to_owned()
+        };
+
+        anyhow!("git {operation} worker thread panicked: {reason}").context(
+            but_error::Context::new(format!("git {operation} failed unexpectedly"))
+                .with_code(Code::Unknown),
+        )
+    }
+}
+
 fn serialize_branch_to_remote<S>(
     branch_to_remote: &[(String, gix::refs::FullName)],
     serializer: S,
@@ -40,4 +40,20 @@ pub enum Error<BE: std::error::Error + core::fmt::Debug + Send + Sync + 'static>
     /// contains commits the local side does not. Recoverable by re-fetching and retrying.
     #[error("the push was rejected because the remote branch has moved (non-fast-forward)")]
     NonFastForward(BE),
+    /// An atomic push was rejected, so none of the refs were updated on the remote.
+    ///
+    /// Contains the outcome of each ref as reported by Git, which includes at least one
+    /// rejection with its reason.
+    #[error("the push was rejected, no ref was updated:{}", crate::push::rejections(.0))]
+    PushRejected(Vec<crate::RefUpdate>, #[source] BE),
+    /// A push of several refs was rejected after the remote refused to push them atomically,
+    /// so the refs that weren't rejected may have been updated on the remote.
+    ///
+    /// Contains the outcome of each ref as reported by Git, which includes at least one
+    /// rejection with its reason.
+    #[error(
+        "the push was rejected, and as the remote doesn't support atomic pushes other refs may have been updated:{}",
+        crate::push::rejections(.0)
+    )]
+    PushPartlyRejected(Vec<crate::RefUpdate>, #[source] BE),
 }
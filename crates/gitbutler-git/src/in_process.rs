@@ -0,0 +1,221 @@
+//! Fetching with `gitoxide` in the current process, instead of spawning `git fetch`.
+//!
+//! This saves a process and an askpass harness per fetch, which adds up when many repositories
+//! are fetched in the background. It is used for repositories that set
+//! `gitbutler.fetchBackend = gix`, see [`FetchBackend`].
+//!
+//! Like with `git`, credentials are obtained from the configured credential helpers first.
+//! If these have none, the prompts `git` would show are passed to `on_prompt`, just like the
+//! askpass harness does for the Git CLI.
+
+use std::{
+    cell::Cell,
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use gix::{bstr::ByteSlice, remote::Direction};
+
+const FETCH_BACKEND: &str = "gitbutler.fetchBackend";
+
+/// The implementation to fetch with, configured per repository with `gitbutler.fetchBackend`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FetchBackend {
+    /// Spawn `git fetch`, configured with `cli`.
+    #[default]
+    Cli,
+    /// Fetch with `gitoxide` in the current process, configured with `gix`.
+    InProcess,
+}
+
+impl FetchBackend {
+    /// Return the backend configured for `repo`, or the default if it is unset or unknown.
+    pub fn from_repo(repo: &gix::Repository) -> Self {
+        let Some(value) = repo.config_snapshot().string(FETCH_BACKEND) else {
+            return FetchBackend::default();
+        };
+        match value.as_bytes() {
+            b"cli" => FetchBackend::Cli,
+            b"gix" => FetchBackend::InProcess,
+            unknown => {
+                tracing::warn!(
+                    "ignoring unknown value {:?} of {FETCH_BACKEND}, expected `cli` or `gix`",
+                    unknown.as_bstr()
+                );
+                FetchBackend::default()
+            }
+        }
+    }
+}
+
+/// Errors that can occur when fetching in-process.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The repository could not be opened.
+    #[error("failed to open repository at `{}`: {source}", path.display())]
+    RepositoryOpen {
+        /// The repository path that could not be opened.
+        path: PathBuf,
+        /// The repository open error.
+        #[source]
+        source: gix::open::Error,
+    },
+    /// The remote configuration could not be read.
+    #[error("failed to read configuration for remote `{remote}`: {source}")]
+    RemoteConfiguration {
+        /// The remote whose configuration could not be read.
+        remote: String,
+        /// The configuration lookup error.
+        #[source]
+        source: gix::remote::find::existing::Error,
+    },
+    /// The remote has no URL to fetch from.
+    #[error("remote `{0}` has no URL to fetch from")]
+    NoUrl(String),
+    /// The default fetch refspec could not be set.
+    #[error(transparent)]
+    Refspec(#[from] gix::refspec::parse::Error),
+    /// The credential helpers could not be configured.
+    #[error(transparent)]
+    CredentialHelpers(#[from] gix::config::credential_helpers::Error),
+    /// The remote could not be connected to.
+    #[error(transparent)]
+    Connect(#[from] gix::remote::connect::Error),
+    /// The remote refs could not be obtained.
+    #[error(transparent)]
+    PrepareFetch(#[from] gix::remote::fetch::prepare::Error),
+    /// The pack could not be received, or the refs could not be updated.
+    #[error(transparent)]
+    Fetch(#[from] gix::remote::fetch::Error),
+    /// Credentials were needed, but no credential helper had any and nobody answered the prompt.
+    #[error("git requires authorization credentials but none were provided: prompt was {0:?}")]
+    NeedsAuthorization(String),
+}
+
+/// Fetch from `remote` in the repository at `repo_path` using its configured fetch refspecs, just
+/// like [`fetch()`](crate::fetch()) does with the Git CLI.
+///
+/// If `on_prompt` is provided, it is asked for credentials that none of the credential helpers
+/// provided. The progress of the fetch, including the messages of the remote, is reported to
+/// `progress`, and the fetch stops early once `should_interrupt` is set.
+pub fn fetch<F, P>(
+    repo_path: impl AsRef<Path>,
+    remote: &str,
+    mut on_prompt: Option<F>,
+    mut progress: P,
+    should_interrupt: &AtomicBool,
+) -> Result<(), crate::Error<Error>>
+where
+    F: FnMut(String) -> Option<String>,
+    P: gix::NestedProgress,
+    P::SubProgress: 'static,
+{
+    let repo_path = repo_path.as_ref();
+    let repo = gix::open(repo_path).map_err(|source| Error::RepositoryOpen {
+        path: repo_path.to_owned(),
+        source,
+    })?;
+    let mut remote_handle = repo.find_remote(remote).map_err(|source| {
+        let remote_not_found =
+            matches!(source, gix::remote::find::existing::Error::NotFound { .. });
+        let source = Error::RemoteConfiguration {
+            remote: remote.to_owned(),
+            source,
+        };
+        if remote_not_found {
+            crate::Error::NoSuchRemote(remote.to_owned(), source)
+        } else {
+            source.into()
+        }
+    })?;
+    if remote_handle.refspecs(Direction::Fetch).is_empty() {
+        let default_refspec = format!("+refs/heads/*:refs/remotes/{remote}/*");
+        remote_handle
+            .replace_refspecs([default_refspec.as_str()], Direction::Fetch)
+            .map_err(Error::from)?;
+    }
+    let url = remote_handle
+        .url(Direction::Fetch)
+        .ok_or_else(|| Error::NoUrl(remote.to_owned()))?
+        .to_owned();
+    let prompt_url = prompt_url(&url);
+
+    let (mut helpers, _, mut prompt_options) = repo
+        .config_snapshot()
+        .credential_helpers(url)
+        .map_err(Error::from)?;
+    // There is no terminal to prompt on, `on_prompt` takes its place.
+    prompt_options.mode = gix::prompt::Mode::Disable;
+    let unanswered_prompt = Cell::new(None::<String>);
+
+    let connection = remote_handle
+        .connect(Direction::Fetch)
+        .map_err(Error::from)?
+        .with_credentials(|action| {
+            let get_ctx = match &action {
+                gix::credentials::helper::Action::Get(ctx) => Some(ctx.clone()),
+                _ => None,
+            };
+            let result = helpers.invoke(action, prompt_options.clone());
+            let Some(mut ctx) = get_ctx else {
+                return result;
+            };
+            if let Ok(Some(outcome)) = result {
+                return Ok(Some(outcome));
+            }
+            let Some(on_prompt) = on_prompt.as_mut() else {
+                return Ok(None);
+            };
+            let mut ask = |prompt: String| {
+                let answer = on_prompt(prompt.clone());
+                if answer.is_none() {
+                    unanswered_prompt.set(Some(prompt));
+                }
+                answer
+            };
+            let Some(username) = ctx
+                .username
+                .clone()
+                .or_else(|| ask(format!("Username for '{prompt_url}': ")))
+            else {
+                return Ok(None);
+            };
+            let Some(password) = ask(format!("Password for '{prompt_url}': ")) else {
+                return Ok(None);
+            };
+            ctx.username = Some(username.clone());
+            ctx.password = Some(password.clone());
+            Ok(Some(gix::credentials::protocol::Outcome {
+                identity: gix::sec::identity::Account {
+                    username,
+                    password,
+                    oauth_refresh_token: None,
+                },
+                next: ctx.into(),
+            }))
+        });
+
+    let result = connection
+        .prepare_fetch(progress.add_child("list refs"), Default::default())
+        .map_err(Error::from)
+        .and_then(|prepare| {
+            prepare
+                .receive(progress, should_interrupt)
+                .map_err(Error::from)
+        });
+    match (result, unanswered_prompt.take()) {
+        (Ok(_), _) => Ok(()),
+        (Err(_), Some(prompt)) => Err(Error::NeedsAuthorization(prompt).into()),
+        (Err(err), None) => Err(err.into()),
+    }
+}
+
+/// The URL to show in credential prompts, like `git` shows it: the scheme and host, along with
+/// the port if `url` names one.
+fn prompt_url(url: &gix::Url) -> String {
+    let host = url.host().unwrap_or_default();
+    match url.port {
+        Some(port) => format!("{}://{host}:{port}", url.scheme.as_str()),
+        None => format!("{}://{host}", url.scheme.as_str()),
+    }
+}
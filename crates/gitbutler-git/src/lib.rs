@@ -13,15 +13,21 @@ mod context;
 mod error;
 /// utilities to execute a command
 pub mod executor;
+pub mod in_process;
+mod push;
 mod refspec;
 mod repository;
 
 #[cfg(feature = "tokio")]
-pub use self::context::{GitContextExt, PushResult, fetch_with_askpass, push_with_askpass};
+pub use self::context::{
+    GitContextExt, PushResult, fetch_with_askpass, push_atomic_with_askpass, push_with_askpass,
+};
 #[cfg(feature = "tokio")]
 pub use self::executor::tokio;
 pub use self::{
     error::Error,
+    in_process::FetchBackend,
+    push::{RefUpdate, RefUpdateKind, parse_porcelain},
     refspec::{Error as RefSpecError, RefSpec},
-    repository::{clone, fetch, push},
+    repository::{clone, fetch, push, push_atomic},
 };
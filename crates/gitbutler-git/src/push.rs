@@ -0,0 +1,134 @@
+use core::fmt;
+
+use serde::Serialize;
+
+/// How `git push` handled a single ref, as indicated by the flag of its `--porcelain` line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RefUpdateKind {
+    /// The remote ref was fast-forwarded.
+    FastForward,
+    /// The remote ref was forcibly updated.
+    Forced,
+    /// The remote ref was deleted.
+    Deleted,
+    /// The remote ref was created.
+    Created,
+    /// The remote ref already had the pushed value.
+    UpToDate,
+    /// The update was rejected, either locally or by the remote.
+    Rejected,
+}
+
+impl RefUpdateKind {
+    fn from_flag(flag: &str) -> Option<Self> {
+        Some(match flag {
+            " " => RefUpdateKind::FastForward,
+            "+" => RefUpdateKind::Forced,
+            "-" => RefUpdateKind::Deleted,
+            "*" => RefUpdateKind::Created,
+            "=" => RefUpdateKind::UpToDate,
+            "!" => RefUpdateKind::Rejected,
+            _ => return None,
+        })
+    }
+}
+
+/// The outcome of pushing a single ref, as reported by `git push --porcelain`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RefUpdate {
+    /// How the ref was handled.
+    pub kind: RefUpdateKind,
+    /// The local side of the refspec, or `None` if the remote ref was to be deleted.
+    pub source: Option<String>,
+    /// The ref on the remote.
+    pub destination: String,
+    /// A summary like `1a2b3c4..5d6e7f8`, `[new branch]` or `[rejected]`.
+    pub summary: String,
+    /// The reason Git gave for the outcome, like `non-fast-forward` or `atomic push failed`.
+    pub reason: Option<String>,
+}
+
+impl RefUpdate {
+    /// Return `true` if the remote ref wasn't updated as requested.
+    pub fn is_rejected(&self) -> bool {
+        self.kind == RefUpdateKind::Rejected
+    }
+
+    /// Return `true` if the update was rejected because the remote ref contains commits that the
+    /// pushed side doesn't, so it needs to be fetched and integrated first.
+    pub fn is_non_fast_forward(&self) -> bool {
+        self.is_rejected_for(&["non-fast-forward", "fetch first"])
+    }
+
+    /// Return `true` if the update was rejected because the remote ref didn't match what
+    /// `--force-with-lease` or `--force-if-includes` expected.
+    pub fn is_stale(&self) -> bool {
+        self.is_rejected_for(&["stale info", "remote ref updated since checkout"])
+    }
+
+    /// Return `true` if the update was rejected only because another ref of the same atomic push
+    /// was rejected.
+    pub fn is_atomic_failure(&self) -> bool {
+        self.is_rejected_for(&["atomic push failed"])
+    }
+
+    fn is_rejected_for(&self, reasons: &[&str]) -> bool {
+        self.is_rejected()
+            && self
+                .reason
+                .as_deref()
+                .is_some_and(|reason| reasons.contains(&reason))
+    }
+
+    /// Parse a single line of `git push --porcelain` output, or return `None` if it isn't a
+    /// line for a ref, like the leading `To <url>` and trailing `Done` lines.
+    fn from_porcelain_line(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(3, '\t');
+        let kind = RefUpdateKind::from_flag(fields.next()?)?;
+        let (source, destination) = fields.next()?.split_once(':')?;
+        let summary = fields.next()?;
+        let (summary, reason) = match summary
+            .strip_suffix(')')
+            .and_then(|summary| summary.rsplit_once(" ("))
+        {
+            Some((summary, reason)) => (summary, Some(reason.to_owned())),
+            None => (summary, None),
+        };
+        Some(RefUpdate {
+            kind,
+            source: (!source.is_empty()).then(|| source.to_owned()),
+            destination: destination.to_owned(),
+            summary: summary.to_owned(),
+            reason,
+        })
+    }
+}
+
+impl fmt::Display for RefUpdate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.destination, self.summary)?;
+        if let Some(reason) = &self.reason {
+            write!(f, " ({reason})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse the per-ref results from the standard output of `git push --porcelain`.
+pub fn parse_porcelain(stdout: &str) -> Vec<RefUpdate> {
+    stdout
+        .lines()
+        .filter_map(RefUpdate::from_porcelain_line)
+        .collect()
+}
+
+/// List all rejected `updates` on their own line, for use in error messages.
+pub(crate) fn rejections(updates: &[RefUpdate]) -> String {
+    updates
+        .iter()
+        .filter(|update| update.is_rejected())
+        .map(|update| format!("\n  {update}"))
+        .collect()
+}
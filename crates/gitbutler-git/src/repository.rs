@@ -10,7 +10,7 @@ use gix::bstr::ByteSlice;
 use rand::{Rng, SeedableRng};
 
 use super::executor::{AskpassServer, GitExecutor, Pid, Socket};
-use crate::RefSpec;
+use crate::{RefSpec, RefUpdate};
 
 /// The number of characters in the secret used for checking
 /// askpass invocations by ssh/git when connecting to our process.
@@ -599,6 +599,132 @@ where
     Err(base_error.into())
 }
 
+/// Pushes all `refspecs` to the given remote in the repository at the given path, atomically.
+///
+/// Either all remote refs are updated, or none of them are. On success, the outcome of each ref is
+/// returned as reported by Git. If any ref is rejected, [`crate::Error::PushRejected`] contains
+/// the outcome of all refs, with the refs that were fine marked as failed due to the atomic push.
+///
+/// `--atomic` is only requested when more than one ref is pushed. If the remote doesn't support
+/// atomic pushes, the refs are pushed again without it, so some of them may be updated even if
+/// others are rejected, which is reported as [`crate::Error::PushPartlyRejected`].
+///
+/// If `on_prompt` is provided, we override SSH_ASKPASS to point to our custom askpass client to
+/// ferry prompts and responses between the SSH process and this process. This should be used
+/// carefully as it will unceremoniously override any askpass configuration the user might have,
+/// making it unsuitable for use in e.g. the CLI. It is designed to be used with a GUI/TUI.
+#[expect(clippy::too_many_arguments)]
+pub async fn push_atomic<P, F, Fut, E>(
+    repo_path: P,
+    executor: E,
+    remote: &str,
+    refspecs: Vec<RefSpec>,
+    force: bool,
+    force_push_protection: bool,
+    on_prompt: Option<F>,
+    push_opts: Vec<String>,
+) -> Result<Vec<RefUpdate>, crate::Error<Error<E>>>
+where
+    P: AsRef<Path>,
+    E: GitExecutor,
+    F: FnMut(String) -> Fut,
+    Fut: std::future::Future<Output = Option<String>>,
+{
+    // `--quiet` would also suppress the per-ref listing of `--porcelain`.
+    let mut args = vec!["push", "--porcelain", "--no-verify"];
+    let atomic = refspecs.len() > 1;
+    if atomic {
+        args.push("--atomic");
+    }
+
+    let has_deletion = refspecs.iter().any(|refspec| refspec.source.is_none());
+    let refspecs: Vec<_> = refspecs.iter().map(ToString::to_string).collect();
+
+    args.push(remote);
+    args.extend(refspecs.iter().map(String::as_str));
+
+    if force {
+        if force_push_protection {
+            args.push("--force-with-lease");
+            // See `push()` for why deletions can't be checked with `--force-if-includes`,
+            // which applies to all refs at once.
+            if !has_deletion {
+                args.push("--force-if-includes");
+            }
+        } else {
+            args.push("--force");
+        }
+    }
+
+    for opt in push_opts.iter() {
+        args.push("-o");
+        args.push(opt.as_str());
+    }
+
+    let mut on_prompt = on_prompt;
+    let (mut status, mut stdout, mut stderr) = execute_with_auth_harness(
+        HarnessEnv::Repo(repo_path.as_ref()),
+        &executor,
+        &args,
+        on_prompt.as_mut(),
+    )
+    .await?;
+
+    // Servers may refuse atomic pushes, in which case nothing was pushed yet.
+    let mut pushed_non_atomically = false;
+    if atomic
+        && status != 0
+        && stderr
+            .to_lowercase()
+            .contains("does not support --atomic push")
+    {
+        args.retain(|arg| *arg != "--atomic");
+        pushed_non_atomically = true;
+        (status, stdout, stderr) = execute_with_auth_harness(
+            HarnessEnv::Repo(repo_path.as_ref()),
+            &executor,
+            &args,
+            on_prompt.as_mut(),
+        )
+        .await?;
+    }
+
+    let updates = crate::parse_porcelain(&stdout);
+    if status == 0 {
+        return Ok(updates);
+    }
+
+    let base_error = Error::<E>::Failed {
+        status,
+        args: args.into_iter().map(Into::into).collect(),
+        stdout,
+        stderr: stderr.clone(),
+    };
+
+    if updates.iter().any(RefUpdate::is_rejected) {
+        return Err(if pushed_non_atomically {
+            crate::Error::PushPartlyRejected(updates, base_error)
+        } else {
+            crate::Error::PushRejected(updates, base_error)
+        });
+    }
+
+    // Without per-ref results the push didn't get as far as talking about refs.
+    if let Some(refname) = stderr
+        .lines()
+        .find(|line| line.to_lowercase().contains("does not match any"))
+        .and_then(|line| line.split_whitespace().last())
+    {
+        return Err(crate::Error::RefNotFound(refname.to_owned()));
+    }
+
+    if stderr.to_lowercase().contains("permission denied") {
+        return Err(crate::Error::AuthorizationFailed(base_error));
+    }
+
+    Err(base_error.into())
+}
+
 /// Clones the given repository URL to the target directory.
 ///
 /// If `on_prompt` is provided, we override SSH_ASKPASS to point to our custom askpass client to
@@ -0,0 +1,354 @@
+//! The in-process fetch must leave a repository in the same state as `git fetch` does.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use but_testsupport::{gix_testtools::tempfile, invoke_bash_at_dir};
+use gitbutler_git::{FetchBackend, tokio::TokioExecutor};
+
+/// A bare `remote.git` with a clone of it in `cli` and `gix` each, after which `remote.git` got a
+/// new commit on `main`, a new branch and a new tag.
+fn remote_and_clones() -> tempfile::TempDir {
+    let tmp = tempfile::TempDir::new().unwrap();
+    invoke_bash_at_dir(
+        r#"
+git init -q --bare remote.git
+git init -q work && cd work
+git commit -q --allow-empty -m base
+git push -q ../remote.git HEAD:refs/heads/main
+cd ..
+git clone -q remote.git cli
+git clone -q remote.git gix
+cd work
+git commit -q --allow-empty -m "new on main"
+git push -q ../remote.git HEAD:refs/heads/main HEAD:refs/heads/feature
+git tag -a -m "a tag" v1
+git push -q ../remote.git v1
+"#,
+        tmp.path(),
+    );
+    tmp
+}
+
+fn set_url(repo: &Path, url: &str) {
+    invoke_bash_at_dir(&format!("git remote set-url origin '{url}'"), repo);
+}
+
+/// All remote tracking branches and tags of the repository at `path`, with what they point to.
+fn fetched_refs(path: &Path) -> Vec<(String, String)> {
+    let repo = gix::open(path).unwrap();
+    let refs = repo.references().unwrap();
+    let mut out: Vec<_> = refs
+        .remote_branches()
+        .unwrap()
+        .chain(refs.tags().unwrap())
+        .map(Result::unwrap)
+        // Tags are peeled too, which doesn't matter for comparing them.
+        .map(|mut r| {
+            let id = r.peel_to_id().unwrap().to_string();
+            (r.name().as_bstr().to_string(), id)
+        })
+        .collect();
+    out.sort();
+    out
+}
+
+async fn fetch_with_cli(repo: &Path) {
+    gitbutler_git::fetch(
+        repo,
+        TokioExecutor,
+        "origin",
+        None::<fn(String) -> std::future::Ready<Option<String>>>,
+    )
+    .await
+    .unwrap();
+}
+
+fn fetch_in_process(repo: &Path) {
+    gitbutler_git::in_process::fetch(
+        repo,
+        "origin",
+        None::<fn(String) -> Option<String>>,
+        gix::progress::Discard,
+        &AtomicBool::new(false),
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn file_remote_matches_cli() {
+    let tmp = remote_and_clones();
+    let url = format!("file://{}", tmp.path().join("remote.git").display());
+    let (cli, gix) = (tmp.path().join("cli"), tmp.path().join("gix"));
+    set_url(&cli, &url);
+    set_url(&gix, &url);
+
+    fetch_with_cli(&cli).await;
+    fetch_in_process(&gix);
+
+    let refs = fetched_refs(&gix);
+    assert!(
+        refs.iter()
+            .any(|(name, _)| name == "refs/remotes/origin/feature"),
+        "new branches are fetched: {refs:?}"
+    );
+    assert_eq!(refs, fetched_refs(&cli));
+}
+
+#[tokio::test]
+async fn http_remote_matches_cli() {
+    let tmp = remote_and_clones();
+    let url = format!(
+        "http://{}/remote.git",
+        http::serve(tmp.path().to_owned(), None)
+    );
+    let (cli, gix) = (tmp.path().join("cli"), tmp.path().join("gix"));
+    set_url(&cli, &url);
+    set_url(&gix, &url);
+
+    fetch_with_cli(&cli).await;
+    fetch_in_process(&gix);
+
+    let refs = fetched_refs(&gix);
+    assert!(
+        refs.iter().any(|(name, _)| name == "refs/tags/v1"),
+        "tags are fetched: {refs:?}"
+    );
+    assert_eq!(refs, fetched_refs(&cli));
+}
+
+#[test]
+fn missing_remote() {
+    let tmp = remote_and_clones();
+    let err = gitbutler_git::in_process::fetch(
+        tmp.path().join("gix"),
+        "missing",
+        None::<fn(String) -> Option<String>>,
+        gix::progress::Discard,
+        &AtomicBool::new(false),
+    )
+    .unwrap_err();
+    assert!(
+        matches!(err, gitbutler_git::Error::NoSuchRemote(ref name, _) if name == "missing"),
+        "{err:?}"
+    );
+}
+
+#[test]
+fn prompts_for_credentials_the_helpers_dont_have() {
+    let tmp = remote_and_clones();
+    // The base64 encoding of `user:secret`.
+    let addr = http::serve(tmp.path().to_owned(), Some("Basic dXNlcjpzZWNyZXQ="));
+    let gix = tmp.path().join("gix");
+    set_url(&gix, &format!("http://{addr}/remote.git"));
+    // Reset the helpers of the user's configuration, so only the prompt can answer.
+    invoke_bash_at_dir("git config credential.helper ''", &gix);
+
+    let mut prompts = Vec::new();
+    gitbutler_git::in_process::fetch(
+        &gix,
+        "origin",
+        Some(|prompt: String| {
+            let answer = if prompt.starts_with("Username") {
+                "user"
+            } else {
+                "secret"
+            };
+            prompts.push(prompt);
+            Some(answer.to_owned())
+        }),
+        gix::progress::Discard,
+        &AtomicBool::new(false),
+    )
+    .unwrap();
+    assert_eq!(
+        prompts,
+        [
+            format!("Username for 'http://{addr}': "),
+            format!("Password for 'http://{addr}': ")
+        ],
+        "the port is part of the prompt, like it is in `git`"
+    );
+    let refs = fetched_refs(&gix);
+    assert!(
+        refs.iter()
+            .any(|(name, _)| name == "refs/remotes/origin/feature"),
+        "the answers were used to fetch: {refs:?}"
+    );
+
+    let err = gitbutler_git::in_process::fetch(
+        &gix,
+        "origin",
+        Some(|_prompt: String| None),
+        gix::progress::Discard,
+        &AtomicBool::new(false),
+    )
+    .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            gitbutler_git::Error::Backend(gitbutler_git::in_process::Error::NeedsAuthorization(
+                ref prompt
+            )) if *prompt == format!("Username for 'http://{addr}': ")
+        ),
+        "unanswered prompts are reported: {err:?}"
+    );
+}
+
+#[test]
+fn backend_is_configured_per_repository() {
+    let tmp = remote_and_clones();
+    let path: PathBuf = tmp.path().join("gix");
+    let backend = || FetchBackend::from_repo(&gix::open(&path).unwrap());
+    assert_eq!(backend(), FetchBackend::Cli, "the CLI is the default");
+
+    invoke_bash_at_dir("git config gitbutler.fetchBackend gix", &path);
+    assert_eq!(backend(), FetchBackend::InProcess);
+
+    invoke_bash_at_dir("git config gitbutler.fetchBackend unknown", &path);
+    assert_eq!(backend(), FetchBackend::Cli, "unknown values are ignored");
+}
+
+/// A minimal server for the smart HTTP protocol, backed by `git http-backend`.
+mod http {
+    use std::{
+        collections::HashMap,
+        io::{self, BufRead, BufReader, Read, Write},
+        net::{SocketAddr, TcpListener, TcpStream},
+        path::{Path, PathBuf},
+        process::{Command, Stdio},
+    };
+
+    /// Serve all repositories in `root` on a local port, and return its address.
+    ///
+    /// With `authorization`, requests must send it as their `Authorization` header.
+    pub fn serve(root: PathBuf, authorization: Option<&'static str>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { break };
+                let root = root.clone();
+                std::thread::spawn(move || handle(stream, &root, authorization));
+            }
+        });
+        addr
+    }
+
+    /// Answer a single request on `stream` and close the connection.
+    fn handle(stream: TcpStream, root: &Path, authorization: Option<&str>) -> io::Result<()> {
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        let mut parts = request_line.split_whitespace();
+        let (method, target) = (
+            parts.next().unwrap_or_default(),
+            parts.next().unwrap_or_default(),
+        );
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+        let mut headers = HashMap::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line)?;
+            let Some((name, value)) = line.trim_end().split_once(':') else {
+                break;
+            };
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_owned());
+        }
+        let body = match headers.get("content-length") {
+            Some(len) => {
+                let mut body = vec![0; len.parse().map_err(io::Error::other)?];
+                reader.read_exact(&mut body)?;
+                body
+            }
+            None if headers
+                .get("transfer-encoding")
+                .is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked")) =>
+            {
+                read_chunked(&mut reader)?
+            }
+            None => Vec::new(),
+        };
+
+        let header = |name: &str| headers.get(name).map(String::as_str).unwrap_or_default();
+        if let Some(authorization) = authorization
+            && header("authorization") != authorization
+        {
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 401 Unauthorized\r\nWWW-Authenticate: Basic realm=\"test\"\r\n\
+                 Content-Length: 0\r\nConnection: close\r\n\r\n"
+            )?;
+            return stream.flush();
+        }
+        let mut child = Command::new(gix::path::env::exe_invocation())
+            .arg("http-backend")
+            .env("GIT_PROJECT_ROOT", root)
+            .env("GIT_HTTP_EXPORT_ALL", "1")
+            .env("REQUEST_METHOD", method)
+            .env("PATH_INFO", path)
+            .env("QUERY_STRING", query)
+            .env("CONTENT_TYPE", header("content-type"))
+            .env("CONTENT_LENGTH", body.len().to_string())
+            .env("HTTP_CONTENT_ENCODING", header("content-encoding"))
+            .env("HTTP_GIT_PROTOCOL", header("git-protocol"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        child.stdin.take().expect("piped").write_all(&body)?;
+        let output = child.wait_with_output()?;
+
+        // Turn the CGI response into an HTTP response.
+        let cgi = output.stdout;
+        let (cgi_headers, body) = match cgi.windows(4).position(|w| w == b"\r\n\r\n") {
+            Some(pos) => (&cgi[..pos], &cgi[pos + 4..]),
+            None => match cgi.windows(2).position(|w| w == b"\n\n") {
+                Some(pos) => (&cgi[..pos], &cgi[pos + 2..]),
+                None => (&cgi[..], &[][..]),
+            },
+        };
+        let mut status = "200 OK".to_owned();
+        let mut response_headers = String::new();
+        for line in String::from_utf8_lossy(cgi_headers).lines() {
+            match line.split_once(':') {
+                Some((name, value)) if name.eq_ignore_ascii_case("status") => {
+                    status = value.trim().to_owned();
+                }
+                Some(_) => {
+                    response_headers.push_str(line);
+                    response_headers.push_str("\r\n");
+                }
+                None => {}
+            }
+        }
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 {status}\r\n{response_headers}Content-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        )?;
+        stream.write_all(body)?;
+        stream.flush()
+    }
+
+    fn read_chunked(reader: &mut impl BufRead) -> io::Result<Vec<u8>> {
+        let mut body = Vec::new();
+        loop {
+            let mut size = String::new();
+            reader.read_line(&mut size)?;
+            let size = size.trim().split(';').next().unwrap_or_default();
+            let size = usize::from_str_radix(size, 16).map_err(io::Error::other)?;
+            // Each chunk is followed by a line break, and the last one has no data.
+            let mut chunk = vec![0; size + 2];
+            reader.read_exact(&mut chunk)?;
+            if size == 0 {
+                return Ok(body);
+            }
+            body.extend_from_slice(&chunk[..size]);
+        }
+    }
+}
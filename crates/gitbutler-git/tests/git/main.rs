@@ -1,3 +1,5 @@
+mod fetch;
+mod push;
 mod refspec;
 
 #[cfg(test)]
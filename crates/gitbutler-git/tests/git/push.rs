@@ -0,0 +1,225 @@
+use gitbutler_git::{RefSpec, RefUpdate, RefUpdateKind, parse_porcelain};
+
+mod porcelain {
+    use super::*;
+
+    #[test]
+    fn parse_all_flags() {
+        let updates = parse_porcelain(
+            "To /tmp/remote.git\n \
+             \trefs/heads/a:refs/heads/a\t1a2b3c4..5d6e7f8\n\
+             +\trefs/heads/b:refs/heads/b\t1a2b3c4...5d6e7f8 (forced update)\n\
+             -\t:refs/heads/c\t[deleted]\n\
+             *\trefs/heads/d:refs/heads/d\t[new branch]\n\
+             =\trefs/heads/e:refs/heads/e\t[up to date]\n\
+             !\trefs/heads/f:refs/heads/f\t[rejected] (non-fast-forward)\n\
+             Done\n",
+        );
+        assert_eq!(
+            updates.iter().map(|update| update.kind).collect::<Vec<_>>(),
+            [
+                RefUpdateKind::FastForward,
+                RefUpdateKind::Forced,
+                RefUpdateKind::Deleted,
+                RefUpdateKind::Created,
+                RefUpdateKind::UpToDate,
+                RefUpdateKind::Rejected,
+            ]
+        );
+        assert_eq!(
+            updates[1],
+            RefUpdate {
+                kind: RefUpdateKind::Forced,
+                source: Some("refs/heads/b".into()),
+                destination: "refs/heads/b".into(),
+                summary: "1a2b3c4...5d6e7f8".into(),
+                reason: Some("forced update".into()),
+            }
+        );
+        assert_eq!(updates[2].source, None, "deletions have no source");
+        assert_eq!(updates[3].reason, None);
+        assert!(updates[5].is_non_fast_forward());
+        assert_eq!(
+            updates[5].to_string(),
+            "refs/heads/f [rejected] (non-fast-forward)"
+        );
+    }
+
+    #[test]
+    fn classify_rejections() {
+        let updates = parse_porcelain(
+            "!\trefs/heads/a:refs/heads/a\t[rejected] (atomic push failed)\n\
+             !\trefs/heads/b:refs/heads/b\t[rejected] (stale info)\n\
+             !\trefs/heads/c:refs/heads/c\t[rejected] (fetch first)\n\
+             !\trefs/heads/d:refs/heads/d\t[remote rejected] (pre-receive hook declined)\n",
+        );
+        assert!(updates[0].is_atomic_failure());
+        assert!(updates[1].is_stale());
+        assert!(!updates[1].is_non_fast_forward());
+        assert!(updates[2].is_non_fast_forward());
+        assert!(updates[3].is_rejected());
+        assert!(!updates[3].is_non_fast_forward() && !updates[3].is_stale());
+        assert_eq!(
+            updates[3].reason.as_deref(),
+            Some("pre-receive hook declined")
+        );
+    }
+
+    #[test]
+    fn ignore_unknown_lines() {
+        assert_eq!(
+            parse_porcelain("To origin\nremote: hello\tthere\nDone\n"),
+            []
+        );
+    }
+}
+
+mod atomic {
+    use std::path::Path;
+
+    use but_testsupport::{gix_testtools::tempfile, invoke_bash_at_dir};
+    use gitbutler_git::tokio::TokioExecutor;
+
+    use super::*;
+
+    /// A repository `local` with branches `a` and `b` pushed to the bare `remote`, after which
+    /// `b` was rewritten locally.
+    fn local_and_remote() -> tempfile::TempDir {
+        let tmp = tempfile::TempDir::new().unwrap();
+        invoke_bash_at_dir(
+            r#"
+git init -q --bare remote.git
+git init -q local && cd local
+git remote add origin ../remote.git
+git commit -q --allow-empty -m base
+git branch a && git branch b
+git push -q origin a b
+git checkout -q a && git commit -q --allow-empty -m "on a"
+git checkout -q b && git commit -q --amend --allow-empty -m "rewritten base"
+"#,
+            tmp.path(),
+        );
+        tmp
+    }
+
+    fn specs(refs: &[&str]) -> Vec<RefSpec> {
+        refs.iter()
+            .map(|name| RefSpec::parse(format!("refs/heads/{name}:refs/heads/{name}")).unwrap())
+            .collect()
+    }
+
+    fn remote_ref(tmp: &Path, name: &str) -> String {
+        let repo = gix::open(tmp.join("remote.git")).unwrap();
+        repo.find_reference(name).unwrap().id().to_string()
+    }
+
+    #[tokio::test]
+    async fn updates_all_refs_or_none() {
+        let tmp = local_and_remote();
+        let local = tmp.path().join("local");
+        let (a_before, b_before) = (remote_ref(tmp.path(), "a"), remote_ref(tmp.path(), "b"));
+
+        let err = gitbutler_git::push_atomic(
+            &local,
+            TokioExecutor,
+            "origin",
+            specs(&["a", "b"]),
+            false,
+            false,
+            None::<fn(String) -> std::future::Ready<Option<String>>>,
+            vec![],
+        )
+        .await
+        .unwrap_err();
+        let gitbutler_git::Error::PushRejected(updates, _) = &err else {
+            panic!("expected a rejected push, got {err:?}");
+        };
+        assert!(updates[0].is_atomic_failure(), "{updates:?}");
+        assert!(updates[1].is_non_fast_forward(), "{updates:?}");
+        assert_eq!(
+            (remote_ref(tmp.path(), "a"), remote_ref(tmp.path(), "b")),
+            (a_before, b_before),
+            "nothing was pushed"
+        );
+
+        let updates = gitbutler_git::push_atomic(
+            &local,
+            TokioExecutor,
+            "origin",
+            specs(&["a", "b"]),
+            true,
+            false,
+            None::<fn(String) -> std::future::Ready<Option<String>>>,
+            vec![],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            updates.iter().map(|update| update.kind).collect::<Vec<_>>(),
+            [RefUpdateKind::FastForward, RefUpdateKind::Forced]
+        );
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_a_regular_push_without_remote_support() {
+        let tmp = local_and_remote();
+        invoke_bash_at_dir(
+            "git -C remote.git config receive.advertiseAtomic false",
+            tmp.path(),
+        );
+        let local = tmp.path().join("local");
+
+        let err = gitbutler_git::push_atomic(
+            &local,
+            TokioExecutor,
+            "origin",
+            specs(&["a", "b"]),
+            false,
+            false,
+            None::<fn(String) -> std::future::Ready<Option<String>>>,
+            vec![],
+        )
+        .await
+        .unwrap_err();
+        let gitbutler_git::Error::PushPartlyRejected(updates, _) = &err else {
+            panic!("expected a partly rejected push, got {err:?}");
+        };
+        assert_eq!(updates[0].kind, RefUpdateKind::FastForward, "{updates:?}");
+        assert!(updates[1].is_non_fast_forward(), "{updates:?}");
+        assert!(
+            err.to_string().contains("other refs may have been updated"),
+            "{err}"
+        );
+        let repo = gix::open(&local).unwrap();
+        assert_eq!(
+            remote_ref(tmp.path(), "a"),
+            repo.find_reference("a").unwrap().id().to_string(),
+            "a was pushed even though b was rejected"
+        );
+
+        let updates = gitbutler_git::push_atomic(
+            &local,
+            TokioExecutor,
+            "origin",
+            specs(&["a", "b"]),
+            true,
+            false,
+            None::<fn(String) -> std::future::Ready<Option<String>>>,
+            vec![],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            updates.iter().map(|update| update.kind).collect::<Vec<_>>(),
+            [RefUpdateKind::UpToDate, RefUpdateKind::Forced]
+        );
+
+        for name in ["a", "b"] {
+            assert_eq!(
+                remote_ref(tmp.path(), name),
+                repo.find_reference(name).unwrap().id().to_string(),
+                "{name} was pushed"
+            );
+        }
+    }
+}
@@ -43,6 +43,12 @@ pub fn read_edit_mode_metadata(ctx: &Context) -> Result<EditModeMetadata> {
     toml::from_str(&edit_mode_metadata).context("Failed to parse edit mode metadata")
 }
 
+/// Return `true` if edit mode metadata was written, whether or not it can be read.
+#[doc(hidden)]
+pub fn has_edit_mode_metadata(ctx: &Context) -> bool {
+    edit_mode_metadata_path(ctx).exists()
+}
+
 #[doc(hidden)]
 pub fn write_edit_mode_metadata(
     ctx: &Context,
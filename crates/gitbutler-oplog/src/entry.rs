@@ -158,6 +158,7 @@ pub enum OperationKind {
     DiscardCommit,
     UnapplyBranch,
     CherryPick,
+    RevertCommit,
     SquashCommit,
     UpdateCommitMessage,
     MoveCommit,
@@ -187,7 +188,9 @@ pub enum OperationKind {
     AutoHandleChangesBefore,
     AutoHandleChangesAfter,
     SplitBranch,
+    SplitCommit,
     CleanWorkspace,
+    RepairWorkspace,
     OnDemandSnapshot,
     Unknown,
 }
@@ -224,11 +227,13 @@ impl OperationKind {
             OperationKind::DiscardChanges => "DISCARD",
             OperationKind::Discard => "DISCARD",
             OperationKind::CleanWorkspace => "CLEAN",
+            OperationKind::RepairWorkspace => "REPAIR",
             OperationKind::OnDemandSnapshot => "SNAPSHOT",
             OperationKind::DiscardLines => "DISCARD_LINES",
             OperationKind::DiscardHunk => "DISCARD_HUNK",
             OperationKind::DiscardFile => "DISCARD_FILE",
             OperationKind::CherryPick => "CHERRY_PICK",
+            OperationKind::RevertCommit => "REVERT",
             OperationKind::MoveBranch => "MOVE_BRANCH",
             OperationKind::TearOffBranch => "UNSTACK_BRANCH",
             OperationKind::MoveCommitFile => "MOVE_FILE",
@@ -238,6 +243,7 @@ impl OperationKind {
             | OperationKind::UpdateDependentBranchDescription
             | OperationKind::UpdateDependentBranchPrNumber => "UPDATE_BRANCH",
             OperationKind::SplitBranch => "SPLIT_BRANCH",
+            OperationKind::SplitCommit => "SPLIT_COMMIT",
             OperationKind::ResolveConflictsAi => "AI_RESOLVE",
             OperationKind::StashIntoBranch
             | OperationKind::SetBaseBranch
@@ -280,6 +286,7 @@ impl OperationKind {
             OperationKind::DiscardCommit => "Discarded commit",
             OperationKind::UnapplyBranch => "Unapplied branch",
             OperationKind::CherryPick => "Cherry-picked commit",
+            OperationKind::RevertCommit => "Reverted commit",
             OperationKind::SquashCommit => "Squashed commit",
             OperationKind::UpdateCommitMessage => "Updated commit message",
             OperationKind::MoveCommit => "Moved commit",
@@ -303,7 +310,9 @@ impl OperationKind {
             OperationKind::AutoHandleChangesBefore => "Handled changes before action",
             OperationKind::AutoHandleChangesAfter => "Handled changes after action",
             OperationKind::SplitBranch => "Split branch",
+            OperationKind::SplitCommit => "Split commit",
             OperationKind::CleanWorkspace => "Cleaned workspace",
+            OperationKind::RepairWorkspace => "Repaired workspace",
             OperationKind::OnDemandSnapshot => "Created snapshot",
             OperationKind::Unknown => "Unknown operation",
         }
@@ -337,6 +346,7 @@ impl OperationKind {
             OperationKind::DiscardCommit => "DiscardCommit",
             OperationKind::UnapplyBranch => "UnapplyBranch",
             OperationKind::CherryPick => "CherryPick",
+            OperationKind::RevertCommit => "RevertCommit",
             OperationKind::SquashCommit => "SquashCommit",
             OperationKind::UpdateCommitMessage => "UpdateCommitMessage",
             OperationKind::MoveCommit => "MoveCommit",
@@ -360,7 +370,9 @@ impl OperationKind {
             OperationKind::AutoHandleChangesBefore => "AutoHandleChangesBefore",
             OperationKind::AutoHandleChangesAfter => "AutoHandleChangesAfter",
             OperationKind::SplitBranch => "SplitBranch",
+            OperationKind::SplitCommit => "SplitCommit",
             OperationKind::CleanWorkspace => "CleanWorkspace",
+            OperationKind::RepairWorkspace => "RepairWorkspace",
             OperationKind::OnDemandSnapshot => "OnDemandSnapshot",
             OperationKind::Unknown => "Unknown",
         }
@@ -394,6 +406,7 @@ impl OperationKind {
             "DiscardCommit" => Self::DiscardCommit,
             "UnapplyBranch" => Self::UnapplyBranch,
             "CherryPick" => Self::CherryPick,
+            "RevertCommit" => Self::RevertCommit,
             "SquashCommit" => Self::SquashCommit,
             "UpdateCommitMessage" => Self::UpdateCommitMessage,
             "MoveCommit" => Self::MoveCommit,
@@ -417,7 +430,9 @@ impl OperationKind {
             "AutoHandleChangesBefore" => Self::AutoHandleChangesBefore,
             "AutoHandleChangesAfter" => Self::AutoHandleChangesAfter,
             "SplitBranch" => Self::SplitBranch,
+            "SplitCommit" => Self::SplitCommit,
             "CleanWorkspace" => Self::CleanWorkspace,
+            "RepairWorkspace" => Self::RepairWorkspace,
             "OnDemandSnapshot" => Self::OnDemandSnapshot,
             "Unknown" => Self::Unknown,
             _ => return None,
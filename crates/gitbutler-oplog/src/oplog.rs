@@ -214,6 +214,12 @@ pub trait OplogExt {
         child_id: Option<gix::ObjectId>,
     ) -> Result<Vec<TreeChange>>;
 
+    /// Returns the diff that restoring the snapshot `sha` would apply to the workspace, from the
+    /// current workspace commit to the state recorded in the snapshot.
+    ///
+    /// Like [`snapshot_diff`](Self::snapshot_diff), this only considers committed branch state.
+    fn restore_diff(&self, sha: gix::ObjectId) -> Result<Vec<TreeChange>>;
+
     /// Gets a specific snapshot by its commit sha.
     fn get_snapshot(&self, sha: gix::ObjectId) -> Result<Snapshot>;
 
@@ -337,6 +343,19 @@ impl OplogExt for Context {
         tree_changes(&repo, Some(before_tree_id), after_tree_id)
     }
 
+    fn restore_diff(&self, sha: gix::ObjectId) -> Result<Vec<TreeChange>> {
+        let repo = self.clone_repo_for_merging()?;
+        let workspace_ref: &gix::refs::FullNameRef = WORKSPACE_REF_NAME.try_into()?;
+        let current_tree_id = repo
+            .find_reference(workspace_ref)?
+            .peel_to_commit()?
+            .tree_id()?
+            .detach();
+        let snapshot_tree_id = tree_from_applied_vbranches(&repo, sha)?;
+
+        tree_changes(&repo, Some(current_tree_id), snapshot_tree_id)
+    }
+
     /// Gets the sha of the last snapshot commit if present.
     fn oplog_head(&self) -> Result<Option<gix::ObjectId>> {
         let oplog_state = OplogHandle::new(&self.project_data_dir());
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     io::Write,
     path::{Path, PathBuf},
     process::Stdio,
@@ -234,41 +235,16 @@ pub fn pre_push(
     remote_tracking_branch: &gitbutler_reference::RemoteRefname,
     run_husky_hooks: bool,
 ) -> Result<HookResult> {
-    let hooks_dir = get_hooks_dir(repo);
-    let hooks_path = hooks_dir.join("pre-push");
-    let husky_path = run_husky_hooks.then(|| {
-        repo.workdir()
-            .map(|workdir| workdir.join(".husky").join("pre-push"))
-    });
-
-    // Check for hook in .git/hooks/pre-push first, then ../.husky/pre-push
-    let hook_path = hooks_path
-        .exists()
-        .then_some(hooks_path)
-        .filter(|path| run_husky_hooks || !path_is_in_husky_dir(repo, path))
-        .or_else(|| husky_path.flatten().filter(|path| path.exists()));
-
-    let Some(hook_path) = hook_path else {
+    let Some(hook_path) = find_hook(repo, "pre-push", run_husky_hooks) else {
         return Ok(HookResult::NotConfigured);
     };
 
     // Execute the pre-push hook with remote name and URL as arguments
-    let mut child = std::process::Command::from({
-        let mut prep = gix::command::prepare(&hook_path);
-        if cfg!(windows) {
-            prep.use_shell = true;
-            prep.allow_manual_arg_splitting = false;
-            // Need unix separators for the unix bash to not swallow the backslash!
-            let with_slashes_for_bash = gix::path::to_unix_separators_on_windows(
-                gix::path::os_str_into_bstr(&prep.command)?,
-            );
-            prep.command = gix::path::from_bstring(with_slashes_for_bash.into_owned()).into();
-        }
-        prep.arg(remote_name).arg(remote_url)
-    })
-    .current_dir(repo.workdir().unwrap_or(repo.git_dir()))
-    .stdin(Stdio::piped())
-    .spawn()?;
+    let mut child =
+        std::process::Command::from(prepare_hook(&hook_path)?.arg(remote_name).arg(remote_url))
+            .current_dir(repo.workdir().unwrap_or(repo.git_dir()))
+            .stdin(Stdio::piped())
+            .spawn()?;
 
     {
         let remote_commit = repo
@@ -293,6 +269,87 @@ pub fn pre_push(
         }
     }
 
+    wait_for_hook(child)
+}
+
+/// Run the `post-rewrite` hook like Git does after a rebase, passing one `<old-id> <new-id>` line
+/// per entry of `rewritten` on standard input.
+///
+/// The hook is always told that commits were rebased, even for amends, as GitButler rebases all
+/// descendants of a rewritten commit, unlike `git commit --amend`.
+///
+/// `rewritten` maps each original commit to the commit that replaced it, as returned by
+/// `RevisionHistory::commit_mappings()`. Nothing is run if it is empty.
+/// Like in Git, a failing hook can't undo the rewrite, so callers typically only report it.
+pub fn post_rewrite(
+    repo: &gix::Repository,
+    rewritten: &BTreeMap<gix::ObjectId, gix::ObjectId>,
+    run_husky_hooks: bool,
+) -> Result<HookResult> {
+    if rewritten.is_empty() {
+        return Ok(HookResult::NotConfigured);
+    }
+    let Some(hook_path) = find_hook(repo, "post-rewrite", run_husky_hooks) else {
+        return Ok(HookResult::NotConfigured);
+    };
+
+    let mut child = std::process::Command::from(prepare_hook(&hook_path)?.arg(REWRITE_COMMAND))
+        .current_dir(repo.workdir().unwrap_or(repo.git_dir()))
+        .stdin(Stdio::piped())
+        .spawn()?;
+
+    {
+        let stdin = child.stdin.as_mut().expect("configured");
+        let input: String = rewritten
+            .iter()
+            .map(|(old, new)| format!("{old} {new}\n"))
+            .collect();
+        if let Err(err) = stdin.write_all(input.as_bytes())
+            && err.kind() != std::io::ErrorKind::BrokenPipe
+        {
+            return Err(err.into());
+        }
+    }
+
+    wait_for_hook(child)
+}
+
+/// The command passed to the `post-rewrite` hook, and used to look up `notes.rewrite.<command>`.
+pub(crate) const REWRITE_COMMAND: &str = "rebase";
+
+/// Find the executable of the hook called `name`, looking into the hooks directory of `repo`
+/// first, and into `.husky` in the worktree if `run_husky_hooks` is set.
+fn find_hook(repo: &gix::Repository, name: &str, run_husky_hooks: bool) -> Option<PathBuf> {
+    let hooks_path = get_hooks_dir(repo).join(name);
+    let husky_path = run_husky_hooks
+        .then(|| {
+            repo.workdir()
+                .map(|workdir| workdir.join(".husky").join(name))
+        })
+        .flatten();
+
+    // Check for hook in .git/hooks/<name> first, then ../.husky/<name>
+    hooks_path
+        .exists()
+        .then_some(hooks_path)
+        .filter(|path| run_husky_hooks || !path_is_in_husky_dir(repo, path))
+        .or_else(|| husky_path.filter(|path| path.exists()))
+}
+
+fn prepare_hook(hook_path: &Path) -> Result<gix::command::Prepare> {
+    let mut prep = gix::command::prepare(hook_path);
+    if cfg!(windows) {
+        prep.use_shell = true;
+        prep.allow_manual_arg_splitting = false;
+        // Need unix separators for the unix bash to not swallow the backslash!
+        let with_slashes_for_bash =
+            gix::path::to_unix_separators_on_windows(gix::path::os_str_into_bstr(&prep.command)?);
+        prep.command = gix::path::from_bstring(with_slashes_for_bash.into_owned()).into();
+    }
+    Ok(prep)
+}
+
+fn wait_for_hook(child: std::process::Child) -> Result<HookResult> {
     let output = child.wait_with_output()?;
     if output.status.success() {
         Ok(HookResult::Success)
@@ -13,6 +13,8 @@ pub use repository_ext::{commit_with_signature_gix, commit_without_signature_gix
 
 pub mod hooks;
 pub mod managed_hooks;
+pub mod notes;
+pub mod patch_series;
 mod remote;
 
 pub const GITBUTLER_COMMIT_AUTHOR_NAME: &str = "GitButler";
@@ -0,0 +1,65 @@
+//! Keep git notes attached to commits that GitButler rewrites.
+
+use std::{collections::BTreeMap, io::Write as _, process::Stdio};
+
+use anyhow::{Context as _, Result, bail};
+use bstr::ByteSlice as _;
+
+use crate::hooks::REWRITE_COMMAND;
+
+/// Copy the notes of each original commit in `rewritten` to the commit that replaced it, like
+/// Git does after `git rebase`.
+///
+/// As in Git this is opt-in: only the notes refs matched by `notes.rewriteRef` (or
+/// `GIT_NOTES_REWRITE_REF`) are copied, for instance `refs/notes/*` for all of them, and
+/// `notes.rewrite.rebase` and `notes.rewriteMode` are respected.
+/// Return `true` if notes rewriting is configured and notes were copied.
+pub fn copy_for_rewrite(
+    repo: &gix::Repository,
+    rewritten: &BTreeMap<gix::ObjectId, gix::ObjectId>,
+) -> Result<bool> {
+    if rewritten.is_empty() || !is_rewrite_configured(repo) {
+        return Ok(false);
+    }
+
+    let mut child =
+        std::process::Command::from(gix::command::prepare(gix::path::env::exe_invocation()))
+            .current_dir(repo.workdir().unwrap_or(repo.git_dir()))
+            .arg("notes")
+            .arg("copy")
+            .arg(format!("--for-rewrite={REWRITE_COMMAND}"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run git notes copy")?;
+    {
+        let stdin = child.stdin.as_mut().expect("configured");
+        for (old, new) in rewritten {
+            writeln!(stdin, "{old} {new}")?;
+        }
+    }
+    let output = child.wait_with_output()?;
+
+    tracing::debug!(
+        stderr = %output.stderr.to_str_lossy(),
+        "git notes copy --for-rewrite={REWRITE_COMMAND}"
+    );
+
+    if output.status.success() {
+        Ok(true)
+    } else {
+        bail!(
+            "Failed to copy notes to rewritten commits\n\n{}",
+            output.stderr.to_str_lossy()
+        )
+    }
+}
+
+fn is_rewrite_configured(repo: &gix::Repository) -> bool {
+    std::env::var_os("GIT_NOTES_REWRITE_REF").is_some_and(|refs| !refs.is_empty())
+        || repo
+            .config_snapshot()
+            .string("notes.rewriteRef")
+            .is_some_and(|refs| !refs.is_empty())
+}
@@ -0,0 +1,330 @@
+//! Exchange commits as series of patches in mbox format, like `git format-patch` and `git am`.
+//!
+//! Both directions use `git` itself, so the patches are exactly what mailing-list driven projects
+//! expect, and so are the patches that can be read.
+
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use anyhow::{Context as _, Result, bail};
+use bstr::{BString, ByteSlice as _};
+use but_core::RepositoryExt as _;
+
+/// Write each commit in `base..branch` as numbered patch in mbox format into `output_dir`,
+/// oldest first, and return the paths of the written files in order.
+///
+/// If `cover_letter` is `Some(description)`, a cover letter is written first. Its subject is the
+/// first line of `description` and its body the rest, or placeholders to fill in if it's empty.
+/// The description Git may have for `branch` is never used.
+pub fn format_patch(
+    repo: &gix::Repository,
+    base: gix::ObjectId,
+    branch: &gix::refs::FullNameRef,
+    cover_letter: Option<&str>,
+    output_dir: &Path,
+) -> Result<Vec<PathBuf>> {
+    let mut cmd = git(repo);
+    cmd.arg("format-patch").arg("--numbered");
+    if cover_letter.is_some() {
+        cmd.arg("--cover-letter")
+            .arg("--cover-from-description=none");
+    }
+    let output = cmd
+        .arg("--output-directory")
+        .arg(output_dir)
+        .arg(format!("{base}..{branch}", branch = branch.as_bstr()))
+        .output()
+        .context("Failed to run git format-patch")?;
+    if !output.status.success() {
+        bail!(
+            "Failed to format patches\n\n{}",
+            output.stderr.to_str_lossy()
+        );
+    }
+
+    let files: Vec<_> = output
+        .stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let path = gix::path::from_bstr(line.as_bstr()).into_owned();
+            repo.workdir()
+                .filter(|_| path.is_relative())
+                .map_or_else(|| path.clone(), |workdir| workdir.join(&path))
+        })
+        .collect();
+    if let Some(description) = cover_letter
+        && let Some(cover_letter_path) = files.first()
+    {
+        describe_cover_letter(cover_letter_path, description)?;
+    }
+    Ok(files)
+}
+
+/// Replace the subject and body placeholders of the cover letter at `path` with the first line
+/// of `description` and the rest of it, respectively.
+fn describe_cover_letter(path: &Path, description: &str) -> Result<()> {
+    const SUBJECT_PLACEHOLDER: &str = "*** SUBJECT HERE ***";
+    const BODY_PLACEHOLDER: &str = "*** BLURB HERE ***";
+
+    let description = description.trim();
+    if description.is_empty() {
+        return Ok(());
+    }
+    let (subject, body) = description
+        .split_once('\n')
+        .map_or((description, ""), |(subject, body)| {
+            (subject.trim_end(), body.trim())
+        });
+    let cover_letter = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read cover letter at '{}'", path.display()))?;
+    let cover_letter = cover_letter
+        .replacen(SUBJECT_PLACEHOLDER, subject, 1)
+        .replacen(BODY_PLACEHOLDER, body, 1);
+    std::fs::write(path, cover_letter)
+        .with_context(|| format!("Failed to write cover letter at '{}'", path.display()))?;
+    Ok(())
+}
+
+/// A patch read from a mailbox by [`commits_from_mailbox()`].
+#[derive(Debug, Clone)]
+pub struct MailboxCommit {
+    /// The commit with the changes, author and message of the patch.
+    ///
+    /// Its parent is the commit its patch was applied to, which isn't connected to any reference.
+    /// For [`three_way`](Self::three_way) patches, that's a parentless commit with just the
+    /// files the patch was created against, so the returned commits don't form a chain that
+    /// leads back to `onto`. They are only meant to be cherry-picked, which leaves the
+    /// parentless commits behind to be garbage-collected.
+    pub commit_id: gix::ObjectId,
+    /// The subject of the patch, without the `[PATCH n/m]` prefix.
+    pub subject: String,
+    /// `true` if the patch didn't apply to the tree of the previous commit and was applied to
+    /// the version of the files it was created against instead.
+    /// Cherry-picking such a commit may cause conflicts.
+    pub three_way: bool,
+}
+
+/// Turn each patch in the mbox file at `mailbox` into a commit, applying the first one to the
+/// tree of `onto` and all others on top of the previous one.
+///
+/// A patch that doesn't apply is instead applied to the version of the files it was created
+/// against, which has to be present in `repo`. This allows to cherry-pick all returned commits
+/// in order, with conflicts being recorded in conflicted commits, just like `git am --3way` would
+/// stop on them.
+pub fn commits_from_mailbox(
+    repo: &gix::Repository,
+    mailbox: &Path,
+    onto: gix::ObjectId,
+) -> Result<Vec<MailboxCommit>> {
+    let tmp = tempfile::tempdir()?;
+    let mails_dir = tmp.path().join("mails");
+    std::fs::create_dir(&mails_dir)?;
+    run(git(repo)
+        .arg("mailsplit")
+        .arg(format!("-o{}", mails_dir.display()))
+        .arg(mailbox))
+    .with_context(|| format!("Failed to read mailbox at '{}'", mailbox.display()))?;
+
+    let mut mails: Vec<_> = std::fs::read_dir(&mails_dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<_>>()?;
+    mails.sort();
+    if mails.is_empty() {
+        bail!("No patches found in '{}'", mailbox.display());
+    }
+
+    let (_, committer) = repo.commit_signatures()?;
+    let mut parent = onto;
+    let mut commits = Vec::with_capacity(mails.len());
+    for (idx, mail) in mails.iter().enumerate() {
+        let msg_path = tmp.path().join("msg");
+        let patch_path = tmp.path().join("patch");
+        let info = run(git(repo)
+            .arg("mailinfo")
+            .arg(&msg_path)
+            .arg(&patch_path)
+            .stdin(std::fs::File::open(mail)?))
+        .with_context(|| format!("Failed to read patch {}", idx + 1))?;
+        let info = MailInfo::parse(info.as_ref())?;
+        if std::fs::metadata(&patch_path)?.len() == 0 {
+            bail!("Patch {} '{}' has no changes", idx + 1, info.subject);
+        }
+
+        let parent_tree = repo.find_commit(parent)?.tree_id()?.detach();
+        let (commit_parent, tree, three_way) =
+            match apply_to_tree(repo, tmp.path(), parent_tree, &patch_path) {
+                Ok(tree) => (parent, tree, false),
+                Err(err) => {
+                    let (preimage_commit, tree) = apply_to_preimage(
+                        repo,
+                        tmp.path(),
+                        &patch_path,
+                        &committer,
+                    )
+                    .with_context(|| {
+                        format!("Patch {} '{}' does not apply: {err}", idx + 1, info.subject)
+                    })?;
+                    (preimage_commit, tree, true)
+                }
+            };
+
+        let mut message = BString::from(info.subject.as_str());
+        let body = std::fs::read(&msg_path)?;
+        let body = body.trim();
+        if !body.is_empty() {
+            message.extend_from_slice(b"\n\n");
+            message.extend_from_slice(body);
+        }
+        message.push(b'\n');
+
+        let commit_id = repo
+            .write_object(&gix::objs::Commit {
+                tree,
+                parents: [commit_parent].into(),
+                author: gix::actor::Signature {
+                    name: info.author.into(),
+                    email: info.email.into(),
+                    time: info.time.unwrap_or(committer.time),
+                },
+                committer: committer.clone(),
+                encoding: None,
+                message,
+                extra_headers: Vec::new(),
+            })?
+            .detach();
+        commits.push(MailboxCommit {
+            commit_id,
+            subject: info.subject,
+            three_way,
+        });
+        parent = commit_id;
+    }
+    Ok(commits)
+}
+
+/// What `git mailinfo` extracted from the headers of a mail.
+struct MailInfo {
+    author: String,
+    email: String,
+    subject: String,
+    time: Option<gix::date::Time>,
+}
+
+impl MailInfo {
+    fn parse(info: &[u8]) -> Result<Self> {
+        let mut author = None;
+        let mut email = None;
+        let mut subject = None;
+        let mut time = None;
+        for line in info.lines() {
+            let Some((key, value)) = line.split_once_str(": ") else {
+                continue;
+            };
+            let value = value.to_str_lossy().into_owned();
+            match key {
+                b"Author" => author = Some(value),
+                b"Email" => email = Some(value),
+                b"Subject" => subject = Some(value),
+                b"Date" => time = gix::date::parse(&value, None).ok(),
+                _ => {}
+            }
+        }
+        match (author, email, subject) {
+            (Some(author), Some(email), Some(subject)) => Ok(MailInfo {
+                author,
+                email,
+                subject,
+                time,
+            }),
+            _ => bail!("The patch lacks an author or subject"),
+        }
+    }
+}
+
+/// Apply the patch at `patch_path` to `tree` and return the resulting tree, using an index
+/// in `tmp_dir` to keep the index of the repository untouched.
+fn apply_to_tree(
+    repo: &gix::Repository,
+    tmp_dir: &Path,
+    tree: gix::ObjectId,
+    patch_path: &Path,
+) -> Result<gix::ObjectId> {
+    let index_path = tmp_dir.join("index");
+    let with_index = |args: &[&OsStr]| {
+        let mut cmd = git(repo);
+        cmd.env("GIT_INDEX_FILE", &index_path).args(args);
+        run(&mut cmd)
+    };
+    with_index(&["read-tree".as_ref(), tree.to_string().as_ref()])?;
+    with_index(&[
+        "apply".as_ref(),
+        "--cached".as_ref(),
+        patch_path.as_os_str(),
+    ])?;
+    let tree = with_index(&["write-tree".as_ref()])?;
+    Ok(gix::ObjectId::from_hex(tree.trim())?)
+}
+
+/// Apply the patch at `patch_path` to the files it was created against, and return a commit
+/// with these files along with the tree of the patch applied to them.
+fn apply_to_preimage(
+    repo: &gix::Repository,
+    tmp_dir: &Path,
+    patch_path: &Path,
+    committer: &gix::actor::Signature,
+) -> Result<(gix::ObjectId, gix::ObjectId)> {
+    let preimage = preimage_tree(repo, tmp_dir, patch_path)
+        .context("The files it was created against are unknown")?;
+    let tree = apply_to_tree(repo, tmp_dir, preimage, patch_path)?;
+    let preimage_commit = repo
+        .write_object(&gix::objs::Commit {
+            tree: preimage,
+            parents: Default::default(),
+            author: committer.clone(),
+            committer: committer.clone(),
+            encoding: None,
+            message: "preimage".into(),
+            extra_headers: Vec::new(),
+        })?
+        .detach();
+    Ok((preimage_commit, tree))
+}
+
+/// Return a tree with the files that the patch at `patch_path` was created against, as far as
+/// they are present in `repo`.
+fn preimage_tree(
+    repo: &gix::Repository,
+    tmp_dir: &Path,
+    patch_path: &Path,
+) -> Result<gix::ObjectId> {
+    let index_path = tmp_dir.join("preimage-index");
+    run(git(repo)
+        .arg("apply")
+        .arg(format!("--build-fake-ancestor={}", index_path.display()))
+        .arg(patch_path))?;
+    let tree = run(git(repo)
+        .env("GIT_INDEX_FILE", &index_path)
+        .arg("write-tree"))?;
+    Ok(gix::ObjectId::from_hex(tree.trim())?)
+}
+
+fn git(repo: &gix::Repository) -> Command {
+    let mut cmd = Command::from(gix::command::prepare(gix::path::env::exe_invocation()));
+    cmd.current_dir(repo.workdir().unwrap_or(repo.git_dir()))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    cmd
+}
+
+/// Run `cmd` and return its standard output, or fail with its standard error.
+fn run(cmd: &mut Command) -> Result<BString> {
+    let output = cmd.output()?;
+    if !output.status.success() {
+        bail!("{}", output.stderr.to_str_lossy().trim());
+    }
+    Ok(output.stdout.into())
+}
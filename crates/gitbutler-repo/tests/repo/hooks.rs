@@ -1,13 +1,13 @@
-use std::fs;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
+use std::{collections::BTreeMap, fs};
 
 use crate::support::RepoWithOrigin;
 use but_ctx::{Context, RepoOpenMode};
 use but_settings::AppSettings;
 use but_testsupport::open_repo;
-use gitbutler_repo::hooks::{HookResult, pre_commit_with_tree, pre_push};
+use gitbutler_repo::hooks::{HookResult, post_rewrite, pre_commit_with_tree, pre_push};
 
 fn context_for_repo(workdir: &Path) -> Context {
     let project = gitbutler_project::Project::new_for_gitbutler_repo(workdir.to_path_buf());
@@ -267,3 +267,53 @@ fn pre_push_resolves_relative_core_hooks_path_against_workdir() -> anyhow::Resul
     assert!(workdir.join("relative-pre-push-ran").exists());
     Ok(())
 }
+
+#[test]
+fn post_rewrite_hook_receives_rewritten_commits() -> anyhow::Result<()> {
+    let test_project = RepoWithOrigin::default();
+
+    let repo = open_repo(test_project.local_repo.path())?;
+    let hooks_dir = repo.path().join("hooks");
+    fs::create_dir_all(&hooks_dir)?;
+    let hook_path = hooks_dir.join("post-rewrite");
+
+    fs::write(
+        &hook_path,
+        "#!/bin/sh\necho $@ >hook.args\ncat >hook.input\n",
+    )?;
+
+    #[cfg(unix)]
+    fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))?;
+
+    let old = repo.head_id()?.detach();
+    let new = repo.object_hash().null();
+    let result = post_rewrite(&repo, &BTreeMap::from([(old, new)]), true)?;
+    assert_eq!(result, HookResult::Success);
+
+    let workdir = repo.workdir().expect("non-bare");
+    assert_eq!(fs::read_to_string(workdir.join("hook.args"))?, "rebase\n");
+    assert_eq!(
+        fs::read_to_string(workdir.join("hook.input"))?,
+        format!("{old} {new}\n")
+    );
+    Ok(())
+}
+
+#[test]
+fn post_rewrite_hook_is_skipped_without_rewritten_commits() -> anyhow::Result<()> {
+    let test_project = RepoWithOrigin::default();
+
+    let repo = open_repo(test_project.local_repo.path())?;
+    let hooks_dir = repo.path().join("hooks");
+    fs::create_dir_all(&hooks_dir)?;
+    let hook_path = hooks_dir.join("post-rewrite");
+
+    fs::write(&hook_path, "#!/bin/sh\nexit 1\n")?;
+
+    #[cfg(unix)]
+    fs::set_permissions(&hook_path, fs::Permissions::from_mode(0o755))?;
+
+    let result = post_rewrite(&repo, &BTreeMap::new(), true)?;
+    assert_eq!(result, HookResult::NotConfigured);
+    Ok(())
+}
@@ -1,6 +1,7 @@
 mod create_wd_tree;
 mod hooks;
 mod managed_hooks_tests;
+mod notes;
 mod read_file_from_workspace_security;
 mod remotes;
 mod support;
@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+
+use but_testsupport::{invoke_bash, open_repo};
+use gitbutler_repo::notes::copy_for_rewrite;
+
+use crate::support::RepoWithOrigin;
+
+/// Add a note to the initial commit and commit on top of it with `config` applied, returning
+/// the repository along with the mapping of the initial commit to the new one.
+fn repo_with_note(
+    test_project: &RepoWithOrigin,
+    config: &str,
+) -> anyhow::Result<(gix::Repository, BTreeMap<gix::ObjectId, gix::ObjectId>)> {
+    invoke_bash(
+        &format!(
+            "git config user.name Tester\ngit config user.email tester@example.com\n\
+             git notes add -m 'a note' HEAD\n{config}\ngit commit --allow-empty -m rewritten\n"
+        ),
+        &test_project.local_repo,
+    );
+    let repo = open_repo(test_project.local_repo.path())?;
+    let new = repo.head_id()?;
+    let old = new
+        .object()?
+        .into_commit()
+        .parent_ids()
+        .next()
+        .expect("parent");
+    Ok((repo, BTreeMap::from([(old.detach(), new.detach())])))
+}
+
+fn note_of_head(repo: &gix::Repository) -> Option<String> {
+    let output = but_testsupport::git(repo)
+        .args(["notes", "show", "HEAD"])
+        .output()
+        .expect("git can run");
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[test]
+fn copies_notes_to_rewritten_commits_if_configured() -> anyhow::Result<()> {
+    let test_project = RepoWithOrigin::default();
+    let (repo, rewritten) =
+        repo_with_note(&test_project, "git config notes.rewriteRef 'refs/notes/*'")?;
+
+    assert!(copy_for_rewrite(&repo, &rewritten)?);
+    assert_eq!(note_of_head(&repo).as_deref(), Some("a note\n"));
+    Ok(())
+}
+
+#[test]
+fn respects_disabled_rewrite_command() -> anyhow::Result<()> {
+    let test_project = RepoWithOrigin::default();
+    let (repo, rewritten) = repo_with_note(
+        &test_project,
+        "git config notes.rewriteRef 'refs/notes/*'\ngit config notes.rewrite.rebase false",
+    )?;
+
+    assert!(copy_for_rewrite(&repo, &rewritten)?);
+    assert_eq!(note_of_head(&repo), None);
+    Ok(())
+}
+
+#[test]
+fn does_nothing_without_configuration() -> anyhow::Result<()> {
+    let test_project = RepoWithOrigin::default();
+    let (repo, rewritten) = repo_with_note(&test_project, "")?;
+
+    assert!(!copy_for_rewrite(&repo, &rewritten)?);
+    assert_eq!(note_of_head(&repo), None);
+    Ok(())
+}
@@ -70,6 +70,9 @@ fn main() -> anyhow::Result<()> {
     let mut app_settings =
         AppSettingsWithDiskSync::new_with_customization(config_dir.clone(), custom_settings)
             .expect("failed to create app settings");
+    if let Ok(settings) = app_settings.get() {
+        but_secret::secret::setup_from_settings(&settings.secrets);
+    }
 
     if let Ok(updated_csp) = csp_with_extras(
         tauri_context.config().app.security.csp.as_ref().cloned(),
@@ -237,6 +240,7 @@ fn main() -> anyhow::Result<()> {
                 workspace::tauri_get_workspace::get_workspace,
                 workspace::tauri_set_target_ref_and_init_project::set_target_ref_and_init_project,
                 but_api::branch::tauri_branch_diff::branch_diff,
+                but_api::branch::tauri_branch_range_diff::branch_range_diff,
                 but_api::branch::tauri_move_branch::move_branch,
                 but_api::branch::tauri_tear_off_branch::tear_off_branch,
                 but_api::branch::tauri_branch_remove::branch_remove,
@@ -308,6 +312,9 @@ fn main() -> anyhow::Result<()> {
                 legacy::modes::tauri_enter_edit_mode::enter_edit_mode,
                 legacy::modes::tauri_save_edit_and_return_to_workspace::save_edit_and_return_to_workspace,
                 legacy::modes::tauri_abort_edit_and_return_to_workspace::abort_edit_and_return_to_workspace,
+                legacy::modes::tauri_enter_edit_mode_in_worktree::enter_edit_mode_in_worktree,
+                legacy::modes::tauri_save_worktree_edit_and_return::save_worktree_edit_and_return,
+                legacy::modes::tauri_abort_worktree_edit::abort_worktree_edit,
                 legacy::modes::tauri_edit_initial_index_state::edit_initial_index_state,
                 legacy::modes::tauri_edit_changes_from_initial::edit_changes_from_initial,
                 open::tauri_open_url::open_url,
@@ -346,6 +353,7 @@ fn main() -> anyhow::Result<()> {
                 legacy::absorb::tauri_absorption_plan::absorption_plan,
                 diff::tauri_changes_in_worktree::changes_in_worktree,
                 diff::tauri_tree_change_diffs::tree_change_diffs,
+                diff::tauri_blame_worktree_file::blame_worktree_file,
                 diff::tauri_assign_hunk::assign_hunk,
                 #[cfg(unix)]
                 legacy::workspace::tauri_show_graph_svg::show_graph_svg,
@@ -373,6 +381,8 @@ fn main() -> anyhow::Result<()> {
                 commit::create::tauri_commit_create::commit_create,
                 commit::amend::tauri_commit_amend::commit_amend,
                 commit::cherry_pick::tauri_commit_cherry_pick::commit_cherry_pick,
+                commit::revert::tauri_commit_revert::commit_revert,
+                commit::split::tauri_commit_split::commit_split,
                 commit::move_commit::tauri_commit_move::commit_move,
                 commit::move_changes::tauri_commit_move_changes_between::commit_move_changes_between,
                 commit::squash::tauri_commit_squash::commit_squash,
@@ -57,6 +57,16 @@ export declare function applyBranchIntegration(projectId: string, branch: string
  */
 export declare function assignHunk(projectId: string, assignments: Array<HunkAssignmentRequest>): Promise<void>
 
+/**
+ * Attributes each line of the file at `path` in the worktree to where it came from: the
+ * worktree itself if it is uncommitted, the workspace commit and branch that introduced it,
+ * or the commit outside of the workspace that did, typically one of the target branch.
+ *
+ * For lower-level implementation details, see
+ * [`but_hunk_dependency::blame::blame_worktree_file()`].
+ */
+export declare function blameWorktreeFile(projectId: string, path: string): Promise<Array<BlameLine>>
+
 /**
  * Checks out an existing local branch and returns the resulting workspace state.
  *
@@ -128,6 +138,16 @@ export declare function branchLand(projectId: string, branch: string, noFf: bool
  */
 export declare function branchList(projectId: string): Promise<Array<ListedStack>>
 
+/**
+ * Compares the version of `branch` on its remote-tracking branch with the local version, commit by commit.
+ *
+ * This shows what changed relative to what was pushed, for instance after commits were amended or
+ * reordered, before force-pushing. Commits reachable from the project target are left out, so
+ * rebasing the branch onto a newer target doesn't make the target's commits show up.
+ * For lower-level implementation details, see [`but_workspace::range_diff()`].
+ */
+export declare function branchRangeDiff(projectId: string, branch: string): Promise<BranchRangeDiff>
+
 /**
  * Removes the local branch `ref_name` from the workspace, deleting its git
  * reference along with its metadata (including its `branch_order` entry).
@@ -309,6 +329,15 @@ export declare function commitMove(projectId: string, subjectCommitIds: Array<st
  */
 export declare function commitMoveChangesBetween(projectId: string, sourceCommitId: string, destinationCommitId: string, changes: Array<DiffSpec>, dryRun: boolean): Promise<MoveChangesResult>
 
+/**
+ * Creates a commit that undoes the changes of `subject_commit_id` on `side`
+ * of `relative_to` and records an oplog snapshot on success.
+ *
+ * When `dry_run` is enabled, the returned workspace previews the revert and
+ * no oplog entry is persisted. For details, see [`commit_revert_only()`].
+ */
+export declare function commitRevert(projectId: string, subjectCommitId: string, relativeTo: RelativeTo, side: InsertSide, dryRun: boolean): Promise<CommitRevertResult>
+
 /**
  * Reword `commit_id` to `message` using the behavior described by
  * [`commit_reword_with_perm()`].
@@ -320,6 +349,15 @@ export declare function commitMoveChangesBetween(projectId: string, sourceCommit
  */
 export declare function commitReword(projectId: string, commitId: string, message: string, dryRun: boolean): Promise<CommitRewordResult>
 
+/**
+ * Splits `commit_id` into one commit per entry in `pieces` and records an
+ * oplog snapshot on success.
+ *
+ * When `dry_run` is enabled, the returned workspace previews the split and
+ * no oplog entry is persisted. For details, see [`commit_split_only()`].
+ */
+export declare function commitSplit(projectId: string, commitId: string, pieces: Array<CommitSplitPiece>, dryRun: boolean): Promise<CommitSplitResult>
+
 /**
  * Squash `subject_commit_ids` into `target_commit_id` and record an oplog
  * snapshot on success.
@@ -448,7 +486,8 @@ export declare function getWorkspace(projectId: string): Promise<DetailedGraphWo
 export declare function headInfo(projectId: string): Promise<RefInfo>
 
 /**
- * Initialize the secret namespace used by build-kind scoped credentials.
+ * Initialize the secret namespace used by build-kind scoped credentials, and
+ * store secrets in the backend configured in the application settings.
  *
  * Applications embedding the SDK should call this once during startup before
  * invoking APIs that read or write forge credentials. If `identifier` is
@@ -979,12 +1018,20 @@ export type AppSettings = {
   reviews: Reviews;
   /** UI settings. */
   ui: UiSettings;
+  /** Settings related to storing secrets. */
+  secrets: Secrets;
   /**
    * The duration between application update checks in seconds. If `0`, no update checks will be performed.
    * This setting controls background update checks for both the CLI and GUI.
    * In the future, this will replace the legacy `ui.checkForUpdatesIntervalInSeconds` setting.
    */
   appUpdatesCheckIntervalSec: number;
+  /**
+   * Where `but update install` obtains releases from instead of GitButler's servers.
+   * Either the URL of a server which mirrors the release API, or the path to a local release directory.
+   * If empty, the `GITBUTLER_INSTALL_MIRROR` environment variable is used, or GitButler's servers otherwise.
+   */
+  appUpdatesMirror: string;
 };
 
 /** JSON sibling of [`but_workspace::branch::apply::Outcome`]. */
@@ -1070,6 +1117,16 @@ export type BitbucketAuthenticatedUserSensitive = {
   email: string | null;
 };
 
+/** JSON sibling of [`but_hunk_dependency::blame::BlameLine`]. */
+export type BlameLine = {
+  /** The 1-based line number in the worktree file. */
+  lineNumber: number;
+  /** The content of the line, without its line terminator. */
+  content: string;
+  /** Where the line came from. */
+  origin: LineOrigin;
+};
+
 /** JSON transport type describing one stack bottom to update. */
 export type BottomUpdate = {
   /** How the selected stack bottom should be updated. */
@@ -1302,6 +1359,14 @@ export type BranchListingFilter = {
   applied: boolean | null;
 };
 
+/** JSON sibling of [`crate::branch::BranchRangeDiff`]. */
+export type BranchRangeDiff = {
+  /** The remote-tracking branch that holds the old version of the branch. */
+  upstream: FullRefName;
+  /** The commits of both versions, in the order of the local version, oldest first. */
+  entries: Array<RangeDiffEntry>;
+};
+
 /** A reference in `refs/heads`. */
 export type BranchReference = {
   /** The full ref name, like `refs/heads/feat`, for usage with the backend. */
@@ -1515,6 +1580,14 @@ export type CommitMoveResult = {
   workspace: WorkspaceState;
 };
 
+/** JSON transport type for reverting a commit. */
+export type CommitRevertResult = {
+  /** The ID of the commit that undoes the reverted commit's changes. */
+  newCommit: string;
+  /** Workspace state after the revert. */
+  workspace: WorkspaceState;
+};
+
 /** JSON transport type for rewording a commit. */
 export type CommitRewordResult = {
   /** The new commit ID after rewording. */
@@ -1523,6 +1596,26 @@ export type CommitRewordResult = {
   workspace: WorkspaceState;
 };
 
+/** One of the commits a commit is split into. */
+export type CommitSplitPiece = {
+  /**
+   * The changes of the split commit that go into this piece.
+   *
+   * Changes that no piece selects stay in the first piece.
+   */
+  changes: Array<DiffSpec>;
+  /** The message of the commit created for this piece. */
+  message: string;
+};
+
+/** JSON transport type for splitting a commit. */
+export type CommitSplitResult = {
+  /** The IDs of the commits created for each piece, in parent-to-child order. */
+  newCommits: Array<string>;
+  /** Workspace state after the split. */
+  workspace: WorkspaceState;
+};
+
 /** JSON transport type for squashing commits. */
 export type CommitSquashResult = {
   /** The new commit ID after squashing. */
@@ -2426,6 +2519,27 @@ export type InteractiveIntegrationStep = {
   kind: "merge";
 };
 
+/** JSON sibling of [`but_hunk_dependency::blame::LineOrigin`]. */
+export type LineOrigin = {
+  type: "uncommitted";
+} | {
+  type: "workspace";
+  subject: {
+    /** The stack that owns the commit, if it can be identified. */
+    stackId: string | null;
+    /** The commit that introduced the line. */
+    commitId: HexHashString;
+    /** The branch of the stack that contains the commit, if it is named. */
+    branch: FullRefName | null;
+  };
+} | {
+  type: "target";
+  subject: {
+    /** The commit that introduced the line. */
+    commitId: HexHashString;
+  };
+};
+
 /** Line statistics obtained from diffing the blobs of one or more [TreeChange](crate::TreeChange). */
 export type LineStats = {
   /** The total amount of lines added in the between blobs of the two trees. */
@@ -2613,7 +2727,7 @@ export type OperatingMode = {
   subject: EditModeMetadata;
 };
 
-export type OperationKind = "CreateCommit" | "CreateBranch" | "StashIntoBranch" | "SetBaseBranch" | "MergeUpstream" | "UpdateWorkspaceBase" | "MoveHunk" | "UpdateBranchName" | "UpdateBranchNotes" | "ReorderBranches" | "UpdateBranchRemoteName" | "GenericBranchUpdate" | "DeleteBranch" | "ApplyBranch" | "DiscardLines" | "DiscardHunk" | "DiscardFile" | "DiscardChanges" | "Discard" | "AmendCommit" | "Absorb" | "AutoCommit" | "UndoCommit" | "DiscardCommit" | "UnapplyBranch" | "CherryPick" | "SquashCommit" | "UpdateCommitMessage" | "MoveCommit" | "MoveBranch" | "TearOffBranch" | "ReorderCommit" | "InsertBlankCommit" | "MoveCommitFile" | "FileChanges" | "EnterEditMode" | "ResolveConflictsAi" | "SyncWorkspace" | "CreateDependentBranch" | "RemoveDependentBranch" | "UpdateDependentBranchName" | "UpdateDependentBranchDescription" | "UpdateDependentBranchPrNumber" | "AutoHandleChangesBefore" | "AutoHandleChangesAfter" | "SplitBranch" | "CleanWorkspace" | "OnDemandSnapshot" | "Unknown" | "RestoreFromSnapshotViaUndo" | "RestoreFromSnapshotViaRedo" | "RestoreFromSnapshot";
+export type OperationKind = "CreateCommit" | "CreateBranch" | "StashIntoBranch" | "SetBaseBranch" | "MergeUpstream" | "UpdateWorkspaceBase" | "MoveHunk" | "UpdateBranchName" | "UpdateBranchNotes" | "ReorderBranches" | "UpdateBranchRemoteName" | "GenericBranchUpdate" | "DeleteBranch" | "ApplyBranch" | "DiscardLines" | "DiscardHunk" | "DiscardFile" | "DiscardChanges" | "Discard" | "AmendCommit" | "Absorb" | "AutoCommit" | "UndoCommit" | "DiscardCommit" | "UnapplyBranch" | "CherryPick" | "RevertCommit" | "SquashCommit" | "UpdateCommitMessage" | "MoveCommit" | "MoveBranch" | "TearOffBranch" | "ReorderCommit" | "InsertBlankCommit" | "MoveCommitFile" | "FileChanges" | "EnterEditMode" | "ResolveConflictsAi" | "SyncWorkspace" | "CreateDependentBranch" | "RemoveDependentBranch" | "UpdateDependentBranchName" | "UpdateDependentBranchDescription" | "UpdateDependentBranchPrNumber" | "AutoHandleChangesBefore" | "AutoHandleChangesAfter" | "SplitBranch" | "SplitCommit" | "CleanWorkspace" | "RepairWorkspace" | "OnDemandSnapshot" | "Unknown" | "RestoreFromSnapshotViaUndo" | "RestoreFromSnapshotViaRedo" | "RestoreFromSnapshot";
 
 /** What kind of apply operation completed. */
 export type OutcomeStatus = "alreadyApplied" | "applied" | "conflictAborted";
@@ -2628,6 +2742,14 @@ export type OutsideWorkspaceMetadata = {
 /** A column in a detailed graph padding row. */
 export type PadLine = "blank" | "ancestor" | "parent";
 
+/** The parsed content of a Git LFS pointer file. */
+export type Pointer = {
+  /** The object id of the file content in the LFS store, like `sha256:4d7a…`. */
+  oid: string;
+  /** The size of the file content in bytes. */
+  size: number;
+};
+
 /**
  * API-specific project type that can be enriched with computed/derived data
  * while preserving the original project structure for persistence.
@@ -2732,6 +2854,31 @@ export type PushResult = {
 /** Represents the pushable status for the current stack. */
 export type PushStatus = "nothingToPush" | "unpushedCommits" | "unpushedCommitsRequiringForce" | "completelyUnpushed" | "integrated";
 
+/** JSON sibling of [`but_workspace::range_diff::RangeDiffEntry`]. */
+export type RangeDiffEntry = {
+  /** The commit in the old version, if there is one. */
+  old: HexHashString | null;
+  /** The commit in the new version, if there is one. */
+  new: HexHashString | null;
+  /** The title of the commit, taken from the new version if there is one. */
+  title: string;
+  /** How `old` and `new` relate. */
+  status: RangeDiffStatus;
+  /** How `old` and `new` were paired, or `null` if only one of them is set. */
+  pairing: RangeDiffPairing | null;
+  /**
+   * The diff from the patch of `old` to the patch of `new`, including their messages,
+   * or `null` if the patches are the same or there is nothing to compare to.
+   */
+  interdiff: string | null;
+};
+
+/** JSON sibling of [`but_workspace::range_diff::RangeDiffPairing`]. */
+export type RangeDiffPairing = "changeId" | "similarity";
+
+/** JSON sibling of [`but_workspace::range_diff::RangeDiffStatus`]. */
+export type RangeDiffStatus = "added" | "removed" | "unchanged" | "modified";
+
 /**
  * The UI-clone of [`crate::RefInfo`].
  * TODO: should also include base-branch data, see `get_base_branch_data()`.
@@ -2785,7 +2932,7 @@ export type RejectedChange = {
 };
 
 /** Provide a description of why a [`crate::DiffSpec`] was rejected for application to the tree of a commit. */
-export type RejectionReason = "noEffectiveChanges" | "cherryPickMergeConflict" | "workspaceMergeConflict" | "workspaceMergeConflictOfUnrelatedFile" | "worktreeFileMissingForObjectConversion" | "fileToLargeOrBinary" | "pathNotFoundInBaseTree" | "unsupportedDirectoryEntry" | "unsupportedTreeEntry" | "missingDiffSpecAssociation";
+export type RejectionReason = "noEffectiveChanges" | "cherryPickMergeConflict" | "workspaceMergeConflict" | "workspaceMergeConflictOfUnrelatedFile" | "worktreeFileMissingForObjectConversion" | "fileToLargeOrBinary" | "pathNotFoundInBaseTree" | "unsupportedDirectoryEntry" | "unsupportedTreeEntry" | "missingDiffSpecAssociation" | "lfsCleanFilterNotApplied";
 
 /**
  * Specifies a location, usually used to either have something inserted
@@ -2929,6 +3076,24 @@ export type ReviewsUpdate = {
   autoFillPrDescriptionFromCommit?: boolean | null;
 };
 
+export type Secrets = {
+  /** Where secrets like forge tokens are stored. */
+  backend: SecretsBackend;
+  /**
+   * The file to store secrets in with the "encryptedFile" backend.
+   * If empty, `secrets.enc` in the application data directory is used.
+   */
+  encryptedFilePath: string;
+  /**
+   * A file whose content is the key for the "encryptedFile" backend.
+   * If empty, the passphrase in `GITBUTLER_SECRETS_PASSPHRASE` is used.
+   */
+  keyFile: string;
+};
+
+/** Where secrets like forge tokens are stored. */
+export type SecretsBackend = "keychain" | "encryptedFile" | "gitCredentials";
+
 /** A segment of a commit graph, representing a set of commits exclusively. */
 export type Segment = {
   /**
@@ -3136,6 +3301,14 @@ export type StackReference = {
   pullRequests: Record<string, number>;
 };
 
+/** A commit of a submodule as listed in a [`UnifiedPatch::Submodule`]. */
+export type SubmoduleCommit = {
+  /** The id of the commit in the submodule repository. */
+  id: string;
+  /** The first line of the commit message. */
+  title: string;
+};
+
 /** Information about the target reference, the one we want to integrate with. */
 export type Target = {
   /** The remote tracking branch of the target to integrate with, like `refs/remotes/origin/main`. */
@@ -3361,6 +3534,34 @@ export type UnifiedPatch = {
     /** The total amount of lines removed. */
     linesRemoved: number;
   };
+} | {
+  type: "Submodule";
+  subject: {
+    /** The commit the submodule pointed to before, or `None` if it was added. */
+    previousCommit: string | null;
+    /** The commit the submodule points to now, or `None` if it was removed. */
+    commit: string | null;
+    /**
+     * The commits reachable from `commit` but not from `previous_commit`, newest first.
+     *
+     * This, like `commits_removed`, is empty if the submodule repository or its commits aren't available locally,
+     * and holds at most [`unified_diff::SUBMODULE_LOG_LIMIT`] commits.
+     */
+    commitsAdded: Array<SubmoduleCommit>;
+    /**
+     * The commits reachable from `previous_commit` but not from `commit`, newest first, which is the case
+     * if the submodule was moved back or to another branch.
+     */
+    commitsRemoved: Array<SubmoduleCommit>;
+  };
+} | {
+  type: "Lfs";
+  subject: {
+    /** The pointer before the change, or `None` if the file was added. */
+    previousPointer: Pointer | null;
+    /** The pointer after the change, or `None` if the file was removed. */
+    pointer: Pointer | null;
+  };
 };
 
 /**